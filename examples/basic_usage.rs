@@ -1,5 +1,6 @@
 //! Basic usage examples of QuickCodes
 
+use quickcodes::payloads::PixPayload;
 use quickcodes::{generate, generate_to_file, BarcodeType, ExportFormat};
 use std::fs;
 
@@ -35,10 +36,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Example 5: Generate QR Code for Pix payment (Brazilian instant payment)
     println!("5. Generating QR Code for Pix payment...");
-    let pix_data = "00020126580014BR.GOV.BCB.PIX0114+5551999999995204000053039865405100.005802BR5920Padaria Exemplo6009SAO PAULO62070503***6304ABCD";
+    let mut pix = PixPayload::new_static("+5551999999995", "Padaria Exemplo", "SAO PAULO");
+    pix.amount = Some(10000);
     generate_to_file(
         BarcodeType::QRCode,
-        pix_data,
+        &pix.encode()?,
         "examples/output/pix_payment.svg",
     )?;
     println!("   ✅ Saved to examples/output/pix_payment.svg");
@@ -9,7 +9,7 @@ pub mod svg;
 
 // Re-export exporter functions
 #[cfg(feature = "pdf")]
-pub use pdf::export_pdf;
+pub use pdf::{export_logistics_labels, export_pdf};
 #[cfg(feature = "png")]
 pub use png::export_png;
 #[cfg(feature = "svg")]
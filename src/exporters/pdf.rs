@@ -6,13 +6,30 @@
 //! Note: This is a simplified implementation for demonstration purposes.
 //! A production implementation would use more advanced PDF rendering techniques.
 
-use crate::types::{Barcode, BarcodeModules, QuickCodesError, Result};
+use crate::gs1::label::{self, LogisticsLabel};
+use crate::types::{BarGeometry, BarState, Barcode, BarcodeModules, QuickCodesError, Result};
+
+/// Left edge and top of height-modulated bars on the page, in millimetres
+const BARS_ORIGIN: (f64, f64) = (20.0, 195.0);
+
+/// Vertical pitch of the label barcode rows, in millimetres: the bars and
+/// the human-readable line under the row above
+const LABEL_ROW_PITCH: f64 = label::MIN_BAR_HEIGHT + 5.0;
+
+/// Bottom of the lowest label barcode (the SSCC), in millimetres
+const LABEL_FIRST_ROW: f64 = 9.0;
+
+/// Vertical pitch of the address lines, in millimetres
+const ADDRESS_LINE_PITCH: f64 = 3.5;
+
+/// Vertical pitch of the rows of data titles and values, in millimetres
+const TEXT_ROW_PITCH: f64 = 7.5;
 
 /// Export a barcode to PDF format
 ///
 /// This is a simplified implementation that generates a basic PDF
-/// with the barcode data as text. A full implementation would render
-/// the actual barcode graphics.
+/// with the barcode data as text. Height-modulated barcodes are also drawn
+/// at their physical size; other barcode graphics are not rendered yet.
 ///
 /// # Arguments
 /// * `barcode` - The barcode to export
@@ -55,12 +72,23 @@ fn generate_simple_pdf(barcode: &Barcode) -> Result<Vec<u8>> {
             matrix.len(),
             matrix.first().map(|row| row.len()).unwrap_or(0)
         ),
+        BarcodeModules::HeightModulated { bars, .. } => format!("Bar Count: {}", bars.len()),
+        BarcodeModules::Hexagonal(grid) => format!(
+            "Hexagonal Grid: {}x{}",
+            grid.len(),
+            grid.first().map(|row| row.len()).unwrap_or(0)
+        ),
     };
     current_layer.set_text_cursor(Mm(20.0), Mm(210.0));
     current_layer.write_text(dimensions, &font);
 
     current_layer.end_text_section();
 
+    // Height-modulated bars are drawn at their physical size
+    if let BarcodeModules::HeightModulated { bars, geometry } = &barcode.modules {
+        draw_bars(&current_layer, bars, geometry);
+    }
+
     // Save to bytes
     let mut buffer = Vec::new();
     {
@@ -72,6 +100,207 @@ fn generate_simple_pdf(barcode: &Barcode) -> Result<Vec<u8>> {
     Ok(buffer)
 }
 
+/// Export GS1 logistics labels to a PDF with one page per label, each at
+/// its [`label::LabelSize`]
+///
+/// Returns an error when a label's addresses, data lines and barcodes do not
+/// all fit on its page; at the default 4 x 6 inches, three barcode rows
+/// leave room for up to three address lines.
+pub fn export_logistics_labels(labels: &[LogisticsLabel]) -> Result<Vec<u8>> {
+    use printpdf::{BuiltinFont, Mm, PdfDocument};
+
+    let Some(first) = labels.first() else {
+        return Err(QuickCodesError::ExportError(
+            "No labels to export".to_string(),
+        ));
+    };
+
+    let (width, height) = first.size.dimensions();
+    let (doc, first_page, first_layer) = PdfDocument::new(
+        "GS1 Logistics Label",
+        Mm(width as f32),
+        Mm(height as f32),
+        "Label",
+    );
+    let font = doc
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| QuickCodesError::ExportError(format!("Font error: {}", e)))?;
+    let bold = doc
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| QuickCodesError::ExportError(format!("Font error: {}", e)))?;
+
+    for (i, label) in labels.iter().enumerate() {
+        let barcodes = label.barcodes()?;
+        let text_block = label.text_block()?;
+        let (width, height) = label.size.dimensions();
+
+        // Top section: address titles, then the longer address's lines
+        let address_top = height - label::LABEL_MARGIN - 2.5;
+        let address_lines = label.ship_from.len().max(label.ship_to.len());
+        let address_rule = address_top - address_lines as f64 * ADDRESS_LINE_PITCH - 2.0;
+
+        // The middle section runs from below the addresses down to the
+        // rule above the barcodes, two data lines to a row
+        let text_rows = (text_block.len() + 1) / 2;
+        let text_bottom = address_rule - 3.0 - (text_rows - 1) as f64 * TEXT_ROW_PITCH - 4.5;
+        let barcodes_top =
+            LABEL_FIRST_ROW + (barcodes.len() - 1) as f64 * LABEL_ROW_PITCH + label::MIN_BAR_HEIGHT;
+        if text_bottom < barcodes_top + 2.5 {
+            return Err(QuickCodesError::ExportError(format!(
+                "{} address lines, {} data lines and {} barcodes do not fit on the {} x {} mm label",
+                address_lines,
+                text_block.len(),
+                barcodes.len(),
+                width,
+                height
+            )));
+        }
+
+        let (page, layer) = if i == 0 {
+            (first_page, first_layer)
+        } else {
+            doc.add_page(Mm(width as f32), Mm(height as f32), "Label")
+        };
+        let layer = doc.get_page(page).get_layer(layer);
+
+        // Top section: free-format addresses
+        let column = (width - 2.0 * label::LABEL_MARGIN) / 2.0;
+        for (x, title, lines) in [
+            (label::LABEL_MARGIN, "FROM", &label.ship_from),
+            (label::LABEL_MARGIN + column, "TO", &label.ship_to),
+        ] {
+            let mut y = address_top;
+            layer.use_text(title, 6.0, Mm(x as f32), Mm(y as f32), &font);
+            for line in lines {
+                y -= ADDRESS_LINE_PITCH;
+                layer.use_text(line.as_str(), 8.0, Mm(x as f32), Mm(y as f32), &bold);
+            }
+        }
+        draw_rule(&layer, width, address_rule);
+
+        // Middle section: data titles with their values below
+        for (k, (title, value)) in text_block.iter().enumerate() {
+            let x = label::LABEL_MARGIN + (k % 2) as f64 * column;
+            let y = address_rule - 3.0 - (k / 2) as f64 * TEXT_ROW_PITCH;
+            layer.use_text(*title, 6.0, Mm(x as f32), Mm(y as f32), &font);
+            layer.use_text(
+                value.as_str(),
+                10.0,
+                Mm(x as f32),
+                Mm((y - 3.5) as f32),
+                &bold,
+            );
+        }
+        draw_rule(&layer, width, barcodes_top + 1.5);
+
+        // Bottom section: GS1-128 barcodes, the SSCC lowest
+        for (row, barcode) in barcodes.iter().rev().enumerate() {
+            let modules = barcode.modules.as_linear().map_or(&[][..], |m| &m[..]);
+            let available = width - 2.0 * label::LABEL_MARGIN;
+            let module_width = (available / (modules.len() + 2 * label::QUIET_ZONE) as f64)
+                .min(label::MODULE_WIDTH.1);
+            if module_width < label::MODULE_WIDTH.0 {
+                return Err(QuickCodesError::ExportError(format!(
+                    "{} does not fit on the label width",
+                    barcode.data
+                )));
+            }
+            let bottom = LABEL_FIRST_ROW + row as f64 * LABEL_ROW_PITCH;
+            let left = (width - modules.len() as f64 * module_width) / 2.0;
+            draw_modules(
+                &layer,
+                modules,
+                left,
+                bottom,
+                module_width,
+                label::MIN_BAR_HEIGHT,
+            );
+            let text_left = (width - barcode.data.len() as f64 * 1.6) / 2.0;
+            layer.use_text(
+                barcode.data.as_str(),
+                8.0,
+                Mm(text_left.max(label::LABEL_MARGIN) as f32),
+                Mm((bottom - 3.5) as f32),
+                &font,
+            );
+        }
+    }
+
+    let mut buffer = Vec::new();
+    {
+        let mut writer = std::io::BufWriter::new(&mut buffer);
+        doc.save(&mut writer)
+            .map_err(|e| QuickCodesError::ExportError(format!("PDF save failed: {}", e)))?;
+    }
+
+    Ok(buffer)
+}
+
+/// Draw the dark modules of a linear barcode as filled rectangles, in
+/// millimetres from the bottom left of the page
+fn draw_modules(
+    layer: &printpdf::PdfLayerReference,
+    modules: &[bool],
+    left: f64,
+    bottom: f64,
+    module_width: f64,
+    height: f64,
+) {
+    use printpdf::{Color, Mm, Rect, Rgb};
+
+    layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+
+    let mut start = 0;
+    while start < modules.len() {
+        let run = modules[start..]
+            .iter()
+            .take_while(|&&dark| dark == modules[start])
+            .count();
+        if modules[start] {
+            let x = left + start as f64 * module_width;
+            layer.add_rect(Rect::new(
+                Mm(x as f32),
+                Mm(bottom as f32),
+                Mm((x + run as f64 * module_width) as f32),
+                Mm((bottom + height) as f32),
+            ));
+        }
+        start += run;
+    }
+}
+
+/// Horizontal line across a label of `width` between sections
+fn draw_rule(layer: &printpdf::PdfLayerReference, width: f64, y: f64) {
+    use printpdf::{Mm, Rect};
+
+    layer.add_rect(Rect::new(
+        Mm(label::LABEL_MARGIN as f32),
+        Mm(y as f32),
+        Mm((width - label::LABEL_MARGIN) as f32),
+        Mm((y + 0.3) as f32),
+    ));
+}
+
+/// Draw height-modulated bars as filled rectangles, in millimetres
+fn draw_bars(layer: &printpdf::PdfLayerReference, bars: &[BarState], geometry: &BarGeometry) {
+    use printpdf::{Color, Mm, Rect, Rgb};
+
+    layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+
+    let (left, top) = BARS_ORIGIN;
+    for (i, bar) in bars.iter().enumerate() {
+        let (bar_top, bar_bottom) = bar.span(geometry);
+        let x = left + i as f64 * geometry.pitch;
+        // PDF coordinates grow upwards from the bottom of the page
+        layer.add_rect(Rect::new(
+            Mm(x as f32),
+            Mm((top - bar_bottom) as f32),
+            Mm((x + geometry.bar_width) as f32),
+            Mm((top - bar_top) as f32),
+        ));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,4 +368,50 @@ mod tests {
         // Our simplified PDF implementation handles empty matrices gracefully
         assert!(result.is_ok());
     }
+
+    #[test]
+    #[cfg(feature = "pdf")]
+    fn test_pdf_export_height_modulated() {
+        let barcode = Barcode {
+            barcode_type: BarcodeType::IntelligentMail,
+            data: "TEST".to_string(),
+            modules: BarcodeModules::HeightModulated {
+                bars: vec![
+                    BarState::Full,
+                    BarState::Ascender,
+                    BarState::Descender,
+                    BarState::Tracker,
+                ],
+                geometry: BarGeometry {
+                    bar_width: 0.5,
+                    pitch: 1.0,
+                    height: 3.6,
+                    tracker_top: 1.2,
+                    tracker_bottom: 2.4,
+                },
+            },
+            config: BarcodeConfig::default(),
+        };
+
+        let pdf_data = export_pdf(&barcode).unwrap();
+        assert!(pdf_data.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    #[cfg(feature = "pdf")]
+    fn test_export_logistics_labels() {
+        use crate::gs1::SsccAllocator;
+
+        let mut allocator = SsccAllocator::new(0, "0614141").unwrap();
+        let labels: Vec<LogisticsLabel> = allocator
+            .allocate_many(3)
+            .unwrap()
+            .into_iter()
+            .map(LogisticsLabel::new)
+            .collect();
+        let pdf_data = export_logistics_labels(&labels).unwrap();
+        assert!(pdf_data.starts_with(b"%PDF"));
+
+        assert!(export_logistics_labels(&[]).is_err());
+    }
 }
@@ -1,26 +1,43 @@
 //! PNG export functionality
 
 #[cfg(feature = "png")]
-use crate::types::{Barcode, BarcodeModules, QuickCodesError, Result};
+use crate::types::{
+    bullseye_center, hex_center, hex_grid_size, swiss_cross, BarGeometry, BarState, Barcode,
+    BarcodeModules, QuickCodesError, Result, BULLSEYE_RADII, HEX_RADIUS,
+};
 #[cfg(feature = "png")]
 use image::{ImageBuffer, Rgb, RgbImage};
 #[cfg(feature = "png")]
 use std::io::Cursor;
 
+/// Resolution used to rasterize barcodes defined in physical units
+#[cfg(feature = "png")]
+const PHYSICAL_DPI: f64 = 300.0;
+
 /// Export a barcode to PNG format
 #[cfg(feature = "png")]
 pub fn export_png(barcode: &Barcode) -> Result<Vec<u8>> {
     match &barcode.modules {
         BarcodeModules::Linear(pattern) => export_linear_png(barcode, pattern),
         BarcodeModules::Matrix(matrix) => export_matrix_png(barcode, matrix),
+        BarcodeModules::HeightModulated { bars, geometry } => {
+            export_bars_png(barcode, bars, geometry)
+        }
+        BarcodeModules::Hexagonal(grid) => export_hexagonal_png(barcode, grid),
     }
 }
 
 /// Export a linear (1D) barcode to PNG
 #[cfg(feature = "png")]
 fn export_linear_png(barcode: &Barcode, pattern: &[bool]) -> Result<Vec<u8>> {
-    let module_width = 2u32; // Width of each module in pixels
-    let height = 60u32; // Height of the barcode
+    // Barcodes with a physical size are rasterized at 300 DPI
+    let (module_width, height) = match barcode.config.dimensions {
+        Some(dimensions) => {
+            let to_px = |mm: f64| ((mm / 25.4 * PHYSICAL_DPI).round() as u32).max(1);
+            (to_px(dimensions.module_width), to_px(dimensions.height))
+        }
+        None => (2, 60),
+    };
     let margin = barcode.config.margin;
 
     let total_width = (pattern.len() as u32 * module_width) + (2 * margin);
@@ -102,6 +119,139 @@ fn export_matrix_png(barcode: &Barcode, matrix: &[Vec<bool>]) -> Result<Vec<u8>>
         }
     }
 
+    if barcode.config.swiss_cross {
+        let size = matrix_width as f64;
+        for part in swiss_cross() {
+            let color = if part.dark {
+                Rgb([0, 0, 0])
+            } else {
+                Rgb([255, 255, 255])
+            };
+            let left = margin + (part.x * size).round() as u32;
+            let top = margin + (part.y * size).round() as u32;
+            let right = margin + ((part.x + part.width) * size).round() as u32;
+            let bottom = margin + ((part.y + part.height) * size).round() as u32;
+            for y in top..bottom {
+                for x in left..right {
+                    img.put_pixel(x, y, color);
+                }
+            }
+        }
+    }
+
+    // Convert to PNG bytes
+    let mut buffer = Vec::new();
+    let mut cursor = Cursor::new(&mut buffer);
+
+    img.write_to(&mut cursor, image::ImageFormat::Png)
+        .map_err(|e| QuickCodesError::ExportError(format!("PNG export failed: {}", e)))?;
+
+    Ok(buffer)
+}
+
+/// Export a hexagonal-grid (MaxiCode) barcode to PNG
+#[cfg(feature = "png")]
+fn export_hexagonal_png(barcode: &Barcode, grid: &[Vec<bool>]) -> Result<Vec<u8>> {
+    if grid.is_empty() || grid[0].is_empty() {
+        return Err(QuickCodesError::ExportError("Grid is empty".to_string()));
+    }
+
+    let module_width = 10.0; // Width of each hexagon in pixels
+    let margin = barcode.config.margin;
+
+    let (grid_width, grid_height) = hex_grid_size(grid.len(), grid[0].len());
+    let total_width = (grid_width * module_width).ceil() as u32 + (2 * margin);
+    let total_height = (grid_height * module_width).ceil() as u32 + (2 * margin);
+
+    // Create image buffer with white background
+    let mut img: RgbImage =
+        ImageBuffer::from_pixel(total_width, total_height, Rgb([255, 255, 255]));
+
+    // Pixel centres in module widths, relative to the grid origin
+    let to_units = |px: u32| (f64::from(px) - f64::from(margin) + 0.5) / module_width;
+    let to_px = |units: f64| (units * module_width + f64::from(margin)).max(0.0) as u32;
+
+    // Draw hexagonal modules, pointing up and down
+    for (row, row_data) in grid.iter().enumerate() {
+        for (col, &is_black) in row_data.iter().enumerate() {
+            if !is_black {
+                continue;
+            }
+            let (cx, cy) = hex_center(row, col);
+            for y in to_px(cy - HEX_RADIUS)..to_px(cy + HEX_RADIUS) + 1 {
+                for x in to_px(cx - 0.5)..to_px(cx + 0.5) + 1 {
+                    let dx = (to_units(x) - cx).abs();
+                    let dy = (to_units(y) - cy).abs();
+                    let inside = dx <= 0.5 && dy <= HEX_RADIUS - dx * HEX_RADIUS;
+                    if inside && x < total_width && y < total_height {
+                        img.put_pixel(x, y, Rgb([0, 0, 0]));
+                    }
+                }
+            }
+        }
+    }
+
+    // Bullseye: a pixel is dark inside an odd number of circles
+    let (cx, cy) = bullseye_center(grid.len(), grid[0].len());
+    let outer = BULLSEYE_RADII[0];
+    for y in to_px(cy - outer)..to_px(cy + outer) + 1 {
+        for x in to_px(cx - outer)..to_px(cx + outer) + 1 {
+            let distance = (to_units(x) - cx).hypot(to_units(y) - cy);
+            let circles = BULLSEYE_RADII.iter().filter(|&&r| distance <= r).count();
+            if circles > 0 && x < total_width && y < total_height {
+                let shade = if circles % 2 == 1 { 0 } else { 255 };
+                img.put_pixel(x, y, Rgb([shade, shade, shade]));
+            }
+        }
+    }
+
+    // Convert to PNG bytes
+    let mut buffer = Vec::new();
+    let mut cursor = Cursor::new(&mut buffer);
+
+    img.write_to(&mut cursor, image::ImageFormat::Png)
+        .map_err(|e| QuickCodesError::ExportError(format!("PNG export failed: {}", e)))?;
+
+    Ok(buffer)
+}
+
+/// Export a height-modulated barcode to PNG, rasterized at 300 DPI
+#[cfg(feature = "png")]
+fn export_bars_png(
+    barcode: &Barcode,
+    bars: &[BarState],
+    geometry: &BarGeometry,
+) -> Result<Vec<u8>> {
+    if bars.is_empty() {
+        return Err(QuickCodesError::ExportError(
+            "Bar list is empty".to_string(),
+        ));
+    }
+
+    let to_px = |mm: f64| (mm / 25.4 * PHYSICAL_DPI).round() as u32;
+    let margin = barcode.config.margin;
+    let bar_width = to_px(geometry.bar_width).max(1);
+
+    let total_width = to_px(geometry.width(bars.len())).max(bar_width) + (2 * margin);
+    let total_height = to_px(geometry.height) + (2 * margin);
+
+    // Create image buffer with white background
+    let mut img: RgbImage =
+        ImageBuffer::from_pixel(total_width, total_height, Rgb([255, 255, 255]));
+
+    // Draw bars
+    for (i, bar) in bars.iter().enumerate() {
+        let (top, bottom) = bar.span(geometry);
+        let start_x = margin + to_px(i as f64 * geometry.pitch);
+        for y in (margin + to_px(top))..(margin + to_px(bottom)) {
+            for x in start_x..(start_x + bar_width) {
+                if x < total_width && y < total_height {
+                    img.put_pixel(x, y, Rgb([0, 0, 0]));
+                }
+            }
+        }
+    }
+
     // Convert to PNG bytes
     let mut buffer = Vec::new();
     let mut cursor = Cursor::new(&mut buffer);
@@ -141,4 +291,90 @@ mod tests {
         // PNG files start with specific magic bytes
         assert_eq!(&data[0..8], &[137, 80, 78, 71, 13, 10, 26, 10]);
     }
+
+    #[test]
+    fn test_png_export_height_modulated() {
+        use crate::generators::pharmacode::{generate_pharmacode_with_tracks, PharmacodeTracks};
+
+        let barcode = generate_pharmacode_with_tracks("1234", PharmacodeTracks::Two).unwrap();
+        let png_data = export_png(&barcode).unwrap();
+
+        let img = image::load_from_memory(&png_data).unwrap();
+        let margin = barcode.config.margin * 2;
+        // 8 mm at 300 DPI is 94 pixels
+        assert_eq!(img.height(), 94 + margin);
+    }
+
+    #[test]
+    fn test_png_export_swiss_cross() {
+        let mut barcode = crate::generators::generate_qr("SPC").unwrap();
+        barcode.config.margin = 0;
+        barcode.config.swiss_cross = true;
+        let img = image::load_from_memory(&export_png(&barcode).unwrap())
+            .unwrap()
+            .to_rgb8();
+
+        // The centre is in a white arm of the cross, with the black square
+        // around it
+        let size = img.width() as f64;
+        let at = |x: f64, y: f64| img.get_pixel((x * size) as u32, (y * size) as u32)[0];
+        assert_eq!(at(0.5, 0.5), 255);
+        let square = swiss_cross()[1];
+        assert_eq!(at(square.x + 0.01, square.y + 0.01), 0);
+        assert_eq!(at(square.x + square.width - 0.01, square.y + 0.01), 0);
+    }
+
+    #[test]
+    fn test_png_export_linear_dimensions() {
+        let mut barcode =
+            crate::generators::interleaved2of5::generate_interleaved_2of5("1234").unwrap();
+        barcode.config.margin = 0;
+        barcode.config.dimensions = Some(crate::types::LinearDimensions {
+            module_width: 0.5,
+            height: 10.0,
+        });
+        let modules = barcode.modules.as_linear().unwrap().len() as u32;
+
+        let img = image::load_from_memory(&export_png(&barcode).unwrap()).unwrap();
+        // 0.5 mm is 6 pixels and 10 mm is 118 pixels at 300 DPI
+        assert_eq!(img.width(), modules * 6);
+        assert_eq!(img.height(), 118);
+    }
+
+    #[test]
+    fn test_png_export_postnet_bar_heights() {
+        let barcode = crate::generators::postnet::generate_postnet("12345").unwrap();
+        let png_data = export_png(&barcode).unwrap();
+
+        let img = image::load_from_memory(&png_data).unwrap().to_rgb8();
+        let margin = barcode.config.margin;
+        let column_height = |x: u32| {
+            (0..img.height())
+                .filter(|&y| img.get_pixel(x, y)[0] == 0)
+                .count()
+        };
+        // 0.125" full frame bar and 0.050" half bar at 300 DPI
+        assert_eq!(column_height(margin), 38);
+        let half_bar_x = margin + (25.4_f64 / 22.0 / 25.4 * 300.0).round() as u32;
+        assert_eq!(column_height(half_bar_x), 15);
+    }
+
+    #[test]
+    fn test_png_export_hexagonal() {
+        let barcode = crate::generators::maxicode::generate_maxicode("MAXICODE TEST").unwrap();
+        let png_data = export_png(&barcode).unwrap();
+
+        let img = image::load_from_memory(&png_data).unwrap().to_rgb8();
+        let margin = barcode.config.margin;
+        // 30 modules of 10 pixels across
+        assert_eq!(img.width(), 300 + 2 * margin);
+
+        // Light centre and dark outer ring of the bullseye
+        let (cx, cy) = bullseye_center(33, 30);
+        let centre_x = margin + (cx * 10.0) as u32;
+        let centre_y = margin + (cy * 10.0) as u32;
+        assert_eq!(img.get_pixel(centre_x, centre_y)[0], 255);
+        let ring = ((BULLSEYE_RADII[0] + BULLSEYE_RADII[1]) / 2.0 * 10.0) as u32;
+        assert_eq!(img.get_pixel(centre_x + ring, centre_y)[0], 0);
+    }
 }
@@ -1,29 +1,48 @@
 //! SVG export functionality
 
 #[cfg(feature = "svg")]
-use crate::types::{Barcode, BarcodeModules, BarcodeType, QuickCodesError, Result};
+use crate::types::{
+    bullseye_center, hex_center, hex_grid_size, swiss_cross, BarGeometry, BarState, Barcode,
+    BarcodeModules, BarcodeType, ModuleShape, QuickCodesError, Result, BULLSEYE_RADII, HEX_RADIUS,
+};
 #[cfg(feature = "svg")]
-use svg::node::element::{Rectangle, Text};
+use svg::node::element::{Circle, Polygon, Rectangle, Text};
 #[cfg(feature = "svg")]
 use svg::Document;
 
+#[cfg(feature = "svg")]
+const MM_PER_INCH: f64 = 25.4;
+
 /// Export a barcode to SVG format
 #[cfg(feature = "svg")]
 pub fn export_svg(barcode: &Barcode) -> Result<Vec<u8>> {
     match &barcode.modules {
         BarcodeModules::Linear(pattern) => export_linear_svg(barcode, pattern),
         BarcodeModules::Matrix(matrix) => export_matrix_svg(barcode, matrix),
+        BarcodeModules::HeightModulated { bars, geometry } => {
+            export_bars_svg(barcode, bars, geometry)
+        }
+        BarcodeModules::Hexagonal(grid) => export_hexagonal_svg(barcode, grid),
     }
 }
 
 /// Export a linear (1D) barcode to SVG
 #[cfg(feature = "svg")]
 fn export_linear_svg(barcode: &Barcode, pattern: &[bool]) -> Result<Vec<u8>> {
-    let module_width = 2.0; // Width of each module in SVG units
-    let height = 60.0; // Height of the barcode
-    let margin = barcode.config.margin as f64;
+    // Barcodes with a physical size are drawn in millimetres, with the text
+    // scaled to match
+    let (module_width, height, margin, scale, unit) = match barcode.config.dimensions {
+        Some(dimensions) => (
+            dimensions.module_width,
+            dimensions.height,
+            barcode.config.margin as f64 * MM_PER_INCH / 96.0,
+            0.25,
+            "mm",
+        ),
+        None => (2.0, 60.0, barcode.config.margin as f64, 1.0, ""),
+    };
     let text_height = if barcode.config.include_text {
-        20.0
+        20.0 * scale
     } else {
         0.0
     };
@@ -32,9 +51,9 @@ fn export_linear_svg(barcode: &Barcode, pattern: &[bool]) -> Result<Vec<u8>> {
     let total_height = height + text_height + (2.0 * margin);
 
     let mut document = Document::new()
-        .set("width", total_width)
-        .set("height", total_height)
-        .set("viewBox", (0, 0, total_width as i32, total_height as i32))
+        .set("width", format!("{}{}", total_width, unit))
+        .set("height", format!("{}{}", total_height, unit))
+        .set("viewBox", (0.0, 0.0, total_width, total_height))
         .set("xmlns", "http://www.w3.org/2000/svg");
 
     // White background
@@ -61,17 +80,41 @@ fn export_linear_svg(barcode: &Barcode, pattern: &[bool]) -> Result<Vec<u8>> {
 
     // Add human-readable text if enabled
     if barcode.config.include_text {
-        let text_y = margin + height + 15.0;
+        let text_y = margin + height + 15.0 * scale;
         let text_x = total_width / 2.0;
+        let font_size = 12.0 * scale;
 
         let text = Text::new(&barcode.data)
             .set("x", text_x)
             .set("y", text_y)
             .set("text-anchor", "middle")
             .set("font-family", "monospace")
-            .set("font-size", "12")
+            .set("font-size", font_size)
             .set("fill", "black");
         document = document.add(text);
+
+        // Boxed character right after the centred text (a monospace
+        // character is 0.6 font sizes wide)
+        if let Some(boxed) = barcode.config.boxed_character {
+            let box_x =
+                text_x + barcode.data.chars().count() as f64 * 0.3 * font_size + 4.0 * scale;
+            let frame = Rectangle::new()
+                .set("x", box_x)
+                .set("y", text_y - 11.0 * scale)
+                .set("width", 11.0 * scale)
+                .set("height", 14.0 * scale)
+                .set("fill", "none")
+                .set("stroke", "black")
+                .set("stroke-width", scale);
+            let character = Text::new(boxed.to_string())
+                .set("x", box_x + 5.5 * scale)
+                .set("y", text_y)
+                .set("text-anchor", "middle")
+                .set("font-family", "monospace")
+                .set("font-size", font_size)
+                .set("fill", "black");
+            document = document.add(frame).add(character);
+        }
     }
 
     // Convert to bytes
@@ -119,17 +162,40 @@ fn export_matrix_svg(barcode: &Barcode, matrix: &[Vec<bool>]) -> Result<Vec<u8>>
                 let x = margin + (col as f64 * module_size);
                 let y = margin + (row as f64 * module_size);
 
-                let module = Rectangle::new()
-                    .set("x", x)
-                    .set("y", y)
-                    .set("width", module_size)
-                    .set("height", module_size)
-                    .set("fill", "black");
-                document = document.add(module);
+                document = match barcode.config.module_shape {
+                    ModuleShape::Square => document.add(
+                        Rectangle::new()
+                            .set("x", x)
+                            .set("y", y)
+                            .set("width", module_size)
+                            .set("height", module_size)
+                            .set("fill", "black"),
+                    ),
+                    ModuleShape::Dot => document.add(
+                        Circle::new()
+                            .set("cx", x + module_size / 2.0)
+                            .set("cy", y + module_size / 2.0)
+                            .set("r", module_size / 2.0)
+                            .set("fill", "black"),
+                    ),
+                };
             }
         }
     }
 
+    if barcode.config.swiss_cross {
+        for part in swiss_cross() {
+            document = document.add(
+                Rectangle::new()
+                    .set("x", margin + part.x * matrix_width)
+                    .set("y", margin + part.y * matrix_width)
+                    .set("width", part.width * matrix_width)
+                    .set("height", part.height * matrix_width)
+                    .set("fill", if part.dark { "black" } else { "white" }),
+            );
+        }
+    }
+
     // Add human-readable text if enabled
     if barcode.config.include_text {
         let text_y = margin + matrix_height + 15.0;
@@ -162,6 +228,171 @@ fn export_matrix_svg(barcode: &Barcode, matrix: &[Vec<bool>]) -> Result<Vec<u8>>
     Ok(svg_string.into_bytes())
 }
 
+/// Export a height-modulated barcode to SVG at its physical size
+///
+/// Coordinates are in millimetres; the configured margin (in pixels) is
+/// converted assuming 96 DPI.
+#[cfg(feature = "svg")]
+fn export_bars_svg(
+    barcode: &Barcode,
+    bars: &[BarState],
+    geometry: &BarGeometry,
+) -> Result<Vec<u8>> {
+    if bars.is_empty() {
+        return Err(QuickCodesError::ExportError(
+            "Bar list is empty".to_string(),
+        ));
+    }
+
+    let margin = barcode.config.margin as f64 * MM_PER_INCH / 96.0;
+    let text_height = if barcode.config.include_text {
+        5.0
+    } else {
+        0.0
+    };
+
+    let total_width = geometry.width(bars.len()) + (2.0 * margin);
+    let total_height = geometry.height + text_height + (2.0 * margin);
+
+    let mut document = Document::new()
+        .set("width", format!("{}mm", total_width))
+        .set("height", format!("{}mm", total_height))
+        .set("viewBox", (0, 0, total_width, total_height))
+        .set("xmlns", "http://www.w3.org/2000/svg");
+
+    // White background
+    let background = Rectangle::new()
+        .set("width", "100%")
+        .set("height", "100%")
+        .set("fill", "white");
+    document = document.add(background);
+
+    // Draw bars
+    for (i, bar) in bars.iter().enumerate() {
+        let (top, bottom) = bar.span(geometry);
+        let rect = Rectangle::new()
+            .set("x", margin + i as f64 * geometry.pitch)
+            .set("y", margin + top)
+            .set("width", geometry.bar_width)
+            .set("height", bottom - top)
+            .set("fill", "black");
+        document = document.add(rect);
+    }
+
+    // Add human-readable text if enabled
+    if barcode.config.include_text {
+        let text = Text::new(&barcode.data)
+            .set("x", total_width / 2.0)
+            .set("y", margin + geometry.height + 4.0)
+            .set("text-anchor", "middle")
+            .set("font-family", "monospace")
+            .set("font-size", "3")
+            .set("fill", "black");
+        document = document.add(text);
+    }
+
+    // Convert to bytes
+    let svg_string = document.to_string();
+    Ok(svg_string.into_bytes())
+}
+
+/// Export a hexagonal-grid (MaxiCode) barcode to SVG
+#[cfg(feature = "svg")]
+fn export_hexagonal_svg(barcode: &Barcode, grid: &[Vec<bool>]) -> Result<Vec<u8>> {
+    if grid.is_empty() || grid[0].is_empty() {
+        return Err(QuickCodesError::ExportError("Grid is empty".to_string()));
+    }
+
+    let module_width = 8.0; // Width of each hexagon in SVG units
+    let margin = barcode.config.margin as f64;
+    let text_height = if barcode.config.include_text {
+        20.0
+    } else {
+        0.0
+    };
+
+    let (grid_width, grid_height) = hex_grid_size(grid.len(), grid[0].len());
+    let grid_width = grid_width * module_width;
+    let grid_height = grid_height * module_width;
+    let total_width = grid_width + (2.0 * margin);
+    let total_height = grid_height + text_height + (2.0 * margin);
+
+    let mut document = Document::new()
+        .set("width", total_width)
+        .set("height", total_height)
+        .set("viewBox", (0.0, 0.0, total_width, total_height))
+        .set("xmlns", "http://www.w3.org/2000/svg");
+
+    // White background
+    let background = Rectangle::new()
+        .set("width", "100%")
+        .set("height", "100%")
+        .set("fill", "white");
+    document = document.add(background);
+
+    // Draw hexagonal modules, pointing up and down
+    let radius = HEX_RADIUS * module_width;
+    for (row, row_data) in grid.iter().enumerate() {
+        for (col, &is_black) in row_data.iter().enumerate() {
+            if is_black {
+                let (x, y) = hex_center(row, col);
+                let (x, y) = (margin + x * module_width, margin + y * module_width);
+                let half = module_width / 2.0;
+                let points = format!(
+                    "{},{} {},{} {},{} {},{} {},{} {},{}",
+                    x,
+                    y - radius,
+                    x + half,
+                    y - radius / 2.0,
+                    x + half,
+                    y + radius / 2.0,
+                    x,
+                    y + radius,
+                    x - half,
+                    y + radius / 2.0,
+                    x - half,
+                    y - radius / 2.0
+                );
+                document = document.add(Polygon::new().set("points", points).set("fill", "black"));
+            }
+        }
+    }
+
+    // Bullseye: alternating dark and light circles, outermost first
+    let (x, y) = bullseye_center(grid.len(), grid[0].len());
+    for (i, r) in BULLSEYE_RADII.iter().enumerate() {
+        let circle = Circle::new()
+            .set("cx", margin + x * module_width)
+            .set("cy", margin + y * module_width)
+            .set("r", r * module_width)
+            .set("fill", if i % 2 == 0 { "black" } else { "white" });
+        document = document.add(circle);
+    }
+
+    // Add human-readable text if enabled
+    if barcode.config.include_text {
+        // Control characters (GS, RS, EOT) are not allowed in XML
+        let display_text: String = barcode
+            .data
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect();
+
+        let text = Text::new(&display_text)
+            .set("x", total_width / 2.0)
+            .set("y", margin + grid_height + 15.0)
+            .set("text-anchor", "middle")
+            .set("font-family", "monospace")
+            .set("font-size", "10")
+            .set("fill", "black");
+        document = document.add(text);
+    }
+
+    // Convert to bytes
+    let svg_string = document.to_string();
+    Ok(svg_string.into_bytes())
+}
+
 #[cfg(all(test, feature = "svg"))]
 mod tests {
     use super::*;
@@ -192,4 +423,92 @@ mod tests {
         assert!(svg_string.contains("rect"));
         assert!(svg_string.contains("1234567890128")); // Should include the text
     }
+
+    #[test]
+    fn test_svg_export_height_modulated() {
+        use crate::generators::pharmacode::{generate_pharmacode_with_tracks, PharmacodeTracks};
+
+        let barcode = generate_pharmacode_with_tracks("1234", PharmacodeTracks::Two).unwrap();
+        let svg_data = export_svg(&barcode);
+        assert!(svg_data.is_ok());
+
+        let svg_string = String::from_utf8(svg_data.unwrap()).unwrap();
+        assert!(svg_string.contains("mm\""));
+        assert!(svg_string.contains("rect"));
+    }
+
+    #[test]
+    fn test_svg_export_linear_dimensions() {
+        let mut barcode =
+            crate::generators::interleaved2of5::generate_interleaved_2of5("1234").unwrap();
+        barcode.config.margin = 0;
+        barcode.config.dimensions = Some(crate::types::LinearDimensions {
+            module_width: 0.5,
+            height: 10.0,
+        });
+        let modules = barcode.modules.as_linear().unwrap().len();
+
+        let svg_string = String::from_utf8(export_svg(&barcode).unwrap()).unwrap();
+        assert!(svg_string.contains(&format!("width=\"{}mm\"", modules as f64 * 0.5)));
+        assert!(svg_string.contains("height=\"10\""));
+    }
+
+    #[test]
+    fn test_svg_export_hexagonal() {
+        let barcode = crate::generators::maxicode::generate_maxicode("MAXICODE TEST").unwrap();
+        let svg_data = export_svg(&barcode);
+        assert!(svg_data.is_ok());
+
+        let svg_string = String::from_utf8(svg_data.unwrap()).unwrap();
+        assert!(svg_string.contains("<polygon"));
+        assert_eq!(svg_string.matches("<circle").count(), BULLSEYE_RADII.len());
+    }
+
+    #[test]
+    fn test_svg_export_dots() {
+        let barcode = crate::generators::dotcode::generate_dotcode("DOTCODE").unwrap();
+        let dots = barcode
+            .modules
+            .as_matrix()
+            .unwrap()
+            .iter()
+            .flatten()
+            .filter(|&&dot| dot)
+            .count();
+
+        let svg_string = String::from_utf8(export_svg(&barcode).unwrap()).unwrap();
+        assert_eq!(svg_string.matches("<circle").count(), dots);
+        // Only the background is a rectangle
+        assert_eq!(svg_string.matches("<rect").count(), 1);
+    }
+
+    #[test]
+    fn test_svg_export_boxed_character() {
+        let mut barcode = crate::generators::code39::generate_code39("W1234").unwrap();
+        let plain = String::from_utf8(export_svg(&barcode).unwrap()).unwrap();
+
+        barcode.config.boxed_character = Some('K');
+        let boxed = String::from_utf8(export_svg(&barcode).unwrap()).unwrap();
+        assert_eq!(
+            boxed.matches("<text").count(),
+            plain.matches("<text").count() + 1
+        );
+        assert!(boxed.contains("stroke=\"black\""));
+        assert!(boxed.contains("\nK\n</text>"));
+    }
+
+    #[test]
+    fn test_svg_export_swiss_cross() {
+        let mut barcode = crate::generators::generate_qr("SPC").unwrap();
+        let plain = String::from_utf8(export_svg(&barcode).unwrap()).unwrap();
+
+        barcode.config.swiss_cross = true;
+        let crossed = String::from_utf8(export_svg(&barcode).unwrap()).unwrap();
+        // White border, black square and the two arms, drawn last
+        assert_eq!(
+            crossed.matches("<rect").count(),
+            plain.matches("<rect").count() + 4
+        );
+        assert_eq!(crossed.matches("fill=\"white\"").count(), 4);
+    }
 }
@@ -0,0 +1,7 @@
+//! Brazilian fiscal document identifiers encoded with the existing generators
+
+pub mod nfe;
+
+pub use nfe::{
+    generate_access_key, generate_nfce_qrcode, AccessKey, Environment, NfceOffline, NfceQrCode,
+};
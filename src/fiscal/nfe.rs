@@ -0,0 +1,423 @@
+//! NF-e and NFC-e access keys and consumer QR codes (Manual de Orientação
+//! do Contribuinte)
+//!
+//! Every electronic invoice is identified by a 44-digit access key that
+//! carries the issuer, model, series and number, ending in a modulo-11
+//! check digit. The DANFE prints it as a Code 128 code set C barcode. NFC-e
+//! consumer receipts add a QR code pointing to the state SEFAZ query page,
+//! signed with a SHA-1 hash of the parameters and the taxpayer security
+//! code (CSC).
+
+use sha1::{Digest, Sha1};
+
+use crate::generators;
+use crate::types::{Barcode, QuickCodesError, Result};
+
+/// IBGE codes of the states
+const STATE_CODES: [u8; 27] = [
+    11, 12, 13, 14, 15, 16, 17, 21, 22, 23, 24, 25, 26, 27, 28, 29, 31, 32, 33, 35, 41, 42, 43, 50,
+    51, 52, 53,
+];
+
+/// Invoice models
+const MODEL_NFE: u8 = 55;
+const MODEL_NFCE: u8 = 65;
+
+/// Emission type of NFC-e issued offline (contingência off-line)
+const EMISSION_OFFLINE: u8 = 9;
+
+/// Version of the consumer QR code parameters
+const QR_VERSION: u8 = 2;
+
+/// Access key (chave de acesso) of an NF-e or NFC-e
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessKey {
+    /// IBGE code of the issuer's state, e.g. 35 for São Paulo
+    pub state: u8,
+    /// Year of issue (last two digits)
+    pub year: u8,
+    /// Month of issue
+    pub month: u8,
+    /// Issuer CNPJ, or CPF padded with zeros (14 digits)
+    pub issuer: String,
+    /// Model: 55 (NF-e) or 65 (NFC-e)
+    pub model: u8,
+    /// Series (0-999)
+    pub series: u16,
+    /// Invoice number (1-999999999)
+    pub number: u32,
+    /// Emission type (1 normal, 2-9 contingency)
+    pub emission_type: u8,
+    /// Random numeric code chosen by the issuer (8 digits)
+    pub code: u32,
+}
+
+/// SEFAZ environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Environment {
+    Production,
+    /// Homologation (testing)
+    Homologation,
+}
+
+/// Data of an NFC-e issued offline, which the QR code carries because the
+/// invoice is not yet authorized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NfceOffline {
+    /// Day of the month the invoice was issued
+    pub day: u8,
+    /// Invoice total in centavos
+    pub total: u64,
+    /// DigestValue of the invoice signature, as in the XML (Base64)
+    pub digest_value: String,
+}
+
+/// NFC-e consumer QR code, version 2
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NfceQrCode {
+    /// Access key of a model 65 invoice
+    pub access_key: AccessKey,
+    pub environment: Environment,
+    /// Identifier of the CSC at the SEFAZ (cIdToken)
+    pub csc_id: u32,
+    /// Taxpayer security code (CSC) provided by the SEFAZ
+    pub csc: String,
+    /// Required when the key has emission type 9
+    pub offline: Option<NfceOffline>,
+}
+
+impl AccessKey {
+    /// Parse and validate a 44-digit access key; spaces are ignored, as the
+    /// DANFE prints the key in groups of four digits
+    pub fn parse(key: &str) -> Result<Self> {
+        let digits: String = key.chars().filter(|&c| c != ' ').collect();
+        if digits.len() != 44 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("the access key must have 44 digits"));
+        }
+        let number =
+            |range: std::ops::Range<usize>| -> u32 { digits[range].parse().expect("digits") };
+        let access_key = Self {
+            state: number(0..2) as u8,
+            year: number(2..4) as u8,
+            month: number(4..6) as u8,
+            issuer: digits[6..20].to_string(),
+            model: number(20..22) as u8,
+            series: number(22..25) as u16,
+            number: number(25..34),
+            emission_type: number(34..35) as u8,
+            code: number(35..43),
+        };
+        let expected = access_key.encode()?;
+        if expected != digits {
+            return Err(invalid(&format!(
+                "wrong check digit {}, expected {}",
+                &digits[43..],
+                &expected[43..]
+            )));
+        }
+        Ok(access_key)
+    }
+
+    /// The 44 digits of the key, including the check digit
+    pub fn encode(&self) -> Result<String> {
+        if !STATE_CODES.contains(&self.state) {
+            return Err(invalid(&format!("unknown state code {}", self.state)));
+        }
+        if self.year > 99 || !(1..=12).contains(&self.month) {
+            return Err(invalid(&format!(
+                "invalid year and month {:02}/{:02}",
+                self.month, self.year
+            )));
+        }
+        if self.issuer.len() != 14 || !self.issuer.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("the issuer CNPJ must have 14 digits"));
+        }
+        if self.model != MODEL_NFE && self.model != MODEL_NFCE {
+            return Err(invalid(&format!(
+                "model must be 55 or 65, got {}",
+                self.model
+            )));
+        }
+        if self.series > 999 {
+            return Err(invalid(&format!(
+                "series must be 0-999, got {}",
+                self.series
+            )));
+        }
+        if !(1..=999_999_999).contains(&self.number) {
+            return Err(invalid(&format!(
+                "number must be 1-999999999, got {}",
+                self.number
+            )));
+        }
+        if !(1..=9).contains(&self.emission_type) {
+            return Err(invalid(&format!(
+                "emission type must be 1-9, got {}",
+                self.emission_type
+            )));
+        }
+        if self.code > 99_999_999 {
+            return Err(invalid("the numeric code is longer than 8 digits"));
+        }
+
+        let body = format!(
+            "{:02}{:02}{:02}{}{:02}{:03}{:09}{}{:08}",
+            self.state,
+            self.year,
+            self.month,
+            self.issuer,
+            self.model,
+            self.series,
+            self.number,
+            self.emission_type,
+            self.code
+        );
+        let check = check_digit(&body);
+        Ok(format!("{}{}", body, check))
+    }
+
+    /// The key in groups of four digits, as printed on the DANFE
+    pub fn formatted(&self) -> Result<String> {
+        let key = self.encode()?;
+        let groups: Vec<&str> = (0..11).map(|i| &key[i * 4..(i + 1) * 4]).collect();
+        Ok(groups.join(" "))
+    }
+}
+
+impl NfceQrCode {
+    /// Query URL for the QR code, from the state's SEFAZ query page (for
+    /// example `https://www.sefaz.rs.gov.br/NFCE/NFCE-COM.aspx`)
+    pub fn url(&self, base_url: &str) -> Result<String> {
+        if base_url.is_empty() {
+            return Err(invalid("the SEFAZ query URL is empty"));
+        }
+        let separator = if base_url.contains('?') { '&' } else { '?' };
+        Ok(format!("{}{}p={}", base_url, separator, self.parameters()?))
+    }
+
+    /// The `p` parameter: key, version, environment, offline data, CSC
+    /// identifier and hash, separated by `|`
+    pub fn parameters(&self) -> Result<String> {
+        let key = self.access_key.encode()?;
+        if self.access_key.model != MODEL_NFCE {
+            return Err(invalid("consumer QR codes are only for NFC-e (model 65)"));
+        }
+        if self.csc_id == 0 || self.csc_id > 999_999 {
+            return Err(invalid("the CSC identifier must be 1-999999"));
+        }
+        if !(16..=36).contains(&self.csc.len())
+            || !self.csc.bytes().all(|b| b.is_ascii_alphanumeric())
+        {
+            return Err(invalid("the CSC must have 16-36 letters and digits"));
+        }
+
+        let environment = match self.environment {
+            Environment::Production => 1,
+            Environment::Homologation => 2,
+        };
+        let mut fields = vec![key, QR_VERSION.to_string(), environment.to_string()];
+        match (&self.offline, self.access_key.emission_type) {
+            (Some(offline), EMISSION_OFFLINE) => {
+                if !(1..=31).contains(&offline.day) {
+                    return Err(invalid(&format!("invalid day of issue {}", offline.day)));
+                }
+                if offline.digest_value.is_empty() {
+                    return Err(invalid(
+                        "the offline QR code needs the signature DigestValue",
+                    ));
+                }
+                fields.push(format!("{:02}", offline.day));
+                fields.push(format!(
+                    "{}.{:02}",
+                    offline.total / 100,
+                    offline.total % 100
+                ));
+                fields.push(hex(offline.digest_value.as_bytes(), false));
+            }
+            (None, EMISSION_OFFLINE) => {
+                return Err(invalid(
+                    "offline NFC-e (emission type 9) needs the offline data",
+                ));
+            }
+            (Some(_), _) => {
+                return Err(invalid("offline data is only for emission type 9"));
+            }
+            (None, _) => {}
+        }
+        fields.push(self.csc_id.to_string());
+
+        let signed = fields.join("|");
+        let hash = Sha1::digest(format!("{}{}", signed, self.csc).as_bytes());
+        Ok(format!("{}|{}", signed, hex(&hash, true)))
+    }
+}
+
+/// Generate the DANFE barcode of an access key (Code 128 code set C)
+pub fn generate_access_key(access_key: &AccessKey) -> Result<Barcode> {
+    generators::generate_code128_set_c(&access_key.encode()?)
+}
+
+/// Generate the NFC-e consumer QR code for a SEFAZ query page
+pub fn generate_nfce_qrcode(qr_code: &NfceQrCode, base_url: &str) -> Result<Barcode> {
+    generators::generate_qr(&qr_code.url(base_url)?)
+}
+
+/// Modulo-11 check digit with weights 2-9 from the right; remainders 0 and
+/// 1 give 0
+fn check_digit(digits: &str) -> u8 {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .zip((2..=9).cycle())
+        .map(|(b, weight)| u32::from(b - b'0') * weight)
+        .sum();
+    match sum % 11 {
+        0 | 1 => 0,
+        remainder => (11 - remainder) as u8,
+    }
+}
+
+fn hex(bytes: &[u8], upper: bool) -> String {
+    bytes
+        .iter()
+        .map(|b| {
+            if upper {
+                format!("{:02X}", b)
+            } else {
+                format!("{:02x}", b)
+            }
+        })
+        .collect()
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid NF-e data: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BarcodeType;
+
+    const CSC: &str = "123456789012345678901234567890123456";
+    const BASE_URL: &str = "https://www.sefaz.rs.gov.br/NFCE/NFCE-COM.aspx";
+
+    fn nfce_key(emission_type: u8) -> AccessKey {
+        AccessKey {
+            state: 43,
+            year: 25,
+            month: 10,
+            issuer: "08530528000184".to_string(),
+            model: 65,
+            series: 1,
+            number: 123,
+            emission_type,
+            code: 42,
+        }
+    }
+
+    fn qr_code(access_key: AccessKey) -> NfceQrCode {
+        NfceQrCode {
+            access_key,
+            environment: Environment::Homologation,
+            csc_id: 1,
+            csc: CSC.to_string(),
+            offline: None,
+        }
+    }
+
+    #[test]
+    fn test_access_key() {
+        let key = nfce_key(1);
+        assert_eq!(
+            key.encode().unwrap(),
+            "43251008530528000184650010000001231000000421"
+        );
+        assert_eq!(
+            key.formatted().unwrap(),
+            "4325 1008 5305 2800 0184 6500 1000 0001 2310 0000 0421"
+        );
+        assert_eq!(AccessKey::parse(&key.formatted().unwrap()).unwrap(), key);
+
+        let nfe = AccessKey::parse("35170608530528000184550000000000011000000015").unwrap();
+        assert_eq!(nfe.state, 35);
+        assert_eq!(nfe.model, 55);
+        assert_eq!(nfe.number, 1);
+    }
+
+    #[test]
+    fn test_access_key_validation() {
+        assert!(AccessKey::parse("35170608530528000184550000000000011000000016").is_err());
+        assert!(AccessKey::parse("3517060853052800018455000000000001100000001").is_err());
+        // Unknown state 99 with a matching check digit
+        let mut key = nfce_key(1);
+        key.state = 99;
+        assert!(key.encode().is_err());
+        let mut key = nfce_key(1);
+        key.model = 57;
+        assert!(key.encode().is_err());
+        let mut key = nfce_key(1);
+        key.month = 13;
+        assert!(key.encode().is_err());
+        let mut key = nfce_key(1);
+        key.number = 0;
+        assert!(key.encode().is_err());
+    }
+
+    #[test]
+    fn test_access_key_barcode() {
+        let barcode = generate_access_key(&nfce_key(1)).unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::Code128);
+        // Start, 22 digit pairs, check character and stop
+        assert_eq!(barcode.modules.as_linear().unwrap().len(), 24 * 11 + 13);
+    }
+
+    #[test]
+    fn test_nfce_online_qrcode() {
+        let qr_code = qr_code(nfce_key(1));
+        assert_eq!(
+            qr_code.url(BASE_URL).unwrap(),
+            "https://www.sefaz.rs.gov.br/NFCE/NFCE-COM.aspx?p=43251008530528000184650010000001231000000421\
+             |2|2|1|93D6B593269CCCB6B6CA8C97DE152F6DD3FC91DA"
+        );
+        assert!(qr_code
+            .url("https://example.com/qr?uf=rs")
+            .unwrap()
+            .starts_with("https://example.com/qr?uf=rs&p=4325"));
+        assert!(generate_nfce_qrcode(&qr_code, BASE_URL).is_ok());
+    }
+
+    #[test]
+    fn test_nfce_offline_qrcode() {
+        let mut qr_code = qr_code(nfce_key(9));
+        assert!(qr_code.parameters().is_err());
+
+        qr_code.offline = Some(NfceOffline {
+            day: 15,
+            total: 5990,
+            digest_value: "hP3mT8Cz5Bd0XLeY5Uk2kfGWHnM=".to_string(),
+        });
+        assert_eq!(
+            qr_code.parameters().unwrap(),
+            "43251008530528000184650010000001239000000427|2|2|15|59.90|\
+             6850336d5438437a35426430584c655935556b326b664757486e4d3d|1|\
+             F0AB74BA843FB48E79A29A14BBDED3D73CD3DED6"
+        );
+    }
+
+    #[test]
+    fn test_nfce_validation() {
+        // NF-e (model 55) has no consumer QR code
+        let mut key = nfce_key(1);
+        key.model = 55;
+        assert!(qr_code(key).parameters().is_err());
+
+        let mut qr_code = self::qr_code(nfce_key(1));
+        qr_code.csc = "short".to_string();
+        assert!(qr_code.parameters().is_err());
+        let mut qr_code = self::qr_code(nfce_key(1));
+        qr_code.csc_id = 0;
+        assert!(qr_code.parameters().is_err());
+        assert!(self::qr_code(nfce_key(1)).url("").is_err());
+    }
+}
@@ -0,0 +1,302 @@
+//! Australia Post 4-state customer barcode generator
+//!
+//! The customer barcodes encode an 8-digit Delivery Point Identifier (DPID)
+//! and optional customer information, protected by a Reed-Solomon code
+//! over GF(64):
+//!
+//! - Standard Customer Barcode (FCC 11): 37 bars, DPID only
+//! - Customer Barcode 2 (FCC 59): 52 bars, up to 8 digits or 5 characters of customer information
+//! - Customer Barcode 3 (FCC 62): 67 bars, up to 15 digits or 10 characters of customer information
+
+use crate::types::{
+    BarGeometry, BarState, Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError,
+    Result,
+};
+
+const DPID_LENGTH: usize = 8;
+
+/// Character set of the C encoding table
+const C_CHARSET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz #";
+
+/// C encoding table: three bar values per character
+/// (0 = full, 1 = ascender, 2 = descender, 3 = tracker)
+const C_TABLE: [&str; 64] = [
+    "222", "300", "301", "302", "310", "311", "312", "320", "321", "322", "000", "001", "002",
+    "010", "011", "012", "020", "021", "022", "100", "101", "102", "110", "111", "112", "120",
+    "121", "122", "200", "201", "202", "210", "211", "212", "220", "221", "023", "030", "031",
+    "032", "033", "103", "113", "123", "130", "131", "132", "133", "203", "213", "223", "230",
+    "231", "232", "233", "303", "313", "323", "330", "331", "332", "333", "003", "013",
+];
+
+/// N encoding table: two bar values per digit
+const N_TABLE: [&str; 10] = ["00", "01", "02", "10", "11", "12", "20", "21", "22", "30"];
+
+/// Start and stop bars (ascender, tracker)
+const START_STOP: &str = "13";
+
+/// Reed-Solomon parity symbols, three bars each
+const PARITY_SYMBOLS: usize = 4;
+
+/// GF(64) primitive polynomial x^6 + x + 1
+const GF_POLYNOMIAL: u8 = 0x43;
+
+/// Nominal dimensions: 0.5 mm bars at 1.2 mm pitch, 5 mm full bars and a
+/// centred 1.4 mm tracker
+const AUSPOST_GEOMETRY: BarGeometry = BarGeometry {
+    bar_width: 0.5,
+    pitch: 1.2,
+    height: 5.0,
+    tracker_top: 1.8,
+    tracker_bottom: 3.2,
+};
+
+/// Customer barcode format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Standard,
+    Customer2,
+    Customer3,
+}
+
+impl Format {
+    fn fcc(self) -> &'static str {
+        match self {
+            Format::Standard => "11",
+            Format::Customer2 => "59",
+            Format::Customer3 => "62",
+        }
+    }
+
+    /// Bars between the start bars and the Reed-Solomon parity
+    fn data_bars(self) -> usize {
+        match self {
+            Format::Standard => 21,
+            Format::Customer2 => 36,
+            Format::Customer3 => 51,
+        }
+    }
+}
+
+/// Generate an Australia Post customer barcode from an 8-digit DPID followed
+/// by optional customer information; the format (37, 52 or 67 bars) is
+/// selected from the length of the customer information
+pub fn generate_auspost(data: &str) -> Result<Barcode> {
+    if data.len() < DPID_LENGTH || !data.is_char_boundary(DPID_LENGTH) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Australia Post barcode requires an {}-digit DPID",
+            DPID_LENGTH
+        )));
+    }
+
+    let (dpid, customer) = data.split_at(DPID_LENGTH);
+    if !dpid.chars().all(|c| c.is_ascii_digit()) {
+        return Err(QuickCodesError::InvalidData(
+            "Australia Post DPID must contain only digits".to_string(),
+        ));
+    }
+
+    let numeric = customer.chars().all(|c| c.is_ascii_digit());
+    if let Some(c) = customer.chars().find(|&c| !C_CHARSET.contains(c)) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Invalid character '{}' in Australia Post customer information",
+            c
+        )));
+    }
+
+    let format = match (customer.len(), numeric) {
+        (0, _) => Format::Standard,
+        (1..=8, true) | (1..=5, false) => Format::Customer2,
+        (9..=15, true) | (6..=10, false) => Format::Customer3,
+        _ => {
+            return Err(QuickCodesError::InvalidData(
+                "Australia Post customer information is limited to 15 digits or 10 characters"
+                    .to_string(),
+            ))
+        }
+    };
+
+    let mut values = String::new();
+    for digit in format.fcc().bytes().chain(dpid.bytes()) {
+        values.push_str(N_TABLE[usize::from(digit - b'0')]);
+    }
+    for c in customer.chars() {
+        if numeric {
+            values.push_str(N_TABLE[c as usize - '0' as usize]);
+        } else {
+            values.push_str(C_TABLE[C_CHARSET.find(c).unwrap_or(0)]);
+        }
+    }
+    // Filler bars
+    while values.len() < format.data_bars() {
+        values.push('3');
+    }
+
+    let symbols: Vec<u8> = values
+        .as_bytes()
+        .chunks(3)
+        .map(|triple| triple.iter().fold(0, |acc, &v| acc * 4 + (v - b'0')))
+        .collect();
+    for symbol in reed_solomon_parity(&symbols) {
+        for shift in [4, 2, 0] {
+            values.push((b'0' + ((symbol >> shift) & 3)) as char);
+        }
+    }
+
+    let bars = START_STOP
+        .chars()
+        .chain(values.chars())
+        .chain(START_STOP.chars())
+        .map(|value| match value {
+            '0' => BarState::Full,
+            '1' => BarState::Ascender,
+            '2' => BarState::Descender,
+            _ => BarState::Tracker,
+        })
+        .collect();
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::AustraliaPost,
+        data: data.to_string(),
+        modules: BarcodeModules::HeightModulated {
+            bars,
+            geometry: AUSPOST_GEOMETRY,
+        },
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// GF(64) arithmetic through exponent and logarithm tables
+struct Gf64 {
+    exp: [u8; 63],
+    log: [u8; 64],
+}
+
+impl Gf64 {
+    fn new() -> Self {
+        let mut exp = [0u8; 63];
+        let mut log = [0u8; 64];
+        let mut x = 1u8;
+        for (i, e) in exp.iter_mut().enumerate() {
+            *e = x;
+            log[usize::from(x)] = i as u8;
+            x <<= 1;
+            if x & 0x40 != 0 {
+                x ^= GF_POLYNOMIAL;
+            }
+        }
+        Self { exp, log }
+    }
+
+    fn mul(&self, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            let sum = usize::from(self.log[usize::from(a)]) + usize::from(self.log[usize::from(b)]);
+            self.exp[sum % 63]
+        }
+    }
+}
+
+/// Reed-Solomon parity symbols, highest degree first, for a generator
+/// polynomial with roots a^1 to a^4
+fn reed_solomon_parity(data: &[u8]) -> [u8; PARITY_SYMBOLS] {
+    let gf = Gf64::new();
+
+    // Generator coefficients, highest degree first
+    let mut generator = vec![1u8];
+    for root in &gf.exp[1..=PARITY_SYMBOLS] {
+        let mut next = generator.clone();
+        next.push(0);
+        for (i, &coefficient) in generator.iter().enumerate() {
+            next[i + 1] ^= gf.mul(coefficient, *root);
+        }
+        generator = next;
+    }
+
+    let mut parity = [0u8; PARITY_SYMBOLS];
+    for &symbol in data {
+        let feedback = symbol ^ parity[0];
+        parity.rotate_left(1);
+        parity[PARITY_SYMBOLS - 1] = 0;
+        for (p, &g) in parity.iter_mut().zip(&generator[1..]) {
+            *p ^= gf.mul(feedback, g);
+        }
+    }
+    parity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar_values(barcode: &Barcode) -> Vec<u8> {
+        barcode
+            .modules
+            .as_bars()
+            .unwrap()
+            .iter()
+            .map(|bar| match bar {
+                BarState::Full => 0,
+                BarState::Ascender => 1,
+                BarState::Descender => 2,
+                BarState::Tracker => 3,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_auspost_formats() {
+        let standard = generate_auspost("96184209").unwrap();
+        assert_eq!(standard.barcode_type, BarcodeType::AustraliaPost);
+        assert_eq!(standard.modules.as_bars().unwrap().len(), 37);
+
+        let customer2 = generate_auspost("3999999912345678").unwrap();
+        assert_eq!(customer2.modules.as_bars().unwrap().len(), 52);
+        assert_eq!(
+            generate_auspost("39999999ABcd ")
+                .unwrap()
+                .modules
+                .as_bars()
+                .unwrap()
+                .len(),
+            52
+        );
+
+        let customer3 = generate_auspost("39999999123456789012345").unwrap();
+        assert_eq!(customer3.modules.as_bars().unwrap().len(), 67);
+    }
+
+    #[test]
+    fn test_auspost_structure() {
+        let values = bar_values(&generate_auspost("96184209").unwrap());
+        // Start and stop bars
+        assert_eq!(&values[..2], &[1, 3]);
+        assert_eq!(&values[35..], &[1, 3]);
+        // FCC 11 in N encoding
+        assert_eq!(&values[2..6], &[0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_auspost_reed_solomon() {
+        // A valid codeword has zero syndromes at the generator roots
+        let gf = Gf64::new();
+        let values = bar_values(&generate_auspost("3999999912345678").unwrap());
+        let symbols: Vec<u8> = values[2..values.len() - 2]
+            .chunks(3)
+            .map(|triple| triple.iter().fold(0, |acc, &v| acc * 4 + v))
+            .collect();
+
+        for root in &gf.exp[1..=PARITY_SYMBOLS] {
+            let syndrome = symbols.iter().fold(0, |acc, &s| gf.mul(acc, *root) ^ s);
+            assert_eq!(syndrome, 0);
+        }
+    }
+
+    #[test]
+    fn test_auspost_invalid_data() {
+        assert!(generate_auspost("1234567").is_err());
+        assert!(generate_auspost("1234567A").is_err());
+        assert!(generate_auspost("12345678!").is_err());
+        assert!(generate_auspost("123456781234567890123456").is_err());
+    }
+}
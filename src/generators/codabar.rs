@@ -13,6 +13,7 @@ const CODABAR_PATTERNS: &[(&str, &str)] = &[
 /// Gera um código de barras Codabar
 ///
 /// O Codabar é usado principalmente em bibliotecas, bancos de sangue e etiquetas de remessa.
+/// Bancos de sangue atuais usam ISBT 128 (ver `healthcare::isbt128`).
 /// Ele pode codificar dígitos, alguns símbolos especiais e as letras A-D (usadas como start/stop).
 ///
 /// # Arguments
@@ -0,0 +1,262 @@
+//! Codablock F generator (AIM Europe ISS Codablock F)
+//!
+//! Codablock F stacks 2 to 44 rows that are each a complete Code 128
+//! symbol: start character, code set selector and row indicator, the data
+//! characters, a modulo-103 row check character and the stop character.
+//! The last row also carries two modulo-86 check characters over the whole
+//! message. Rows are separated by bars that leave the start and stop
+//! characters clear.
+
+use super::code128::{self, CodeSet, CODE_B, CODE_C, PATTERNS, SHIFT, START_A, STOP};
+use crate::types::{Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError, Result};
+
+const MIN_ROWS: usize = 2;
+const MAX_ROWS: usize = 44;
+const MIN_COLUMNS: usize = 4;
+const MAX_COLUMNS: usize = 62;
+
+/// Row height in modules, not counting the separator bars
+const ROW_HEIGHT: usize = 10;
+
+/// Codablock F configuration
+#[derive(Debug, Clone, Default)]
+pub struct CodablockConfig {
+    /// Data characters per row (4-62), chosen from the data length when
+    /// not set
+    pub columns: Option<usize>,
+}
+
+/// Generate a Codablock F symbol with automatic row width
+pub fn generate_codablock_f(data: &str) -> Result<Barcode> {
+    generate_codablock_f_with_config(data, &CodablockConfig::default())
+}
+
+/// Generate a Codablock F symbol with a fixed number of data characters
+/// per row
+pub fn generate_codablock_f_with_config(data: &str, config: &CodablockConfig) -> Result<Barcode> {
+    let rows = encode(data, config)?;
+
+    let width = rows[0].len() * 11 + 2;
+    let separator = |full: bool| -> Vec<bool> {
+        (0..width)
+            .map(|x| full || (11..width - 13).contains(&x))
+            .collect()
+    };
+
+    let mut matrix = vec![separator(true)];
+    for (i, values) in rows.iter().enumerate() {
+        let mut modules = Vec::with_capacity(width);
+        for &value in values {
+            code128::append_widths(&mut modules, PATTERNS[usize::from(value)], true);
+        }
+        matrix.extend(std::iter::repeat(modules).take(ROW_HEIGHT));
+        matrix.push(separator(i + 1 == rows.len()));
+    }
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::CodablockF,
+        data: data.to_string(),
+        modules: BarcodeModules::Matrix(matrix),
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Symbol character values of every row, from the start character to the
+/// stop character
+fn encode(data: &str, config: &CodablockConfig) -> Result<Vec<Vec<u8>>> {
+    if data.is_empty() {
+        return Err(QuickCodesError::InvalidData(
+            "Codablock F data cannot be empty".to_string(),
+        ));
+    }
+    if let Some(c) = data.chars().find(|c| !c.is_ascii()) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Invalid character '{}' for Codablock F. Only ASCII is supported.",
+            c
+        )));
+    }
+
+    let bytes = data.as_bytes();
+    let columns = config
+        .columns
+        .unwrap_or_else(|| ((bytes.len() as f64).sqrt().ceil() as usize).max(MIN_COLUMNS));
+    if !(MIN_COLUMNS..=MAX_COLUMNS).contains(&columns) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Codablock F rows hold {}-{} data characters, got {}",
+            MIN_COLUMNS, MAX_COLUMNS, columns
+        )));
+    }
+
+    // Data characters of each row with the code set the row starts in
+    let mut rows = Vec::new();
+    let mut position = 0;
+    loop {
+        let start = if position < bytes.len() {
+            code128::start_set(bytes, position)
+        } else {
+            CodeSet::B
+        };
+        let mut set = start;
+        let mut row = Vec::with_capacity(columns);
+        while position < bytes.len() {
+            let (values, next_position, next_set) = code128::next_values(bytes, position, set);
+            if row.len() + values.len() > columns {
+                break;
+            }
+            row.extend(values);
+            position = next_position;
+            set = next_set;
+        }
+
+        let last =
+            position == bytes.len() && row.len() + 2 <= columns && rows.len() + 1 >= MIN_ROWS;
+        pad(&mut row, set, if last { columns - 2 } else { columns });
+        if last {
+            let (k1, k2) = message_checks(bytes);
+            row.extend([k1, k2]);
+        }
+        rows.push((start, row));
+
+        if rows.len() > MAX_ROWS {
+            return Err(QuickCodesError::InvalidData(format!(
+                "Data too long for Codablock F: needs more than {} rows of {} characters",
+                MAX_ROWS, columns
+            )));
+        }
+        if last {
+            break;
+        }
+    }
+
+    let row_count = rows.len();
+    Ok(rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, (set, data))| {
+            // A shift makes the row indicator of a code set A row read in B
+            let selector = match set {
+                CodeSet::A => SHIFT,
+                CodeSet::B => CODE_B,
+                CodeSet::C => CODE_C,
+            };
+            let indicator = if i == 0 { row_count - MIN_ROWS } else { i + 42 };
+            let mut values = vec![START_A, selector, indicator as u8];
+            values.extend(data);
+            values.push(row_check(&values));
+            values.push(STOP);
+            values
+        })
+        .collect())
+}
+
+/// Fill a row with code set changes, which carry no data
+fn pad(row: &mut Vec<u8>, mut set: CodeSet, length: usize) {
+    while row.len() < length {
+        if set == CodeSet::C {
+            row.push(CODE_B);
+            set = CodeSet::B;
+        } else {
+            row.push(CODE_C);
+            set = CodeSet::C;
+        }
+    }
+}
+
+/// Code 128 modulo-103 check character of a row
+fn row_check(values: &[u8]) -> u8 {
+    let sum: usize = values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| i.max(1) * usize::from(value))
+        .sum();
+    (sum % 103) as u8
+}
+
+/// K1 and K2 modulo-86 check characters over the message
+fn message_checks(data: &[u8]) -> (u8, u8) {
+    let mut k1 = 0;
+    let mut k2 = 0;
+    for (i, &byte) in data.iter().enumerate() {
+        k1 += (i + 1) * usize::from(byte);
+        k2 += i * usize::from(byte);
+    }
+    ((k1 % 86) as u8, (k2 % 86) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_columns(data: &str, columns: usize) -> Result<Vec<Vec<u8>>> {
+        encode(
+            data,
+            &CodablockConfig {
+                columns: Some(columns),
+            },
+        )
+    }
+
+    #[test]
+    fn test_codablock_rows() {
+        let rows = encode_columns("CODABLOCK F 2024", 5).unwrap();
+        assert!(rows.len() >= 3);
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(row.len(), 5 + 5);
+            assert_eq!(row[0], START_A);
+            assert_eq!(row[1], CODE_B);
+            let indicator = if i == 0 { rows.len() - 2 } else { i + 42 };
+            assert_eq!(usize::from(row[2]), indicator);
+            assert_eq!(row[8], row_check(&row[..8]));
+            assert_eq!(row[9], STOP);
+        }
+        assert_eq!(&rows[0][3..8], &[35, 47, 36, 33, 34]);
+
+        let (k1, k2) = message_checks(b"CODABLOCK F 2024");
+        let last = rows.last().unwrap();
+        assert_eq!(&last[6..8], &[k1, k2]);
+    }
+
+    #[test]
+    fn test_codablock_single_row_data() {
+        // Short data still gets a second row for the message checks
+        let rows = encode_columns("12", 4).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][1], CODE_C);
+        assert_eq!(rows[0][2], 0);
+        assert_eq!(rows[0][3], 12);
+    }
+
+    #[test]
+    fn test_codablock_shift_not_split() {
+        // The shifted tab needs two characters, so it moves to the next row,
+        // which starts in code set A
+        let rows = encode_columns("abc\tdefg", 4).unwrap();
+        assert_eq!(&rows[0][3..7], &[65, 66, 67, CODE_C]);
+        assert_eq!(rows[1][1], SHIFT);
+        assert_eq!(&rows[1][3..7], &[73, CODE_B, 68, 69]);
+    }
+
+    #[test]
+    fn test_codablock_matrix() {
+        let barcode = generate_codablock_f("Codablock F stacked Code 128 rows").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::CodablockF);
+        let matrix = barcode.modules.as_matrix().unwrap();
+        let width = matrix[0].len();
+        assert!(matrix.iter().all(|row| row.len() == width));
+        assert!(matrix[0].iter().all(|&m| m));
+        // Inner separators leave the start and stop characters clear
+        let separator = &matrix[ROW_HEIGHT + 1];
+        assert!(!separator[5] && separator[11] && !separator[width - 5]);
+        // Every row ends with the stop character
+        assert!(matrix[1].ends_with(&[true, true, false, true, false, true, true]));
+    }
+
+    #[test]
+    fn test_codablock_invalid_data() {
+        assert!(generate_codablock_f("").is_err());
+        assert!(generate_codablock_f("Ação").is_err());
+        assert!(encode_columns("ABC", 3).is_err());
+        assert!(encode_columns("ABC", 63).is_err());
+        assert!(encode_columns(&"x".repeat(200), 4).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! Code 11 barcode generator
+//!
+//! Code 11 (USD-8) is a discrete, width-modulated symbology used mainly for
+//! labeling telecommunications equipment. It encodes the digits 0-9 and the
+//! dash, protected by one ("C") or two ("C" and "K") modulo-11 check digits.
+
+use crate::types::{Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError, Result};
+
+// Code 11 encoding patterns: 5 elements (bar, space, bar, space, bar),
+// where '1' is a wide element and '0' a narrow one
+const CODE11_PATTERNS: &[(char, &str)] = &[
+    ('0', "00001"),
+    ('1', "10001"),
+    ('2', "01001"),
+    ('3', "11000"),
+    ('4', "00101"),
+    ('5', "10100"),
+    ('6', "01100"),
+    ('7', "00011"),
+    ('8', "10010"),
+    ('9', "10000"),
+    ('-', "00100"),
+];
+
+// Start/stop pattern
+const START_STOP: &str = "00110";
+
+/// Payload length from which the K check digit is added in `Auto` mode
+const K_CHECK_THRESHOLD: usize = 10;
+
+/// Check digit policy for Code 11
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Code11CheckDigits {
+    /// C check digit always, K check digit for payloads of 10 or more characters
+    #[default]
+    Auto,
+    /// No check digit
+    None,
+    /// C check digit only
+    C,
+    /// Both C and K check digits
+    CK,
+}
+
+/// Generate a Code 11 barcode with automatic check digits
+pub fn generate_code11(data: &str) -> Result<Barcode> {
+    generate_code11_with_check_digits(data, Code11CheckDigits::Auto)
+}
+
+/// Generate a Code 11 barcode with an explicit check digit policy
+pub fn generate_code11_with_check_digits(
+    data: &str,
+    check_digits: Code11CheckDigits,
+) -> Result<Barcode> {
+    if data.is_empty() {
+        return Err(QuickCodesError::InvalidData(
+            "Code 11 data cannot be empty".to_string(),
+        ));
+    }
+
+    if let Some(c) = data.chars().find(|&c| value_of(c).is_none()) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Invalid character '{}' for Code 11. Only 0-9 and '-' are allowed.",
+            c
+        )));
+    }
+
+    let (with_c, with_k) = match check_digits {
+        Code11CheckDigits::Auto => (true, data.len() >= K_CHECK_THRESHOLD),
+        Code11CheckDigits::None => (false, false),
+        Code11CheckDigits::C => (true, false),
+        Code11CheckDigits::CK => (true, true),
+    };
+
+    let mut encoded = data.to_string();
+    if with_c {
+        encoded.push(calculate_check_digit(&encoded, 10));
+    }
+    if with_k {
+        encoded.push(calculate_check_digit(&encoded, 9));
+    }
+
+    let pattern = generate_code11_pattern(&encoded);
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::Code11,
+        data: encoded,
+        modules: BarcodeModules::Linear(pattern),
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Numeric value of a Code 11 character ('-' is 10)
+fn value_of(c: char) -> Option<usize> {
+    CODE11_PATTERNS.iter().position(|&(ch, _)| ch == c)
+}
+
+/// Calculate a modulo-11 check digit with weights cycling from 1 to `max_weight`,
+/// starting at the rightmost character
+fn calculate_check_digit(data: &str, max_weight: usize) -> char {
+    let sum: usize = data
+        .chars()
+        .rev()
+        .enumerate()
+        .map(|(i, c)| value_of(c).unwrap_or(0) * (i % max_weight + 1))
+        .sum();
+
+    CODE11_PATTERNS[sum % 11].0
+}
+
+/// Expand start/stop, data and check characters into modules
+fn generate_code11_pattern(data: &str) -> Vec<bool> {
+    let mut modules = String::new();
+
+    let patterns = std::iter::once(START_STOP)
+        .chain(
+            data.chars()
+                .filter_map(|c| value_of(c).map(|v| CODE11_PATTERNS[v].1)),
+        )
+        .chain(std::iter::once(START_STOP));
+
+    for (i, pattern) in patterns.enumerate() {
+        // Narrow inter-character gap
+        if i > 0 {
+            modules.push('0');
+        }
+
+        for (j, element) in pattern.bytes().enumerate() {
+            let module = if j % 2 == 0 { '1' } else { '0' };
+            modules.push(module);
+            if element == b'1' {
+                modules.push(module); // wide element
+            }
+        }
+    }
+
+    modules.chars().map(|c| c == '1').collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code11_check_digits() {
+        // Reference value: 123-45 -> C = 5, K = 2
+        assert_eq!(calculate_check_digit("123-45", 10), '5');
+        assert_eq!(calculate_check_digit("123-455", 9), '2');
+    }
+
+    #[test]
+    fn test_code11_auto_short_payload_has_only_c() {
+        let barcode = generate_code11("123-45").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::Code11);
+        assert_eq!(barcode.data, "123-455");
+    }
+
+    #[test]
+    fn test_code11_auto_long_payload_has_c_and_k() {
+        let barcode = generate_code11("0123456789").unwrap();
+        assert_eq!(barcode.data.len(), 12);
+        assert_eq!(&barcode.data[..10], "0123456789");
+    }
+
+    #[test]
+    fn test_code11_selectable_check_digits() {
+        let none = generate_code11_with_check_digits("123", Code11CheckDigits::None).unwrap();
+        assert_eq!(none.data, "123");
+
+        let ck = generate_code11_with_check_digits("123-45", Code11CheckDigits::CK).unwrap();
+        assert_eq!(ck.data, "123-4552");
+    }
+
+    #[test]
+    fn test_code11_module_count() {
+        let barcode = generate_code11_with_check_digits("1", Code11CheckDigits::None).unwrap();
+        let modules = barcode.modules.as_linear().unwrap();
+        // Start (7) + gap + '1' (7) + gap + stop (7)
+        assert_eq!(modules.len(), 23);
+        assert!(modules[0]);
+        assert!(*modules.last().unwrap());
+    }
+
+    #[test]
+    fn test_code11_invalid_data() {
+        assert!(generate_code11("").is_err());
+
+        let result = generate_code11("12A34");
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Invalid character"));
+    }
+}
@@ -1,73 +1,261 @@
 //! Code128 barcode generator
 
+use crate::gs1;
 use crate::types::{Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError, Result};
 
+// Symbol character values with a special meaning
+pub(crate) const SHIFT: u8 = 98;
+pub(crate) const CODE_C: u8 = 99;
+pub(crate) const CODE_B: u8 = 100; // FNC4 in code set B
+pub(crate) const CODE_A: u8 = 101; // FNC4 in code set A
+pub(crate) const FNC1: u8 = 102;
+pub(crate) const START_A: u8 = 103;
+pub(crate) const START_B: u8 = 104;
+pub(crate) const START_C: u8 = 105;
+pub(crate) const STOP: u8 = 106;
+
+/// Maximum number of data characters in a GS1-128 symbol, FNC1 separators
+/// included
+const GS1_128_MAX_DATA: usize = 48;
+
+/// Element widths (bar first) of symbol characters 0-105 and the stop
+/// character (106), which has a seventh element
+pub(crate) const PATTERNS: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+
+/// Code 128 code sets: A (control characters and upper case), B (printable
+/// ASCII) and C (digit pairs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodeSet {
+    A,
+    B,
+    C,
+}
+
+impl CodeSet {
+    fn contains(self, c: u8) -> bool {
+        match self {
+            CodeSet::A => c < 96,
+            CodeSet::B => (32..128).contains(&c),
+            CodeSet::C => false,
+        }
+    }
+
+    /// Symbol character value of an ASCII character in code set A or B
+    fn value(self, c: u8) -> u8 {
+        if c < 32 {
+            c + 64
+        } else {
+            c - 32
+        }
+    }
+}
+
+/// Append the modules of a width pattern, alternating from a bar or a space
+pub(crate) fn append_widths(modules: &mut Vec<bool>, widths: &str, bar_first: bool) {
+    for (i, width) in widths.bytes().enumerate() {
+        let dark = (i % 2 == 0) == bar_first;
+        modules.extend(std::iter::repeat(dark).take(usize::from(width - b'0')));
+    }
+}
+
+fn digit_run(data: &[u8], position: usize) -> usize {
+    data[position..]
+        .iter()
+        .take_while(|c| c.is_ascii_digit())
+        .count()
+}
+
+/// Code set to start encoding the data at the position in
+pub(crate) fn start_set(data: &[u8], position: usize) -> CodeSet {
+    let digits = digit_run(data, position);
+    let all_digits = position + digits == data.len();
+    // An odd run leaves its last digit to code set A or B
+    if digits >= 4 || digits == 2 && all_digits {
+        CodeSet::C
+    } else if data.get(position).is_some_and(|&c| c < 32) {
+        CodeSet::A
+    } else {
+        CodeSet::B
+    }
+}
+
+/// Symbol characters for the next step of encoding ASCII data: a digit
+/// pair, a character, a shifted character or a code set change. Returns
+/// the values with the position and code set that follow them
+pub(crate) fn next_values(data: &[u8], position: usize, set: CodeSet) -> (Vec<u8>, usize, CodeSet) {
+    let digits = digit_run(data, position);
+    let c = data[position];
+    match set {
+        CodeSet::C if digits >= 2 => {
+            let pair = (c - b'0') * 10 + (data[position + 1] - b'0');
+            (vec![pair], position + 2, set)
+        }
+        CodeSet::C => {
+            let target = if c < 32 { CodeSet::A } else { CodeSet::B };
+            let code = if c < 32 { CODE_A } else { CODE_B };
+            (vec![code], position, target)
+        }
+        _ if digits >= 4 && digits % 2 == 0 => (vec![CODE_C], position, CodeSet::C),
+        _ if set.contains(c) => (vec![set.value(c)], position + 1, set),
+        _ => {
+            let (target, code) = if set == CodeSet::A {
+                (CodeSet::B, CODE_B)
+            } else {
+                (CodeSet::A, CODE_A)
+            };
+            let next_needs_target = data
+                .get(position + 1)
+                .is_some_and(|&next| !set.contains(next) && target.contains(next));
+            if next_needs_target {
+                (vec![code], position, target)
+            } else {
+                (vec![SHIFT, target.value(c)], position + 1, set)
+            }
+        }
+    }
+}
+
+/// Modules of a complete symbol from its start and data characters: the
+/// modulo-103 check character and the stop character are added
+pub(crate) fn symbol_modules(values: &[u8]) -> Vec<bool> {
+    let sum: usize = values
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| i.max(1) * usize::from(value))
+        .sum();
+    let check = (sum % 103) as u8;
+
+    let mut modules = Vec::with_capacity((values.len() + 1) * 11 + 13);
+    for &value in values.iter().chain([check, STOP].iter()) {
+        append_widths(&mut modules, PATTERNS[usize::from(value)], true);
+    }
+    modules
+}
+
+/// Generate a Code128 barcode entirely in code set C from an even number
+/// of digits, as required by fixed-length numeric keys
+pub fn generate_code128_set_c(data: &str) -> Result<Barcode> {
+    if data.is_empty() || data.len() % 2 != 0 || !data.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(QuickCodesError::InvalidData(
+            "Code128 code set C requires an even number of digits".to_string(),
+        ));
+    }
+
+    let mut values = vec![START_C];
+    values.extend(
+        data.as_bytes()
+            .chunks(2)
+            .map(|pair| (pair[0] - b'0') * 10 + (pair[1] - b'0')),
+    );
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::Code128,
+        data: data.to_string(),
+        modules: BarcodeModules::Linear(symbol_modules(&values)),
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Generate a GS1-128 barcode from a GS1 element string, bracketed
+/// (`(00)012345678901234567`) or unbracketed with GS separators.
+///
+/// The symbol starts with FNC1 and each GS becomes FNC1. The barcode data
+/// is the human-readable interpretation.
+pub fn generate_gs1_128(data: &str) -> Result<Barcode> {
+    gs1_128_symbol(&gs1::parse(data)?)
+}
+
+/// GS1-128 symbol of elements validated by the caller; AI associations may
+/// be met by other symbols on the same label
+pub(crate) fn gs1_128_symbol(elements: &[gs1::Element]) -> Result<Barcode> {
+    let encoded = gs1::to_unbracketed(elements);
+    if encoded.len() > GS1_128_MAX_DATA {
+        return Err(QuickCodesError::InvalidData(format!(
+            "GS1-128 holds at most {} data characters, got {}",
+            GS1_128_MAX_DATA,
+            encoded.len()
+        )));
+    }
+
+    let values = data_values(encoded.as_bytes(), true);
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::GS1128,
+        data: gs1::format_hri(elements),
+        modules: BarcodeModules::Linear(symbol_modules(&values)),
+        config: BarcodeConfig::default(),
+    })
+}
+
 /// Generate a Code128 barcode with default configuration
 pub fn generate_code128(data: &str) -> Result<Barcode> {
     generate_code128_with_config(data, &BarcodeConfig::default())
 }
 
 /// Generate a Code128 barcode with custom configuration
+///
+/// Any ASCII data can be encoded; the code sets are chosen to keep the
+/// symbol short (code set C for runs of digits).
 pub fn generate_code128_with_config(data: &str, config: &BarcodeConfig) -> Result<Barcode> {
-    // For now, implement a basic Code128 Auto mode
-    // This is a simplified implementation - full Code128 is quite complex
-
     if data.is_empty() {
         return Err(QuickCodesError::InvalidData(
             "Code128 data cannot be empty".to_string(),
         ));
     }
-
-    // Generate a basic pattern (placeholder implementation)
-    // In a full implementation, this would:
-    // 1. Analyze the data to choose optimal encoding (A/B/C)
-    // 2. Generate proper Code128 patterns
-    // 3. Calculate check digit
-    // 4. Add start/stop codes
-
-    let pattern = generate_basic_code128_pattern(data)?;
+    if !data.is_ascii() {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Code128 encodes ASCII characters only, got '{}'",
+            data
+        )));
+    }
 
     Ok(Barcode {
         barcode_type: BarcodeType::Code128,
         data: data.to_string(),
-        modules: BarcodeModules::Linear(pattern),
+        modules: BarcodeModules::Linear(symbol_modules(&data_values(data.as_bytes(), false))),
         config: config.clone(),
     })
 }
 
-/// Generate a basic Code128 pattern (simplified implementation)
-fn generate_basic_code128_pattern(data: &str) -> Result<Vec<bool>> {
-    // This is a placeholder implementation
-    // Real Code128 would need proper encoding tables and algorithms
-
-    let mut pattern = Vec::new();
-
-    // Start pattern (simplified)
-    pattern.extend([
-        true, true, false, true, false, false, true, true, false, true, false,
-    ]);
-
-    // Data encoding (very simplified - just alternating pattern based on characters)
-    for (i, ch) in data.chars().enumerate() {
-        let char_code = ch as u8;
-        let base_pattern = match i % 3 {
-            0 => [true, true, false, false, true, true, false],
-            1 => [true, false, true, true, false, false, true],
-            _ => [false, true, true, false, true, true, false],
-        };
-
-        // Modify pattern based on character
-        for (j, &bit) in base_pattern.iter().enumerate() {
-            pattern.push(bit ^ ((char_code + j as u8) % 2 == 0));
+/// Start character and data characters of ASCII data. With `gs1`, FNC1
+/// follows the start character and each GS is encoded as FNC1.
+fn data_values(data: &[u8], gs1: bool) -> Vec<u8> {
+    let mut set = start_set(data, 0);
+    let start = match set {
+        CodeSet::A => START_A,
+        CodeSet::B => START_B,
+        CodeSet::C => START_C,
+    };
+    let mut values = vec![start];
+    if gs1 {
+        values.push(FNC1);
+    }
+    let mut position = 0;
+    while position < data.len() {
+        if gs1 && char::from(data[position]) == gs1::GS {
+            values.push(FNC1);
+            position += 1;
+            continue;
         }
+        let (next, next_position, next_set) = next_values(data, position, set);
+        values.extend(next);
+        position = next_position;
+        set = next_set;
     }
-
-    // Stop pattern (simplified)
-    pattern.extend([
-        true, true, false, false, true, false, true, true, false, false, true,
-    ]);
-
-    Ok(pattern)
+    values
 }
 
 #[cfg(test)]
@@ -76,19 +264,20 @@ mod tests {
 
     #[test]
     fn test_code128_generation() {
-        let result = generate_code128("Hello123");
-        assert!(result.is_ok());
-
-        let barcode = result.unwrap();
+        let barcode = generate_code128("Hello123").unwrap();
         assert_eq!(barcode.barcode_type, BarcodeType::Code128);
         assert_eq!(barcode.data, "Hello123");
 
-        match barcode.modules {
-            BarcodeModules::Linear(pattern) => {
-                assert!(!pattern.is_empty());
-            }
-            _ => panic!("Code128 should generate a linear pattern"),
+        // Code set B; check character (104 + 1*40 + 2*69 + ... + 8*19) % 103 = 44
+        let mut expected = Vec::new();
+        for value in [START_B, 40, 69, 76, 76, 79, 17, 18, 19, 44, STOP] {
+            append_widths(&mut expected, PATTERNS[usize::from(value)], true);
         }
+        assert_eq!(barcode.modules.as_linear().unwrap(), &expected[..]);
+
+        // An odd run of digits starts in code set C and ends in B
+        assert_eq!(data_values(b"12345", false), [START_C, 12, 34, CODE_B, 21]);
+        assert!(generate_code128("Olá").is_err());
     }
 
     #[test]
@@ -96,4 +285,91 @@ mod tests {
         let result = generate_code128("");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_code128_set_c() {
+        let barcode = generate_code128_set_c("123456").unwrap();
+        let modules = barcode.modules.as_linear().unwrap();
+        // Start, three digit pairs, check character and stop
+        assert_eq!(modules.len(), 5 * 11 + 13);
+
+        let mut expected = Vec::new();
+        // Check character: (105 + 1*12 + 2*34 + 3*56) % 103 = 44
+        for value in [START_C, 12, 34, 56, 44, STOP] {
+            append_widths(&mut expected, PATTERNS[usize::from(value)], true);
+        }
+        assert_eq!(modules, &expected[..]);
+
+        assert!(generate_code128_set_c("12345").is_err());
+        assert!(generate_code128_set_c("12AB").is_err());
+        assert!(generate_code128_set_c("").is_err());
+    }
+
+    #[test]
+    fn test_gs1_128() {
+        let barcode = generate_gs1_128("(00)012345678901234560").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::GS1128);
+        assert_eq!(barcode.data, "(00)012345678901234560");
+        // Start C, FNC1 and ten digit pairs
+        let values = [START_C, FNC1, 0, 1, 23, 45, 67, 89, 1, 23, 45, 60];
+        assert_eq!(
+            barcode.modules.as_linear().unwrap(),
+            &symbol_modules(&values)[..]
+        );
+
+        // A variable-length value before another AI ends with FNC1
+        let barcode = generate_gs1_128("(400)AB1(420)12345").unwrap();
+        let values = [
+            START_B, FNC1, 20, 16, 16, 33, 34, 17, FNC1, CODE_C, 42, 1, 23, 45,
+        ];
+        assert_eq!(
+            barcode.modules.as_linear().unwrap(),
+            &symbol_modules(&values)[..]
+        );
+
+        assert!(generate_gs1_128("(00)012345678901234567").is_err());
+        assert!(generate_gs1_128("ABC").is_err());
+        let too_long = format!("(400){}(420){}", "A".repeat(30), "1".repeat(20));
+        assert!(generate_gs1_128(&too_long).is_err());
+    }
+
+    #[test]
+    fn test_code128_patterns() {
+        // Every symbol character is 11 modules wide, the stop character 13
+        for pattern in &PATTERNS[..106] {
+            let width: u32 = pattern.bytes().map(|w| u32::from(w - b'0')).sum();
+            assert_eq!(width, 11);
+        }
+        let mut modules = Vec::new();
+        append_widths(&mut modules, PATTERNS[usize::from(STOP)], true);
+        assert_eq!(modules.len(), 13);
+        assert!(modules[0] && modules[12]);
+    }
+
+    #[test]
+    fn test_code128_code_sets() {
+        assert_eq!(start_set(b"1234AB", 0), CodeSet::C);
+        assert_eq!(start_set(b"12", 0), CodeSet::C);
+        assert_eq!(start_set(b"12AB", 0), CodeSet::B);
+        assert_eq!(start_set(b"\tAB", 0), CodeSet::A);
+
+        assert_eq!(next_values(b"12", 0, CodeSet::C), (vec![12], 2, CodeSet::C));
+        assert_eq!(
+            next_values(b"A", 0, CodeSet::C),
+            (vec![CODE_B], 0, CodeSet::B)
+        );
+        assert_eq!(
+            next_values(b"x1234", 1, CodeSet::B),
+            (vec![CODE_C], 1, CodeSet::C)
+        );
+        // A single control character is shifted, a run changes code set
+        assert_eq!(
+            next_values(b"\tx", 0, CodeSet::B),
+            (vec![SHIFT, 73], 1, CodeSet::B)
+        );
+        assert_eq!(
+            next_values(b"\t\r", 0, CodeSet::B),
+            (vec![CODE_A], 0, CodeSet::A)
+        );
+    }
 }
@@ -0,0 +1,167 @@
+//! Code 16K generator (EN 12323)
+//!
+//! Code 16K stacks 2 to 16 rows of five Code 128 symbol characters. The
+//! start and stop patterns of each row together identify the row number,
+//! and the rows are separated by one-module bars. The first symbol
+//! character gives the number of rows and the initial code set, and two
+//! modulo-107 check characters end the data.
+
+use super::code128::{self, CodeSet, PATTERNS};
+use crate::types::{Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError, Result};
+
+const MIN_ROWS: usize = 2;
+const MAX_ROWS: usize = 16;
+const CHARACTERS_PER_ROW: usize = 5;
+
+/// Row height in modules, not counting the separator bars
+const ROW_HEIGHT: usize = 10;
+
+/// Pad character filling the last row before the check characters
+const PAD: u8 = 103;
+
+/// Start and stop patterns, bar first (EN 12323 Tables 3 and 4)
+const START_STOP: [&str; 8] = [
+    "3211", "2221", "2122", "1411", "1132", "1231", "1114", "3112",
+];
+
+/// Start and stop pattern of each row (Table 5)
+const START_VALUES: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 0, 1, 2, 3, 4, 5, 6, 7];
+const STOP_VALUES: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 4, 5, 6, 7, 0, 1, 2, 3];
+
+/// Generate a Code 16K symbol from ASCII data
+pub fn generate_code16k(data: &str) -> Result<Barcode> {
+    let values = encode(data)?;
+    let rows = values.len() / CHARACTERS_PER_ROW;
+
+    // Separator bars above, between and below the rows
+    let row_width = 70;
+    let mut matrix = vec![vec![true; row_width]];
+    for (row, characters) in values.chunks(CHARACTERS_PER_ROW).enumerate() {
+        let mut modules = Vec::with_capacity(row_width);
+        code128::append_widths(&mut modules, START_STOP[START_VALUES[row]], true);
+        modules.push(true);
+        for &value in characters {
+            code128::append_widths(&mut modules, PATTERNS[usize::from(value)], false);
+        }
+        code128::append_widths(&mut modules, START_STOP[STOP_VALUES[row]], false);
+
+        matrix.extend(std::iter::repeat(modules).take(ROW_HEIGHT));
+        matrix.push(vec![true; row_width]);
+    }
+    debug_assert_eq!(matrix.len(), rows * (ROW_HEIGHT + 1) + 1);
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::Code16K,
+        data: data.to_string(),
+        modules: BarcodeModules::Matrix(matrix),
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Symbol character values of all rows: mode character, data, padding and
+/// the two check characters
+fn encode(data: &str) -> Result<Vec<u8>> {
+    if data.is_empty() {
+        return Err(QuickCodesError::InvalidData(
+            "Code 16K data cannot be empty".to_string(),
+        ));
+    }
+    if let Some(c) = data.chars().find(|c| !c.is_ascii()) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Invalid character '{}' for Code 16K. Only ASCII is supported.",
+            c
+        )));
+    }
+
+    let bytes = data.as_bytes();
+    let start = code128::start_set(bytes, 0);
+    let mut values = vec![0]; // mode character
+    let (mut position, mut set) = (0, start);
+    while position < bytes.len() {
+        let (next, next_position, next_set) = code128::next_values(bytes, position, set);
+        values.extend(next);
+        position = next_position;
+        set = next_set;
+    }
+
+    let rows = ((values.len() + 2 + CHARACTERS_PER_ROW - 1) / CHARACTERS_PER_ROW).max(MIN_ROWS);
+    if rows > MAX_ROWS {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Data too long for Code 16K: needs {} symbol characters, maximum is {}",
+            values.len() - 1,
+            MAX_ROWS * CHARACTERS_PER_ROW - 3
+        )));
+    }
+
+    // Mode character: number of rows and initial code set (Table 2)
+    let mode = match start {
+        CodeSet::A => 0,
+        CodeSet::B => 1,
+        CodeSet::C => 2,
+    };
+    values[0] = 7 * (rows - MIN_ROWS) as u8 + mode;
+    values.resize(rows * CHARACTERS_PER_ROW - 2, PAD);
+
+    let (first, second) = check_characters(&values);
+    values.extend([first, second]);
+    Ok(values)
+}
+
+/// Two modulo-107 check characters, the second also weighting the first
+fn check_characters(values: &[u8]) -> (u8, u8) {
+    let mut first = 0;
+    let mut second = 0;
+    for (i, &value) in values.iter().enumerate() {
+        first += (i + 2) * usize::from(value);
+        second += (i + 1) * usize::from(value);
+    }
+    let first = first % 107;
+    second += first * (values.len() + 1);
+    (first as u8, (second % 107) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code16k_encoding() {
+        // Two rows, code set C: mode 2, pairs, padding and checks
+        let values = encode("12345678").unwrap();
+        assert_eq!(values.len(), 10);
+        assert_eq!(&values[..5], &[2, 12, 34, 56, 78]);
+        assert_eq!(&values[5..8], &[PAD, PAD, PAD]);
+        assert_eq!((values[8], values[9]), check_characters(&values[..8]));
+
+        // Code set B with a shifted control character
+        let values = encode("Ab\tc").unwrap();
+        assert_eq!(values[0], 1);
+        assert_eq!(&values[1..6], &[33, 66, 98, 73, 67]);
+    }
+
+    #[test]
+    fn test_code16k_rows() {
+        let data = "CODE 16K STACKED ROWS FOR AUTOMOTIVE LABELS";
+        let values = encode(data).unwrap();
+        let rows = values.len() / CHARACTERS_PER_ROW;
+        assert!(rows > 2);
+        assert_eq!(usize::from(values[0] / 7) + 2, rows);
+
+        let barcode = generate_code16k(data).unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::Code16K);
+        let matrix = barcode.modules.as_matrix().unwrap();
+        assert_eq!(matrix.len(), rows * (ROW_HEIGHT + 1) + 1);
+        assert!(matrix.iter().all(|row| row.len() == 70));
+        assert!(matrix[0].iter().all(|&m| m));
+        // Rows differ in their start patterns
+        assert_ne!(matrix[1][..7], matrix[ROW_HEIGHT + 2][..7]);
+    }
+
+    #[test]
+    fn test_code16k_invalid_data() {
+        assert!(generate_code16k("").is_err());
+        assert!(generate_code16k("Ação").is_err());
+        assert!(generate_code16k(&"x".repeat(78)).is_err());
+        assert!(generate_code16k(&"x".repeat(77)).is_ok());
+    }
+}
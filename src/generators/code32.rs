@@ -0,0 +1,111 @@
+//! Code 32 generator (Italian pharmacode)
+//!
+//! Code 32 (Codice Farmaceutico Italiano) identifies medicines sold in
+//! Italy. The 8-digit AIC code and its modulo-10 check digit are read as one
+//! number, converted to six base-32 characters and printed as Code 39. The
+//! human-readable text is the nine digits prefixed with `A`.
+
+use super::code39::generate_code39;
+use crate::types::{Barcode, BarcodeType, QuickCodesError, Result};
+
+/// Base-32 digits of Code 32: 0-9 and the consonants B-Z
+const BASE32: &[u8; 32] = b"0123456789BCDFGHJKLMNPQRSTUVWXYZ";
+
+/// Generate a Code 32 barcode from an 8-digit AIC code
+///
+/// A ninth digit is checked against the computed check digit, and the
+/// leading `A` of the printed code is accepted.
+pub fn generate_code32(data: &str) -> Result<Barcode> {
+    let digits = data.strip_prefix(['A', 'a']).unwrap_or(data);
+    if !matches!(digits.len(), 8 | 9) || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(QuickCodesError::InvalidData(
+            "Code 32 requires 8 digits, or 9 digits with the check digit".to_string(),
+        ));
+    }
+
+    let check = check_digit(&digits[..8]);
+    if let Some(given) = digits[8..].chars().next() {
+        if given != check {
+            return Err(QuickCodesError::InvalidData(format!(
+                "Invalid Code 32 check digit: expected {}, got {}",
+                check, given
+            )));
+        }
+    }
+    let code = format!("{}{}", &digits[..8], check);
+
+    let mut barcode = generate_code39(&to_base32(&code))
+        .map_err(|e| QuickCodesError::GenerationError(e.to_string()))?;
+    barcode.barcode_type = BarcodeType::Code32;
+    barcode.data = format!("A{}", code);
+    Ok(barcode)
+}
+
+/// Modulo-10 check digit: digits in even positions are doubled and their
+/// digit sums added to the digits in odd positions
+fn check_digit(digits: &str) -> char {
+    let sum: u32 = digits
+        .bytes()
+        .enumerate()
+        .map(|(i, b)| {
+            let digit = u32::from(b - b'0');
+            if i % 2 == 1 {
+                let doubled = digit * 2;
+                doubled / 10 + doubled % 10
+            } else {
+                digit
+            }
+        })
+        .sum();
+    char::from(b'0' + (sum % 10) as u8)
+}
+
+/// Six base-32 characters of the nine-digit code
+fn to_base32(code: &str) -> String {
+    let mut value: u32 = code.parse().expect("nine digits fit in u32");
+    let mut characters = [b'0'; 6];
+    for character in characters.iter_mut().rev() {
+        *character = BASE32[(value % 32) as usize];
+        value /= 32;
+    }
+    String::from_utf8(characters.to_vec()).expect("base-32 digits are ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code32_check_digit() {
+        assert_eq!(check_digit("01234567"), '6');
+        assert_eq!(check_digit("00000000"), '0');
+    }
+
+    #[test]
+    fn test_code32_base32() {
+        assert_eq!(to_base32("012345676"), "0CSSBD");
+        assert_eq!(to_base32("000000000"), "000000");
+        assert_eq!(to_base32("999999999"), "XTPLHZ");
+    }
+
+    #[test]
+    fn test_code32_generation() {
+        let barcode = generate_code32("01234567").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::Code32);
+        assert_eq!(barcode.data, "A012345676");
+
+        // Same symbol as Code 39 with the base-32 characters
+        let code39 = generate_code39("0CSSBD").unwrap();
+        assert_eq!(barcode.modules.as_linear(), code39.modules.as_linear());
+
+        assert_eq!(generate_code32("A012345676").unwrap().data, "A012345676");
+    }
+
+    #[test]
+    fn test_code32_invalid_data() {
+        assert!(generate_code32("").is_err());
+        assert!(generate_code32("1234567").is_err());
+        assert!(generate_code32("0123456A").is_err());
+        assert!(generate_code32("012345675").is_err());
+    }
+}
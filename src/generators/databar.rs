@@ -0,0 +1,1143 @@
+//! GS1 DataBar barcode generators
+//!
+//! GS1 DataBar (formerly RSS) is a family of compact symbologies for GS1
+//! element strings, used on fresh produce, loose items and coupons.
+//!
+//! - Omnidirectional, Truncated, Stacked and Stacked Omnidirectional encode a GTIN-14
+//! - Limited encodes a GTIN-14 with indicator digit 0 or 1 in a 79-module row
+//! - Expanded and Expanded Stacked encode any GS1 element string, up to 74 digits
+//!
+//! Symbol characters are built with the RSS value-to-widths algorithm of
+//! ISO/IEC 24724. Stacked variants are returned as `BarcodeModules::Matrix`
+//! rows, including the separator patterns between rows.
+
+use crate::gs1;
+use crate::types::{Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError, Result};
+
+/// Internal marker for FNC1 in Expanded general-purpose data
+const FNC1: u8 = 0x1d;
+
+// Character groups: (group start value, pattern count of the "t" side,
+// odd modules, even modules, widest odd element, widest even element)
+type Group = (u32, u32, u32, u32, u32, u32);
+
+/// A row of modules
+type Row = Vec<bool>;
+
+/// Element width, flagged when it belongs to a finder pattern
+type Element = (u8, bool);
+
+/// Omnidirectional outside characters (values 0-2840)
+const OUTSIDE_GROUPS: [Group; 5] = [
+    (0, 1, 12, 4, 8, 1),
+    (161, 10, 10, 6, 6, 3),
+    (961, 34, 8, 8, 4, 5),
+    (2015, 70, 6, 10, 3, 6),
+    (2715, 126, 4, 12, 1, 8),
+];
+
+/// Omnidirectional inside characters (values 0-1596)
+const INSIDE_GROUPS: [Group; 4] = [
+    (0, 4, 5, 10, 2, 7),
+    (336, 20, 7, 8, 4, 5),
+    (1036, 48, 9, 6, 6, 3),
+    (1516, 81, 11, 4, 8, 1),
+];
+
+/// Expanded characters (values 0-4191)
+const EXPANDED_GROUPS: [Group; 5] = [
+    (0, 4, 12, 5, 7, 2),
+    (348, 20, 10, 7, 5, 4),
+    (1388, 52, 8, 9, 4, 5),
+    (2948, 104, 6, 11, 3, 6),
+    (3988, 204, 4, 13, 1, 8),
+];
+
+/// Omnidirectional finder patterns, five elements each
+const FINDER_PATTERNS: [[u8; 5]; 9] = [
+    [3, 8, 2, 1, 1],
+    [3, 5, 5, 1, 1],
+    [3, 3, 7, 1, 1],
+    [3, 1, 9, 1, 1],
+    [2, 7, 4, 1, 1],
+    [2, 5, 6, 1, 1],
+    [2, 3, 8, 1, 1],
+    [1, 5, 7, 1, 1],
+    [1, 3, 9, 1, 1],
+];
+
+/// Expanded finder patterns A1 to F2
+const EXPANDED_FINDER_PATTERNS: [[u8; 5]; 12] = [
+    [1, 8, 4, 1, 1],
+    [1, 1, 4, 8, 1],
+    [3, 6, 4, 1, 1],
+    [1, 1, 4, 6, 3],
+    [3, 4, 6, 1, 1],
+    [1, 1, 6, 4, 3],
+    [3, 2, 8, 1, 1],
+    [1, 1, 8, 2, 3],
+    [2, 6, 5, 1, 1],
+    [1, 1, 5, 6, 2],
+    [2, 2, 9, 1, 1],
+    [1, 1, 9, 2, 2],
+];
+
+/// Expanded finder sequences, indexed by the number of finder patterns minus 2
+/// (values are 1-based indices into `EXPANDED_FINDER_PATTERNS`)
+const FINDER_SEQUENCES: [&[usize]; 10] = [
+    &[1, 2],
+    &[1, 4, 3],
+    &[1, 6, 3, 8],
+    &[1, 10, 3, 8, 5],
+    &[1, 10, 3, 8, 7, 12],
+    &[1, 10, 3, 8, 9, 12, 11],
+    &[1, 2, 3, 4, 5, 6, 7, 8],
+    &[1, 2, 3, 4, 5, 6, 7, 10, 9],
+    &[1, 2, 3, 4, 5, 6, 7, 10, 11, 12],
+    &[1, 2, 3, 4, 5, 8, 7, 10, 9, 12, 11],
+];
+
+/// Limited characters (values 0-2013570); odd and even modules add up to 26
+/// and the widest odd and even elements to 9
+const LIMITED_GROUPS: [Group; 7] = [
+    (0, 28, 17, 9, 6, 3),
+    (183_064, 728, 13, 13, 5, 4),
+    (820_064, 6454, 9, 17, 3, 6),
+    (1_000_776, 203, 15, 11, 5, 4),
+    (1_491_021, 2408, 11, 15, 4, 5),
+    (1_979_845, 1, 19, 7, 8, 1),
+    (1_996_939, 16632, 7, 19, 1, 8),
+];
+
+/// Limited checksum weights of the left and right character elements
+const LIMITED_CHECKSUM_WEIGHTS: [[u32; 14]; 2] = [
+    [1, 3, 9, 27, 81, 65, 17, 51, 64, 14, 42, 37, 22, 66],
+    [20, 60, 2, 6, 18, 54, 73, 41, 34, 13, 39, 28, 84, 74],
+];
+
+/// Limited finder patterns, indexed by the checksum (mod 89)
+const LIMITED_FINDER_PATTERNS: [[u8; 14]; 89] = [
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 3, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 3, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 3, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 1, 1, 3, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 1, 2, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 3, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 3, 2, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 1, 2, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 2, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 3, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 1, 2, 3, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 2, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 2, 1, 2, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 3, 1, 1, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 3, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 2, 1, 1, 3, 1, 1, 1],
+    [1, 2, 1, 1, 1, 2, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 2, 1, 2, 1, 1, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 3, 1, 1, 1, 1, 1, 1, 1, 1, 3, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 2, 1, 2, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 2, 3, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 2, 1, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 2, 1, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 1, 2, 2, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 2, 1, 2, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 3, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 2, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 2, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 3, 1, 1, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 2, 2, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 2, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 2, 1, 2, 1, 1, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 3, 1, 1, 1, 1, 1, 1, 2, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 3, 1, 1, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 1, 2, 3, 1, 1, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 2, 3, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 1, 1, 2, 2, 2, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 1, 1, 3, 2, 1, 1, 1],
+    [1, 1, 1, 1, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1],
+    [1, 1, 1, 2, 1, 1, 2, 1, 1, 2, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 1, 2, 2, 1, 1, 2, 1, 1, 1],
+    [1, 1, 1, 2, 1, 2, 2, 1, 1, 1, 2, 1, 1, 1],
+    [1, 1, 1, 3, 1, 1, 2, 1, 1, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 1, 1, 2, 1, 1, 2, 2, 1, 1, 1],
+    [1, 2, 1, 2, 1, 1, 2, 1, 1, 1, 2, 1, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 2, 3, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 1, 1, 2, 2, 2, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 1, 1, 3, 2, 1, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 2, 1, 1, 2, 2, 1, 1],
+    [1, 1, 1, 1, 2, 1, 1, 2, 1, 2, 2, 1, 1, 1],
+    [1, 1, 1, 1, 2, 2, 1, 1, 1, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 2, 1, 1, 1, 1, 1, 2, 2, 1, 1],
+    [1, 2, 1, 1, 2, 1, 1, 1, 1, 2, 2, 1, 1, 1],
+    [1, 2, 1, 1, 2, 1, 1, 2, 1, 1, 2, 1, 1, 1],
+    [1, 2, 1, 1, 2, 2, 1, 1, 1, 1, 2, 1, 1, 1],
+    [1, 2, 1, 2, 2, 1, 1, 1, 1, 1, 2, 1, 1, 1],
+    [1, 3, 1, 1, 2, 1, 1, 1, 1, 1, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 3, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 2, 1, 1, 2, 2, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1, 1],
+    [1, 1, 2, 1, 1, 2, 1, 1, 1, 1, 2, 2, 1, 1],
+    [1, 1, 2, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1, 1],
+    [1, 1, 2, 2, 1, 1, 1, 1, 1, 1, 2, 2, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 1, 1, 3, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 2, 1, 1, 2, 2, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 2, 1, 2, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 3, 1, 1, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 2, 1, 2, 1, 1, 2, 1, 1, 1],
+    [2, 1, 1, 2, 1, 1, 1, 1, 1, 2, 2, 1, 1, 1],
+    [2, 1, 1, 1, 1, 1, 1, 1, 2, 2, 1, 2, 1, 1],
+];
+
+// Row heights in modules
+const TRUNCATED_HEIGHT: usize = 13;
+const STACKED_TOP_HEIGHT: usize = 5;
+const STACKED_BOTTOM_HEIGHT: usize = 7;
+const STACKED_OMNI_ROW_HEIGHT: usize = 33;
+const EXPANDED_ROW_HEIGHT: usize = 34;
+
+/// Maximum number of Expanded data characters (plus one check character)
+const MAX_EXPANDED_DATA_CHARS: usize = 21;
+
+/// Default number of segments per row for Expanded Stacked
+pub const DEFAULT_SEGMENTS_PER_ROW: usize = 4;
+
+/// Generate a GS1 DataBar Omnidirectional barcode from a GTIN
+pub fn generate_databar(data: &str) -> Result<Barcode> {
+    let gtin = normalize_gtin(data)?;
+    let modules = BarcodeModules::Linear(render_row(&omni_widths(&gtin), false));
+    Ok(gtin_barcode(BarcodeType::DataBar, gtin, modules))
+}
+
+/// Generate a GS1 DataBar Truncated barcode (13 modules high) from a GTIN
+pub fn generate_databar_truncated(data: &str) -> Result<Barcode> {
+    let gtin = normalize_gtin(data)?;
+    let row = render_row(&omni_widths(&gtin), false);
+    let modules = BarcodeModules::Matrix(vec![row; TRUNCATED_HEIGHT]);
+    Ok(gtin_barcode(BarcodeType::DataBarTruncated, gtin, modules))
+}
+
+/// Generate a two-row GS1 DataBar Stacked barcode from a GTIN
+pub fn generate_databar_stacked(data: &str) -> Result<Barcode> {
+    let gtin = normalize_gtin(data)?;
+    let (top, bottom) = stacked_rows(&omni_widths(&gtin));
+
+    // A single separator row: the complement of the rows where they agree,
+    // alternating where they differ
+    let mut separator = vec![false; top.len()];
+    for i in 4..top.len() - 4 {
+        separator[i] = if top[i] == bottom[i] {
+            !top[i]
+        } else {
+            !separator[i - 1]
+        };
+    }
+
+    let mut matrix = vec![top; STACKED_TOP_HEIGHT];
+    matrix.push(separator);
+    matrix.extend(std::iter::repeat(bottom).take(STACKED_BOTTOM_HEIGHT));
+
+    Ok(gtin_barcode(
+        BarcodeType::DataBarStacked,
+        gtin,
+        BarcodeModules::Matrix(matrix),
+    ))
+}
+
+/// Generate a two-row GS1 DataBar Stacked Omnidirectional barcode from a GTIN
+pub fn generate_databar_stacked_omni(data: &str) -> Result<Barcode> {
+    let gtin = normalize_gtin(data)?;
+    let (top, bottom) = stacked_rows(&omni_widths(&gtin));
+
+    // Finder patterns start after the guard and the outside character
+    let top_separator = separator_row(&top, &[(18, 33)], false);
+    let bottom_separator = separator_row(&bottom, &[(17, 32)], false);
+
+    let mut matrix = vec![top.clone(); STACKED_OMNI_ROW_HEIGHT];
+    matrix.push(top_separator);
+    matrix.push(middle_separator_row(top.len(), top.len()));
+    matrix.push(bottom_separator);
+    matrix.extend(std::iter::repeat(bottom).take(STACKED_OMNI_ROW_HEIGHT));
+
+    Ok(gtin_barcode(
+        BarcodeType::DataBarStackedOmni,
+        gtin,
+        BarcodeModules::Matrix(matrix),
+    ))
+}
+
+/// Generate a GS1 DataBar Limited barcode from a GTIN whose indicator digit
+/// is 0 or 1
+pub fn generate_databar_limited(data: &str) -> Result<Barcode> {
+    let gtin = normalize_gtin(data)?;
+    if !gtin.starts_with(['0', '1']) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "GS1 DataBar Limited requires a GTIN with indicator digit 0 or 1, got {}",
+            gtin
+        )));
+    }
+    let modules = BarcodeModules::Linear(render_row(&limited_widths(&gtin), false));
+    Ok(gtin_barcode(BarcodeType::DataBarLimited, gtin, modules))
+}
+
+/// Generate a GS1 DataBar Expanded barcode from a bracketed element string,
+/// e.g. `(01)09501101530003(3103)000123`
+pub fn generate_databar_expanded(data: &str) -> Result<Barcode> {
+    let chars = expanded_characters(data)?;
+    let pairs = expanded_pairs(&chars);
+
+    let mut widths = vec![1, 1];
+    for pair in &pairs {
+        widths.extend(pair.iter().map(|&(width, _)| width));
+    }
+    widths.extend([1, 1]);
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::DataBarExpanded,
+        data: data.to_string(),
+        modules: BarcodeModules::Linear(render_row(&widths, false)),
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Generate a GS1 DataBar Expanded Stacked barcode with the default number
+/// of segments per row
+pub fn generate_databar_expanded_stacked(data: &str) -> Result<Barcode> {
+    generate_databar_expanded_stacked_with_segments(data, DEFAULT_SEGMENTS_PER_ROW)
+}
+
+/// Generate a GS1 DataBar Expanded Stacked barcode with `segments` symbol
+/// characters per row (an even number from 2 to 20)
+pub fn generate_databar_expanded_stacked_with_segments(
+    data: &str,
+    segments: usize,
+) -> Result<Barcode> {
+    if !(2..=20).contains(&segments) || segments % 2 != 0 {
+        return Err(QuickCodesError::InvalidData(format!(
+            "DataBar Expanded Stacked segments per row must be an even number from 2 to 20, got {}",
+            segments
+        )));
+    }
+
+    let mut chars = expanded_characters(data)?;
+    // The last row must hold at least two symbol characters
+    if chars.len() > segments && chars.len() % segments == 1 {
+        chars = expanded_characters_padded(data, chars.len() + 1)?;
+    }
+
+    let pairs = expanded_pairs(&chars);
+    let pairs_per_row = segments / 2;
+    let row_count = (pairs.len() + pairs_per_row - 1) / pairs_per_row;
+
+    let mut rows = Vec::with_capacity(row_count);
+    for (r, row_pairs) in pairs.chunks(pairs_per_row).enumerate() {
+        let row_number = r + 1;
+        let first_pair = r * pairs_per_row;
+        let partial = row_pairs.len() < pairs_per_row;
+        let left_to_right = pairs_per_row % 2 == 1
+            || row_number % 2 == 1
+            || (partial && (pairs_per_row * row_number - pairs.len()) % 2 == 1);
+        // Even last rows read left to right are shifted by one module
+        let shifted = left_to_right && partial && row_number % 2 == 0 && pairs_per_row % 2 == 0;
+
+        let mut elements: Vec<Element> = Vec::new();
+        if left_to_right {
+            for pair in row_pairs {
+                elements.extend(pair.iter().copied());
+            }
+        } else {
+            for pair in row_pairs.iter().rev() {
+                elements.extend(pair.iter().rev().copied());
+            }
+        }
+
+        // Pairs alternate colour: even pairs start with a space, odd pairs with a bar
+        let last_pair = first_pair + row_pairs.len() - 1;
+        let starts_dark = if left_to_right {
+            first_pair % 2 == 1
+        } else {
+            last_pair % 2 == 1
+        };
+
+        let lead = if shifted { 2 } else { 1 };
+        let mut widths = vec![lead, 1];
+        widths.extend(elements.iter().map(|&(width, _)| width));
+        widths.extend([1, 1]);
+        let row = render_row(&widths, starts_dark);
+
+        // Module ranges covered by finder patterns in this row
+        let mut finders = Vec::new();
+        let mut position = usize::from(lead) + 1;
+        let mut finder_start = None;
+        for &(width, is_finder) in &elements {
+            match (is_finder, finder_start) {
+                (true, None) => finder_start = Some(position),
+                (false, Some(start)) => {
+                    finders.push((start, position));
+                    finder_start = None;
+                }
+                _ => {}
+            }
+            position += usize::from(width);
+        }
+        if let Some(start) = finder_start {
+            finders.push((start, position));
+        }
+
+        rows.push((row, finders, left_to_right));
+    }
+
+    let width = rows.iter().map(|(row, _, _)| row.len()).max().unwrap_or(0);
+    let mut matrix: Vec<Vec<bool>> = Vec::new();
+    for (i, (row, finders, left_to_right)) in rows.iter().enumerate() {
+        if i > 0 {
+            let (above, above_finders, above_ltr) = &rows[i - 1];
+            matrix.push(separator_row(above, above_finders, !above_ltr));
+            matrix.push(middle_separator_row(above.len(), width));
+            matrix.push(separator_row(row, finders, !left_to_right));
+        }
+        matrix.extend(std::iter::repeat(row.clone()).take(EXPANDED_ROW_HEIGHT));
+    }
+    for row in &mut matrix {
+        row.resize(width, false);
+    }
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::DataBarExpandedStacked,
+        data: data.to_string(),
+        modules: BarcodeModules::Matrix(matrix),
+        config: BarcodeConfig::default(),
+    })
+}
+
+fn gtin_barcode(barcode_type: BarcodeType, gtin: String, modules: BarcodeModules) -> Barcode {
+    Barcode {
+        barcode_type,
+        data: format!("(01){}", gtin),
+        modules,
+        config: BarcodeConfig::default(),
+    }
+}
+
+/// Accept a GTIN of 13 digits (check digit computed) or 14 digits (check
+/// digit verified), optionally prefixed with `(01)`
+fn normalize_gtin(data: &str) -> Result<String> {
+    let digits = data.trim();
+    let digits = digits.strip_prefix("(01)").unwrap_or(digits);
+
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(QuickCodesError::InvalidData(
+            "GS1 DataBar GTIN must contain only digits".to_string(),
+        ));
+    }
+
+    match digits.len() {
+        13 => gs1::complete_gtin(digits),
+        14 => gs1::normalize_gtin(digits),
+        n => Err(QuickCodesError::InvalidData(format!(
+            "GS1 DataBar requires a GTIN of 13 or 14 digits, got {}",
+            n
+        ))),
+    }
+}
+
+/// Number of combinations of `r` items out of `n`
+fn combins(n: i64, r: i64) -> i64 {
+    if n < 0 || r < 0 || r > n {
+        return 0;
+    }
+    let r = r.min(n - r);
+    (0..r).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// RSS value-to-widths algorithm: the `val`-th combination of `elements`
+/// element widths summing to `modules`, each at most `max_width`. When
+/// `no_narrow` is false, at least one element must be one module wide.
+fn rss_widths(val: u32, modules: u32, elements: usize, max_width: u32, no_narrow: bool) -> Vec<u8> {
+    let mut val = i64::from(val);
+    let mut n = i64::from(modules);
+    let max_width = i64::from(max_width);
+    let elements = elements as i64;
+    let mut widths = Vec::with_capacity(elements as usize);
+    let mut narrow_mask = 0u32;
+
+    for bar in 0..elements - 1 {
+        let mut width = 1;
+        narrow_mask |= 1 << bar;
+        let mut sub_val;
+        loop {
+            // All combinations of the remaining elements
+            sub_val = combins(n - width - 1, elements - bar - 2);
+            // Less combinations without a single-module element
+            if !no_narrow
+                && narrow_mask == 0
+                && n - width - (elements - bar - 1) >= elements - bar - 1
+            {
+                sub_val -= combins(n - width - (elements - bar), elements - bar - 2);
+            }
+            // Less combinations with an element wider than the maximum
+            if elements - bar - 1 > 1 {
+                let mut less_val = 0;
+                let mut widest = n - width - (elements - bar - 2);
+                while widest > max_width {
+                    less_val += combins(n - width - widest - 1, elements - bar - 3);
+                    widest -= 1;
+                }
+                sub_val -= less_val * (elements - 1 - bar);
+            } else if n - width > max_width {
+                sub_val -= 1;
+            }
+
+            val -= sub_val;
+            if val < 0 {
+                break;
+            }
+            width += 1;
+            narrow_mask &= !(1 << bar);
+        }
+        val += sub_val;
+        n -= width;
+        widths.push(width as u8);
+    }
+    widths.push(n as u8);
+    widths
+}
+
+/// Element widths of a symbol character, odd and even elements interleaved
+fn character_widths(value: u32, groups: &[Group], odd_is_t: bool, odd_no_narrow: bool) -> [u8; 8] {
+    let &(start, t, odd_modules, even_modules, odd_widest, even_widest) = groups
+        .iter()
+        .rev()
+        .find(|group| value >= group.0)
+        .expect("group tables start at zero");
+
+    let value = value - start;
+    let (v_odd, v_even) = if odd_is_t {
+        (value % t, value / t)
+    } else {
+        (value / t, value % t)
+    };
+
+    let odd = rss_widths(v_odd, odd_modules, 4, odd_widest, odd_no_narrow);
+    let even = rss_widths(v_even, even_modules, 4, even_widest, !odd_no_narrow);
+
+    let mut widths = [0; 8];
+    for i in 0..4 {
+        widths[2 * i] = odd[i];
+        widths[2 * i + 1] = even[i];
+    }
+    widths
+}
+
+/// The 46 element widths of a DataBar Omnidirectional symbol for a GTIN-14
+fn omni_widths(gtin: &str) -> Vec<u8> {
+    // The check digit is implied
+    let value: u64 = gtin[..13].parse().expect("GTIN is numeric");
+    let left = (value / 4_537_077) as u32;
+    let right = (value % 4_537_077) as u32;
+    let values = [left / 1597, left % 1597, right / 1597, right % 1597];
+
+    let chars: Vec<[u8; 8]> = values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            if i % 2 == 0 {
+                character_widths(v, &OUTSIDE_GROUPS, false, true)
+            } else {
+                character_widths(v, &INSIDE_GROUPS, true, false)
+            }
+        })
+        .collect();
+
+    // Checksum weights are 3^n mod 79
+    let mut checksum = 0u32;
+    let mut weight = 1u32;
+    for widths in &chars {
+        for &width in widths {
+            checksum += weight * u32::from(width);
+            weight = weight * 3 % 79;
+        }
+    }
+    let mut checksum = checksum % 79;
+    if checksum >= 8 {
+        checksum += 1;
+    }
+    if checksum >= 72 {
+        checksum += 1;
+    }
+    let left_finder = FINDER_PATTERNS[(checksum / 9) as usize];
+    let right_finder = FINDER_PATTERNS[(checksum % 9) as usize];
+
+    let mut widths = vec![1, 1];
+    widths.extend(chars[0]);
+    widths.extend(left_finder);
+    widths.extend(chars[1].iter().rev());
+    widths.extend(chars[3]);
+    widths.extend(right_finder.iter().rev());
+    widths.extend(chars[2].iter().rev());
+    widths.extend([1, 1]);
+    widths
+}
+
+/// The 47 element widths of a DataBar Limited symbol for a GTIN-14
+fn limited_widths(gtin: &str) -> Vec<u8> {
+    // The check digit is implied
+    let value: u64 = gtin[..13].parse().expect("GTIN is numeric");
+    let chars = [
+        limited_character_widths((value / 2_013_571) as u32),
+        limited_character_widths((value % 2_013_571) as u32),
+    ];
+
+    let mut checksum = 0;
+    for (widths, weights) in chars.iter().zip(&LIMITED_CHECKSUM_WEIGHTS) {
+        for (&width, &weight) in widths.iter().zip(weights) {
+            checksum += weight * u32::from(width);
+        }
+    }
+    let finder = LIMITED_FINDER_PATTERNS[(checksum % 89) as usize];
+
+    let mut widths = vec![1, 1];
+    widths.extend(chars[0]);
+    widths.extend(finder);
+    widths.extend(chars[1]);
+    widths.extend([1, 1, 5]);
+    widths
+}
+
+/// Element widths of a Limited symbol character, seven odd and seven even
+/// elements interleaved
+fn limited_character_widths(value: u32) -> [u8; 14] {
+    let &(start, t, odd_modules, even_modules, odd_widest, even_widest) = LIMITED_GROUPS
+        .iter()
+        .rev()
+        .find(|group| value >= group.0)
+        .expect("group tables start at zero");
+
+    let value = value - start;
+    let odd = rss_widths(value / t, odd_modules, 7, odd_widest, true);
+    let even = rss_widths(value % t, even_modules, 7, even_widest, false);
+
+    let mut widths = [0; 14];
+    for i in 0..7 {
+        widths[2 * i] = odd[i];
+        widths[2 * i + 1] = even[i];
+    }
+    widths
+}
+
+/// Split an Omnidirectional symbol into its two stacked rows
+fn stacked_rows(widths: &[u8]) -> (Row, Row) {
+    let mut top_widths = widths[..23].to_vec();
+    top_widths.extend([1, 1]);
+    let mut bottom_widths = vec![1, 1];
+    bottom_widths.extend(&widths[23..]);
+    (
+        render_row(&top_widths, false),
+        render_row(&bottom_widths, true),
+    )
+}
+
+/// Expand element widths into modules, alternating colour
+fn render_row(widths: &[u8], starts_dark: bool) -> Vec<bool> {
+    let mut modules = Vec::new();
+    let mut dark = starts_dark;
+    for &width in widths {
+        modules.extend(std::iter::repeat(dark).take(usize::from(width)));
+        dark = !dark;
+    }
+    modules
+}
+
+/// Separator row next to `row`: its complement, four light modules at each
+/// end, and alternating modules over the light parts of finder patterns
+fn separator_row(row: &[bool], finders: &[(usize, usize)], right_to_left: bool) -> Vec<bool> {
+    let mut separator = vec![false; row.len()];
+    let inner = 4..row.len().saturating_sub(4);
+    for i in inner.clone() {
+        separator[i] = !row[i];
+    }
+
+    for &(start, end) in finders {
+        let positions: Vec<usize> = if right_to_left {
+            (start..end).rev().collect()
+        } else {
+            (start..end).collect()
+        };
+        let mut latch = true;
+        for i in positions.into_iter().filter(|i| inner.contains(i)) {
+            if row[i] {
+                separator[i] = false;
+                latch = true;
+            } else {
+                separator[i] = latch;
+                latch = !latch;
+            }
+        }
+    }
+    separator
+}
+
+/// Middle separator row between stacked rows: alternating modules
+fn middle_separator_row(pattern_width: usize, width: usize) -> Vec<bool> {
+    let mut separator = vec![false; width];
+    for i in (5..pattern_width.saturating_sub(4)).step_by(2) {
+        separator[i] = true;
+    }
+    separator
+}
+
+/// Symbol character values of an Expanded symbol, check character first
+fn expanded_characters(data: &str) -> Result<Vec<u32>> {
+    expanded_characters_padded(data, 0)
+}
+
+/// As `expanded_characters`, padding to at least `min_chars` symbol characters
+fn expanded_characters_padded(data: &str, min_chars: usize) -> Result<Vec<u32>> {
+    let elements = gs1::parse(data)?;
+    let source = gs1::to_unbracketed(&elements).into_bytes();
+    let starts_with_gtin = elements.first().is_some_and(|element| element.ai == "01");
+    let mut bits = encode_expanded_bits(&source, starts_with_gtin)?;
+
+    // Pad to a whole number of 12-bit data characters, at least three
+    let data_bits = ((bits.len() + 11) / 12 * 12)
+        .max(36)
+        .max(min_chars.saturating_sub(1) * 12);
+    let remaining = data_bits.saturating_sub(bits.len());
+    bits.push_str(&bits.pad_pattern()[..remaining]);
+
+    let data_chars = bits.len() / 12;
+    if data_chars > MAX_EXPANDED_DATA_CHARS {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Data too long for GS1 DataBar Expanded ({} symbol characters, maximum {})",
+            data_chars, MAX_EXPANDED_DATA_CHARS
+        )));
+    }
+
+    // Variable length symbol field
+    let total_chars = data_chars + 1;
+    bits.set(bits.vls_position, total_chars % 2 == 1);
+    bits.set(bits.vls_position + 1, data_chars * 12 > 156);
+
+    let values: Vec<u32> = bits
+        .bits
+        .chunks(12)
+        .map(|chunk| chunk.iter().fold(0, |acc, &b| (acc << 1) | u32::from(b)))
+        .collect();
+
+    // Weighted checksum: each data character takes the weight row of its
+    // position next to its finder pattern
+    let sequence = FINDER_SEQUENCES[(total_chars + 1) / 2 - 2];
+    let mut checksum = 0u32;
+    for (i, &value) in values.iter().enumerate() {
+        let position = i + 1;
+        let finder = sequence[position / 2];
+        let row = 2 * (finder - 1) + position % 2 - 1;
+        let widths = character_widths(value, &EXPANDED_GROUPS, false, false);
+        for (j, &width) in widths.iter().enumerate() {
+            checksum += u32::from(width) * expanded_weight(row * 8 + j);
+        }
+    }
+    let check = 211 * (total_chars as u32 - 4) + checksum % 211;
+
+    let mut chars = vec![check];
+    chars.extend(values);
+    Ok(chars)
+}
+
+/// Checksum weight 3^n mod 211
+fn expanded_weight(n: usize) -> u32 {
+    (0..n).fold(1, |acc, _| acc * 3 % 211)
+}
+
+/// Element widths of each Expanded pair (left character, finder, right
+/// character), flagging finder elements
+fn expanded_pairs(chars: &[u32]) -> Vec<Vec<Element>> {
+    let sequence = FINDER_SEQUENCES[(chars.len() + 1) / 2 - 2];
+
+    chars
+        .chunks(2)
+        .zip(sequence)
+        .map(|(pair, &finder)| {
+            let mut elements: Vec<Element> = Vec::with_capacity(21);
+            let left = character_widths(pair[0], &EXPANDED_GROUPS, false, false);
+            elements.extend(left.iter().map(|&w| (w, false)));
+            elements.extend(
+                EXPANDED_FINDER_PATTERNS[finder - 1]
+                    .iter()
+                    .map(|&w| (w, true)),
+            );
+            if let Some(&right) = pair.get(1) {
+                let right = character_widths(right, &EXPANDED_GROUPS, false, false);
+                elements.extend(right.iter().rev().map(|&w| (w, false)));
+            }
+            elements
+        })
+        .collect()
+}
+
+/// Bit buffer for Expanded data, tracking the VLS field and the final mode
+struct BitString {
+    bits: Vec<bool>,
+    vls_position: usize,
+    numeric_pad: bool,
+}
+
+impl BitString {
+    fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    fn push(&mut self, value: u32, width: usize) {
+        for i in (0..width).rev() {
+            self.bits.push((value >> i) & 1 == 1);
+        }
+    }
+
+    fn push_str(&mut self, pattern: &str) {
+        self.bits.extend(pattern.bytes().map(|b| b == b'1'));
+    }
+
+    fn set(&mut self, position: usize, value: bool) {
+        self.bits[position] = value;
+    }
+
+    /// Padding starts with a latch to alphanumeric when the data ends in numeric mode
+    fn pad_pattern(&self) -> String {
+        let pad = "00100".repeat(60);
+        if self.numeric_pad {
+            format!("0000{}", pad)
+        } else {
+            pad
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Numeric,
+    Alphanumeric,
+    Iso646,
+}
+
+/// Encode the linkage flag, encoding method and general-purpose data field
+fn encode_expanded_bits(source: &[u8], gtin_first: bool) -> Result<BitString> {
+    let mut bits = BitString {
+        bits: Vec::new(),
+        vls_position: 0,
+        numeric_pad: false,
+    };
+    bits.push(0, 1); // no 2D component
+
+    let general = if gtin_first {
+        let gtin = source.get(2..16).and_then(|g| std::str::from_utf8(g).ok());
+        let gtin = gtin.unwrap_or_default();
+        if gtin.len() != 14 || !gtin.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(QuickCodesError::InvalidData(
+                "GTIN (01) must be 14 digits".to_string(),
+            ));
+        }
+        normalize_gtin(gtin)?;
+
+        // Method 1: indicator digit and 12 digits in 10-bit groups, check digit implied
+        bits.push(1, 1);
+        bits.vls_position = bits.len();
+        bits.push(0, 2);
+        bits.push(u32::from(source[2] - b'0'), 4);
+        for group in source[3..15].chunks(3) {
+            let value = std::str::from_utf8(group).unwrap().parse().unwrap();
+            bits.push(value, 10);
+        }
+        &source[16..]
+    } else {
+        bits.push(0, 2);
+        bits.vls_position = bits.len();
+        bits.push(0, 2);
+        source
+    };
+
+    let mode = encode_general_purpose(general, &mut bits);
+    bits.numeric_pad = mode == Mode::Numeric;
+    Ok(bits)
+}
+
+/// Encode the general-purpose data field, returning the final mode
+fn encode_general_purpose(data: &[u8], bits: &mut BitString) -> Mode {
+    let is_numeric = |c: u8| c.is_ascii_digit() || c == FNC1;
+    let numeric_run = |from: usize| data[from..].iter().take_while(|&&c| is_numeric(c)).count();
+
+    let mut mode = Mode::Numeric;
+    let mut i = 0;
+    while i < data.len() {
+        let c = data[i];
+        match mode {
+            Mode::Numeric => {
+                if i + 1 < data.len() && is_numeric(c) && is_numeric(data[i + 1]) {
+                    let value = |c: u8| if c == FNC1 { 10 } else { u32::from(c - b'0') };
+                    bits.push(11 * value(c) + value(data[i + 1]) + 8, 7);
+                    i += 2;
+                } else {
+                    bits.push(0, 4); // latch to alphanumeric
+                    mode = Mode::Alphanumeric;
+                }
+            }
+            Mode::Alphanumeric | Mode::Iso646 => {
+                if c == FNC1 {
+                    bits.push(0b01111, 5); // FNC1 returns to numeric
+                    mode = Mode::Numeric;
+                    i += 1;
+                } else if numeric_run(i) >= 4 {
+                    bits.push(0, 3); // latch to numeric
+                    mode = Mode::Numeric;
+                } else if mode == Mode::Alphanumeric {
+                    if let Some((value, width)) = alphanumeric_value(c) {
+                        bits.push(value, width);
+                        i += 1;
+                    } else {
+                        bits.push(0b00100, 5); // latch to ISO/IEC 646
+                        mode = Mode::Iso646;
+                    }
+                } else {
+                    let alpha_run = data[i..]
+                        .iter()
+                        .take_while(|&&c| c != FNC1 && alphanumeric_value(c).is_some())
+                        .count();
+                    if alpha_run >= 5 || (alpha_run > 0 && i + alpha_run == data.len()) {
+                        bits.push(0b00100, 5); // latch to alphanumeric
+                        mode = Mode::Alphanumeric;
+                    } else {
+                        let (value, width) = iso646_value(c);
+                        bits.push(value, width);
+                        i += 1;
+                    }
+                }
+            }
+        }
+    }
+    mode
+}
+
+/// Alphanumeric encodation value and bit width
+fn alphanumeric_value(c: u8) -> Option<(u32, usize)> {
+    match c {
+        b'0'..=b'9' => Some((u32::from(c - b'0') + 5, 5)),
+        b'A'..=b'Z' => Some((u32::from(c - b'A') + 32, 6)),
+        b'*' => Some((58, 6)),
+        b',' => Some((59, 6)),
+        b'-' => Some((60, 6)),
+        b'.' => Some((61, 6)),
+        b'/' => Some((62, 6)),
+        _ => None,
+    }
+}
+
+/// ISO/IEC 646 encodation value and bit width
+fn iso646_value(c: u8) -> (u32, usize) {
+    const PUNCTUATION: &[u8] = b"!\"%&'()*+,-./:;<=>?_ ";
+    match c {
+        b'0'..=b'9' => (u32::from(c - b'0') + 5, 5),
+        b'A'..=b'Z' => (u32::from(c - b'A') + 64, 7),
+        b'a'..=b'z' => (u32::from(c - b'a') + 90, 7),
+        _ => {
+            let index = PUNCTUATION.iter().position(|&p| p == c).unwrap_or(0);
+            (0xE8 + index as u32, 8)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(modules: &[bool]) -> Vec<usize> {
+        let mut runs = vec![1];
+        for pair in modules.windows(2) {
+            if pair[0] == pair[1] {
+                *runs.last_mut().unwrap() += 1;
+            } else {
+                runs.push(1);
+            }
+        }
+        runs
+    }
+
+    #[test]
+    fn test_rss_widths() {
+        // Each value maps to a distinct combination with the requested module sum
+        let mut seen = std::collections::HashSet::new();
+        for value in 0..81 {
+            let widths = rss_widths(value, 11, 4, 8, false);
+            assert_eq!(widths.iter().map(|&w| u32::from(w)).sum::<u32>(), 11);
+            assert!(widths.iter().all(|&w| (1..=8).contains(&w)));
+            assert!(widths.contains(&1));
+            assert!(seen.insert(widths));
+        }
+        assert_eq!(rss_widths(0, 4, 4, 1, true), vec![1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_databar_omni() {
+        let barcode = generate_databar("0001234567890").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::DataBar);
+        assert_eq!(barcode.data, "(01)00012345678905");
+
+        let modules = barcode.modules.as_linear().unwrap();
+        assert_eq!(modules.len(), 96);
+        assert_eq!(runs(modules).len(), 46);
+        assert!(!modules[0]);
+
+        // Same symbol from the full GTIN-14 with AI prefix
+        let same = generate_databar("(01)00012345678905").unwrap();
+        assert_eq!(same.modules.as_linear(), barcode.modules.as_linear());
+    }
+
+    #[test]
+    fn test_databar_invalid_gtin() {
+        assert!(generate_databar("00012345678901").is_err()); // wrong check digit
+        assert!(generate_databar("12345").is_err());
+        assert!(generate_databar("000123456789A").is_err());
+    }
+
+    #[test]
+    fn test_databar_truncated_and_stacked() {
+        let truncated = generate_databar_truncated("0001234567890").unwrap();
+        let matrix = truncated.modules.as_matrix().unwrap();
+        assert_eq!(matrix.len(), 13);
+        assert_eq!(matrix[0].len(), 96);
+
+        let stacked = generate_databar_stacked("0001234567890").unwrap();
+        let matrix = stacked.modules.as_matrix().unwrap();
+        assert_eq!(matrix.len(), 5 + 1 + 7);
+        assert!(matrix.iter().all(|row| row.len() == 50));
+
+        let omni = generate_databar_stacked_omni("0001234567890").unwrap();
+        let matrix = omni.modules.as_matrix().unwrap();
+        assert_eq!(matrix.len(), 33 + 3 + 33);
+        assert!(matrix.iter().all(|row| row.len() == 50));
+        // Separator rows keep four light modules at each end
+        assert!(matrix[33][..4].iter().all(|&m| !m));
+        assert!(matrix[35][46..].iter().all(|&m| !m));
+    }
+
+    #[test]
+    fn test_databar_limited() {
+        let barcode = generate_databar_limited("0950110153000").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::DataBarLimited);
+        assert_eq!(barcode.data, "(01)09501101530003");
+
+        // Reference symbols: guards, left character, finder, right character
+        let bits = |modules: &[bool]| -> String {
+            modules.iter().map(|&m| if m { '1' } else { '0' }).collect()
+        };
+        let modules = barcode.modules.as_linear().unwrap();
+        assert_eq!(
+            bits(modules),
+            "0101100001100111101101010001001010101110100101001000101101100100111001110100000"
+        );
+        assert_eq!(runs(modules).len(), 47);
+        let barcode = generate_databar_limited("(01)19999999999991").unwrap();
+        assert_eq!(
+            bits(barcode.modules.as_linear().unwrap()),
+            "0100111100110110101101111101010101101011000101010000101110001101011110010100000"
+        );
+
+        // Each group's first and last values keep its odd module count
+        for (i, group) in LIMITED_GROUPS.iter().enumerate() {
+            let last = LIMITED_GROUPS
+                .get(i + 1)
+                .map_or(2_013_570, |next| next.0 - 1);
+            for value in [group.0, last] {
+                let widths = limited_character_widths(value);
+                let odd: u32 = widths.iter().step_by(2).map(|&w| u32::from(w)).sum();
+                let all: u32 = widths.iter().map(|&w| u32::from(w)).sum();
+                assert_eq!((odd, all), (group.2, 26));
+            }
+        }
+
+        // Only indicator digits 0 and 1 fit the value range
+        assert!(generate_databar_limited("2950110153000").is_err());
+        assert!(generate_databar_limited("09501101530004").is_err());
+    }
+
+    #[test]
+    fn test_expanded_general_purpose_encoding() {
+        let mut bits = BitString {
+            bits: Vec::new(),
+            vls_position: 0,
+            numeric_pad: false,
+        };
+        // "12" in numeric mode: 11 * 1 + 2 + 8 = 21
+        let mode = encode_general_purpose(b"12", &mut bits);
+        assert_eq!(mode, Mode::Numeric);
+        assert_eq!(
+            bits.bits,
+            vec![false, false, true, false, true, false, true]
+        );
+
+        // Bracketed, GS-separated and Digital Link input give the same data
+        let bracketed = expanded_characters("(01)09501101530003(10)AB12(21)X").unwrap();
+        assert_eq!(
+            expanded_characters("010950110153000310AB12\x1d21X").unwrap(),
+            bracketed
+        );
+        assert_eq!(
+            expanded_characters("https://id.gs1.org/01/09501101530003/10/AB12/21/X").unwrap(),
+            bracketed
+        );
+    }
+
+    #[test]
+    fn test_databar_expanded() {
+        let barcode =
+            generate_databar_expanded("(01)98898765432106(3202)012345(15)991231").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::DataBarExpanded);
+
+        let modules = barcode.modules.as_linear().unwrap();
+        let chars = expanded_characters("(01)98898765432106(3202)012345(15)991231").unwrap();
+        let finders = (chars.len() + 1) / 2;
+        assert_eq!(modules.len(), 4 + 17 * chars.len() + 15 * finders);
+        assert!(chars.iter().all(|&c| c < 4192));
+    }
+
+    #[test]
+    fn test_databar_expanded_invalid() {
+        assert!(generate_databar_expanded("01095011015300").is_err());
+        assert!(generate_databar_expanded("(01)09501101530004").is_err());
+        assert!(generate_databar_expanded("(10)").is_err());
+        assert!(generate_databar_expanded("(10)ABC~").is_err());
+        assert!(generate_databar_expanded(&format!("(10){}", "A".repeat(70))).is_err());
+    }
+
+    #[test]
+    fn test_databar_expanded_stacked() {
+        let data = "(01)09501101530003(17)140704(10)AB-123";
+        let barcode = generate_databar_expanded_stacked(data).unwrap();
+        let matrix = barcode.modules.as_matrix().unwrap();
+
+        let chars = expanded_characters(data).unwrap().len();
+        let rows = (chars + DEFAULT_SEGMENTS_PER_ROW - 1) / DEFAULT_SEGMENTS_PER_ROW;
+        assert_eq!(matrix.len(), rows * 34 + (rows - 1) * 3);
+        assert!(matrix.iter().all(|row| row.len() == matrix[0].len()));
+
+        assert!(generate_databar_expanded_stacked_with_segments(data, 3).is_err());
+        assert!(generate_databar_expanded_stacked_with_segments(data, 22).is_err());
+    }
+}
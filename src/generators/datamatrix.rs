@@ -1,13 +1,77 @@
-//! DataMatrix barcode generator
+//! DataMatrix ECC 200 generator (ISO/IEC 16022)
 //!
-//! DataMatrix is a 2D barcode commonly used in pharmaceutical and industrial applications.
-//! It's particularly important for ANVISA compliance in Brazil.
+//! DataMatrix is a 2D barcode commonly used in pharmaceutical and industrial
+//! applications, and the carrier of GS1 DataMatrix (ANVISA IUM, HIBC and
+//! ISBT 128 compound messages).
 //!
-//! This is a simplified implementation for demonstration purposes.
-//! A production implementation would use proper DataMatrix encoding algorithms.
+//! Data is encoded in ASCII encodation: digit pairs in one codeword, other
+//! bytes below 128 as themselves plus one and bytes from 128 through the
+//! Upper Shift codeword. Strings are encoded as their UTF-8 bytes. The
+//! codewords are padded to the smallest square symbol (10 x 10 to
+//! 144 x 144) that holds them, protected by interleaved Reed-Solomon blocks
+//! over GF(256) and placed with the Annex F algorithm.
+//!
+//! In GS1 DataMatrix the first codeword is FNC1 (232), and a GS separator
+//! after a variable-length value is encoded as FNC1 too.
 
+use crate::gs1;
 use crate::types::{Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError, Result};
 
+/// GF(256) primitive polynomial x^8 + x^5 + x^3 + x^2 + 1
+const GF256_POLYNOMIAL: u16 = 0x12D;
+
+// Special codewords of ASCII encodation
+const PAD: u8 = 129;
+const DIGIT_PAIRS: u8 = 130;
+const FNC1: u8 = 232;
+const UPPER_SHIFT: u8 = 235;
+
+/// Group separator, encoded as FNC1 in GS1 DataMatrix
+const GS: u8 = 0x1D;
+
+/// A square ECC 200 symbol size
+struct SymbolSize {
+    /// Modules per side, finder and alignment patterns included
+    size: usize,
+    /// Modules per side of each data region
+    region: usize,
+    /// Data codewords
+    data: usize,
+    /// Error correction codewords, over all blocks
+    ecc: usize,
+    /// Interleaved Reed-Solomon blocks
+    blocks: usize,
+}
+
+/// Square symbol sizes of ISO/IEC 16022 Table 7
+#[rustfmt::skip]
+const SYMBOL_SIZES: [SymbolSize; 24] = [
+    SymbolSize { size: 10, region: 8, data: 3, ecc: 5, blocks: 1 },
+    SymbolSize { size: 12, region: 10, data: 5, ecc: 7, blocks: 1 },
+    SymbolSize { size: 14, region: 12, data: 8, ecc: 10, blocks: 1 },
+    SymbolSize { size: 16, region: 14, data: 12, ecc: 12, blocks: 1 },
+    SymbolSize { size: 18, region: 16, data: 18, ecc: 14, blocks: 1 },
+    SymbolSize { size: 20, region: 18, data: 22, ecc: 18, blocks: 1 },
+    SymbolSize { size: 22, region: 20, data: 30, ecc: 20, blocks: 1 },
+    SymbolSize { size: 24, region: 22, data: 36, ecc: 24, blocks: 1 },
+    SymbolSize { size: 26, region: 24, data: 44, ecc: 28, blocks: 1 },
+    SymbolSize { size: 32, region: 14, data: 62, ecc: 36, blocks: 1 },
+    SymbolSize { size: 36, region: 16, data: 86, ecc: 42, blocks: 1 },
+    SymbolSize { size: 40, region: 18, data: 114, ecc: 48, blocks: 1 },
+    SymbolSize { size: 44, region: 20, data: 144, ecc: 56, blocks: 1 },
+    SymbolSize { size: 48, region: 22, data: 174, ecc: 68, blocks: 1 },
+    SymbolSize { size: 52, region: 24, data: 204, ecc: 84, blocks: 2 },
+    SymbolSize { size: 64, region: 14, data: 280, ecc: 112, blocks: 2 },
+    SymbolSize { size: 72, region: 16, data: 368, ecc: 144, blocks: 4 },
+    SymbolSize { size: 80, region: 18, data: 456, ecc: 192, blocks: 4 },
+    SymbolSize { size: 88, region: 20, data: 576, ecc: 224, blocks: 4 },
+    SymbolSize { size: 96, region: 22, data: 696, ecc: 272, blocks: 4 },
+    SymbolSize { size: 104, region: 24, data: 816, ecc: 336, blocks: 6 },
+    SymbolSize { size: 120, region: 18, data: 1050, ecc: 408, blocks: 6 },
+    SymbolSize { size: 132, region: 20, data: 1304, ecc: 496, blocks: 8 },
+    SymbolSize { size: 144, region: 22, data: 1558, ecc: 620, blocks: 10 },
+];
+
 /// Generate a DataMatrix with default configuration
 pub fn generate_datamatrix(data: &str) -> Result<Barcode> {
     generate_datamatrix_with_config(data, &BarcodeConfig::default())
@@ -21,114 +85,344 @@ pub fn generate_datamatrix_with_config(data: &str, config: &BarcodeConfig) -> Re
         ));
     }
 
-    // Generate a simplified DataMatrix pattern
-    // This is a placeholder implementation for demonstration
-    // A full implementation would need proper DataMatrix encoding algorithms
-    let matrix = generate_datamatrix_pattern(data)?;
-
     Ok(Barcode {
         barcode_type: BarcodeType::DataMatrix,
         data: data.to_string(),
-        modules: BarcodeModules::Matrix(matrix),
+        modules: BarcodeModules::Matrix(encode_symbol(&encode_ascii(data.as_bytes(), false))?),
         config: config.clone(),
     })
 }
 
-/// Generate a simplified DataMatrix pattern
-fn generate_datamatrix_pattern(data: &str) -> Result<Vec<Vec<bool>>> {
-    // This is a simplified implementation for demonstration
-    // Real DataMatrix encoding is quite complex and involves:
-    // 1. Data encoding (ASCII, C40, Text, X12, EDIFACT, Base256)
-    // 2. Error correction using Reed-Solomon
-    // 3. Symbol construction with finder patterns
-    // 4. Module placement algorithm
+/// Generate a GS1 DataMatrix from a GS1 element string, bracketed
+/// (`(01)07891000315507(17)270331`) or unbracketed with GS separators.
+///
+/// The elements are validated against the GS1 AI table and encoded
+/// unbracketed after a leading FNC1, with FNC1 after each variable-length
+/// value that is not last. The barcode data is the human-readable
+/// interpretation.
+pub fn generate_gs1_datamatrix(data: &str) -> Result<Barcode> {
+    let elements = gs1::parse(data)?;
+    let codewords = encode_ascii(gs1::to_unbracketed(&elements).as_bytes(), true);
+    Ok(Barcode {
+        barcode_type: BarcodeType::DataMatrix,
+        data: gs1::format_hri(&elements),
+        modules: BarcodeModules::Matrix(encode_symbol(&codewords)?),
+        config: BarcodeConfig::default(),
+    })
+}
 
-    let data_bytes = data.as_bytes();
+/// ASCII encodation of `data`; with `gs1`, FNC1 first and GS as FNC1
+fn encode_ascii(data: &[u8], gs1: bool) -> Vec<u8> {
+    let mut codewords = Vec::with_capacity(data.len() + 1);
+    if gs1 {
+        codewords.push(FNC1);
+    }
+    let mut i = 0;
+    while i < data.len() {
+        let byte = data[i];
+        match data.get(i + 1) {
+            Some(next) if byte.is_ascii_digit() && next.is_ascii_digit() => {
+                codewords.push(DIGIT_PAIRS + (byte - b'0') * 10 + (next - b'0'));
+                i += 1;
+            }
+            _ if gs1 && byte == GS => codewords.push(FNC1),
+            _ if byte >= 128 => codewords.extend([UPPER_SHIFT, byte - 127]),
+            _ => codewords.push(byte + 1),
+        }
+        i += 1;
+    }
+    codewords
+}
 
-    // Calculate size based on data length (simplified)
-    let size = calculate_datamatrix_size(data_bytes.len());
-    let mut matrix = vec![vec![false; size]; size];
+/// Pad, protect and place the data codewords in the smallest symbol that
+/// holds them
+fn encode_symbol(data: &[u8]) -> Result<Vec<Vec<bool>>> {
+    let symbol = SYMBOL_SIZES
+        .iter()
+        .find(|symbol| symbol.data >= data.len())
+        .ok_or_else(|| {
+            QuickCodesError::InvalidData(format!(
+                "DataMatrix data needs {} codewords, more than the {} of a 144 x 144 symbol",
+                data.len(),
+                SYMBOL_SIZES[SYMBOL_SIZES.len() - 1].data
+            ))
+        })?;
 
-    // Generate finder patterns (L-shaped borders)
-    generate_finder_patterns(&mut matrix, size);
+    let mut codewords = data.to_vec();
+    pad(&mut codewords, symbol.data);
+    add_error_correction(&mut codewords, symbol);
+    Ok(build_matrix(symbol, &place_codewords(&codewords, symbol)))
+}
 
-    // Fill data area with encoded data (simplified)
-    fill_data_area(&mut matrix, data_bytes, size);
+/// Fill the data capacity with a pad codeword followed by pads scrambled
+/// by the 253-state randomising algorithm
+fn pad(codewords: &mut Vec<u8>, capacity: usize) {
+    if codewords.len() < capacity {
+        codewords.push(PAD);
+    }
+    while codewords.len() < capacity {
+        let position = codewords.len() + 1;
+        let pseudo_random = (149 * position) % 253 + 1;
+        let value = usize::from(PAD) + pseudo_random;
+        codewords.push(if value <= 254 { value } else { value - 254 } as u8);
+    }
+}
 
-    Ok(matrix)
+/// Append the check codewords: data codeword `i` belongs to block
+/// `i % blocks`, and the check codewords are interleaved the same way
+fn add_error_correction(codewords: &mut Vec<u8>, symbol: &SymbolSize) {
+    let ecc_per_block = symbol.ecc / symbol.blocks;
+    codewords.resize(symbol.data + symbol.ecc, 0);
+    for block in 0..symbol.blocks {
+        let data: Vec<u8> = (block..symbol.data)
+            .step_by(symbol.blocks)
+            .map(|i| codewords[i])
+            .collect();
+        for (i, check) in reed_solomon(&data, ecc_per_block).into_iter().enumerate() {
+            codewords[symbol.data + block + i * symbol.blocks] = check;
+        }
+    }
 }
 
-/// Calculate DataMatrix size based on data length (simplified)
-fn calculate_datamatrix_size(data_len: usize) -> usize {
-    // Simplified size calculation
-    // Real DataMatrix has specific size standards: 10x10, 12x12, 14x14, etc.
-    match data_len {
-        0..=6 => 10,
-        7..=10 => 12,
-        11..=16 => 14,
-        17..=24 => 16,
-        25..=36 => 18,
-        37..=44 => 20,
-        45..=60 => 22,
-        61..=72 => 24,
-        73..=88 => 26,
-        _ => ((data_len as f64).sqrt() as usize + 8).max(28),
+/// Reed-Solomon check codewords over GF(256), highest degree first, for a
+/// generator polynomial with roots 2^1 to 2^count
+fn reed_solomon(data: &[u8], count: usize) -> Vec<u8> {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut x = 1u16;
+    for (i, e) in exp.iter_mut().enumerate() {
+        *e = x as u8;
+        log[usize::from(x)] = i as u8;
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= GF256_POLYNOMIAL;
+        }
+    }
+    let mul = |a: u8, b: u8| {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            exp[(usize::from(log[usize::from(a)]) + usize::from(log[usize::from(b)])) % 255]
+        }
+    };
+
+    let mut generator = vec![1u8];
+    for &root in &exp[1..=count] {
+        let mut next = generator.clone();
+        next.push(0);
+        for (i, &coefficient) in generator.iter().enumerate() {
+            next[i + 1] ^= mul(coefficient, root);
+        }
+        generator = next;
     }
+
+    let mut check = vec![0u8; count];
+    for &codeword in data {
+        let feedback = codeword ^ check[0];
+        check.rotate_left(1);
+        check[count - 1] = 0;
+        for (c, &g) in check.iter_mut().zip(&generator[1..]) {
+            *c ^= mul(feedback, g);
+        }
+    }
+    check
+}
+
+/// Mapping matrix of the data regions put together, filled by the Annex F
+/// placement algorithm
+struct Mapping<'a> {
+    rows: isize,
+    columns: isize,
+    codewords: &'a [u8],
+    modules: Vec<Option<bool>>,
 }
 
-/// Generate finder patterns (L-shaped solid borders)
-fn generate_finder_patterns(matrix: &mut [Vec<bool>], size: usize) {
-    // Left border (solid line)
-    for y in 0..size {
-        matrix[y][0] = true;
+impl Mapping<'_> {
+    fn is_filled(&self, row: isize, column: isize) -> bool {
+        self.modules[(row * self.columns + column) as usize].is_some()
     }
 
-    // Bottom border (solid line)
-    for x in 0..size {
-        matrix[size - 1][x] = true;
+    /// Place bit `bit` (1 is the most significant) of codeword `index`,
+    /// wrapping positions outside the matrix around to the other side
+    fn module(&mut self, mut row: isize, mut column: isize, index: usize, bit: u8) {
+        if row < 0 {
+            row += self.rows;
+            column += 4 - (self.rows + 4) % 8;
+        }
+        if column < 0 {
+            column += self.columns;
+            row += 4 - (self.columns + 4) % 8;
+        }
+        let dark = self.codewords[index] & (0x80 >> (bit - 1)) != 0;
+        self.modules[(row * self.columns + column) as usize] = Some(dark);
     }
 
-    // Top border (alternating pattern)
-    for x in (0..size).step_by(2) {
-        matrix[0][x] = true;
+    /// The standard L-shaped "utah" placement of a codeword whose last bit
+    /// is at (`row`, `column`)
+    fn utah(&mut self, row: isize, column: isize, index: usize) {
+        let positions = [
+            (row - 2, column - 2),
+            (row - 2, column - 1),
+            (row - 1, column - 2),
+            (row - 1, column - 1),
+            (row - 1, column),
+            (row, column - 2),
+            (row, column - 1),
+            (row, column),
+        ];
+        self.place(positions, index);
     }
 
-    // Right border (alternating pattern)
-    for y in (1..size).step_by(2) {
-        matrix[y][size - 1] = true;
+    fn place(&mut self, positions: [(isize, isize); 8], index: usize) {
+        for (bit, (row, column)) in (1..).zip(positions) {
+            self.module(row, column, index, bit);
+        }
     }
 }
 
-/// Fill data area with encoded data (simplified)
-fn fill_data_area(matrix: &mut [Vec<bool>], data: &[u8], size: usize) {
-    let mut bit_index = 0;
-    let total_bits = data.len() * 8;
-
-    // Fill in a diagonal pattern (simplified placement algorithm)
-    for diagonal in 0..(size * 2) {
-        for i in 0..=diagonal {
-            let x = i;
-            let y = diagonal - i;
-
-            if x < size && y < size && x > 0 && y > 0 && x < size - 1 && y < size - 1 {
-                if bit_index < total_bits {
-                    let byte_idx = bit_index / 8;
-                    let bit_pos = 7 - (bit_index % 8);
-
-                    if byte_idx < data.len() {
-                        matrix[y][x] = (data[byte_idx] >> bit_pos) & 1 == 1;
-                        bit_index += 1;
-                    }
-                }
+/// Place the codewords in the mapping matrix, one module per bit
+fn place_codewords(codewords: &[u8], symbol: &SymbolSize) -> Vec<Vec<bool>> {
+    let side = (symbol.size / (symbol.region + 2) * symbol.region) as isize;
+    let (rows, columns) = (side, side);
+    let mut mapping = Mapping {
+        rows,
+        columns,
+        codewords,
+        modules: vec![None; (rows * columns) as usize],
+    };
+
+    let (mut row, mut column, mut index) = (4, 0, 0);
+    loop {
+        // The four corner cases
+        if row == rows && column == 0 {
+            #[rustfmt::skip]
+            let positions = [
+                (rows - 1, 0), (rows - 1, 1), (rows - 1, 2), (0, columns - 2),
+                (0, columns - 1), (1, columns - 1), (2, columns - 1), (3, columns - 1),
+            ];
+            mapping.place(positions, index);
+            index += 1;
+        }
+        if row == rows - 2 && column == 0 && columns % 4 != 0 {
+            #[rustfmt::skip]
+            let positions = [
+                (rows - 3, 0), (rows - 2, 0), (rows - 1, 0), (0, columns - 4),
+                (0, columns - 3), (0, columns - 2), (0, columns - 1), (1, columns - 1),
+            ];
+            mapping.place(positions, index);
+            index += 1;
+        }
+        if row == rows - 2 && column == 0 && columns % 8 == 4 {
+            #[rustfmt::skip]
+            let positions = [
+                (rows - 3, 0), (rows - 2, 0), (rows - 1, 0), (0, columns - 2),
+                (0, columns - 1), (1, columns - 1), (2, columns - 1), (3, columns - 1),
+            ];
+            mapping.place(positions, index);
+            index += 1;
+        }
+        if row == rows + 4 && column == 2 && columns % 8 == 0 {
+            #[rustfmt::skip]
+            let positions = [
+                (rows - 1, 0), (rows - 1, columns - 1), (0, columns - 3), (0, columns - 2),
+                (0, columns - 1), (1, columns - 3), (1, columns - 2), (1, columns - 1),
+            ];
+            mapping.place(positions, index);
+            index += 1;
+        }
+
+        // Sweep up and to the right
+        loop {
+            if row < rows && column >= 0 && !mapping.is_filled(row, column) {
+                mapping.utah(row, column, index);
+                index += 1;
+            }
+            row -= 2;
+            column += 2;
+            if row < 0 || column >= columns {
+                break;
+            }
+        }
+        row += 1;
+        column += 3;
+
+        // Sweep down and to the left
+        loop {
+            if row >= 0 && column < columns && !mapping.is_filled(row, column) {
+                mapping.utah(row, column, index);
+                index += 1;
+            }
+            row += 2;
+            column -= 2;
+            if row >= rows || column < 0 {
+                break;
             }
         }
+        row += 3;
+        column += 1;
+
+        if row >= rows && column >= columns {
+            break;
+        }
     }
+
+    // An unfilled lower right corner gets a fixed pattern
+    let last = (rows * columns - 1) as usize;
+    if mapping.modules[last].is_none() {
+        mapping.modules[last] = Some(true);
+        mapping.modules[last - columns as usize - 1] = Some(true);
+    }
+
+    mapping
+        .modules
+        .chunks(columns as usize)
+        .map(|row| row.iter().map(|module| module.unwrap_or(false)).collect())
+        .collect()
+}
+
+/// Split the mapping matrix into data regions and surround each with its
+/// finder pattern: solid left and bottom edges, alternating top and right
+fn build_matrix(symbol: &SymbolSize, mapping: &[Vec<bool>]) -> Vec<Vec<bool>> {
+    let cell = symbol.region + 2;
+    let mut matrix = vec![vec![false; symbol.size]; symbol.size];
+    for (y, row) in matrix.iter_mut().enumerate() {
+        for (x, module) in row.iter_mut().enumerate() {
+            let (region_y, region_x) = (y % cell, x % cell);
+            *module = if region_x == 0 || region_y == cell - 1 {
+                true
+            } else if region_y == 0 {
+                region_x % 2 == 0
+            } else if region_x == cell - 1 {
+                region_y % 2 == 1
+            } else {
+                let mapping_y = y / cell * symbol.region + region_y - 1;
+                let mapping_x = x / cell * symbol.region + region_x - 1;
+                mapping[mapping_y][mapping_x]
+            };
+        }
+    }
+    matrix
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Rows of a symbol as strings of 0 and 1
+    fn rows(barcode: &Barcode) -> Vec<String> {
+        barcode
+            .modules
+            .as_matrix()
+            .unwrap()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&dark| if dark { '1' } else { '0' })
+                    .collect()
+            })
+            .collect()
+    }
+
     #[test]
     fn test_datamatrix_generation() {
         let result = generate_datamatrix("Hello DataMatrix");
@@ -140,9 +434,8 @@ mod tests {
 
         match barcode.modules {
             BarcodeModules::Matrix(matrix) => {
-                assert!(!matrix.is_empty());
-                assert!(!matrix[0].is_empty());
-                // DataMatrix should be square
+                // 16 codewords fit the 18 of an 18 x 18 symbol
+                assert_eq!(matrix.len(), 18);
                 assert_eq!(matrix.len(), matrix[0].len());
             }
             _ => panic!("DataMatrix should generate a matrix pattern"),
@@ -155,6 +448,90 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_datamatrix_codewords() {
+        // Digit pairs, characters plus one and Upper Shift from byte 128
+        assert_eq!(encode_ascii(b"123456", false), [142, 164, 186]);
+        assert_eq!(encode_ascii(b"A1B", false), [66, 50, 67]);
+        assert_eq!(encode_ascii("á".as_bytes(), false), [235, 68, 235, 34]);
+
+        // GS1: FNC1 first and in place of GS
+        assert_eq!(
+            encode_ascii(b"10L1\x1d0107", true),
+            [232, 140, 77, 50, 232, 131, 137]
+        );
+
+        // Pads after the first are scrambled by their position
+        let mut codewords = encode_ascii(b"010789100031550710L123", true);
+        pad(&mut codewords, 18);
+        assert_eq!(codewords[13..], [PAD, 87, 237, 133, 28],);
+        add_error_correction(&mut codewords, &SYMBOL_SIZES[4]);
+        assert_eq!(
+            codewords[18..],
+            [201, 10, 35, 138, 47, 95, 17, 130, 219, 82, 188, 208, 11, 172]
+        );
+    }
+
+    #[test]
+    fn test_datamatrix_reference_symbols() {
+        // Reference symbols from zint
+        let barcode = generate_datamatrix("123456").unwrap();
+        assert_eq!(
+            rows(&barcode),
+            [
+                "1010101010",
+                "1100101101",
+                "1100000100",
+                "1100011101",
+                "1100001000",
+                "1000001111",
+                "1110110000",
+                "1111011001",
+                "1001110100",
+                "1111111111",
+            ]
+        );
+
+        let barcode = generate_gs1_datamatrix("(01)07891000315507(10)L123").unwrap();
+        assert_eq!(
+            rows(&barcode),
+            [
+                "101010101010101010",
+                "110001011000010101",
+                "100010011110111110",
+                "101100110100101111",
+                "111010000110101100",
+                "100000111000011001",
+                "100100010010101110",
+                "100101010111001111",
+                "101001111000000100",
+                "110100001000100101",
+                "100100001010100100",
+                "100100101001111101",
+                "110001001111100110",
+                "111000011101010001",
+                "100111010000010110",
+                "101101110011010001",
+                "110001110110000100",
+                "111111111111111111",
+            ]
+        );
+
+        // Four data regions and two interleaved blocks: each region has
+        // its own finder pattern
+        let barcode = generate_datamatrix(&"0123456789".repeat(36)).unwrap();
+        let matrix = barcode.modules.as_matrix().unwrap();
+        assert_eq!(matrix.len(), 52);
+        for edge in [0, 26] {
+            assert!(matrix.iter().all(|row| row[edge]));
+            assert!((0..52).all(|x| matrix[edge][x] == (x % 2 == 0)));
+        }
+        for edge in [25, 51] {
+            assert!(matrix[edge].iter().all(|&dark| dark));
+            assert!((0..52).all(|y| matrix[y][edge] == (y % 2 == 1)));
+        }
+    }
+
     #[test]
     fn test_datamatrix_pharmaceutical_data() {
         // Test with pharmaceutical-like data (GS1 format)
@@ -166,6 +543,26 @@ mod tests {
         assert_eq!(barcode.data, gs1_data);
     }
 
+    #[test]
+    fn test_gs1_datamatrix() {
+        let barcode = generate_gs1_datamatrix("0107891000315507\x1d10L123").unwrap();
+        assert_eq!(barcode.data, "(01)07891000315507(10)L123");
+        let bracketed = generate_gs1_datamatrix("(01)07891000315507(10)L123").unwrap();
+        assert_eq!(barcode.modules.as_matrix(), bracketed.modules.as_matrix());
+
+        // Wrong GTIN check digit and unknown AI
+        assert!(generate_gs1_datamatrix("(01)07891000315508").is_err());
+        assert!(generate_gs1_datamatrix("(99X)1").is_err());
+    }
+
+    #[test]
+    fn test_datamatrix_capacity() {
+        // 3116 digits fill a 144 x 144 symbol
+        let barcode = generate_datamatrix(&"1".repeat(3116)).unwrap();
+        assert_eq!(barcode.modules.as_matrix().unwrap().len(), 144);
+        assert!(generate_datamatrix(&"1".repeat(3117)).is_err());
+    }
+
     #[test]
     fn test_datamatrix_unicode_data() {
         let result = generate_datamatrix("Olá DataMatrix! 🇧🇷");
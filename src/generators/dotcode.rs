@@ -0,0 +1,909 @@
+//! DotCode generator (AIM ISS DotCode)
+//!
+//! DotCode is a grid of isolated dots: in a symbol of W columns and H rows
+//! (W + H odd) only the positions where the row and column have the same
+//! parity are printed, so dots never touch. This keeps the symbol readable
+//! when printed by continuous inkjet or laser marking at line speed.
+//!
+//! Data is encoded in codewords 0-112 using code sets A, B and C and a
+//! binary mode, protected by interleaved Reed-Solomon codes over GF(113).
+//! Every codeword is printed as nine dot positions, five of them dark, and
+//! one of four data masks is chosen to keep the edges of the symbol lit.
+
+use crate::gs1;
+use crate::types::{
+    Barcode, BarcodeConfig, BarcodeModules, BarcodeType, ModuleShape, QuickCodesError, Result,
+};
+
+/// Codeword values are elements of the prime field GF(113)
+const GF: u32 = 113;
+
+/// Primitive element of GF(113), the generator polynomial roots are 3^1..3^n
+const PRIMITIVE: u32 = 3;
+
+const MIN_SIZE: usize = 5;
+const MAX_SIZE: usize = 200;
+
+/// Dot patterns of codeword values 0-112 (Annex C), nine dots with five printed
+const DOT_PATTERNS: [u16; 113] = [
+    0x155, 0x0ab, 0x0ad, 0x0b5, 0x0d5, 0x156, 0x15a, 0x16a, 0x1aa, 0x0ae, 0x0b6, 0x0ba, 0x0d6,
+    0x0da, 0x0ea, 0x12b, 0x12d, 0x135, 0x14b, 0x14d, 0x153, 0x159, 0x165, 0x169, 0x195, 0x1a5,
+    0x1a9, 0x057, 0x05b, 0x05d, 0x06b, 0x06d, 0x075, 0x097, 0x09b, 0x09d, 0x0a7, 0x0b3, 0x0b9,
+    0x0cb, 0x0cd, 0x0d3, 0x0d9, 0x0e5, 0x0e9, 0x12e, 0x136, 0x13a, 0x14e, 0x15c, 0x166, 0x16c,
+    0x172, 0x174, 0x196, 0x19a, 0x1a6, 0x1ac, 0x1b2, 0x1b4, 0x1ca, 0x1d2, 0x1d4, 0x05e, 0x06e,
+    0x076, 0x07a, 0x09e, 0x0bc, 0x0ce, 0x0dc, 0x0e6, 0x0ec, 0x0f2, 0x0f4, 0x117, 0x11b, 0x11d,
+    0x127, 0x133, 0x139, 0x147, 0x163, 0x171, 0x18b, 0x18d, 0x193, 0x199, 0x1a3, 0x1b1, 0x1c5,
+    0x1c9, 0x1d1, 0x02f, 0x037, 0x03b, 0x03d, 0x04f, 0x067, 0x073, 0x079, 0x08f, 0x0c7, 0x0e3,
+    0x0f1, 0x11e, 0x13c, 0x178, 0x18e, 0x19c, 0x1b8, 0x1c6, 0x1cc,
+];
+
+/// Dot grid, rows of columns
+type Dots = Vec<Vec<bool>>;
+
+/// Separator after variable-length GS1 elements, encoded as FNC1
+const GS1_SEPARATOR: u8 = 0x1d;
+
+// Control codewords of code set C
+const C_LATCH_A: u8 = 101;
+const C_SHIFT_B: u8 = 101; // plus the number of shifted characters (1-4)
+const C_LATCH_B: u8 = 106;
+
+// Control codewords of code sets A and B
+const AB_SHIFT: u8 = 101; // Shift B in code set A, Shift A in code set B
+const AB_SHIFT_C: u8 = 101; // plus the number of shifted digit pairs (2-4)
+const AB_LATCH_C: u8 = 106;
+
+// Control codewords available in every code set
+const FNC1: u8 = 107;
+const UPPER_SHIFT_A: u8 = 110;
+const UPPER_SHIFT_B: u8 = 111;
+const BINARY_LATCH: u8 = 112;
+
+// Binary mode terminators
+const BINARY_TO_A: u8 = 109;
+const BINARY_TO_B: u8 = 110;
+const BINARY_TO_C: u8 = 111;
+
+/// Pad codeword, a latch between code sets B and C
+const PAD: u8 = 106;
+
+/// Code set B values of the control characters it accepts
+const B_CRLF: u8 = 96;
+const B_CONTROLS: [(u8, u8); 4] = [(9, 97), (28, 98), (29, 99), (30, 100)];
+
+/// DotCode configuration options
+#[derive(Debug, Clone, Default)]
+pub struct DotCodeConfig {
+    /// Number of columns (5-200); the rows follow from the data. Without
+    /// it the symbol is sized at about 3:2
+    pub columns: Option<usize>,
+    /// Treat the data as a GS1 element string such as `(01)09501101530003(10)AB1`,
+    /// in any form accepted by [`gs1::parse`]
+    pub gs1: bool,
+}
+
+/// Generate a DotCode with automatic sizing
+pub fn generate_dotcode(data: &str) -> Result<Barcode> {
+    generate_dotcode_with_config(data, &DotCodeConfig::default())
+}
+
+/// Generate a GS1 DotCode from an element string, e.g. `(01)09501101530003(17)250101(10)AB1`
+pub fn generate_dotcode_gs1(data: &str) -> Result<Barcode> {
+    let config = DotCodeConfig {
+        gs1: true,
+        ..DotCodeConfig::default()
+    };
+    generate_dotcode_with_config(data, &config)
+}
+
+/// Generate a DotCode with a given number of columns or GS1 data
+pub fn generate_dotcode_with_config(data: &str, config: &DotCodeConfig) -> Result<Barcode> {
+    if data.is_empty() {
+        return Err(QuickCodesError::InvalidData(
+            "DotCode data cannot be empty".to_string(),
+        ));
+    }
+
+    let bytes = if config.gs1 {
+        gs1::to_unbracketed(&gs1::parse(data)?).into_bytes()
+    } else {
+        data.as_bytes().to_vec()
+    };
+
+    let source = Source {
+        bytes: &bytes,
+        gs1: config.gs1,
+    };
+    let (codewords, binary_finish) = encode_message(&source);
+    let dots = build_symbol(codewords, binary_finish, config.columns)?;
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::DotCode,
+        data: data.to_string(),
+        modules: BarcodeModules::Matrix(dots),
+        config: BarcodeConfig {
+            module_shape: ModuleShape::Dot,
+            ..BarcodeConfig::default()
+        },
+    })
+}
+
+/// Input bytes with the look-ahead tests of the encodation rules (Annex F)
+struct Source<'a> {
+    bytes: &'a [u8],
+    gs1: bool,
+}
+
+impl Source<'_> {
+    fn byte(&self, position: usize) -> Option<u8> {
+        self.bytes.get(position).copied()
+    }
+
+    fn is_digit(&self, position: usize) -> bool {
+        self.byte(position).is_some_and(|b| b.is_ascii_digit())
+    }
+
+    fn is_binary(&self, position: usize) -> bool {
+        self.byte(position).is_some_and(|b| b >= 128)
+    }
+
+    fn is_fnc1(&self, position: usize) -> bool {
+        self.gs1 && self.byte(position) == Some(GS1_SEPARATOR)
+    }
+
+    /// Whether the byte is in code set A
+    fn datum_a(&self, position: usize) -> bool {
+        self.byte(position).is_some_and(|b| b <= 95)
+    }
+
+    /// Number of bytes forming the next code set B character, 0 if none
+    fn datum_b(&self, position: usize) -> usize {
+        match self.byte(position) {
+            Some(13) if self.byte(position + 1) == Some(10) => 2,
+            Some(32..=127) | Some(9) | Some(28..=30) => 1,
+            _ => 0,
+        }
+    }
+
+    fn datum_c(&self, position: usize) -> bool {
+        self.is_digit(position) && self.is_digit(position + 1)
+    }
+
+    /// Digit pairs available from the position
+    fn ahead_c(&self, position: usize) -> usize {
+        let mut count = 0;
+        while self.datum_c(position + 2 * count) {
+            count += 1;
+        }
+        count
+    }
+
+    /// Digit pairs worth encoding in code set C from the position, 0 if
+    /// starting one digit later is as good
+    fn try_c(&self, position: usize) -> usize {
+        let pairs = self.ahead_c(position);
+        if pairs > self.ahead_c(position + 1) {
+            pairs
+        } else {
+            0
+        }
+    }
+
+    /// Characters encodable in code set A before a run of digits
+    fn ahead_a(&self, position: usize) -> usize {
+        let mut i = position;
+        while self.datum_a(i) && self.try_c(i) < 2 {
+            i += 1;
+        }
+        i - position
+    }
+
+    /// Bytes and characters encodable in code set B before a run of digits
+    fn ahead_b(&self, position: usize) -> (usize, usize) {
+        let (mut i, mut count) = (position, 0);
+        loop {
+            let length = self.datum_b(i);
+            if length == 0 || self.try_c(i) >= 2 {
+                return (i - position, count);
+            }
+            i += length;
+            count += 1;
+        }
+    }
+
+    /// Code set A value and length of the character at the position
+    fn a_value(&self, position: usize) -> (u8, usize) {
+        match self.byte(position) {
+            _ if self.is_fnc1(position) => (FNC1, 1),
+            Some(b) if b < 32 => (b + 64, 1),
+            Some(b) => (b - 32, 1),
+            None => (0, 1),
+        }
+    }
+
+    /// Code set B value and length of the character at the position
+    fn b_value(&self, position: usize) -> (u8, usize) {
+        if self.is_fnc1(position) {
+            return (FNC1, 1);
+        }
+        match self.byte(position) {
+            Some(13) => (B_CRLF, 2),
+            Some(b) if b >= 32 => (b - 32, 1),
+            Some(b) => B_CONTROLS
+                .iter()
+                .find(|&&(control, _)| control == b)
+                .map_or((0, 1), |&(_, value)| (value, 1)),
+            None => (0, 1),
+        }
+    }
+
+    fn pair_value(&self, position: usize) -> u8 {
+        (self.bytes[position] - b'0') * 10 + (self.bytes[position + 1] - b'0')
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CodeSet {
+    A,
+    B,
+    C,
+    Binary,
+}
+
+/// Shift a single byte above 127 into code set A or B
+fn upper_shift(codewords: &mut Vec<u8>, byte: u8) {
+    let value = byte - 128;
+    if value < 32 {
+        codewords.extend([UPPER_SHIFT_A, value + 64]);
+    } else {
+        codewords.extend([UPPER_SHIFT_B, value - 32]);
+    }
+}
+
+/// Encode the data as codewords, returning whether it ends in binary mode
+fn encode_message(source: &Source) -> (Vec<u8>, bool) {
+    let mut codewords = Vec::new();
+    let mut code_set = CodeSet::C;
+    let mut position = 0;
+
+    // Leading digit pairs in code set C mark GS1 data
+    if !source.gs1 && source.datum_c(0) {
+        codewords.push(C_LATCH_B);
+        code_set = CodeSet::B;
+    }
+
+    while position < source.bytes.len() {
+        match code_set {
+            CodeSet::C => {
+                if source.is_fnc1(position) {
+                    codewords.push(FNC1);
+                    position += 1;
+                } else if source.datum_c(position) {
+                    codewords.push(source.pair_value(position));
+                    position += 2;
+                } else if source.is_binary(position) {
+                    if source.is_binary(position + 1) {
+                        codewords.push(BINARY_LATCH);
+                        code_set = CodeSet::Binary;
+                    } else {
+                        upper_shift(&mut codewords, source.bytes[position]);
+                        position += 1;
+                    }
+                } else {
+                    let a_length = source.ahead_a(position);
+                    let (b_length, b_count) = source.ahead_b(position);
+                    if a_length > b_length {
+                        codewords.push(C_LATCH_A);
+                        code_set = CodeSet::A;
+                    } else if (1..=4).contains(&b_count) {
+                        codewords.push(C_SHIFT_B + b_count as u8);
+                        for _ in 0..b_count {
+                            let (value, length) = source.b_value(position);
+                            codewords.push(value);
+                            position += length;
+                        }
+                    } else {
+                        codewords.push(C_LATCH_B);
+                        code_set = CodeSet::B;
+                    }
+                }
+            }
+            CodeSet::A | CodeSet::B => {
+                let in_set = |position: usize| {
+                    if code_set == CodeSet::A {
+                        source.datum_a(position)
+                    } else {
+                        source.datum_b(position) > 0
+                    }
+                };
+
+                let pairs = source.try_c(position);
+                if pairs >= 2 {
+                    if pairs <= 4 {
+                        codewords.push(AB_SHIFT_C + pairs as u8);
+                        for _ in 0..pairs {
+                            codewords.push(source.pair_value(position));
+                            position += 2;
+                        }
+                    } else {
+                        codewords.push(AB_LATCH_C);
+                        code_set = CodeSet::C;
+                    }
+                } else if source.is_fnc1(position) {
+                    codewords.push(FNC1);
+                    position += 1;
+                } else if in_set(position) {
+                    let (value, length) = if code_set == CodeSet::A {
+                        source.a_value(position)
+                    } else {
+                        source.b_value(position)
+                    };
+                    codewords.push(value);
+                    position += length;
+                } else if source.is_binary(position) {
+                    if source.is_binary(position + 1) {
+                        codewords.push(BINARY_LATCH);
+                        code_set = CodeSet::Binary;
+                    } else {
+                        upper_shift(&mut codewords, source.bytes[position]);
+                        position += 1;
+                    }
+                } else {
+                    // A single character of the other code set
+                    let (value, length) = if code_set == CodeSet::A {
+                        source.b_value(position)
+                    } else {
+                        source.a_value(position)
+                    };
+                    codewords.extend([AB_SHIFT, value]);
+                    position += length;
+                }
+            }
+            CodeSet::Binary => {
+                // Groups of five bytes as six base-103 digits
+                let mut group: Vec<u8> = Vec::with_capacity(5);
+                while position < source.bytes.len()
+                    && (source.is_binary(position) || source.is_binary(position + 1))
+                {
+                    group.push(source.bytes[position]);
+                    position += 1;
+                    if group.len() == 5 {
+                        codewords.extend(binary_group(&group));
+                        group.clear();
+                    }
+                }
+                if !group.is_empty() {
+                    codewords.extend(binary_group(&group));
+                }
+
+                if position == source.bytes.len() {
+                    return (codewords, true);
+                }
+                if source.try_c(position) >= 2 {
+                    codewords.push(BINARY_TO_C);
+                    code_set = CodeSet::C;
+                } else if source.ahead_a(position) > source.ahead_b(position).0 {
+                    codewords.push(BINARY_TO_A);
+                    code_set = CodeSet::A;
+                } else {
+                    codewords.push(BINARY_TO_B);
+                    code_set = CodeSet::B;
+                }
+            }
+        }
+    }
+
+    (codewords, false)
+}
+
+/// Up to five bytes as a base-259 number written in one more base-103 digits
+fn binary_group(bytes: &[u8]) -> Vec<u8> {
+    let mut value = bytes.iter().fold(0u64, |acc, &b| acc * 259 + u64::from(b));
+    let mut digits = vec![0u8; bytes.len() + 1];
+    for digit in digits.iter_mut().rev() {
+        *digit = (value % 103) as u8;
+        value /= 103;
+    }
+    digits
+}
+
+/// Dots needed by the data codewords, their check codewords and the mask
+fn min_dots(data_length: usize) -> usize {
+    9 * (data_length + 3 + data_length / 2) + 2
+}
+
+/// Symbol width and height: at least twice the dots needed, W + H odd
+fn symbol_size(min_area: usize, columns: Option<usize>) -> Result<(usize, usize)> {
+    let (width, height) = match columns {
+        Some(width) => {
+            if !(MIN_SIZE..=MAX_SIZE).contains(&width) {
+                return Err(QuickCodesError::InvalidData(format!(
+                    "DotCode columns must be {}-{}, got {}",
+                    MIN_SIZE, MAX_SIZE, width
+                )));
+            }
+            let mut height = ((min_area + width - 1) / width).max(MIN_SIZE);
+            if (width + height) % 2 == 0 {
+                height += 1;
+            }
+            (width, height)
+        }
+        None => auto_size(min_area),
+    };
+
+    if height > MAX_SIZE || width > MAX_SIZE {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Data too long for DotCode: needs {}x{} dots",
+            width, height
+        )));
+    }
+    Ok((width, height))
+}
+
+/// Smallest symbol near a 3:2 width to height ratio
+fn auto_size(min_area: usize) -> (usize, usize) {
+    let h = (min_area as f64 * 0.666).sqrt();
+    let w = (min_area as f64 * 1.5).sqrt();
+    let (mut width, mut height) = (w as usize, h as usize);
+
+    if (width + height) % 2 == 1 {
+        if width * height < min_area {
+            width += 1;
+            height += 1;
+        }
+    } else if h * (width as f64) < w * (height as f64) {
+        width += 1;
+        if width * height < min_area {
+            width -= 1;
+            height += 1;
+            if width * height < min_area {
+                width += 2;
+            }
+        }
+    } else {
+        height += 1;
+        if width * height < min_area {
+            width += 1;
+            height -= 1;
+            if width * height < min_area {
+                height += 2;
+            }
+        }
+    }
+
+    while width * height < min_area || width.min(height) < MIN_SIZE {
+        width += 1;
+        height += 1;
+    }
+    (width, height)
+}
+
+/// Pad, mask, protect and place the codewords
+fn build_symbol(
+    mut codewords: Vec<u8>,
+    binary_finish: bool,
+    columns: Option<usize>,
+) -> Result<Dots> {
+    let (width, height) = symbol_size(2 * min_dots(codewords.len()), columns)?;
+    let n_dots = width * height / 2;
+
+    // Fill spare dots with pad codewords, each pair of which also adds a
+    // check codeword; data ending in binary mode is terminated first
+    let mut padding = n_dots - min_dots(codewords.len());
+    let mut first = true;
+    loop {
+        let needed = if codewords.len() % 2 == 0 { 9 } else { 18 };
+        if padding < needed {
+            break;
+        }
+        padding -= needed;
+        codewords.push(if first && binary_finish {
+            BINARY_TO_A
+        } else {
+            PAD
+        });
+        first = false;
+    }
+    let ecc_length = 3 + codewords.len() / 2;
+
+    let symbols = |forced_corners: bool| -> Vec<Dots> {
+        (0..4)
+            .map(|mask| {
+                let masked = apply_mask(&codewords, mask, ecc_length);
+                let stream = make_dotstream(&masked, n_dots);
+                let mut dots = fold_dotstream(&stream, width, height);
+                if forced_corners {
+                    for (row, col) in corners(width, height) {
+                        dots[row][col] = true;
+                    }
+                }
+                dots
+            })
+            .collect()
+    };
+
+    let (best_score, dots) = best_mask(symbols(false));
+    if best_score > n_dots as i64 {
+        return Ok(dots);
+    }
+    // No mask keeps the edges populated enough: retry with the corners lit
+    Ok(best_mask(symbols(true)).1)
+}
+
+/// Highest scoring symbol, the lowest mask winning ties
+fn best_mask(symbols: Vec<Dots>) -> (i64, Dots) {
+    symbols.into_iter().map(|dots| (score(&dots), dots)).fold(
+        (i64::MIN, Vec::new()),
+        |best, candidate| {
+            if best.1.is_empty() || candidate.0 > best.0 {
+                candidate
+            } else {
+                best
+            }
+        },
+    )
+}
+
+/// Prefix the mask value, add the mask weights to the data codewords and
+/// append the check codewords
+fn apply_mask(codewords: &[u8], mask: u8, ecc_length: usize) -> Vec<u8> {
+    let step = [0, 3, 7, 17][usize::from(mask)];
+    let mut masked = vec![mask];
+    masked.extend(
+        codewords
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| ((u32::from(c) + i as u32 * step) % GF) as u8),
+    );
+    add_error_correction(&mut masked, ecc_length);
+    masked
+}
+
+/// Append check codewords, interleaved over blocks of at most 112 codewords:
+/// block `start` holds every `step`-th codeword from `start`, its check
+/// codewords following its data codewords
+fn add_error_correction(codewords: &mut Vec<u8>, ecc_length: usize) {
+    let data_length = codewords.len();
+    let total = data_length + ecc_length;
+    let step = (total + GF as usize - 2) / (GF as usize - 1);
+    codewords.resize(total, 0);
+
+    for start in 0..step {
+        let block_data = (data_length - start + step - 1) / step;
+        let block_total = (total - start + step - 1) / step;
+        let block: Vec<u8> = (0..block_data)
+            .map(|i| codewords[start + i * step])
+            .collect();
+        for (i, check) in reed_solomon(&block, block_total - block_data)
+            .into_iter()
+            .enumerate()
+        {
+            codewords[start + (block_data + i) * step] = check;
+        }
+    }
+}
+
+/// Reed-Solomon check codewords over GF(113), highest degree first, for a
+/// generator polynomial with roots 3^1 to 3^count
+fn reed_solomon(data: &[u8], count: usize) -> Vec<u8> {
+    let mut generator = vec![1u32];
+    let mut root = 1;
+    for _ in 0..count {
+        root = root * PRIMITIVE % GF;
+        let mut next = generator.clone();
+        next.push(0);
+        for (i, &coefficient) in generator.iter().enumerate() {
+            next[i + 1] = (next[i + 1] + GF - coefficient * root % GF) % GF;
+        }
+        generator = next;
+    }
+
+    let mut remainder = vec![0u32; count];
+    for &symbol in data {
+        let feedback = (u32::from(symbol) + remainder[0]) % GF;
+        remainder.rotate_left(1);
+        remainder[count - 1] = 0;
+        for (r, &g) in remainder.iter_mut().zip(&generator[1..]) {
+            *r = (*r + GF - feedback * g % GF) % GF;
+        }
+    }
+    remainder.iter().map(|&r| ((GF - r) % GF) as u8).collect()
+}
+
+/// Mask value in two dots followed by the dot pattern of each codeword,
+/// padded with printed dots
+fn make_dotstream(masked: &[u8], n_dots: usize) -> Vec<bool> {
+    let mut stream = vec![masked[0] & 2 != 0, masked[0] & 1 != 0];
+    for &codeword in &masked[1..] {
+        let pattern = DOT_PATTERNS[usize::from(codeword)];
+        stream.extend((0..9).rev().map(|bit| pattern & (1 << bit) != 0));
+    }
+    stream.resize(n_dots, true);
+    stream
+}
+
+/// The six corner positions (row, column), in the order they are filled
+fn corners(width: usize, height: usize) -> [(usize, usize); 6] {
+    let (w, h) = (width, height);
+    if h % 2 == 1 {
+        [
+            (0, w - 2),
+            (h - 1, w - 2),
+            (1, w - 1),
+            (h - 2, w - 1),
+            (0, 0),
+            (h - 1, 0),
+        ]
+    } else {
+        [
+            (h - 2, w - 1),
+            (h - 2, 0),
+            (h - 1, w - 2),
+            (h - 1, 1),
+            (0, w - 1),
+            (0, 0),
+        ]
+    }
+}
+
+/// Place the dot stream: row by row from the bottom in symbols with an odd
+/// height, column by column otherwise, with the corners filled last
+fn fold_dotstream(stream: &[bool], width: usize, height: usize) -> Dots {
+    let corners = corners(width, height);
+    let positions: Vec<(usize, usize)> = if height % 2 == 1 {
+        (0..height)
+            .rev()
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .collect()
+    } else {
+        (0..width)
+            .flat_map(|col| (0..height).map(move |row| (row, col)))
+            .collect()
+    };
+
+    let mut dots = vec![vec![false; width]; height];
+    let data_positions = positions
+        .into_iter()
+        .filter(|&(row, col)| (row + col) % 2 == 0 && !corners.contains(&(row, col)));
+    for ((row, col), &dot) in data_positions.chain(corners).zip(stream) {
+        dots[row][col] = dot;
+    }
+    dots
+}
+
+/// Mask score (Annex A): the extent of the weakest edge, less isolated dots
+/// and gaps, and a penalty for empty interior rows and columns
+fn score(dots: &[Vec<bool>]) -> i64 {
+    let (height, width) = (dots.len() as i64, dots[0].len() as i64);
+    let dot = |x: i64, y: i64| {
+        (0..width).contains(&x) && (0..height).contains(&y) && dots[y as usize][x as usize]
+    };
+
+    // Printed dots plus their spread along each edge
+    let edge = |points: Vec<(i64, i64)>, weight: i64| {
+        let lit: Vec<i64> = points
+            .iter()
+            .enumerate()
+            .filter(|(_, &(x, y))| dot(x, y))
+            .map(|(i, _)| 2 * i as i64)
+            .collect();
+        match (lit.first(), lit.last()) {
+            (Some(first), Some(last)) => Some((lit.len() as i64 + last - first) * weight),
+            _ => None,
+        }
+    };
+    let top = (0..width).step_by(2).map(|x| (x, 0)).collect();
+    let bottom = ((height - 1) % 2..width)
+        .step_by(2)
+        .map(|x| (x, height - 1))
+        .collect();
+    let left = (0..height).step_by(2).map(|y| (0, y)).collect();
+    let right = ((width - 1) % 2..height)
+        .step_by(2)
+        .map(|y| (width - 1, y))
+        .collect();
+
+    let mut worst_edge = i64::MAX;
+    for (points, weight) in [
+        (top, height),
+        (bottom, height),
+        (left, width),
+        (right, width),
+    ] {
+        match edge(points, weight) {
+            Some(sum) => worst_edge = worst_edge.min(sum),
+            None => return i64::MIN,
+        }
+    }
+
+    // Unprinted crosses and printed dots without printed neighbours
+    let mut isolated = 0;
+    for y in 0..height {
+        for x in (y % 2..width).step_by(2) {
+            let diagonals_clear = !dot(x - 1, y - 1)
+                && !dot(x + 1, y - 1)
+                && !dot(x - 1, y + 1)
+                && !dot(x + 1, y + 1);
+            let neighbours_clear =
+                !dot(x - 2, y) && !dot(x, y - 2) && !dot(x + 2, y) && !dot(x, y + 2);
+            if diagonals_clear && (!dot(x, y) || neighbours_clear) {
+                isolated += 1;
+            }
+        }
+    }
+
+    let row_clear = |y: i64| (0..width).all(|x| !dot(x, y));
+    let column_clear = |x: i64| (0..height).all(|y| !dot(x, y));
+    let penalty = gap_penalty((1..height - 1).map(row_clear), width)
+        + gap_penalty((1..width - 1).map(column_clear), height);
+
+    worst_edge - isolated * isolated - penalty
+}
+
+/// Sum of N^n over runs of n consecutive empty lines of N positions
+fn gap_penalty(clear: impl Iterator<Item = bool>, positions: i64) -> i64 {
+    let (mut penalty, mut run) = (0i64, 0i64);
+    for is_clear in clear {
+        if is_clear {
+            run = if run == 0 {
+                positions
+            } else {
+                run.saturating_mul(positions)
+            };
+        } else {
+            penalty = penalty.saturating_add(run);
+            run = 0;
+        }
+    }
+    penalty.saturating_add(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(data: &str) -> Vec<u8> {
+        let source = Source {
+            bytes: data.as_bytes(),
+            gs1: false,
+        };
+        encode_message(&source).0
+    }
+
+    #[test]
+    fn test_dotcode_dot_patterns() {
+        let mut patterns = DOT_PATTERNS.to_vec();
+        assert!(patterns.iter().all(|p| p.count_ones() == 5 && *p < 0x200));
+        patterns.sort_unstable();
+        patterns.dedup();
+        assert_eq!(patterns.len(), 113);
+    }
+
+    #[test]
+    fn test_dotcode_encodation() {
+        // Shift B for three characters
+        assert_eq!(encode("Abc"), vec![104, 33, 66, 67]);
+        // Non-GS1 data starting with digits leaves code set C first
+        assert_eq!(encode("1234"), vec![C_LATCH_B, 103, 12, 34]);
+        // Digits stay in code set C after a shifted character
+        assert_eq!(encode("A1234567890"), vec![102, 33, 12, 34, 56, 78, 90]);
+        // Control characters from code set A
+        assert_eq!(encode("\u{1}\u{2}"), vec![C_LATCH_A, 65, 66]);
+        // Two bytes above 127 in binary mode: two bytes in three codewords
+        assert_eq!(encode("é"), vec![BINARY_LATCH, 4, 79, 101]);
+    }
+
+    #[test]
+    fn test_dotcode_gs1_encodation() {
+        let elements = gs1::parse("(01)09501101530003(10)AB1(17)250101").unwrap();
+        let bytes = gs1::to_unbracketed(&elements).into_bytes();
+        let source = Source {
+            bytes: &bytes,
+            gs1: true,
+        };
+        let (codewords, _) = encode_message(&source);
+        assert_eq!(&codewords[..8], &[1, 9, 50, 11, 1, 53, 0, 3]);
+        assert!(codewords.contains(&FNC1));
+
+        // GS-separated and Digital Link input encode the same symbol
+        let bracketed = generate_dotcode_gs1("(01)09501101530003(10)AB1(17)250101").unwrap();
+        for data in [
+            "010950110153000310AB1\x1d17250101",
+            "https://id.gs1.org/01/09501101530003/10/AB1?17=250101",
+        ] {
+            let barcode = generate_dotcode_gs1(data).unwrap();
+            assert_eq!(barcode.modules.as_matrix(), bracketed.modules.as_matrix());
+        }
+    }
+
+    #[test]
+    fn test_dotcode_reed_solomon() {
+        // Interleaved blocks once there are more than 112 codewords
+        for length in [10, 150, 300] {
+            let data: Vec<u8> = (0..length).map(|i| (i * 7 % 113) as u8).collect();
+            let ecc_length = 3 + length / 2;
+            let mut codewords = data.clone();
+            add_error_correction(&mut codewords, ecc_length);
+
+            // Data codewords stay in place, check codewords follow
+            let total = length + ecc_length;
+            assert_eq!(codewords.len(), total);
+            assert_eq!(codewords[..length], data[..]);
+            let step = (total + 111) / 112;
+            for start in 0..step {
+                let block: Vec<u32> = codewords
+                    .iter()
+                    .skip(start)
+                    .step_by(step)
+                    .map(|&c| u32::from(c))
+                    .collect();
+                let checks = block.len() - (length - start + step - 1) / step;
+                let mut root = 1;
+                for _ in 0..checks {
+                    root = root * PRIMITIVE % GF;
+                    let syndrome = block.iter().fold(0, |acc, &c| (acc * root + c) % GF);
+                    assert_eq!(syndrome, 0, "length {} block {}", length, start);
+                }
+            }
+        }
+
+        // Symbols of several blocks
+        assert!(generate_dotcode(&"A".repeat(190)).is_ok());
+        assert!(generate_dotcode(&"é".repeat(170)).is_ok());
+    }
+
+    #[test]
+    fn test_dotcode_generation() {
+        let barcode = generate_dotcode("DotCode 2025").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::DotCode);
+        assert_eq!(barcode.config.module_shape, ModuleShape::Dot);
+
+        let dots = barcode.modules.as_matrix().unwrap();
+        let (height, width) = (dots.len(), dots[0].len());
+        assert_eq!((width + height) % 2, 1);
+        assert!(width > height);
+        // Dots only on the checkerboard, so no two are adjacent
+        for (row, line) in dots.iter().enumerate() {
+            for (col, &dot) in line.iter().enumerate() {
+                assert!(!dot || (row + col) % 2 == 0);
+            }
+        }
+        // Every edge carries printed dots
+        assert!(dots[0].iter().any(|&d| d));
+        assert!(dots[height - 1].iter().any(|&d| d));
+        assert!(dots.iter().any(|line| line[0]));
+        assert!(dots.iter().any(|line| line[width - 1]));
+    }
+
+    #[test]
+    fn test_dotcode_sizing() {
+        let config = DotCodeConfig {
+            columns: Some(9),
+            gs1: false,
+        };
+        let barcode = generate_dotcode_with_config("1234567890ABCDEFGH", &config).unwrap();
+        let dots = barcode.modules.as_matrix().unwrap();
+        assert_eq!(dots[0].len(), 9);
+        assert_eq!((dots.len() + 9) % 2, 1);
+
+        // Dots used by data, checks and mask fit in the symbol
+        let data_length = encode("1234567890ABCDEFGH").len();
+        assert!(dots.len() * 9 / 2 >= min_dots(data_length));
+
+        let gs1 = generate_dotcode_gs1("(01)09501101530003(17)250101(10)AB1").unwrap();
+        assert_eq!(gs1.data, "(01)09501101530003(17)250101(10)AB1");
+    }
+
+    #[test]
+    fn test_dotcode_invalid_data() {
+        assert!(generate_dotcode("").is_err());
+        assert!(generate_dotcode(&"A".repeat(5000)).is_err());
+        assert!(generate_dotcode_gs1("0109501101530004").is_err());
+
+        let config = DotCodeConfig {
+            columns: Some(4),
+            gs1: false,
+        };
+        assert!(generate_dotcode_with_config("test", &config).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! EAN-13 barcode generator
 
+use crate::gs1;
 use crate::types::{Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError, Result};
 
 // EAN-13 encoding patterns
@@ -125,24 +126,7 @@ fn process_ean13_data(data: &str) -> Result<Vec<u8>> {
 
 /// Calculate EAN-13 check digit
 fn calculate_ean13_check_digit(digits: &[u8]) -> u8 {
-    let sum: u32 = digits
-        .iter()
-        .enumerate()
-        .map(|(i, &digit)| {
-            if i % 2 == 0 {
-                digit as u32
-            } else {
-                (digit as u32) * 3
-            }
-        })
-        .sum();
-
-    let remainder = sum % 10;
-    if remainder == 0 {
-        0
-    } else {
-        10 - remainder as u8
-    }
+    gs1::check_digit(&format_ean13_data(digits))
 }
 
 /// Generate the binary pattern for EAN-13
@@ -0,0 +1,1441 @@
+//! Han Xin Code generator (ISO/IEC 20830)
+//!
+//! Han Xin Code is a square matrix symbol of 23x23 (version 1) to 189x189
+//! (version 84) modules with a finder pattern in each corner, designed for
+//! efficient encoding of Chinese text. Data is split into mode segments
+//! (numeric, text, binary, GB 2312 region 1 and 2, GB 18030 double-byte
+//! and four-byte), protected by Reed-Solomon codes over GF(256) at one of
+//! four error correction levels, and placed row by row after interleaving.
+//!
+//! The error correction blocks of every version and level follow Table D.1
+//! of the standard.
+
+use crate::types::{Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError, Result};
+
+const MAX_VERSION: u8 = 84;
+
+/// GF(256) primitive polynomial x^8 + x^6 + x^5 + x + 1
+const GF256_POLYNOMIAL: u16 = 0x163;
+
+/// GF(16) primitive polynomial x^4 + x + 1, used by the function information
+const GF16_POLYNOMIAL: u16 = 0x13;
+
+/// Codewords are interleaved in batches of 13 before placement
+const INTERLEAVE: usize = 13;
+
+// Mode indicators (4 bits)
+const MODE_NUMERIC: u32 = 1;
+const MODE_TEXT: u32 = 2;
+const MODE_BINARY: u32 = 3;
+const MODE_REGION1: u32 = 4;
+const MODE_REGION2: u32 = 5;
+const MODE_DOUBLE_BYTE: u32 = 6;
+const MODE_FOUR_BYTE: u32 = 7;
+
+// Text mode control values
+const TEXT_SUBMODE_SWITCH: u32 = 62;
+const TEXT_TERMINATOR: u32 = 63;
+
+/// Digit runs shorter than this are kept in an adjacent text segment
+const MIN_NUMERIC_RUN: usize = 9;
+
+/// Alignment pattern spacing `k` per version (Annex A)
+const MODULE_K: [u8; 84] = [
+    0, 0, 0, 14, 16, 16, 17, 18, 19, 20, 14, 15, 16, 16, 17, 17, 18, 19, 20, 20, 21, 16, 17, 17,
+    18, 18, 19, 19, 20, 20, 21, 17, 17, 18, 18, 19, 19, 19, 20, 20, 17, 17, 18, 18, 18, 19, 19, 19,
+    17, 17, 18, 18, 18, 18, 19, 19, 19, 17, 17, 18, 18, 18, 18, 19, 19, 17, 17, 17, 18, 18, 18, 18,
+    19, 19, 17, 17, 17, 18, 18, 18, 18, 18, 17, 17,
+];
+
+/// Width `r` of the last alignment region per version (Annex A)
+const MODULE_R: [u8; 84] = [
+    0, 0, 0, 15, 15, 17, 18, 19, 20, 21, 15, 15, 15, 17, 17, 19, 19, 19, 19, 21, 21, 17, 16, 18,
+    17, 19, 18, 20, 19, 21, 20, 17, 19, 17, 19, 17, 19, 21, 19, 21, 18, 20, 17, 19, 21, 18, 20, 22,
+    17, 19, 15, 17, 19, 21, 17, 19, 21, 18, 20, 15, 17, 19, 21, 16, 18, 17, 19, 21, 15, 17, 19, 21,
+    15, 17, 18, 20, 22, 15, 17, 19, 21, 23, 17, 19,
+];
+
+/// Number `m` of full alignment regions per version (Annex A)
+const MODULE_M: [u8; 84] = [
+    0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 4,
+    4, 4, 4, 4, 4, 4, 4, 4, 5, 5, 5, 5, 5, 5, 5, 5, 6, 6, 6, 6, 6, 6, 6, 6, 6, 7, 7, 7, 7, 7, 7, 7,
+    7, 8, 8, 8, 8, 8, 8, 8, 8, 8, 9, 9, 9, 9, 9, 9, 9, 9, 10, 10,
+];
+
+/// Error correction block groups: (blocks, data codewords, check codewords)
+type BlockGroup = (usize, usize, usize);
+
+/// Block groups of each error correction level
+type LevelBlocks = [&'static [BlockGroup]; 4];
+
+/// Block layout of each version per error correction level (Table D.1)
+const TABLE_D1: [LevelBlocks; 84] = [
+    [&[(1, 21, 4)], &[(1, 17, 8)], &[(1, 13, 12)], &[(1, 9, 16)]],
+    [
+        &[(1, 31, 6)],
+        &[(1, 25, 12)],
+        &[(1, 19, 18)],
+        &[(1, 15, 22)],
+    ],
+    [
+        &[(1, 42, 8)],
+        &[(1, 34, 16)],
+        &[(1, 26, 24)],
+        &[(1, 20, 30)],
+    ],
+    [
+        &[(1, 46, 8)],
+        &[(1, 38, 16)],
+        &[(1, 30, 24)],
+        &[(1, 22, 32)],
+    ],
+    [
+        &[(1, 57, 12)],
+        &[(1, 49, 20)],
+        &[(1, 37, 32)],
+        &[(1, 14, 20), (1, 13, 22)],
+    ],
+    [
+        &[(1, 70, 14)],
+        &[(1, 58, 26)],
+        &[(1, 24, 20), (1, 22, 18)],
+        &[(1, 16, 24), (1, 18, 26)],
+    ],
+    [
+        &[(1, 84, 16)],
+        &[(1, 70, 30)],
+        &[(1, 26, 22), (1, 28, 24)],
+        &[(2, 14, 20), (1, 12, 20)],
+    ],
+    [
+        &[(1, 99, 18)],
+        &[(1, 40, 18), (1, 41, 18)],
+        &[(1, 31, 26), (1, 32, 28)],
+        &[(2, 16, 24), (1, 15, 22)],
+    ],
+    [
+        &[(1, 114, 22)],
+        &[(2, 48, 20)],
+        &[(2, 24, 20), (1, 26, 22)],
+        &[(2, 18, 28), (1, 18, 26)],
+    ],
+    [
+        &[(1, 131, 24)],
+        &[(1, 52, 22), (1, 57, 24)],
+        &[(2, 27, 24), (1, 29, 24)],
+        &[(2, 21, 32), (1, 19, 30)],
+    ],
+    [
+        &[(1, 135, 26)],
+        &[(1, 56, 24), (1, 57, 24)],
+        &[(2, 28, 24), (1, 31, 26)],
+        &[(2, 22, 32), (1, 21, 32)],
+    ],
+    [
+        &[(1, 153, 28)],
+        &[(1, 62, 26), (1, 65, 28)],
+        &[(2, 32, 28), (1, 33, 28)],
+        &[(3, 17, 26), (1, 22, 30)],
+    ],
+    [
+        &[(1, 86, 16), (1, 85, 16)],
+        &[(1, 71, 30), (1, 72, 30)],
+        &[(2, 37, 32), (1, 35, 30)],
+        &[(3, 20, 30), (1, 21, 32)],
+    ],
+    [
+        &[(1, 94, 18), (1, 95, 18)],
+        &[(2, 51, 22), (1, 55, 24)],
+        &[(3, 30, 26), (1, 31, 26)],
+        &[(4, 18, 28), (1, 17, 24)],
+    ],
+    [
+        &[(1, 104, 20), (1, 105, 20)],
+        &[(2, 57, 24), (1, 61, 26)],
+        &[(3, 33, 28), (1, 36, 30)],
+        &[(4, 20, 30), (1, 19, 30)],
+    ],
+    [
+        &[(1, 115, 22), (1, 114, 22)],
+        &[(2, 65, 28), (1, 61, 26)],
+        &[(3, 38, 32), (1, 33, 30)],
+        &[(5, 19, 28), (1, 14, 24)],
+    ],
+    [
+        &[(1, 126, 24), (1, 125, 24)],
+        &[(2, 70, 30), (1, 69, 30)],
+        &[(4, 33, 28), (1, 29, 26)],
+        &[(5, 20, 30), (1, 19, 30)],
+    ],
+    [
+        &[(1, 136, 26), (1, 137, 26)],
+        &[(3, 56, 24), (1, 59, 26)],
+        &[(5, 35, 30)],
+        &[(6, 18, 28), (1, 21, 28)],
+    ],
+    [
+        &[(1, 148, 28), (1, 149, 28)],
+        &[(3, 61, 26), (1, 64, 28)],
+        &[(7, 24, 20), (1, 23, 22)],
+        &[(6, 20, 30), (1, 21, 32)],
+    ],
+    [
+        &[(3, 107, 20)],
+        &[(3, 65, 28), (1, 72, 30)],
+        &[(7, 26, 22), (1, 23, 22)],
+        &[(7, 19, 28), (1, 20, 32)],
+    ],
+    [
+        &[(3, 115, 22)],
+        &[(4, 56, 24), (1, 63, 28)],
+        &[(7, 28, 24), (1, 25, 22)],
+        &[(8, 18, 28), (1, 21, 22)],
+    ],
+    [
+        &[(2, 116, 22), (1, 122, 24)],
+        &[(4, 56, 24), (1, 72, 30)],
+        &[(7, 28, 24), (1, 32, 26)],
+        &[(8, 18, 28), (1, 24, 30)],
+    ],
+    [
+        &[(3, 127, 24)],
+        &[(5, 51, 22), (1, 62, 26)],
+        &[(7, 30, 26), (1, 35, 26)],
+        &[(8, 20, 30), (1, 21, 32)],
+    ],
+    [
+        &[(2, 135, 26), (1, 137, 26)],
+        &[(5, 56, 24), (1, 59, 26)],
+        &[(7, 33, 28), (1, 30, 28)],
+        &[(11, 16, 24), (1, 19, 26)],
+    ],
+    [
+        &[(3, 105, 20), (1, 121, 22)],
+        &[(5, 61, 26), (1, 57, 26)],
+        &[(9, 28, 24), (1, 28, 22)],
+        &[(10, 19, 28), (1, 18, 30)],
+    ],
+    [
+        &[(2, 157, 30), (1, 150, 28)],
+        &[(5, 65, 28), (1, 61, 26)],
+        &[(8, 33, 28), (1, 34, 30)],
+        &[(10, 19, 28), (2, 15, 26)],
+    ],
+    [
+        &[(3, 126, 24), (1, 115, 22)],
+        &[(7, 51, 22), (1, 54, 22)],
+        &[(8, 35, 30), (1, 37, 30)],
+        &[(15, 15, 22), (1, 10, 22)],
+    ],
+    [
+        &[(4, 105, 20), (1, 103, 20)],
+        &[(7, 56, 24), (1, 45, 18)],
+        &[(10, 31, 26), (1, 27, 26)],
+        &[(10, 17, 26), (3, 20, 28), (1, 21, 28)],
+    ],
+    [
+        &[(3, 139, 26), (1, 137, 28)],
+        &[(6, 66, 28), (1, 66, 30)],
+        &[(9, 36, 30), (1, 34, 32)],
+        &[(13, 19, 28), (1, 17, 32)],
+    ],
+    [
+        &[(6, 84, 16), (1, 82, 16)],
+        &[(6, 70, 30), (1, 68, 30)],
+        &[(7, 35, 30), (3, 33, 28), (1, 32, 28)],
+        &[(13, 20, 30), (1, 20, 28)],
+    ],
+    [
+        &[(5, 105, 20), (1, 94, 18)],
+        &[(6, 74, 32), (1, 71, 30)],
+        &[(11, 33, 28), (1, 34, 32)],
+        &[(13, 19, 28), (3, 16, 26)],
+    ],
+    [
+        &[(4, 127, 24), (1, 126, 24)],
+        &[(7, 66, 28), (1, 66, 30)],
+        &[(12, 30, 24), (1, 24, 28), (1, 24, 30)],
+        &[(15, 19, 28), (1, 17, 32)],
+    ],
+    [
+        &[(7, 84, 16), (1, 78, 16)],
+        &[(7, 70, 30), (1, 66, 28)],
+        &[(12, 33, 28), (1, 32, 30)],
+        &[(14, 21, 32), (1, 24, 28)],
+    ],
+    [
+        &[(5, 117, 22), (1, 117, 24)],
+        &[(8, 66, 28), (1, 58, 26)],
+        &[(11, 38, 32), (1, 34, 32)],
+        &[(15, 20, 30), (2, 17, 26)],
+    ],
+    [
+        &[(4, 148, 28), (1, 146, 28)],
+        &[(8, 68, 30), (1, 70, 24)],
+        &[(10, 36, 32), (3, 38, 28)],
+        &[(16, 19, 28), (3, 16, 26)],
+    ],
+    [
+        &[(4, 126, 24), (2, 135, 26)],
+        &[(8, 70, 28), (2, 43, 26)],
+        &[(13, 32, 28), (2, 41, 30)],
+        &[(17, 19, 28), (3, 15, 26)],
+    ],
+    [
+        &[(5, 136, 26), (1, 132, 24)],
+        &[(5, 67, 30), (4, 68, 28), (1, 69, 28)],
+        &[(14, 35, 30), (1, 32, 24)],
+        &[(18, 18, 26), (3, 16, 28), (1, 14, 28)],
+    ],
+    [
+        &[(3, 142, 26), (3, 141, 28)],
+        &[(8, 70, 30), (1, 73, 32), (1, 74, 32)],
+        &[(12, 34, 30), (3, 34, 26), (1, 35, 28)],
+        &[(18, 21, 32), (1, 27, 30)],
+    ],
+    [
+        &[(5, 116, 22), (2, 103, 20), (1, 102, 20)],
+        &[(9, 74, 32), (1, 74, 30)],
+        &[(14, 34, 28), (2, 32, 32), (1, 32, 30)],
+        &[(19, 21, 32), (1, 25, 26)],
+    ],
+    [
+        &[(7, 116, 22), (1, 117, 22)],
+        &[(11, 65, 28), (1, 58, 24)],
+        &[(15, 38, 32), (1, 27, 28)],
+        &[(20, 20, 30), (1, 20, 32), (1, 21, 32)],
+    ],
+    [
+        &[(6, 136, 26), (1, 130, 24)],
+        &[(11, 66, 28), (1, 62, 30)],
+        &[(14, 34, 28), (3, 34, 32), (1, 30, 30)],
+        &[(18, 20, 30), (3, 20, 28), (2, 15, 26)],
+    ],
+    [
+        &[(5, 105, 20), (2, 115, 22), (2, 116, 22)],
+        &[(10, 75, 32), (1, 73, 32)],
+        &[(16, 38, 32), (1, 27, 28)],
+        &[(22, 19, 28), (2, 16, 30), (1, 19, 30)],
+    ],
+    [
+        &[(6, 147, 28), (1, 146, 28)],
+        &[(11, 66, 28), (2, 65, 30)],
+        &[(18, 33, 28), (2, 33, 30)],
+        &[(22, 21, 32), (1, 28, 30)],
+    ],
+    [
+        &[(6, 116, 22), (3, 125, 24)],
+        &[(11, 75, 32), (1, 68, 30)],
+        &[(13, 35, 28), (6, 34, 32), (1, 30, 30)],
+        &[(23, 21, 32), (1, 26, 30)],
+    ],
+    [
+        &[(7, 105, 20), (4, 95, 18)],
+        &[(12, 67, 28), (1, 63, 30), (1, 62, 32)],
+        &[(21, 31, 26), (2, 33, 32)],
+        &[(23, 21, 32), (2, 24, 30)],
+    ],
+    [
+        &[(10, 116, 22)],
+        &[(12, 74, 32), (1, 78, 30)],
+        &[(18, 37, 32), (1, 39, 30), (1, 41, 28)],
+        &[(25, 21, 32), (1, 27, 28)],
+    ],
+    [
+        &[(5, 126, 24), (4, 115, 22), (1, 114, 22)],
+        &[(12, 67, 28), (2, 66, 32), (1, 68, 30)],
+        &[(21, 35, 30), (1, 39, 30)],
+        &[(26, 21, 32), (1, 28, 28)],
+    ],
+    [
+        &[(9, 126, 24), (1, 117, 22)],
+        &[(13, 75, 32), (1, 68, 30)],
+        &[(20, 35, 30), (3, 35, 28)],
+        &[(27, 21, 32), (1, 28, 30)],
+    ],
+    [
+        &[(9, 126, 24), (1, 137, 26)],
+        &[(13, 71, 30), (2, 68, 32)],
+        &[(20, 37, 32), (1, 39, 28), (1, 38, 28)],
+        &[(24, 20, 32), (5, 25, 28)],
+    ],
+    [
+        &[(8, 147, 28), (1, 141, 28)],
+        &[(10, 73, 32), (4, 74, 30), (1, 73, 30)],
+        &[(16, 36, 32), (6, 39, 30), (1, 37, 30)],
+        &[(27, 21, 32), (3, 20, 26)],
+    ],
+    [
+        &[(9, 137, 26), (1, 135, 26)],
+        &[(12, 70, 30), (4, 75, 32)],
+        &[(24, 35, 30), (1, 40, 28)],
+        &[(23, 20, 32), (8, 24, 30)],
+    ],
+    [
+        &[(14, 95, 18), (1, 86, 18)],
+        &[(13, 73, 32), (3, 77, 30)],
+        &[(24, 35, 30), (2, 35, 28)],
+        &[(26, 21, 32), (5, 21, 30), (1, 23, 30)],
+    ],
+    [
+        &[(9, 147, 28), (1, 142, 28)],
+        &[(10, 73, 30), (6, 70, 32), (1, 71, 32)],
+        &[(25, 35, 30), (2, 34, 26)],
+        &[(29, 21, 32), (4, 22, 30)],
+    ],
+    [
+        &[(11, 126, 24), (1, 131, 24)],
+        &[(16, 74, 32), (1, 79, 30)],
+        &[(25, 38, 32), (1, 25, 30)],
+        &[(33, 21, 32), (1, 28, 28)],
+    ],
+    [
+        &[(14, 105, 20), (1, 99, 18)],
+        &[(19, 65, 28), (1, 72, 28)],
+        &[(24, 37, 32), (2, 40, 30), (1, 41, 30)],
+        &[(31, 21, 32), (4, 24, 32)],
+    ],
+    [
+        &[(10, 147, 28), (1, 151, 28)],
+        &[(15, 71, 30), (3, 71, 32), (1, 73, 32)],
+        &[(24, 37, 32), (3, 38, 30), (1, 39, 30)],
+        &[(36, 19, 30), (3, 29, 26)],
+    ],
+    [
+        &[(15, 105, 20), (1, 99, 18)],
+        &[(19, 70, 30), (1, 64, 28)],
+        &[(27, 38, 32), (2, 25, 26)],
+        &[(38, 20, 30), (2, 18, 28)],
+    ],
+    [
+        &[(14, 105, 20), (1, 113, 22), (1, 114, 22)],
+        &[(17, 67, 30), (3, 92, 32)],
+        &[(30, 35, 30), (1, 41, 30)],
+        &[(36, 21, 32), (1, 26, 30), (1, 27, 30)],
+    ],
+    [
+        &[(11, 146, 28), (1, 146, 26)],
+        &[(20, 70, 30), (1, 60, 26)],
+        &[(29, 38, 32), (1, 24, 32)],
+        &[(40, 20, 30), (2, 17, 26)],
+    ],
+    [
+        &[(3, 137, 26), (1, 136, 26), (10, 126, 24)],
+        &[(22, 65, 28), (1, 75, 30)],
+        &[(30, 37, 32), (1, 51, 30)],
+        &[(42, 20, 30), (1, 21, 30)],
+    ],
+    [
+        &[(12, 126, 24), (2, 118, 22), (1, 116, 22)],
+        &[(19, 74, 32), (1, 74, 30), (1, 72, 28)],
+        &[(30, 38, 32), (2, 29, 30)],
+        &[(39, 20, 32), (2, 37, 26), (1, 38, 26)],
+    ],
+    [
+        &[(12, 126, 24), (3, 136, 26)],
+        &[(21, 70, 30), (2, 65, 28)],
+        &[(34, 35, 30), (1, 44, 32)],
+        &[(42, 20, 30), (2, 19, 28), (2, 18, 28)],
+    ],
+    [
+        &[(12, 126, 24), (3, 117, 22), (1, 116, 22)],
+        &[(25, 61, 26), (2, 62, 28)],
+        &[(34, 35, 30), (1, 40, 32), (1, 41, 32)],
+        &[(45, 20, 30), (1, 20, 32), (1, 21, 32)],
+    ],
+    [
+        &[(15, 105, 20), (2, 115, 22), (2, 116, 22)],
+        &[(25, 65, 28), (1, 72, 28)],
+        &[(18, 35, 30), (17, 37, 32), (1, 50, 32)],
+        &[(42, 20, 30), (6, 19, 28), (1, 15, 28)],
+    ],
+    [
+        &[(19, 105, 20), (1, 101, 20)],
+        &[(33, 51, 22), (1, 65, 22)],
+        &[(40, 33, 28), (1, 28, 28)],
+        &[(49, 20, 30), (1, 18, 28)],
+    ],
+    [
+        &[(18, 105, 20), (2, 117, 22)],
+        &[(26, 65, 28), (1, 80, 30)],
+        &[(35, 35, 30), (3, 35, 28), (1, 36, 28)],
+        &[(52, 18, 28), (2, 38, 30)],
+    ],
+    [
+        &[(26, 84, 16)],
+        &[(26, 70, 30)],
+        &[(45, 31, 26), (1, 9, 26)],
+        &[(52, 20, 30)],
+    ],
+    [
+        &[(16, 126, 24), (1, 114, 22), (1, 115, 22)],
+        &[(23, 70, 30), (3, 65, 28), (1, 66, 28)],
+        &[(40, 35, 30), (1, 43, 30)],
+        &[(46, 20, 30), (7, 19, 28), (1, 16, 28)],
+    ],
+    [
+        &[(19, 116, 22), (1, 105, 22)],
+        &[(20, 70, 30), (7, 66, 28), (1, 63, 28)],
+        &[(40, 35, 30), (1, 42, 32), (1, 43, 32)],
+        &[(54, 20, 30), (1, 19, 30)],
+    ],
+    [
+        &[(17, 126, 24), (2, 115, 22)],
+        &[(24, 70, 30), (4, 74, 32)],
+        &[(48, 31, 26), (2, 18, 26)],
+        &[(54, 19, 28), (6, 15, 26), (1, 14, 26)],
+    ],
+    [
+        &[(29, 84, 16)],
+        &[(29, 70, 30)],
+        &[(6, 34, 30), (3, 36, 30), (38, 33, 28)],
+        &[(58, 20, 30)],
+    ],
+    [
+        &[(16, 147, 28), (1, 149, 28)],
+        &[(31, 66, 28), (1, 37, 26)],
+        &[(48, 33, 28), (1, 23, 26)],
+        &[(53, 20, 30), (6, 19, 28), (1, 17, 28)],
+    ],
+    [
+        &[(20, 115, 22), (2, 134, 24)],
+        &[(29, 66, 28), (2, 56, 26), (2, 57, 26)],
+        &[(45, 36, 30), (2, 15, 28)],
+        &[(59, 20, 30), (2, 21, 32)],
+    ],
+    [
+        &[(17, 147, 28), (1, 134, 26)],
+        &[(26, 70, 30), (5, 75, 32)],
+        &[(47, 35, 30), (1, 48, 32)],
+        &[(64, 18, 28), (2, 33, 30), (1, 35, 30)],
+    ],
+    [
+        &[(22, 115, 22), (1, 133, 24)],
+        &[(33, 65, 28), (1, 74, 28)],
+        &[(43, 36, 30), (5, 27, 28), (1, 30, 28)],
+        &[(57, 20, 30), (5, 21, 32), (1, 24, 32)],
+    ],
+    [
+        &[(18, 136, 26), (2, 142, 26)],
+        &[(33, 66, 28), (2, 49, 26)],
+        &[(48, 35, 30), (2, 38, 28)],
+        &[(64, 20, 30), (1, 20, 32)],
+    ],
+    [
+        &[(19, 126, 24), (2, 135, 26), (1, 136, 26)],
+        &[(32, 66, 28), (2, 55, 26), (2, 56, 26)],
+        &[(49, 36, 30), (2, 18, 32)],
+        &[(65, 18, 28), (5, 27, 30), (1, 29, 30)],
+    ],
+    [
+        &[(20, 137, 26), (1, 130, 26)],
+        &[(30, 75, 32), (2, 71, 32)],
+        &[(46, 35, 30), (6, 39, 32)],
+        &[(3, 12, 30), (70, 19, 28)],
+    ],
+    [
+        &[(20, 147, 28)],
+        &[(35, 70, 30)],
+        &[(49, 35, 30), (5, 35, 28)],
+        &[(70, 20, 30)],
+    ],
+    [
+        &[(21, 136, 26), (1, 155, 28)],
+        &[(34, 70, 30), (1, 64, 28), (1, 65, 28)],
+        &[(54, 35, 30), (1, 45, 30)],
+        &[(68, 20, 30), (3, 18, 28), (1, 19, 28)],
+    ],
+    [
+        &[(19, 126, 24), (5, 115, 22), (1, 114, 22)],
+        &[(33, 70, 30), (3, 65, 28), (1, 64, 28)],
+        &[(52, 35, 30), (3, 41, 32), (1, 40, 32)],
+        &[(67, 20, 30), (5, 21, 32), (1, 24, 32)],
+    ],
+    [
+        &[(2, 150, 28), (21, 136, 26)],
+        &[(32, 70, 30), (6, 65, 28)],
+        &[(52, 38, 32), (2, 27, 32)],
+        &[(73, 20, 30), (2, 22, 32)],
+    ],
+    [
+        &[(21, 126, 24), (4, 136, 26)],
+        &[(30, 74, 32), (6, 73, 30)],
+        &[(54, 35, 30), (4, 40, 32)],
+        &[(75, 20, 30), (1, 20, 28)],
+    ],
+    [
+        &[(30, 105, 20), (1, 114, 22)],
+        &[(3, 45, 22), (55, 47, 20)],
+        &[(2, 26, 26), (62, 33, 28)],
+        &[(79, 18, 28), (4, 33, 30)],
+    ],
+];
+
+/// Error correction level, from about 8% (L1) to 30% (L4) recovery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HanXinEcLevel {
+    #[default]
+    L1,
+    L2,
+    L3,
+    L4,
+}
+
+impl HanXinEcLevel {
+    fn index(self) -> usize {
+        match self {
+            HanXinEcLevel::L1 => 0,
+            HanXinEcLevel::L2 => 1,
+            HanXinEcLevel::L3 => 2,
+            HanXinEcLevel::L4 => 3,
+        }
+    }
+}
+
+/// Han Xin Code configuration options
+#[derive(Debug, Clone, Default)]
+pub struct HanXinConfig {
+    /// Error correction level
+    pub ec_level: HanXinEcLevel,
+    /// Symbol version (1-84), or the smallest that fits the data
+    pub version: Option<u8>,
+}
+
+/// Generate a Han Xin Code with default configuration
+pub fn generate_hanxin(data: &str) -> Result<Barcode> {
+    generate_hanxin_with_config(data, &HanXinConfig::default())
+}
+
+/// Generate a Han Xin Code with a given error correction level and version
+pub fn generate_hanxin_with_config(data: &str, config: &HanXinConfig) -> Result<Barcode> {
+    if data.is_empty() {
+        return Err(QuickCodesError::InvalidData(
+            "Han Xin data cannot be empty".to_string(),
+        ));
+    }
+
+    let bits = encode_data(data)?;
+    let needed = (bits.len() + 7) / 8;
+    let version = select_version(needed, config)?;
+    let codewords = add_error_correction(&bits, version, config.ec_level);
+    let matrix = build_matrix(version, config.ec_level, &codewords);
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::HanXin,
+        data: data.to_string(),
+        modules: BarcodeModules::Matrix(matrix),
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Symbol size in modules
+fn symbol_size(version: u8) -> usize {
+    21 + 2 * usize::from(version)
+}
+
+/// Error correction blocks of a version and level
+fn block_groups(version: u8, ec_level: HanXinEcLevel) -> &'static [BlockGroup] {
+    TABLE_D1[usize::from(version) - 1][ec_level.index()]
+}
+
+fn data_capacity(version: u8, ec_level: HanXinEcLevel) -> usize {
+    block_groups(version, ec_level)
+        .iter()
+        .map(|&(blocks, data, _)| blocks * data)
+        .sum()
+}
+
+fn select_version(needed: usize, config: &HanXinConfig) -> Result<u8> {
+    match config.version {
+        Some(version) if !(1..=MAX_VERSION).contains(&version) => Err(
+            QuickCodesError::InvalidData(format!("Han Xin version must be 1-84, got {}", version)),
+        ),
+        Some(version) if data_capacity(version, config.ec_level) < needed => {
+            Err(QuickCodesError::InvalidData(format!(
+                "Data needs {} codewords, version {} holds {}",
+                needed,
+                version,
+                data_capacity(version, config.ec_level)
+            )))
+        }
+        Some(version) => Ok(version),
+        None => (1..=MAX_VERSION)
+            .find(|&version| data_capacity(version, config.ec_level) >= needed)
+            .ok_or_else(|| {
+                QuickCodesError::InvalidData(format!(
+                    "Data too long for Han Xin: {} codewords",
+                    needed
+                ))
+            }),
+    }
+}
+
+/// A character's GB 18030 bytes with its encoding mode
+type Character = (Mode, Vec<u8>);
+
+/// Encoding mode of a single character
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Numeric,
+    Text,
+    Binary,
+    Region1,
+    Region2,
+    DoubleByte,
+    FourByte,
+}
+
+/// Character value in text submode 1: digits and letters
+fn text1_value(c: u8) -> Option<u32> {
+    match c {
+        b'0'..=b'9' => Some(u32::from(c - b'0')),
+        b'A'..=b'Z' => Some(u32::from(c - b'A') + 10),
+        b'a'..=b'z' => Some(u32::from(c - b'a') + 36),
+        _ => None,
+    }
+}
+
+/// Character value in text submode 2: control characters and punctuation
+fn text2_value(c: u8) -> Option<u32> {
+    let c = u32::from(c);
+    match c {
+        0..=27 => Some(c),
+        0x20..=0x2F => Some(c - 4),
+        0x3A..=0x40 => Some(c - 14),
+        0x5B..=0x60 => Some(c - 40),
+        0x7B..=0x7F => Some(c - 66),
+        _ => None,
+    }
+}
+
+/// Split the data into characters with their GB 18030 bytes and mode
+fn classify(data: &str) -> Vec<Character> {
+    let mut characters: Vec<Character> = data
+        .chars()
+        .map(|c| {
+            let mut buffer = [0; 4];
+            let (bytes, _, _) = encoding_rs::GB18030.encode(c.encode_utf8(&mut buffer));
+            let bytes = bytes.into_owned();
+            let mode = match bytes.as_slice() {
+                [b] if b.is_ascii_digit() => Mode::Numeric,
+                [b] if text1_value(*b).is_some() || text2_value(*b).is_some() => Mode::Text,
+                [_] => Mode::Binary,
+                [0xB0..=0xD7, 0xA1..=0xFE] => Mode::Region1,
+                [0xD8..=0xF7, 0xA1..=0xFE] => Mode::Region2,
+                [_, _] => Mode::DoubleByte,
+                _ => Mode::FourByte,
+            };
+            (mode, bytes)
+        })
+        .collect();
+
+    // Short digit runs next to text stay in the text segment
+    let mut start = 0;
+    while start < characters.len() {
+        let mut end = start;
+        while end < characters.len() && characters[end].0 == Mode::Numeric {
+            end += 1;
+        }
+        if end == start {
+            start += 1;
+            continue;
+        }
+        let text_before = start > 0 && characters[start - 1].0 == Mode::Text;
+        let text_after = end < characters.len() && characters[end].0 == Mode::Text;
+        if end - start < MIN_NUMERIC_RUN && (text_before || text_after) {
+            for character in &mut characters[start..end] {
+                character.0 = Mode::Text;
+            }
+        }
+        start = end;
+    }
+
+    characters
+}
+
+fn append_bits(bits: &mut Vec<bool>, value: u32, count: usize) {
+    for i in (0..count).rev() {
+        bits.push((value >> i) & 1 == 1);
+    }
+}
+
+/// Encode the data as a bit stream of mode segments
+fn encode_data(data: &str) -> Result<Vec<bool>> {
+    let characters = classify(data);
+    let mut bits = Vec::new();
+
+    for segment in segments(&characters) {
+        let mode = segment[0].0;
+        let bytes: Vec<&[u8]> = segment.iter().map(|(_, bytes)| bytes.as_slice()).collect();
+        match mode {
+            Mode::Numeric => encode_numeric(&mut bits, &bytes),
+            Mode::Text => encode_text(&mut bits, &bytes),
+            Mode::Binary => {
+                if bytes.len() > 8191 {
+                    return Err(QuickCodesError::InvalidData(
+                        "Han Xin binary segment is limited to 8191 bytes".to_string(),
+                    ));
+                }
+                append_bits(&mut bits, MODE_BINARY, 4);
+                append_bits(&mut bits, bytes.len() as u32, 13);
+                for byte in bytes {
+                    append_bits(&mut bits, u32::from(byte[0]), 8);
+                }
+            }
+            Mode::Region1 | Mode::Region2 => {
+                let (indicator, first_row) = if mode == Mode::Region1 {
+                    (MODE_REGION1, 0xB0)
+                } else {
+                    (MODE_REGION2, 0xD8)
+                };
+                append_bits(&mut bits, indicator, 4);
+                for pair in bytes {
+                    let value = u32::from(pair[0] - first_row) * 94 + u32::from(pair[1] - 0xA1);
+                    append_bits(&mut bits, value, 12);
+                }
+                append_bits(&mut bits, 0xFFF, 12);
+            }
+            Mode::DoubleByte => {
+                append_bits(&mut bits, MODE_DOUBLE_BYTE, 4);
+                for pair in bytes {
+                    // Second byte 0x40-0x7E or 0x80-0xFE
+                    let second = u32::from(pair[1]) - 0x40 - u32::from(pair[1] >= 0x80);
+                    append_bits(&mut bits, u32::from(pair[0] - 0x81) * 190 + second, 15);
+                }
+                append_bits(&mut bits, 0x7FFF, 15);
+            }
+            Mode::FourByte => {
+                for quad in bytes {
+                    let value = u32::from(quad[0] - 0x81) * 12600
+                        + u32::from(quad[1] - 0x30) * 1260
+                        + u32::from(quad[2] - 0x81) * 10
+                        + u32::from(quad[3] - 0x30);
+                    append_bits(&mut bits, MODE_FOUR_BYTE, 4);
+                    append_bits(&mut bits, value, 21);
+                }
+            }
+        }
+    }
+
+    Ok(bits)
+}
+
+/// Runs of consecutive characters with the same mode
+fn segments(characters: &[Character]) -> Vec<&[Character]> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for i in 1..=characters.len() {
+        if i == characters.len() || characters[i].0 != characters[start].0 {
+            segments.push(&characters[start..i]);
+            start = i;
+        }
+    }
+    segments
+}
+
+/// Numeric mode: groups of three digits in 10 bits, then a terminator
+/// giving the length of the last group
+fn encode_numeric(bits: &mut Vec<bool>, digits: &[&[u8]]) {
+    append_bits(bits, MODE_NUMERIC, 4);
+    let mut last_group = 0;
+    for group in digits.chunks(3) {
+        let value = group
+            .iter()
+            .fold(0, |acc, digit| acc * 10 + u32::from(digit[0] - b'0'));
+        append_bits(bits, value, 10);
+        last_group = group.len();
+    }
+    append_bits(bits, 1020 + last_group as u32, 10);
+}
+
+/// Text mode: six-bit values in two submodes, starting in submode 1
+fn encode_text(bits: &mut Vec<bool>, characters: &[&[u8]]) {
+    append_bits(bits, MODE_TEXT, 4);
+    let mut submode1 = true;
+    for character in characters {
+        let c = character[0];
+        if text1_value(c).is_some() != submode1 {
+            append_bits(bits, TEXT_SUBMODE_SWITCH, 6);
+            submode1 = !submode1;
+        }
+        let value = if submode1 {
+            text1_value(c)
+        } else {
+            text2_value(c)
+        };
+        append_bits(bits, value.unwrap_or(0), 6);
+    }
+    append_bits(bits, TEXT_TERMINATOR, 6);
+}
+
+/// Galois field arithmetic through exponent and logarithm tables
+struct GaloisField {
+    exp: Vec<u16>,
+    log: Vec<u16>,
+}
+
+impl GaloisField {
+    fn new(bits: u32, polynomial: u16) -> Self {
+        let order = (1usize << bits) - 1;
+        let mut exp = vec![0u16; order];
+        let mut log = vec![0u16; order + 1];
+        let mut x = 1u16;
+        for (i, e) in exp.iter_mut().enumerate() {
+            *e = x;
+            log[usize::from(x)] = i as u16;
+            x <<= 1;
+            if x & (1 << bits) != 0 {
+                x ^= polynomial;
+            }
+        }
+        Self { exp, log }
+    }
+
+    fn mul(&self, a: u16, b: u16) -> u16 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            let sum = usize::from(self.log[usize::from(a)]) + usize::from(self.log[usize::from(b)]);
+            self.exp[sum % self.exp.len()]
+        }
+    }
+
+    /// Reed-Solomon check symbols, highest degree first, for a generator
+    /// polynomial with roots a^1 to a^count
+    fn reed_solomon(&self, data: &[u16], count: usize) -> Vec<u16> {
+        let mut generator = vec![1u16];
+        for root in &self.exp[1..=count] {
+            let mut next = generator.clone();
+            next.push(0);
+            for (i, &coefficient) in generator.iter().enumerate() {
+                next[i + 1] ^= self.mul(coefficient, *root);
+            }
+            generator = next;
+        }
+
+        let mut check = vec![0u16; count];
+        for &symbol in data {
+            let feedback = symbol ^ check[0];
+            check.rotate_left(1);
+            check[count - 1] = 0;
+            for (c, &g) in check.iter_mut().zip(&generator[1..]) {
+                *c ^= self.mul(feedback, g);
+            }
+        }
+        check
+    }
+}
+
+/// Pad the bit stream to the data capacity and append each block's check
+/// codewords
+fn add_error_correction(bits: &[bool], version: u8, ec_level: HanXinEcLevel) -> Vec<u8> {
+    let mut data: Vec<u16> = bits
+        .chunks(8)
+        .map(|byte| {
+            byte.iter()
+                .enumerate()
+                .fold(0, |acc, (i, &bit)| acc | (u16::from(bit) << (7 - i)))
+        })
+        .collect();
+    data.resize(data_capacity(version, ec_level), 0);
+
+    let gf = GaloisField::new(8, GF256_POLYNOMIAL);
+    let mut codewords = Vec::new();
+    let mut position = 0;
+    for &(blocks, length, check) in block_groups(version, ec_level) {
+        for _ in 0..blocks {
+            let block = &data[position..position + length];
+            codewords.extend(block.iter().map(|&c| c as u8));
+            codewords.extend(gf.reed_solomon(block, check).iter().map(|&c| c as u8));
+            position += length;
+        }
+    }
+    codewords
+}
+
+/// Module grid with the function patterns marked as reserved
+struct Grid {
+    size: usize,
+    dark: Vec<Vec<bool>>,
+    reserved: Vec<Vec<bool>>,
+}
+
+impl Grid {
+    fn new(version: u8) -> Self {
+        let size = symbol_size(version);
+        let mut grid = Grid {
+            size,
+            dark: vec![vec![false; size]; size],
+            reserved: vec![vec![false; size]; size],
+        };
+        grid.add_finder_patterns();
+        grid.add_alignment_patterns(version);
+        grid
+    }
+
+    /// Set a function module unless it is outside the grid or already set
+    fn plot(&mut self, x: isize, y: isize, dark: bool) {
+        let size = self.size as isize;
+        if (0..size).contains(&x) && (0..size).contains(&y) {
+            let (x, y) = (x as usize, y as usize);
+            if !self.reserved[y][x] {
+                self.reserved[y][x] = true;
+                self.dark[y][x] = dark;
+            }
+        }
+    }
+
+    fn add_finder_patterns(&mut self) {
+        // Top left, then top right and bottom left, then bottom right
+        const TOP_LEFT: [u8; 7] = [0x7F, 0x40, 0x5F, 0x50, 0x57, 0x57, 0x57];
+        const OTHER: [u8; 7] = [0x7F, 0x01, 0x7D, 0x05, 0x75, 0x75, 0x75];
+        const BOTTOM_RIGHT: [u8; 7] = [0x75, 0x75, 0x75, 0x05, 0x7D, 0x01, 0x7F];
+
+        let far = self.size - 7;
+        for (x0, y0, pattern) in [
+            (0, 0, TOP_LEFT),
+            (far, 0, OTHER),
+            (0, far, OTHER),
+            (far, far, BOTTOM_RIGHT),
+        ] {
+            for (y, row) in pattern.iter().enumerate() {
+                for x in 0..7 {
+                    self.plot((x0 + x) as isize, (y0 + y) as isize, row & (0x40 >> x) != 0);
+                }
+            }
+        }
+
+        // Light separators and the function information region around
+        // each finder pattern
+        let last = self.size as isize - 1;
+        for i in 0..9 {
+            for offset in [7, 8] {
+                for (x, y) in [
+                    (i, offset),
+                    (offset, i),
+                    (last - i, offset),
+                    (last - offset, i),
+                    (i, last - offset),
+                    (offset, last - i),
+                    (last - i, last - offset),
+                    (last - offset, last - i),
+                ] {
+                    if offset == 8 || i < 8 {
+                        self.plot(x, y, false);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Alignment patterns and assistant alignment patterns (version 4 and up)
+    fn add_alignment_patterns(&mut self, version: u8) {
+        if version < 4 {
+            return;
+        }
+
+        let index = usize::from(version) - 1;
+        let k = isize::from(MODULE_K[index]);
+        let r = isize::from(MODULE_R[index]);
+        let m = isize::from(MODULE_M[index]);
+        let size = self.size as isize;
+        let region = |n: isize| if n < m { k } else { r - 1 };
+
+        // Assistant patterns on the left and right edges
+        let (mut y, mut n) = (0, 0);
+        while y < size {
+            if n % 2 == 0 {
+                if m % 2 == 1 {
+                    self.plot_assistant(0, y);
+                }
+            } else {
+                if m % 2 == 0 {
+                    self.plot_assistant(0, y);
+                }
+                self.plot_assistant(size - 1, y);
+            }
+            y += region(n);
+            n += 1;
+        }
+
+        // Assistant patterns on the top and bottom edges
+        let (mut x, mut n) = (size - 1, 0);
+        while x >= 0 {
+            if n % 2 == 0 {
+                if m % 2 == 1 {
+                    self.plot_assistant(x, size - 1);
+                }
+            } else {
+                if m % 2 == 0 {
+                    self.plot_assistant(x, size - 1);
+                }
+                self.plot_assistant(x, 0);
+            }
+            x -= region(n);
+            n += 1;
+        }
+
+        // Alignment corners in a checkerboard of regions
+        let (mut y, mut row) = (0, 0);
+        while y < size {
+            let height = region(row);
+            let (mut x, mut column) = (size - 1, 0);
+            while x >= 0 {
+                let width = region(column);
+                if (row + column) % 2 == 0 && !(y == 0 && x == size - 1) {
+                    self.plot_alignment(x, y, width, height);
+                }
+                x -= width;
+                column += 1;
+            }
+            y += height;
+            row += 1;
+        }
+    }
+
+    /// Dark L-shaped line from (x, y) leftwards and downwards, edged with
+    /// light modules
+    fn plot_alignment(&mut self, x: isize, y: isize, width: isize, height: isize) {
+        self.plot(x, y, true);
+        self.plot(x - 1, y + 1, false);
+        for i in 1..=width {
+            self.plot(x - i, y, true);
+            self.plot(x - i - 1, y + 1, false);
+        }
+        for i in 1..height {
+            self.plot(x, y + i, true);
+            self.plot(x - 1, y + i + 1, false);
+        }
+    }
+
+    /// Dark module surrounded by light modules
+    fn plot_assistant(&mut self, x: isize, y: isize) {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                self.plot(x + dx, y + dy, dx == 0 && dy == 0);
+            }
+        }
+    }
+
+    /// Data module positions in placement order: row by row
+    fn data_positions(&self) -> Vec<(usize, usize)> {
+        (0..self.size)
+            .flat_map(|y| (0..self.size).map(move |x| (x, y)))
+            .filter(|&(x, y)| !self.reserved[y][x])
+            .collect()
+    }
+
+    /// Function information: version, level and mask with GF(16)
+    /// Reed-Solomon check symbols, around the four finder patterns
+    fn add_function_information(&mut self, version: u8, ec_level: HanXinEcLevel, mask: usize) {
+        let info = (u16::from(version) + 20) << 4 | (ec_level.index() as u16) << 2 | mask as u16;
+        let symbols = [info >> 8, (info >> 4) & 0xF, info & 0xF];
+        let check = GaloisField::new(4, GF16_POLYNOMIAL).reed_solomon(&symbols, 4);
+
+        let mut bits = Vec::with_capacity(34);
+        for symbol in symbols.iter().chain(&check) {
+            append_bits(&mut bits, u32::from(*symbol), 4);
+        }
+        bits.resize(34, false);
+
+        let last = self.size - 1;
+        for i in 0..9 {
+            let placements = [
+                (bits[i], [(i, 8), (last - i, last - 8)]),
+                (bits[i + 8], [(8, 8 - i), (last - 8, last - 8 + i)]),
+                (bits[i + 17], [(last - 8, i), (8, last - i)]),
+                (bits[i + 25], [(last - 8 + i, 8), (8 - i, last - 8)]),
+            ];
+            for (bit, cells) in placements {
+                for (x, y) in cells {
+                    self.dark[y][x] = bit;
+                }
+            }
+        }
+    }
+}
+
+/// Whether mask pattern 1-3 inverts the module at (x, y)
+fn mask_applies(mask: usize, x: usize, y: usize) -> bool {
+    let (i, j) = (y + 1, x + 1);
+    match mask {
+        1 => (i + j) % 2 == 0,
+        2 => ((i + j) % 3 + j % 3) % 2 == 0,
+        3 => (i % j + j % i + i % 3 + j % 3) % 2 == 0,
+        _ => false,
+    }
+}
+
+/// Penalty for finder-like 1:1:1:1:3 patterns and long runs of one colour
+fn penalty(dark: &[Vec<bool>]) -> usize {
+    let size = dark.len();
+    let lines = (0..size)
+        .map(|y| dark[y].clone())
+        .chain((0..size).map(|x| dark.iter().map(|row| row[x]).collect()));
+
+    let mut score = 0;
+    for line in lines {
+        for window in line.windows(7) {
+            let pattern = window
+                .iter()
+                .fold(0u8, |acc, &dark| acc << 1 | u8::from(dark));
+            if pattern == 0x57 || pattern == 0x75 {
+                score += 50;
+            }
+        }
+
+        let mut run = 1;
+        for i in 1..=line.len() {
+            if i < line.len() && line[i] == line[i - 1] {
+                run += 1;
+            } else {
+                if run > 3 {
+                    score += 4 * (run - 3);
+                }
+                run = 1;
+            }
+        }
+    }
+    score
+}
+
+fn build_matrix(version: u8, ec_level: HanXinEcLevel, codewords: &[u8]) -> Vec<Vec<bool>> {
+    let mut grid = Grid::new(version);
+    let positions = grid.data_positions();
+
+    // Interleave the codewords in batches of 13
+    let interleaved: Vec<u8> = (0..INTERLEAVE)
+        .flat_map(|start| codewords.iter().skip(start).step_by(INTERLEAVE).copied())
+        .collect();
+    for (i, &(x, y)) in positions.iter().take(interleaved.len() * 8).enumerate() {
+        grid.dark[y][x] = interleaved[i / 8] & (0x80 >> (i % 8)) != 0;
+    }
+
+    // Choose the mask with the lowest penalty
+    let masked = |mask: usize| {
+        let mut dark = grid.dark.clone();
+        for &(x, y) in &positions {
+            if mask_applies(mask, x, y) {
+                dark[y][x] = !dark[y][x];
+            }
+        }
+        dark
+    };
+    let mask = (0..4)
+        .min_by_key(|&mask| penalty(&masked(mask)))
+        .unwrap_or(0);
+
+    grid.dark = masked(mask);
+    grid.add_function_information(version, ec_level, mask);
+    grid.dark
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total_codewords(version: u8, ec_level: HanXinEcLevel) -> usize {
+        block_groups(version, ec_level)
+            .iter()
+            .map(|&(blocks, data, check)| blocks * (data + check))
+            .sum()
+    }
+
+    #[test]
+    fn test_hanxin_geometry_matches_block_table() {
+        for version in 1..=MAX_VERSION {
+            let grid = Grid::new(version);
+            assert_eq!(grid.size, symbol_size(version));
+            let capacity = grid.data_positions().len() / 8;
+            for ec_level in [
+                HanXinEcLevel::L1,
+                HanXinEcLevel::L2,
+                HanXinEcLevel::L3,
+                HanXinEcLevel::L4,
+            ] {
+                assert_eq!(
+                    total_codewords(version, ec_level),
+                    capacity,
+                    "version {} {:?}",
+                    version,
+                    ec_level
+                );
+                assert!(block_groups(version, ec_level)
+                    .iter()
+                    .all(|&(_, data, check)| data + check <= 255));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hanxin_block_table_reference() {
+        // Last check codewords of symbols filled with digits, from zint
+        let digits = |n: usize| -> String {
+            (0..n)
+                .map(|i| char::from(b'0' + (i * 7 % 10) as u8))
+                .collect()
+        };
+        let cases: [(u8, HanXinEcLevel, usize, [u8; 8]); 5] = [
+            (
+                13,
+                HanXinEcLevel::L4,
+                189,
+                [0xa0, 0x0f, 0x11, 0x26, 0x64, 0xdd, 0x09, 0x42],
+            ),
+            (
+                24,
+                HanXinEcLevel::L3,
+                621,
+                [0xd8, 0x16, 0x00, 0x4e, 0x00, 0x0b, 0x81, 0x2d],
+            ),
+            (
+                40,
+                HanXinEcLevel::L2,
+                1851,
+                [0x87, 0x6d, 0x51, 0x40, 0x72, 0x67, 0xe6, 0xa7],
+            ),
+            (
+                62,
+                HanXinEcLevel::L4,
+                2187,
+                [0x93, 0x21, 0x97, 0x4a, 0x54, 0x63, 0x05, 0xc9],
+            ),
+            (
+                84,
+                HanXinEcLevel::L1,
+                7827,
+                [0xcc, 0x9a, 0xad, 0x30, 0xc7, 0xf7, 0x01, 0xbe],
+            ),
+        ];
+        for (version, ec_level, length, tail) in cases {
+            let bits = encode_data(&digits(length)).unwrap();
+            let config = HanXinConfig {
+                ec_level,
+                version: None,
+            };
+            assert_eq!(
+                select_version((bits.len() + 7) / 8, &config).unwrap(),
+                version
+            );
+            let codewords = add_error_correction(&bits, version, ec_level);
+            assert_eq!(
+                codewords[codewords.len() - 8..],
+                tail,
+                "version {}",
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn test_hanxin_mode_encoding() {
+        let mut bits = Vec::new();
+        encode_numeric(&mut bits, &[b"1", b"2", b"3", b"4"]);
+        // Mode 0001, 123, 4, terminator for a one-digit last group
+        let expected = "0001 0001111011 0000000100 1111111101".replace(' ', "");
+        let actual: String = bits.iter().map(|&b| if b { '1' } else { '0' }).collect();
+        assert_eq!(actual, expected);
+
+        let modes: Vec<Mode> = classify("Ab1,汉亍中文𠀀").iter().map(|c| c.0).collect();
+        assert_eq!(
+            modes,
+            vec![
+                Mode::Text,
+                Mode::Text,
+                Mode::Text,
+                Mode::Text,
+                Mode::Region1,
+                Mode::Region2,
+                Mode::Region1,
+                Mode::Region1,
+                Mode::FourByte,
+            ]
+        );
+        assert_eq!(classify("12345678901")[0].0, Mode::Numeric);
+        assert_eq!(classify("\u{1c}")[0].0, Mode::Binary);
+    }
+
+    #[test]
+    fn test_hanxin_text_submodes() {
+        let mut bits = Vec::new();
+        encode_text(&mut bits, &[b"A", b" ", b"a"]);
+        // Mode, 'A' (10), switch, ' ' (28), switch, 'a' (36), terminator
+        let expected = "0010 001010 111110 011100 111110 100100 111111".replace(' ', "");
+        let actual: String = bits.iter().map(|&b| if b { '1' } else { '0' }).collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_hanxin_error_correction() {
+        let gf = GaloisField::new(8, GF256_POLYNOMIAL);
+        let bits = encode_data("Han Xin 汉信码 0123456789").unwrap();
+        let codewords = add_error_correction(&bits, 7, HanXinEcLevel::L4);
+        assert_eq!(codewords.len(), 100);
+
+        // Syndromes of every block are zero at the generator roots
+        let mut position = 0;
+        for &(blocks, data, check) in block_groups(7, HanXinEcLevel::L4) {
+            for _ in 0..blocks {
+                let block = &codewords[position..position + data + check];
+                for root in &gf.exp[1..=check] {
+                    let syndrome = block
+                        .iter()
+                        .fold(0, |acc, &c| gf.mul(acc, *root) ^ u16::from(c));
+                    assert_eq!(syndrome, 0);
+                }
+                position += data + check;
+            }
+        }
+    }
+
+    #[test]
+    fn test_hanxin_generation() {
+        let barcode = generate_hanxin("汉信码 Han Xin").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::HanXin);
+
+        let matrix = barcode.modules.as_matrix().unwrap();
+        assert_eq!(matrix.len(), 23);
+        // Top left finder pattern
+        assert!(matrix[0][..7].iter().all(|&m| m));
+        assert_eq!(
+            &matrix[1][..8],
+            &[true, false, false, false, false, false, false, false]
+        );
+        // Bottom right finder pattern ends with a solid row
+        assert!(matrix[22][16..].iter().all(|&m| m));
+    }
+
+    #[test]
+    fn test_hanxin_config() {
+        let config = HanXinConfig {
+            ec_level: HanXinEcLevel::L3,
+            version: Some(20),
+        };
+        let barcode = generate_hanxin_with_config("1234567890", &config).unwrap();
+        assert_eq!(barcode.modules.as_matrix().unwrap().len(), 61);
+
+        // Largest version
+        let config = HanXinConfig {
+            ec_level: HanXinEcLevel::L2,
+            version: Some(84),
+        };
+        assert!(generate_hanxin_with_config("QuickCodes", &config).is_ok());
+
+        let too_small = HanXinConfig {
+            ec_level: HanXinEcLevel::L4,
+            version: Some(1),
+        };
+        assert!(generate_hanxin_with_config(&"A".repeat(20), &too_small).is_err());
+    }
+
+    #[test]
+    fn test_hanxin_invalid_data() {
+        assert!(generate_hanxin("").is_err());
+        let config = HanXinConfig {
+            ec_level: HanXinEcLevel::L1,
+            version: Some(85),
+        };
+        assert!(generate_hanxin_with_config("test", &config).is_err());
+        assert!(generate_hanxin(&"汉".repeat(5000)).is_err());
+    }
+}
@@ -0,0 +1,307 @@
+//! USPS Intelligent Mail barcode generator
+//!
+//! The Intelligent Mail barcode (IMb, USPS-B-3200) is a 65-bar, 4-state
+//! postal code. It encodes a 20-digit tracking code (barcode ID, service
+//! type, mailer ID and serial number) and an optional 5, 9 or 11-digit
+//! routing ZIP code, protected by an 11-bit CRC.
+
+use crate::types::{
+    BarGeometry, BarState, Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError,
+    Result,
+};
+
+const TRACKING_LENGTH: usize = 20;
+const BAR_COUNT: usize = 65;
+
+/// CRC-11 generator polynomial
+const CRC_POLYNOMIAL: u16 = 0x0F35;
+
+/// Nominal USPS dimensions: 0.020" bars at 22 bars per inch, 0.145" full
+/// bars and a centred 0.050" tracker
+const IMB_GEOMETRY: BarGeometry = BarGeometry {
+    bar_width: 0.508,
+    pitch: 25.4 / 22.0,
+    height: 3.683,
+    tracker_top: 1.2065,
+    tracker_bottom: 2.4765,
+};
+
+/// Bar-to-character mapping (USPS-B-3200 Appendix D, Table IV): entry
+/// `13 * character + bit` is the bar it drives, 1-65 for descenders and
+/// 66-130 for ascenders
+const BAR_MAP: [u8; 130] = [
+    67, 6, 78, 16, 86, 95, 34, 40, 45, 113, 117, 121, 62, 87, 18, 104, 41, 76, 57, 119, 115, 72,
+    97, 2, 127, 26, 105, 35, 122, 52, 114, 7, 24, 82, 68, 63, 94, 44, 77, 112, 70, 100, 39, 30,
+    107, 15, 125, 85, 10, 65, 54, 88, 20, 106, 46, 66, 8, 116, 29, 61, 99, 80, 90, 37, 123, 51, 25,
+    84, 129, 56, 4, 109, 96, 28, 36, 47, 11, 71, 33, 102, 21, 9, 17, 49, 124, 79, 64, 91, 42, 69,
+    53, 60, 14, 1, 27, 103, 126, 75, 89, 50, 120, 19, 32, 110, 92, 111, 130, 59, 31, 12, 81, 43,
+    55, 5, 74, 22, 101, 128, 58, 118, 48, 108, 38, 98, 93, 23, 83, 13, 73, 3,
+];
+
+/// Generate an Intelligent Mail barcode from a 20-digit tracking code
+/// followed by an optional 5, 9 or 11-digit routing code
+pub fn generate_intelligent_mail(data: &str) -> Result<Barcode> {
+    let digits: String = data.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(QuickCodesError::InvalidData(
+            "Intelligent Mail data must contain only digits, spaces and hyphens".to_string(),
+        ));
+    }
+
+    if digits.len() < TRACKING_LENGTH {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Intelligent Mail requires a {}-digit tracking code, got {} characters",
+            TRACKING_LENGTH,
+            digits.len()
+        )));
+    }
+
+    let (tracking, routing) = digits.split_at(TRACKING_LENGTH);
+    generate_intelligent_mail_with_routing(tracking, routing)
+}
+
+/// Generate an Intelligent Mail barcode from separate tracking and routing codes
+pub fn generate_intelligent_mail_with_routing(tracking: &str, routing: &str) -> Result<Barcode> {
+    if tracking.len() != TRACKING_LENGTH || !tracking.chars().all(|c| c.is_ascii_digit()) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Intelligent Mail tracking code must be {} digits",
+            TRACKING_LENGTH
+        )));
+    }
+
+    if tracking.as_bytes()[1] > b'4' {
+        return Err(QuickCodesError::InvalidData(
+            "The second digit of the barcode ID must be 0-4".to_string(),
+        ));
+    }
+
+    if !matches!(routing.len(), 0 | 5 | 9 | 11) || !routing.chars().all(|c| c.is_ascii_digit()) {
+        return Err(QuickCodesError::InvalidData(
+            "Intelligent Mail routing code must be 0, 5, 9 or 11 digits".to_string(),
+        ));
+    }
+
+    let bars = encode_bars(tracking, routing);
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::IntelligentMail,
+        data: format!("{}{}", tracking, routing),
+        modules: BarcodeModules::HeightModulated {
+            bars,
+            geometry: IMB_GEOMETRY,
+        },
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Combine routing and tracking codes into a single binary value
+fn binary_value(tracking: &str, routing: &str) -> u128 {
+    let routing_value: u128 = routing.parse().unwrap_or(0);
+    let mut value = match routing.len() {
+        5 => routing_value + 1,
+        9 => routing_value + 100_000 + 1,
+        11 => routing_value + 1_000_000_000 + 100_000 + 1,
+        _ => 0,
+    };
+
+    let digits: Vec<u128> = tracking.bytes().map(|b| u128::from(b - b'0')).collect();
+    value = value * 10 + digits[0];
+    value = value * 5 + digits[1];
+    for &digit in &digits[2..] {
+        value = value * 10 + digit;
+    }
+    value
+}
+
+/// 11-bit frame check sequence over the 102-bit binary value
+fn crc11(value: u128) -> u16 {
+    let bytes = value.to_be_bytes();
+    let bytes = &bytes[3..]; // 13 bytes, the first holding only 6 significant bits
+
+    let mut fcs: u16 = 0x07FF;
+    fcs = crc11_update(fcs, u16::from(bytes[0]) << 5, 6);
+    for &byte in &bytes[1..] {
+        fcs = crc11_update(fcs, u16::from(byte) << 3, 8);
+    }
+    fcs
+}
+
+/// Feed the top `bits` bits of `data` (aligned to bit 10) into the CRC
+fn crc11_update(mut fcs: u16, mut data: u16, bits: usize) -> u16 {
+    for _ in 0..bits {
+        fcs = if (fcs ^ data) & 0x400 != 0 {
+            (fcs << 1) ^ CRC_POLYNOMIAL
+        } else {
+            fcs << 1
+        } & 0x07FF;
+        data <<= 1;
+    }
+    fcs
+}
+
+/// Build the table of 13-bit characters with `ones` bits set: pairs of
+/// mirror-image characters from the start, symmetric ones from the end
+fn n_of_13_table(ones: u32, length: usize) -> Vec<u16> {
+    let mut table = vec![0u16; length];
+    let mut lower = 0;
+    let mut upper = length - 1;
+
+    for count in 0u16..8192 {
+        if count.count_ones() != ones {
+            continue;
+        }
+        let reverse = count.reverse_bits() >> 3;
+        if reverse < count {
+            continue;
+        }
+        if reverse == count {
+            table[upper] = count;
+            upper = upper.saturating_sub(1);
+        } else {
+            table[lower] = count;
+            table[lower + 1] = reverse;
+            lower += 2;
+        }
+    }
+    table
+}
+
+/// Encode tracking and routing codes into 65 bars
+fn encode_bars(tracking: &str, routing: &str) -> Vec<BarState> {
+    let mut value = binary_value(tracking, routing);
+    let fcs = crc11(value);
+
+    // Codewords A-J: J in base 636, the others in base 1365
+    let mut codewords = [0u16; 10];
+    codewords[9] = (value % 636) as u16;
+    value /= 636;
+    for codeword in codewords[1..9].iter_mut().rev() {
+        *codeword = (value % 1365) as u16;
+        value /= 1365;
+    }
+    codewords[0] = value as u16;
+
+    // Orientation in J, most significant FCS bit in A
+    codewords[9] *= 2;
+    if fcs & 0x400 != 0 {
+        codewords[0] += 659;
+    }
+
+    let five_of_13 = n_of_13_table(5, 1287);
+    let two_of_13 = n_of_13_table(2, 78);
+
+    let mut characters = codewords.map(|codeword| {
+        let codeword = usize::from(codeword);
+        if codeword < 1287 {
+            five_of_13[codeword]
+        } else {
+            two_of_13[codeword - 1287]
+        }
+    });
+    for (i, character) in characters.iter_mut().enumerate() {
+        if fcs & (1 << i) != 0 {
+            *character = !*character & 0x1FFF;
+        }
+    }
+
+    let mut extenders = [false; 2 * BAR_COUNT];
+    for (i, &character) in characters.iter().enumerate() {
+        for bit in 0..13 {
+            if character & (1 << bit) != 0 {
+                extenders[usize::from(BAR_MAP[13 * i + bit]) - 1] = true;
+            }
+        }
+    }
+
+    (0..BAR_COUNT)
+        .map(|i| match (extenders[i], extenders[i + BAR_COUNT]) {
+            (true, true) => BarState::Full,
+            (false, true) => BarState::Ascender,
+            (true, false) => BarState::Descender,
+            (false, false) => BarState::Tracker,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar_string(barcode: &Barcode) -> String {
+        barcode
+            .modules
+            .as_bars()
+            .unwrap()
+            .iter()
+            .map(|bar| match bar {
+                BarState::Full => 'F',
+                BarState::Ascender => 'A',
+                BarState::Descender => 'D',
+                BarState::Tracker => 'T',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_imb_reference_symbols() {
+        // USPS-B-3200 Appendix C examples
+        let tracking = "01234567094987654321";
+        let cases = [
+            (
+                "",
+                "ATTFATTDTTADTAATTDTDTATTDAFDDFADFDFTFFFFFTATFAAAATDFFTDAADFTFDTDT",
+            ),
+            (
+                "01234",
+                "DTTAFADDTTFTDTFTFDTDDADADAFADFATDDFTAAAFDTTADFAAATDFDTDFADDDTDFFT",
+            ),
+            (
+                "012345678",
+                "ADFTTAFDTTTTFATTADTAAATFTFTATDAAAFDDADATATDTDTTDFDTDATADADTDFFTFA",
+            ),
+            (
+                "01234567891",
+                "AADTFFDFTDADTAADAATFDTDDAAADDTDTTDAFADADDDTFFFDDTTTADFAAADFTDAADA",
+            ),
+        ];
+
+        for (routing, expected) in cases {
+            let barcode = generate_intelligent_mail_with_routing(tracking, routing).unwrap();
+            assert_eq!(bar_string(&barcode), expected, "routing '{}'", routing);
+        }
+    }
+
+    #[test]
+    fn test_imb_single_string_input() {
+        let barcode = generate_intelligent_mail("01234567094987654321-01234").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::IntelligentMail);
+        assert_eq!(barcode.data, "0123456709498765432101234");
+        assert_eq!(barcode.modules.as_bars().unwrap().len(), 65);
+    }
+
+    #[test]
+    fn test_n_of_13_tables() {
+        let five = n_of_13_table(5, 1287);
+        assert!(five.iter().all(|c| c.count_ones() == 5));
+        assert_eq!(five[0], 0x001F);
+        assert_eq!(five[1], 0x1F00);
+
+        let two = n_of_13_table(2, 78);
+        assert!(two.iter().all(|c| c.count_ones() == 2));
+        assert_eq!(two[0], 0x0003);
+        assert_eq!(two[1], 0x1800);
+    }
+
+    #[test]
+    fn test_imb_invalid_data() {
+        assert!(generate_intelligent_mail("0123456709498765432").is_err());
+        assert!(generate_intelligent_mail("0123456709498765432101").is_err());
+        assert!(generate_intelligent_mail("一一一一龥龥一").is_err());
+        assert!(generate_intelligent_mail("0123456709498765432é1").is_err());
+        assert!(generate_intelligent_mail_with_routing("01234567094987654321", "0123").is_err());
+        assert!(generate_intelligent_mail_with_routing("0A234567094987654321", "").is_err());
+        // Second digit of the barcode ID must be 0-4
+        assert!(generate_intelligent_mail_with_routing("05234567094987654321", "").is_err());
+    }
+}
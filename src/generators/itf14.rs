@@ -1,8 +1,9 @@
+use crate::gs1;
 use crate::types::{Barcode, BarcodeModules, BarcodeType, BarcodeConfig};
 use anyhow::Result;
 
 // Padrões de codificação ITF-14
-const ITF_PATTERNS: &[(&str, &str)] = &[
+pub(crate) const ITF_PATTERNS: &[(&str, &str)] = &[
     ("0", "00110"), ("1", "10001"), ("2", "01001"), ("3", "11000"),
     ("4", "00101"), ("5", "10100"), ("6", "01100"), ("7", "00011"),
     ("8", "10010"), ("9", "01010"),
@@ -10,13 +11,7 @@ const ITF_PATTERNS: &[(&str, &str)] = &[
 
 /// Calcula o dígito verificador ITF-14
 fn calculate_check_digit(data: &str) -> u8 {
-    let mut sum = 0;
-    for (i, c) in data.chars().enumerate() {
-        let digit = c.to_digit(10).unwrap() as u8;
-        // Posições pares têm peso 3, ímpares têm peso 1
-        sum += if i % 2 == 0 { digit * 3 } else { digit };
-    }
-    ((10 - (sum % 10)) % 10) as u8
+    gs1::check_digit(data)
 }
 
 /// Gera um código de barras ITF-14
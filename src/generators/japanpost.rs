@@ -0,0 +1,161 @@
+//! Japan Post customer barcode generator
+//!
+//! The Japan Post customer barcode (カスタマバーコード) encodes a 7-digit
+//! postal code and the address block number in 20 characters of three
+//! bars each, followed by a modulo-19 check character. Letters are written
+//! as a control character (CC1-CC3) followed by a digit, and unused
+//! positions are filled with CC4.
+
+use crate::types::{
+    BarGeometry, BarState, Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError,
+    Result,
+};
+
+const POSTCODE_LENGTH: usize = 7;
+
+/// Number of data characters, before the check character
+const DATA_CHARACTERS: usize = 20;
+
+/// Character values: digits 0-9, '-' (10), then CC1-CC8 as 'a'-'h' (11-18)
+const CHARSET: &str = "0123456789-abcdefgh";
+
+/// Filler control character CC4
+const FILLER: char = 'd';
+
+// Bar patterns: F = long bar, A = upper semi-long, D = lower semi-long, T = timing
+const PATTERNS: [&str; 19] = [
+    "FTT", "FFT", "FDA", "DFA", "FAD", "FTF", "DAF", "AFD", "ADF", "TFF", "TFT", "DAT", "DTA",
+    "ADT", "TDA", "ATD", "TAD", "TTF", "FFF",
+];
+
+const START: [BarState; 2] = [BarState::Full, BarState::Descender];
+const STOP: [BarState; 2] = [BarState::Descender, BarState::Full];
+
+/// Nominal dimensions: 0.6 mm bars at 1.2 mm pitch, 3.6 mm long bars and a
+/// 1.2 mm timing bar
+const JAPANPOST_GEOMETRY: BarGeometry = BarGeometry {
+    bar_width: 0.6,
+    pitch: 1.2,
+    height: 3.6,
+    tracker_top: 1.2,
+    tracker_bottom: 2.4,
+};
+
+/// Generate a Japan Post customer barcode from a 7-digit postal code
+/// followed by the address number, e.g. `1000001` + `3-1-1`
+pub fn generate_japanpost(data: &str) -> Result<Barcode> {
+    let data = data.to_ascii_uppercase();
+
+    let postcode = data.get(..POSTCODE_LENGTH);
+    if !postcode.is_some_and(|postcode| postcode.bytes().all(|b| b.is_ascii_digit())) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Japan Post barcode must start with a {}-digit postal code",
+            POSTCODE_LENGTH
+        )));
+    }
+
+    let characters = convert(&data)?;
+    let check = check_character(&characters);
+
+    let mut bars = START.to_vec();
+    for c in characters.iter().chain(std::iter::once(&check)) {
+        let index = CHARSET.find(*c).unwrap_or(0);
+        bars.extend(PATTERNS[index].bytes().map(|bar| match bar {
+            b'F' => BarState::Full,
+            b'A' => BarState::Ascender,
+            b'D' => BarState::Descender,
+            _ => BarState::Tracker,
+        }));
+    }
+    bars.extend(STOP);
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::JapanPost,
+        data,
+        modules: BarcodeModules::HeightModulated {
+            bars,
+            geometry: JAPANPOST_GEOMETRY,
+        },
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Convert the input into exactly 20 barcode characters
+fn convert(data: &str) -> Result<Vec<char>> {
+    let mut characters = Vec::with_capacity(DATA_CHARACTERS);
+
+    for c in data.chars() {
+        match c {
+            '0'..='9' | '-' => characters.push(c),
+            'A'..='J' => characters.extend(['a', (b'0' + (c as u8 - b'A')) as char]),
+            'K'..='T' => characters.extend(['b', (b'0' + (c as u8 - b'K')) as char]),
+            'U'..='Z' => characters.extend(['c', (b'0' + (c as u8 - b'U')) as char]),
+            _ => {
+                return Err(QuickCodesError::InvalidData(format!(
+                    "Invalid character '{}' for Japan Post. Only 0-9, A-Z and '-' are allowed.",
+                    c
+                )))
+            }
+        }
+    }
+
+    if characters.len() > DATA_CHARACTERS {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Japan Post data is limited to {} barcode characters (letters take two)",
+            DATA_CHARACTERS
+        )));
+    }
+
+    characters.resize(DATA_CHARACTERS, FILLER);
+    Ok(characters)
+}
+
+/// Check character: the value that brings the sum to a multiple of 19
+fn check_character(characters: &[char]) -> char {
+    let sum: usize = characters.iter().filter_map(|&c| CHARSET.find(c)).sum();
+    let check = (19 - sum % 19) % 19;
+    CHARSET.as_bytes()[check] as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_japanpost_conversion() {
+        let characters = convert("1000001A3-1").unwrap();
+        let text: String = characters.iter().collect();
+        assert_eq!(text, "1000001a03-1dddddddd");
+
+        assert_eq!(convert("1000001Z").unwrap()[7..9], ['c', '5']);
+    }
+
+    #[test]
+    fn test_japanpost_check_character() {
+        let characters = convert("2630023").unwrap();
+        // 2+6+3+0+0+2+3 = 16, 13 x CC4 (14) = 182, total 198 = 19 x 10 + 8
+        assert_eq!(check_character(&characters), 'a');
+    }
+
+    #[test]
+    fn test_japanpost_generation() {
+        let barcode = generate_japanpost("6540123789-A-K-35").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::JapanPost);
+
+        let bars = barcode.modules.as_bars().unwrap();
+        // Start + 21 characters of 3 bars + stop
+        assert_eq!(bars.len(), 2 + 21 * 3 + 2);
+        assert_eq!(&bars[..2], &START);
+        assert_eq!(&bars[bars.len() - 2..], &STOP);
+    }
+
+    #[test]
+    fn test_japanpost_invalid_data() {
+        assert!(generate_japanpost("12345").is_err());
+        assert!(generate_japanpost("1000001#").is_err());
+        assert!(generate_japanpost("1000001ABCDEFGHIJ").is_err());
+        // Non-ASCII input is rejected, not sliced inside a character
+        assert!(generate_japanpost("123456é").is_err());
+        assert!(generate_japanpost("1000001é").is_err());
+    }
+}
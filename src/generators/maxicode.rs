@@ -0,0 +1,638 @@
+//! MaxiCode generator (ISO/IEC 16023)
+//!
+//! MaxiCode is a fixed-size symbol of 884 hexagonal modules in 33 rows
+//! around a central bullseye, used by UPS for package sorting. It carries
+//! 144 six-bit codewords: a 10-codeword primary message protected by 10
+//! Reed-Solomon codewords and a secondary message whose odd and even
+//! codewords are protected separately.
+//!
+//! - Modes 2 and 3: structured carrier message, with the postal code,
+//!   country and service class in the primary message
+//! - Mode 4: standard symbol, up to 93 codewords of data
+//! - Mode 5: enhanced error correction, up to 77 codewords of data
+//! - Mode 6: reader programming
+//!
+//! Text is encoded in code sets A and B (printable ASCII), with control
+//! characters up to SUB through code set E and numeric compaction of runs
+//! of nine digits.
+
+use crate::types::{Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError, Result};
+
+const ROWS: usize = 33;
+const COLUMNS: usize = 30;
+
+/// Codewords in the primary message, and its Reed-Solomon codewords
+const PRIMARY_LENGTH: usize = 10;
+
+/// Total number of codewords in the symbol
+const TOTAL_CODEWORDS: usize = 144;
+
+/// Header of a structured carrier message (ANSI MH10.8.3 transport data)
+const SCM_HEADER: &str = "[)>\u{1e}01\u{1d}";
+
+/// Group separator between structured carrier message fields
+const GS: char = '\u{1d}';
+
+// Special codewords, valid in code sets A and B
+const NS: u8 = 31; // numeric shift: nine digits in five codewords
+const PAD: u8 = 33;
+const SHIFT_E: u8 = 62;
+const LATCH: u8 = 63; // latch B in code set A, latch A in code set B
+const SHIFT: u8 = 59; // shift B in code set A, shift A in code set B
+const SHIFT_2A: u8 = 56;
+const SHIFT_3A: u8 = 57;
+
+/// Placeholder for code set values that are not characters
+const NONE: char = '\u{ffff}';
+
+/// Code set A characters by value; value 0 is CR
+const SET_A: &str = "\rABCDEFGHIJKLMNOPQRSTUVWXYZ\u{ffff}\u{1c}\u{1d}\u{1e}\u{ffff} \u{ffff}\"#$%&'()*+,-./0123456789:";
+
+/// Code set B characters by value
+const SET_B: &str = "`abcdefghijklmnopqrstuvwxyz\u{ffff}\u{1c}\u{1d}\u{1e}\u{ffff}{\u{ffff}}~\u{7f};<=>?[\\]^_ ,./:@!|";
+
+/// GF(64) primitive polynomial x^6 + x + 1
+const GF_POLYNOMIAL: u8 = 0x43;
+
+// Grid cells that are not data bits
+const LIGHT: i16 = -1; // light orientation module
+const DARK: i16 = -2; // dark orientation module
+const EMPTY: i16 = -3; // bullseye area, or beyond the end of an odd row
+
+/// Bit number (codeword * 6 + bit, most significant bit first) of every
+/// module of the grid
+#[rustfmt::skip]
+const GRID: [[i16; COLUMNS]; ROWS] = [
+    [121, 120, 127, 126, 133, 132, 139, 138, 145, 144, 151, 150, 157, 156, 163, 162, 169, 168, 175, 174, 181, 180, 187, 186, 193, 192, 199, 198, -2, -2],
+    [123, 122, 129, 128, 135, 134, 141, 140, 147, 146, 153, 152, 159, 158, 165, 164, 171, 170, 177, 176, 183, 182, 189, 188, 195, 194, 201, 200, 816, -3],
+    [125, 124, 131, 130, 137, 136, 143, 142, 149, 148, 155, 154, 161, 160, 167, 166, 173, 172, 179, 178, 185, 184, 191, 190, 197, 196, 203, 202, 818, 817],
+    [283, 282, 277, 276, 271, 270, 265, 264, 259, 258, 253, 252, 247, 246, 241, 240, 235, 234, 229, 228, 223, 222, 217, 216, 211, 210, 205, 204, 819, -3],
+    [285, 284, 279, 278, 273, 272, 267, 266, 261, 260, 255, 254, 249, 248, 243, 242, 237, 236, 231, 230, 225, 224, 219, 218, 213, 212, 207, 206, 821, 820],
+    [287, 286, 281, 280, 275, 274, 269, 268, 263, 262, 257, 256, 251, 250, 245, 244, 239, 238, 233, 232, 227, 226, 221, 220, 215, 214, 209, 208, 822, -3],
+    [289, 288, 295, 294, 301, 300, 307, 306, 313, 312, 319, 318, 325, 324, 331, 330, 337, 336, 343, 342, 349, 348, 355, 354, 361, 360, 367, 366, 824, 823],
+    [291, 290, 297, 296, 303, 302, 309, 308, 315, 314, 321, 320, 327, 326, 333, 332, 339, 338, 345, 344, 351, 350, 357, 356, 363, 362, 369, 368, 825, -3],
+    [293, 292, 299, 298, 305, 304, 311, 310, 317, 316, 323, 322, 329, 328, 335, 334, 341, 340, 347, 346, 353, 352, 359, 358, 365, 364, 371, 370, 827, 826],
+    [409, 408, 403, 402, 397, 396, 391, 390, 79, 78, -2, -2, 13, 12, 37, 36, 2, -1, 44, 43, 109, 108, 385, 384, 379, 378, 373, 372, 828, -3],
+    [411, 410, 405, 404, 399, 398, 393, 392, 81, 80, 40, -2, 15, 14, 39, 38, 3, -1, -1, 45, 111, 110, 387, 386, 381, 380, 375, 374, 830, 829],
+    [413, 412, 407, 406, 401, 400, 395, 394, 83, 82, 41, -3, -3, -3, -3, -3, 5, 4, 47, 46, 113, 112, 389, 388, 383, 382, 377, 376, 831, -3],
+    [415, 414, 421, 420, 427, 426, 103, 102, 55, 54, 16, -3, -3, -3, -3, -3, -3, -3, 20, 19, 85, 84, 433, 432, 439, 438, 445, 444, 833, 832],
+    [417, 416, 423, 422, 429, 428, 105, 104, 57, 56, -3, -3, -3, -3, -3, -3, -3, -3, 22, 21, 87, 86, 435, 434, 441, 440, 447, 446, 834, -3],
+    [419, 418, 425, 424, 431, 430, 107, 106, 59, 58, -3, -3, -3, -3, -3, -3, -3, -3, -3, 23, 89, 88, 437, 436, 443, 442, 449, 448, 836, 835],
+    [481, 480, 475, 474, 469, 468, 48, -2, 30, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, 0, 53, 52, 463, 462, 457, 456, 451, 450, 837, -3],
+    [483, 482, 477, 476, 471, 470, 49, -1, -2, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, -2, -1, 465, 464, 459, 458, 453, 452, 839, 838],
+    [485, 484, 479, 478, 473, 472, 51, 50, 31, -3, -3, -3, -3, -3, -3, -3, -3, -3, -3, 1, -2, 42, 467, 466, 461, 460, 455, 454, 840, -3],
+    [487, 486, 493, 492, 499, 498, 97, 96, 61, 60, -3, -3, -3, -3, -3, -3, -3, -3, -3, 26, 91, 90, 505, 504, 511, 510, 517, 516, 842, 841],
+    [489, 488, 495, 494, 501, 500, 99, 98, 63, 62, -3, -3, -3, -3, -3, -3, -3, -3, 28, 27, 93, 92, 507, 506, 513, 512, 519, 518, 843, -3],
+    [491, 490, 497, 496, 503, 502, 101, 100, 65, 64, 17, -3, -3, -3, -3, -3, -3, -3, 18, 29, 95, 94, 509, 508, 515, 514, 521, 520, 845, 844],
+    [559, 558, 553, 552, 547, 546, 541, 540, 73, 72, 32, -3, -3, -3, -3, -3, -3, 10, 67, 66, 115, 114, 535, 534, 529, 528, 523, 522, 846, -3],
+    [561, 560, 555, 554, 549, 548, 543, 542, 75, 74, -2, -1, 7, 6, 35, 34, 11, -2, 69, 68, 117, 116, 537, 536, 531, 530, 525, 524, 848, 847],
+    [563, 562, 557, 556, 551, 550, 545, 544, 77, 76, -2, 33, 9, 8, 25, 24, -1, -2, 71, 70, 119, 118, 539, 538, 533, 532, 527, 526, 849, -3],
+    [565, 564, 571, 570, 577, 576, 583, 582, 589, 588, 595, 594, 601, 600, 607, 606, 613, 612, 619, 618, 625, 624, 631, 630, 637, 636, 643, 642, 851, 850],
+    [567, 566, 573, 572, 579, 578, 585, 584, 591, 590, 597, 596, 603, 602, 609, 608, 615, 614, 621, 620, 627, 626, 633, 632, 639, 638, 645, 644, 852, -3],
+    [569, 568, 575, 574, 581, 580, 587, 586, 593, 592, 599, 598, 605, 604, 611, 610, 617, 616, 623, 622, 629, 628, 635, 634, 641, 640, 647, 646, 854, 853],
+    [727, 726, 721, 720, 715, 714, 709, 708, 703, 702, 697, 696, 691, 690, 685, 684, 679, 678, 673, 672, 667, 666, 661, 660, 655, 654, 649, 648, 855, -3],
+    [729, 728, 723, 722, 717, 716, 711, 710, 705, 704, 699, 698, 693, 692, 687, 686, 681, 680, 675, 674, 669, 668, 663, 662, 657, 656, 651, 650, 857, 856],
+    [731, 730, 725, 724, 719, 718, 713, 712, 707, 706, 701, 700, 695, 694, 689, 688, 683, 682, 677, 676, 671, 670, 665, 664, 659, 658, 653, 652, 858, -3],
+    [733, 732, 739, 738, 745, 744, 751, 750, 757, 756, 763, 762, 769, 768, 775, 774, 781, 780, 787, 786, 793, 792, 799, 798, 805, 804, 811, 810, 860, 859],
+    [735, 734, 741, 740, 747, 746, 753, 752, 759, 758, 765, 764, 771, 770, 777, 776, 783, 782, 789, 788, 795, 794, 801, 800, 807, 806, 813, 812, 861, -3],
+    [737, 736, 743, 742, 749, 748, 755, 754, 761, 760, 767, 766, 773, 772, 779, 778, 785, 784, 791, 790, 797, 796, 803, 802, 809, 808, 815, 814, 863, 862],
+];
+
+/// MaxiCode symbol mode for unstructured data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaxiCodeMode {
+    /// Mode 4: standard error correction, 93 data codewords
+    Standard,
+    /// Mode 5: enhanced error correction, 77 data codewords
+    EnhancedEcc,
+    /// Mode 6: reader programming, 93 data codewords
+    ReaderProgramming,
+}
+
+impl MaxiCodeMode {
+    fn value(self) -> u8 {
+        match self {
+            MaxiCodeMode::Standard => 4,
+            MaxiCodeMode::EnhancedEcc => 5,
+            MaxiCodeMode::ReaderProgramming => 6,
+        }
+    }
+
+    /// Reed-Solomon codewords of the secondary message
+    fn secondary_ec(self) -> usize {
+        match self {
+            MaxiCodeMode::EnhancedEcc => 56,
+            _ => 40,
+        }
+    }
+}
+
+/// Primary message of a mode 2 or 3 symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructuredCarrierMessage {
+    /// Up to 9 digits (mode 2) or 6 letters, digits and spaces (mode 3)
+    pub postal_code: String,
+    /// ISO 3166 numeric country code
+    pub country_code: u16,
+    /// Carrier-specific class of service
+    pub service_class: u16,
+}
+
+/// Generate a MaxiCode symbol
+///
+/// Data starting with the `[)>` RS `01` GS `yy` transport header followed by
+/// the postal code, country code and service class (separated by GS), as
+/// in UPS shipping labels, is encoded as a structured carrier message in
+/// mode 2 or 3. Other data is encoded in mode 4.
+pub fn generate_maxicode(data: &str) -> Result<Barcode> {
+    let codewords = match split_structured_carrier_message(data)? {
+        Some((scm, secondary)) => scm_codewords(&scm, &secondary)?,
+        None => mode_codewords(data, MaxiCodeMode::Standard)?,
+    };
+    Ok(build(data, &codewords))
+}
+
+/// Generate a MaxiCode symbol in mode 4, 5 or 6
+pub fn generate_maxicode_with_mode(data: &str, mode: MaxiCodeMode) -> Result<Barcode> {
+    let codewords = mode_codewords(data, mode)?;
+    Ok(build(data, &codewords))
+}
+
+/// Generate a mode 2 or 3 MaxiCode symbol from a structured carrier message
+/// and the secondary message; mode 2 is used for numeric postal codes
+pub fn generate_maxicode_scm(scm: &StructuredCarrierMessage, secondary: &str) -> Result<Barcode> {
+    let codewords = scm_codewords(scm, secondary)?;
+    Ok(build(secondary, &codewords))
+}
+
+/// Structured carrier message and the secondary message
+type TransportData = (StructuredCarrierMessage, String);
+
+/// Split transport data into the structured carrier message and the
+/// secondary message, which keeps the header and the remaining fields
+fn split_structured_carrier_message(data: &str) -> Result<Option<TransportData>> {
+    let rest = match data.strip_prefix(SCM_HEADER) {
+        Some(rest) => rest,
+        None => return Ok(None),
+    };
+
+    let year = rest
+        .get(..2)
+        .filter(|y| y.chars().all(|c| c.is_ascii_digit()));
+    let year = match year {
+        Some(year) => year,
+        None => return Ok(None),
+    };
+
+    let mut fields = rest[2..].splitn(4, GS);
+    let (postal_code, country, service) =
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(postal_code), Some(country), Some(service)) => (postal_code, country, service),
+            _ => return Err(QuickCodesError::InvalidData(
+                "Structured carrier message requires postal code, country code and service class"
+                    .to_string(),
+            )),
+        };
+
+    let scm = StructuredCarrierMessage {
+        postal_code: postal_code.to_string(),
+        country_code: parse_three_digits(country, "country code")?,
+        service_class: parse_three_digits(service, "service class")?,
+    };
+    let secondary = format!("{}{}{}", SCM_HEADER, year, fields.next().unwrap_or(""));
+    Ok(Some((scm, secondary)))
+}
+
+fn parse_three_digits(field: &str, name: &str) -> Result<u16> {
+    if field.len() != 3 || !field.chars().all(|c| c.is_ascii_digit()) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "MaxiCode {} must be 3 digits, got '{}'",
+            name, field
+        )));
+    }
+    Ok(field.parse().unwrap_or(0))
+}
+
+/// Codewords of a mode 4, 5 or 6 symbol: the mode and the first nine data
+/// codewords form the primary message
+fn mode_codewords(data: &str, mode: MaxiCodeMode) -> Result<Vec<u8>> {
+    let capacity = PRIMARY_LENGTH - 1 + secondary_length(mode.secondary_ec());
+    let mut message = encode_text(data)?;
+    pad(&mut message, capacity, "MaxiCode")?;
+
+    let mut primary = vec![mode.value()];
+    primary.extend_from_slice(&message[..PRIMARY_LENGTH - 1]);
+    Ok(assemble(
+        &primary,
+        &message[PRIMARY_LENGTH - 1..],
+        mode.secondary_ec(),
+    ))
+}
+
+/// Codewords of a mode 2 or 3 symbol
+fn scm_codewords(scm: &StructuredCarrierMessage, secondary: &str) -> Result<Vec<u8>> {
+    if scm.country_code > 999 || scm.service_class > 999 {
+        return Err(QuickCodesError::InvalidData(
+            "MaxiCode country code and service class must be 0-999".to_string(),
+        ));
+    }
+
+    let postal_code = scm.postal_code.to_ascii_uppercase();
+    let numeric = postal_code.chars().all(|c| c.is_ascii_digit());
+
+    // Mode, postal code, country and service class in a 60-bit value
+    let mut value = if numeric && (1..=9).contains(&postal_code.len()) {
+        let number: u64 = postal_code.parse().unwrap_or(0);
+        2 | number << 4 | (postal_code.len() as u64) << 34
+    } else if (1..=6).contains(&postal_code.len())
+        && postal_code
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == ' ')
+    {
+        // Code set A values, padded with spaces, first character highest
+        let mut number = 0u64;
+        for c in format!("{:6}", postal_code).chars() {
+            number = number << 6 | u64::from(set_value(SET_A, c).unwrap_or(32));
+        }
+        3 | number << 4
+    } else {
+        return Err(QuickCodesError::InvalidData(format!(
+            "MaxiCode postal code must be 1-9 digits or 1-6 letters and digits, got '{}'",
+            scm.postal_code
+        )));
+    };
+    value |= u64::from(scm.country_code) << 40 | u64::from(scm.service_class) << 50;
+
+    let primary: Vec<u8> = (0..PRIMARY_LENGTH)
+        .map(|i| ((value >> (6 * i)) & 0x3F) as u8)
+        .collect();
+
+    let secondary_ec = MaxiCodeMode::Standard.secondary_ec();
+    let mut message = encode_text(secondary)?;
+    pad(
+        &mut message,
+        secondary_length(secondary_ec),
+        "MaxiCode secondary message",
+    )?;
+    Ok(assemble(&primary, &message, secondary_ec))
+}
+
+/// Data codewords of the secondary message
+fn secondary_length(secondary_ec: usize) -> usize {
+    TOTAL_CODEWORDS - 2 * PRIMARY_LENGTH - secondary_ec
+}
+
+fn pad(message: &mut Vec<u8>, capacity: usize, name: &str) -> Result<()> {
+    if message.len() > capacity {
+        return Err(QuickCodesError::InvalidData(format!(
+            "{} too long: {} codewords, capacity {}",
+            name,
+            message.len(),
+            capacity
+        )));
+    }
+    message.resize(capacity, PAD);
+    Ok(())
+}
+
+/// Value of a character in a code set
+fn set_value(set: &str, c: char) -> Option<u8> {
+    if c == NONE {
+        return None;
+    }
+    set.chars().position(|s| s == c).map(|value| value as u8)
+}
+
+/// Encode text in code sets A and B, starting in code set A
+fn encode_text(text: &str) -> Result<Vec<u8>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut codewords = Vec::new();
+    let (mut set, mut other) = (SET_A, SET_B);
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        // Numeric compaction: nine digits as a 30-bit value
+        if chars.len() - i >= 9 && chars[i..i + 9].iter().all(|c| c.is_ascii_digit()) {
+            let value = chars[i..i + 9]
+                .iter()
+                .fold(0u32, |acc, &d| acc * 10 + (d as u32 - '0' as u32));
+            codewords.push(NS);
+            codewords.extend([24, 18, 12, 6, 0].map(|shift| ((value >> shift) & 0x3F) as u8));
+            i += 9;
+            continue;
+        }
+
+        if let Some(value) = set_value(set, c) {
+            codewords.push(value);
+            i += 1;
+            continue;
+        }
+
+        if set_value(other, c).is_some() {
+            // Characters only available in the other code set
+            let run = chars[i..]
+                .iter()
+                .take_while(|&&c| set_value(set, c).is_none() && set_value(other, c).is_some())
+                .count();
+
+            let shift = match (set == SET_A, run) {
+                (_, 1) => Some(SHIFT),
+                (false, 2) => Some(SHIFT_2A),
+                (false, 3) => Some(SHIFT_3A),
+                _ => None,
+            };
+            match shift {
+                Some(shift) => {
+                    codewords.push(shift);
+                    codewords.extend(
+                        chars[i..i + run]
+                            .iter()
+                            .filter_map(|&c| set_value(other, c)),
+                    );
+                    i += run;
+                }
+                None => {
+                    codewords.push(LATCH);
+                    std::mem::swap(&mut set, &mut other);
+                }
+            }
+            continue;
+        }
+
+        // Control characters NUL to SUB through code set E
+        if (c as u32) < 0x1B {
+            codewords.extend([SHIFT_E, c as u8]);
+            i += 1;
+            continue;
+        }
+
+        return Err(QuickCodesError::InvalidData(format!(
+            "Character '{}' cannot be encoded in MaxiCode",
+            c.escape_default()
+        )));
+    }
+
+    Ok(codewords)
+}
+
+/// GF(64) arithmetic through exponent and logarithm tables
+struct Gf64 {
+    exp: [u8; 63],
+    log: [u8; 64],
+}
+
+impl Gf64 {
+    fn new() -> Self {
+        let mut exp = [0u8; 63];
+        let mut log = [0u8; 64];
+        let mut x = 1u8;
+        for (i, e) in exp.iter_mut().enumerate() {
+            *e = x;
+            log[usize::from(x)] = i as u8;
+            x <<= 1;
+            if x & 0x40 != 0 {
+                x ^= GF_POLYNOMIAL;
+            }
+        }
+        Self { exp, log }
+    }
+
+    fn mul(&self, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            0
+        } else {
+            let sum = usize::from(self.log[usize::from(a)]) + usize::from(self.log[usize::from(b)]);
+            self.exp[sum % 63]
+        }
+    }
+
+    /// Reed-Solomon check codewords, highest degree first, for a generator
+    /// polynomial with roots a^1 to a^count
+    fn reed_solomon(&self, data: &[u8], count: usize) -> Vec<u8> {
+        let mut generator = vec![1u8];
+        for root in &self.exp[1..=count] {
+            let mut next = generator.clone();
+            next.push(0);
+            for (i, &coefficient) in generator.iter().enumerate() {
+                next[i + 1] ^= self.mul(coefficient, *root);
+            }
+            generator = next;
+        }
+
+        let mut check = vec![0u8; count];
+        for &codeword in data {
+            let feedback = codeword ^ check[0];
+            check.rotate_left(1);
+            check[count - 1] = 0;
+            for (c, &g) in check.iter_mut().zip(&generator[1..]) {
+                *c ^= self.mul(feedback, g);
+            }
+        }
+        check
+    }
+}
+
+/// Append the error correction: the primary message is protected as a
+/// whole, the secondary message as interleaved even and odd codewords
+fn assemble(primary: &[u8], secondary: &[u8], secondary_ec: usize) -> Vec<u8> {
+    let gf = Gf64::new();
+
+    let mut codewords = primary.to_vec();
+    codewords.extend(gf.reed_solomon(primary, PRIMARY_LENGTH));
+    codewords.extend_from_slice(secondary);
+
+    let even: Vec<u8> = secondary.iter().step_by(2).copied().collect();
+    let odd: Vec<u8> = secondary.iter().skip(1).step_by(2).copied().collect();
+    let even_ec = gf.reed_solomon(&even, secondary_ec / 2);
+    let odd_ec = gf.reed_solomon(&odd, secondary_ec / 2);
+    for (e, o) in even_ec.into_iter().zip(odd_ec) {
+        codewords.extend([e, o]);
+    }
+
+    codewords
+}
+
+/// Place the codeword bits and orientation modules on the grid
+fn place(codewords: &[u8]) -> Vec<Vec<bool>> {
+    GRID.iter()
+        .map(|row| {
+            row.iter()
+                .map(|&cell| match cell {
+                    DARK => true,
+                    LIGHT | EMPTY => false,
+                    bit => {
+                        let bit = bit as usize;
+                        codewords[bit / 6] & (0x20 >> (bit % 6)) != 0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn build(data: &str, codewords: &[u8]) -> Barcode {
+    Barcode {
+        barcode_type: BarcodeType::MaxiCode,
+        data: data.to_string(),
+        modules: BarcodeModules::Hexagonal(place(codewords)),
+        config: BarcodeConfig::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UPS_MESSAGE: &str = "[)>\u{1e}01\u{1d}96152382802\u{1d}840\u{1d}001\u{1d}1Z00004951\u{1d}UPSN\u{1d}06X610\u{1d}159\u{1d}1234567\u{1d}1/1\u{1d}\u{1d}Y\u{1d}634 ALPHA DR\u{1d}PITTSBURGH\u{1d}PA\u{1e}\u{4}";
+
+    /// Syndromes of a Reed-Solomon block are zero at the generator roots
+    fn assert_valid_block(gf: &Gf64, block: &[u8], count: usize) {
+        for root in &gf.exp[1..=count] {
+            let syndrome = block.iter().fold(0, |acc, &c| gf.mul(acc, *root) ^ c);
+            assert_eq!(syndrome, 0);
+        }
+    }
+
+    #[test]
+    fn test_maxicode_text_encoding() {
+        assert_eq!(encode_text("AB 1").unwrap(), vec![1, 2, 32, 49]);
+        // Single lowercase letter: shift B; longer runs latch B
+        assert_eq!(encode_text("Ab").unwrap(), vec![1, SHIFT, 2]);
+        assert_eq!(encode_text("abC").unwrap(), vec![LATCH, 1, 2, SHIFT, 3]);
+        // Control characters through code set E
+        assert_eq!(encode_text("A\u{4}").unwrap(), vec![1, SHIFT_E, 4]);
+        // 123456789 = 0b000111_010110_111100_110100_010101
+        assert_eq!(
+            encode_text("123456789").unwrap(),
+            vec![NS, 7, 22, 60, 52, 21]
+        );
+        assert!(encode_text("ação").is_err());
+    }
+
+    #[test]
+    fn test_maxicode_structured_carrier_message() {
+        let (scm, secondary) = split_structured_carrier_message(UPS_MESSAGE)
+            .unwrap()
+            .unwrap();
+        assert_eq!(scm.postal_code, "152382802");
+        assert_eq!(scm.country_code, 840);
+        assert_eq!(scm.service_class, 1);
+        assert!(secondary.starts_with("[)>\u{1e}01\u{1d}961Z00004951"));
+
+        let codewords = scm_codewords(&scm, &secondary).unwrap();
+        assert_eq!(codewords.len(), TOTAL_CODEWORDS);
+
+        // Unpack the 60-bit primary message
+        let value = codewords[..PRIMARY_LENGTH]
+            .iter()
+            .rev()
+            .fold(0u64, |acc, &c| acc << 6 | u64::from(c));
+        assert_eq!(value & 0xF, 2);
+        assert_eq!((value >> 4) & 0x3FFF_FFFF, 152_382_802);
+        assert_eq!((value >> 34) & 0x3F, 9);
+        assert_eq!((value >> 40) & 0x3FF, 840);
+        assert_eq!((value >> 50) & 0x3FF, 1);
+
+        // Alphanumeric postal codes use mode 3
+        let scm = StructuredCarrierMessage {
+            postal_code: "b1050".to_string(),
+            country_code: 56,
+            service_class: 999,
+        };
+        let codewords = scm_codewords(&scm, "TEST").unwrap();
+        assert_eq!(codewords[0] & 0xF, 3);
+    }
+
+    #[test]
+    fn test_maxicode_error_correction() {
+        let gf = Gf64::new();
+        for mode in [MaxiCodeMode::Standard, MaxiCodeMode::EnhancedEcc] {
+            let codewords = mode_codewords("MAXICODE TEST 1234567890", mode).unwrap();
+            assert_eq!(codewords.len(), TOTAL_CODEWORDS);
+            assert_eq!(codewords[0], mode.value());
+            assert_valid_block(&gf, &codewords[..2 * PRIMARY_LENGTH], PRIMARY_LENGTH);
+
+            // Even and odd secondary codewords with their interleaved checks
+            let data_length = secondary_length(mode.secondary_ec());
+            let secondary = &codewords[2 * PRIMARY_LENGTH..];
+            for parity in 0..2 {
+                let mut block: Vec<u8> = secondary[..data_length]
+                    .iter()
+                    .skip(parity)
+                    .step_by(2)
+                    .copied()
+                    .collect();
+                block.extend(secondary[data_length..].iter().skip(parity).step_by(2));
+                assert_valid_block(&gf, &block, mode.secondary_ec() / 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_maxicode_grid() {
+        let barcode = generate_maxicode("MAXICODE TEST").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::MaxiCode);
+
+        let grid = barcode.modules.as_hexagonal().unwrap();
+        assert_eq!(grid.len(), ROWS);
+        assert!(grid.iter().all(|row| row.len() == COLUMNS));
+        // Orientation modules
+        assert!(grid[0][28] && grid[0][29]);
+        assert!(grid[9][10] && grid[9][11] && grid[10][11]);
+        assert!(!grid[9][17] && !grid[10][17] && !grid[10][18]);
+        // Odd rows have 29 modules
+        assert!(grid.iter().skip(1).step_by(2).all(|row| !row[29]));
+
+        // Every codeword bit appears exactly once
+        let mut seen = vec![false; TOTAL_CODEWORDS * 6];
+        for &cell in GRID.iter().flatten().filter(|&&cell| cell >= 0) {
+            assert!(!seen[cell as usize]);
+            seen[cell as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn test_maxicode_ups_message() {
+        let barcode = generate_maxicode(UPS_MESSAGE).unwrap();
+        assert_eq!(barcode.data, UPS_MESSAGE);
+
+        let scm = StructuredCarrierMessage {
+            postal_code: "152382802".to_string(),
+            country_code: 840,
+            service_class: 1,
+        };
+        let (_, secondary) = split_structured_carrier_message(UPS_MESSAGE)
+            .unwrap()
+            .unwrap();
+        let direct = generate_maxicode_scm(&scm, &secondary).unwrap();
+        assert_eq!(
+            barcode.modules.as_hexagonal(),
+            direct.modules.as_hexagonal()
+        );
+    }
+
+    #[test]
+    fn test_maxicode_capacity() {
+        assert!(generate_maxicode(&"A".repeat(93)).is_ok());
+        assert!(generate_maxicode(&"A".repeat(94)).is_err());
+        assert!(generate_maxicode_with_mode(&"A".repeat(77), MaxiCodeMode::EnhancedEcc).is_ok());
+        assert!(generate_maxicode_with_mode(&"A".repeat(78), MaxiCodeMode::EnhancedEcc).is_err());
+        // Nine digits take six codewords
+        assert!(generate_maxicode(&"1".repeat(135)).is_ok());
+    }
+
+    #[test]
+    fn test_maxicode_invalid_data() {
+        assert!(generate_maxicode("[)>\u{1e}01\u{1d}96123").is_err());
+        assert!(generate_maxicode("[)>\u{1e}01\u{1d}96123\u{1d}84\u{1d}001").is_err());
+        let scm = StructuredCarrierMessage {
+            postal_code: "1234567890".to_string(),
+            country_code: 840,
+            service_class: 1,
+        };
+        assert!(generate_maxicode_scm(&scm, "").is_err());
+    }
+}
@@ -8,25 +8,69 @@ pub mod upc;
 // Phase 2: Advanced 2D codes
 pub mod aztec;
 pub mod datamatrix;
+pub mod dotcode;
+pub mod hanxin;
+pub mod maxicode;
 pub mod pdf417;
 
 // Phase 3: Legacy formats
 pub mod code39;
 pub mod codabar;
+pub mod interleaved2of5;
 pub mod itf14;
 
+// Specialty linear formats
+pub mod codablock;
+pub mod code11;
+pub mod code16k;
+pub mod code32;
+pub mod databar;
+pub mod pharmacode;
+pub mod pzn;
+
+// Postal formats
+pub mod auspost;
+pub mod intelligent_mail;
+pub mod japanpost;
+pub mod postnet;
+pub mod rm4scc;
+
 // Re-export generator functions
-pub use code128::generate_code128;
+pub use code128::{generate_code128, generate_code128_set_c, generate_gs1_128};
 pub use ean13::generate_ean13;
 pub use qr::generate_qr;
 pub use upc::generate_upc_a;
 
 // Phase 2 generators
 pub use aztec::generate_aztec;
-pub use datamatrix::generate_datamatrix;
+pub use datamatrix::{generate_datamatrix, generate_gs1_datamatrix};
+pub use dotcode::generate_dotcode;
+pub use hanxin::generate_hanxin;
+pub use maxicode::generate_maxicode;
 pub use pdf417::generate_pdf417;
 
 // Phase 3 generators
 pub use codabar::generate_codabar;
 pub use code39::generate_code39;
-pub use itf14::generate_itf14;
\ No newline at end of file
+pub use interleaved2of5::generate_interleaved_2of5;
+pub use itf14::generate_itf14;
+
+// Specialty linear generators
+pub use codablock::generate_codablock_f;
+pub use code11::generate_code11;
+pub use code16k::generate_code16k;
+pub use code32::generate_code32;
+pub use databar::{
+    generate_databar, generate_databar_expanded, generate_databar_expanded_stacked,
+    generate_databar_limited, generate_databar_stacked, generate_databar_stacked_omni,
+    generate_databar_truncated,
+};
+pub use pharmacode::generate_pharmacode;
+pub use pzn::{generate_pzn7, generate_pzn8};
+
+// Postal generators
+pub use auspost::generate_auspost;
+pub use intelligent_mail::generate_intelligent_mail;
+pub use japanpost::generate_japanpost;
+pub use postnet::{generate_planet, generate_postnet};
+pub use rm4scc::{generate_kix, generate_rm4scc};
\ No newline at end of file
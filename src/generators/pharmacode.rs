@@ -0,0 +1,197 @@
+//! Pharmacode (Laetus) barcode generator
+//!
+//! Pharmacode is a binary code printed on pharmaceutical cartons and leaflets
+//! to verify the packaging material on the production line. It encodes a
+//! single integer and has no check digit.
+//!
+//! - One-track: narrow and wide bars, values 3 to 131070
+//! - Two-track (`BarcodeType::PharmacodeTwoTrack`): full, top-only and
+//!   bottom-only bars, values 4 to 64570080
+
+use crate::types::{
+    BarGeometry, BarState, Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError,
+    Result,
+};
+
+/// Valid range for one-track Pharmacode
+const ONE_TRACK_RANGE: (u32, u32) = (3, 131_070);
+
+/// Valid range for two-track Pharmacode
+const TWO_TRACK_RANGE: (u32, u32) = (4, 64_570_080);
+
+// One-track element widths in modules (narrow bar 0.5 mm, wide bar 1.5 mm, space 1.0 mm)
+const NARROW_BAR: usize = 1;
+const WIDE_BAR: usize = 3;
+const SPACE: usize = 2;
+
+/// Nominal two-track dimensions: 1 mm bars, 1 mm gaps and two 4 mm tracks
+const TWO_TRACK_GEOMETRY: BarGeometry = BarGeometry {
+    bar_width: 1.0,
+    pitch: 2.0,
+    height: 8.0,
+    tracker_top: 4.0,
+    tracker_bottom: 4.0,
+};
+
+/// Pharmacode variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PharmacodeTracks {
+    /// Single track of narrow and wide bars
+    #[default]
+    One,
+    /// Two tracks of full, top-only and bottom-only bars
+    Two,
+}
+
+/// Generate a one-track Pharmacode
+pub fn generate_pharmacode(data: &str) -> Result<Barcode> {
+    generate_pharmacode_with_tracks(data, PharmacodeTracks::One)
+}
+
+/// Generate a one-track or two-track Pharmacode
+pub fn generate_pharmacode_with_tracks(data: &str, tracks: PharmacodeTracks) -> Result<Barcode> {
+    let (min, max) = match tracks {
+        PharmacodeTracks::One => ONE_TRACK_RANGE,
+        PharmacodeTracks::Two => TWO_TRACK_RANGE,
+    };
+
+    let value: u32 = data.trim().parse().map_err(|_| {
+        QuickCodesError::InvalidData("Pharmacode data must be a decimal number".to_string())
+    })?;
+
+    if !(min..=max).contains(&value) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Pharmacode value must be between {} and {} for {:?}-track, got {}",
+            min, max, tracks, value
+        )));
+    }
+
+    let modules = match tracks {
+        PharmacodeTracks::One => BarcodeModules::Linear(one_track_pattern(value)),
+        PharmacodeTracks::Two => BarcodeModules::HeightModulated {
+            bars: two_track_bars(value),
+            geometry: TWO_TRACK_GEOMETRY,
+        },
+    };
+
+    let barcode_type = match tracks {
+        PharmacodeTracks::One => BarcodeType::Pharmacode,
+        PharmacodeTracks::Two => BarcodeType::PharmacodeTwoTrack,
+    };
+
+    Ok(Barcode {
+        barcode_type,
+        data: value.to_string(),
+        modules,
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Compute one-track bars, rightmost first: odd values take a narrow bar
+/// (weight 2^i), even values a wide bar (weight 2^(i+1))
+fn one_track_bars(mut value: u32) -> Vec<bool> {
+    let mut wide = Vec::new();
+    while value > 0 {
+        if value % 2 == 0 {
+            wide.push(true);
+            value = (value - 2) / 2;
+        } else {
+            wide.push(false);
+            value = (value - 1) / 2;
+        }
+    }
+    wide.reverse();
+    wide
+}
+
+/// Expand one-track bars into modules
+fn one_track_pattern(value: u32) -> Vec<bool> {
+    let mut pattern = Vec::new();
+    for (i, wide) in one_track_bars(value).into_iter().enumerate() {
+        if i > 0 {
+            pattern.extend(std::iter::repeat(false).take(SPACE));
+        }
+        let width = if wide { WIDE_BAR } else { NARROW_BAR };
+        pattern.extend(std::iter::repeat(true).take(width));
+    }
+    pattern
+}
+
+/// Compute two-track bars: base-3 digits where a bottom-only bar is worth
+/// 1 x 3^i, a top-only bar 2 x 3^i and a full bar 3 x 3^i
+fn two_track_bars(mut value: u32) -> Vec<BarState> {
+    let mut bars = Vec::new();
+    while value > 0 {
+        let (bar, weight) = match value % 3 {
+            0 => (BarState::Full, 3),
+            1 => (BarState::Descender, 1),
+            _ => (BarState::Ascender, 2),
+        };
+        bars.push(bar);
+        value = (value - weight) / 3;
+    }
+    bars.reverse();
+    bars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pharmacode_one_track_bars() {
+        assert_eq!(one_track_bars(3), vec![false, false]);
+        // 1234 = 512 + 256 + 256 + 128 + 32 + 32 + 8 + 4 + 4 + 2
+        assert_eq!(
+            one_track_bars(1234),
+            vec![false, false, true, true, false, true, false, false, true, true]
+        );
+        assert_eq!(one_track_bars(131_070), vec![true; 16]);
+    }
+
+    #[test]
+    fn test_pharmacode_one_track_generation() {
+        let barcode = generate_pharmacode("3").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::Pharmacode);
+        // narrow bar + space + narrow bar
+        assert_eq!(
+            barcode.modules.as_linear().unwrap(),
+            &vec![true, false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_pharmacode_two_track_bars() {
+        assert_eq!(
+            two_track_bars(4),
+            vec![BarState::Descender, BarState::Descender]
+        );
+        assert_eq!(two_track_bars(64_570_080), vec![BarState::Full; 16]);
+        // 2 (top) x 3 + 3 (full) = 9
+        assert_eq!(two_track_bars(9), vec![BarState::Ascender, BarState::Full]);
+    }
+
+    #[test]
+    fn test_pharmacode_two_track_generation() {
+        let barcode = generate_pharmacode_with_tracks("1234", PharmacodeTracks::Two).unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::PharmacodeTwoTrack);
+        let bars = barcode.modules.as_bars().unwrap();
+        assert!(!bars.is_empty());
+        assert!(barcode.modules.as_linear().is_none());
+    }
+
+    #[test]
+    fn test_pharmacode_out_of_range() {
+        assert!(generate_pharmacode("2").is_err());
+        assert!(generate_pharmacode("131071").is_err());
+        assert!(generate_pharmacode_with_tracks("3", PharmacodeTracks::Two).is_err());
+        assert!(generate_pharmacode_with_tracks("64570081", PharmacodeTracks::Two).is_err());
+    }
+
+    #[test]
+    fn test_pharmacode_non_numeric() {
+        let result = generate_pharmacode("12A");
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("decimal"));
+    }
+}
@@ -0,0 +1,151 @@
+//! POSTNET and PLANET barcode generators
+//!
+//! POSTNET encodes a US ZIP code (5, 9 or 11 digits) and PLANET an 11 or
+//! 13-digit mail tracking number, each followed by a modulo-10 check digit.
+//! Every digit is five full or half height bars between two full frame
+//! bars: POSTNET digits have two full bars, PLANET digits two half bars.
+//! Both have been superseded by the Intelligent Mail barcode.
+
+use crate::types::{
+    BarGeometry, BarState, Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError,
+    Result,
+};
+
+// POSTNET digit patterns, weights 7-4-2-1-0: '1' is a full bar, '0' a half bar
+const POSTNET_PATTERNS: [&str; 10] = [
+    "11000", "00011", "00101", "00110", "01001", "01010", "01100", "10001", "10010", "10100",
+];
+
+/// USPS dimensions: 0.020" bars at 22 bars per inch, 0.125" full bars and
+/// 0.050" half bars standing on the baseline
+const POSTNET_GEOMETRY: BarGeometry = BarGeometry {
+    bar_width: 0.508,
+    pitch: 25.4 / 22.0,
+    height: 3.175,
+    tracker_top: 1.905,
+    tracker_bottom: 3.175,
+};
+
+/// Generate a POSTNET barcode from a 5, 9 or 11-digit ZIP code
+pub fn generate_postnet(data: &str) -> Result<Barcode> {
+    let digits = normalize(data, "POSTNET", &[5, 9, 11])?;
+    Ok(build(BarcodeType::Postnet, digits, false))
+}
+
+/// Generate a PLANET barcode from an 11 or 13-digit tracking number
+pub fn generate_planet(data: &str) -> Result<Barcode> {
+    let digits = normalize(data, "PLANET", &[11, 13])?;
+    Ok(build(BarcodeType::Planet, digits, true))
+}
+
+/// Drop separators and validate the digit count
+fn normalize(data: &str, name: &str, lengths: &[usize]) -> Result<String> {
+    let digits: String = data.chars().filter(|c| !matches!(c, ' ' | '-')).collect();
+
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "{} data must contain only digits",
+            name
+        )));
+    }
+
+    if !lengths.contains(&digits.len()) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "{} requires {:?} digits, got {}",
+            name,
+            lengths,
+            digits.len()
+        )));
+    }
+
+    Ok(digits)
+}
+
+/// Check digit that brings the digit sum to a multiple of 10
+fn calculate_check_digit(digits: &str) -> u8 {
+    let sum: u32 = digits.bytes().map(|b| u32::from(b - b'0')).sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+fn build(barcode_type: BarcodeType, digits: String, inverted: bool) -> Barcode {
+    let data = format!("{}{}", digits, calculate_check_digit(&digits));
+
+    let mut bars = vec![BarState::Full]; // frame bar
+    for digit in data.bytes() {
+        for bit in POSTNET_PATTERNS[usize::from(digit - b'0')].bytes() {
+            // PLANET is the POSTNET pattern with full and half bars swapped
+            bars.push(if (bit == b'1') != inverted {
+                BarState::Full
+            } else {
+                BarState::Descender
+            });
+        }
+    }
+    bars.push(BarState::Full); // frame bar
+
+    Barcode {
+        barcode_type,
+        data,
+        modules: BarcodeModules::HeightModulated {
+            bars,
+            geometry: POSTNET_GEOMETRY,
+        },
+        config: BarcodeConfig::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar_string(barcode: &Barcode) -> String {
+        barcode
+            .modules
+            .as_bars()
+            .unwrap()
+            .iter()
+            .map(|bar| if *bar == BarState::Full { '|' } else { '.' })
+            .collect()
+    }
+
+    #[test]
+    fn test_postnet_check_digit() {
+        // 5+5+5+5+5 = 25 -> 5
+        assert_eq!(calculate_check_digit("55555"), 5);
+        assert_eq!(calculate_check_digit("12345"), 5);
+        assert_eq!(calculate_check_digit("801234567"), 4);
+    }
+
+    #[test]
+    fn test_postnet_generation() {
+        let barcode = generate_postnet("12345").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::Postnet);
+        assert_eq!(barcode.data, "123455");
+        assert_eq!(bar_string(&barcode), "|...||..|.|..||..|..|.|.|..|.|.|");
+
+        // ZIP+4 with delivery point: 11 digits + check = 62 bars
+        let barcode = generate_postnet("80123-4567-89").unwrap();
+        assert_eq!(barcode.modules.as_bars().unwrap().len(), 62);
+    }
+
+    #[test]
+    fn test_planet_generation() {
+        let barcode = generate_planet("40123456789").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::Planet);
+        assert_eq!(barcode.data.len(), 12);
+
+        let bars = barcode.modules.as_bars().unwrap();
+        assert_eq!(bars.len(), 2 + 12 * 5);
+        // Each PLANET digit has three full bars
+        let full = bars[1..6].iter().filter(|&&b| b == BarState::Full).count();
+        assert_eq!(full, 3);
+    }
+
+    #[test]
+    fn test_postnet_invalid_data() {
+        assert!(generate_postnet("1234").is_err());
+        assert!(generate_postnet("123456").is_err());
+        assert!(generate_postnet("1234A").is_err());
+        assert!(generate_planet("12345").is_err());
+    }
+}
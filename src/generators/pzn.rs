@@ -0,0 +1,116 @@
+//! PZN generator (Pharmazentralnummer)
+//!
+//! The PZN identifies medicines on the German market. It is printed as
+//! Code 39 with a `-` prefix and a modulo-11 check digit: PZN8 (7 digits
+//! plus check) since 2013 and the older PZN7 (6 digits plus check). The
+//! human-readable text reads `PZN - ` followed by the number.
+
+use super::code39::generate_code39;
+use crate::types::{Barcode, BarcodeType, QuickCodesError, Result};
+
+/// Generate a PZN8 barcode from 7 digits, or 8 digits with the check digit
+pub fn generate_pzn8(data: &str) -> Result<Barcode> {
+    generate_pzn(data, 7, BarcodeType::PZN8)
+}
+
+/// Generate a legacy PZN7 barcode from 6 digits, or 7 digits with the check
+/// digit
+pub fn generate_pzn7(data: &str) -> Result<Barcode> {
+    generate_pzn(data, 6, BarcodeType::PZN7)
+}
+
+fn generate_pzn(data: &str, length: usize, barcode_type: BarcodeType) -> Result<Barcode> {
+    // Accept the printed form "PZN - 12345678" as well as bare digits
+    let digits = data
+        .trim_start_matches("PZN")
+        .trim_start_matches([' ', '-']);
+    if !(digits.len() == length || digits.len() == length + 1)
+        || !digits.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(QuickCodesError::InvalidData(format!(
+            "PZN{} requires {} digits, or {} digits with the check digit",
+            length + 1,
+            length,
+            length + 1
+        )));
+    }
+
+    let check = check_digit(&digits[..length])?;
+    if let Some(given) = digits[length..].chars().next() {
+        if given != check {
+            return Err(QuickCodesError::InvalidData(format!(
+                "Invalid PZN check digit: expected {}, got {}",
+                check, given
+            )));
+        }
+    }
+    let number = format!("{}{}", &digits[..length], check);
+
+    let mut barcode = generate_code39(&format!("-{}", number))
+        .map_err(|e| QuickCodesError::GenerationError(e.to_string()))?;
+    barcode.barcode_type = barcode_type;
+    barcode.data = format!("PZN - {}", number);
+    Ok(barcode)
+}
+
+/// Modulo-11 check digit with weights ending at 7 (1-7 for PZN8, 2-7 for
+/// PZN7). A remainder of 10 is never issued as a PZN.
+fn check_digit(digits: &str) -> Result<char> {
+    let first_weight = 8 - digits.len() as u32;
+    let sum: u32 = digits
+        .bytes()
+        .zip(first_weight..)
+        .map(|(b, weight)| u32::from(b - b'0') * weight)
+        .sum();
+    match sum % 11 {
+        10 => Err(QuickCodesError::InvalidData(format!(
+            "{} is not a valid PZN: the check digit would be 10",
+            digits
+        ))),
+        check => Ok(char::from(b'0' + check as u8)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pzn_check_digit() {
+        assert_eq!(check_digit("1234567").unwrap(), '8');
+        assert_eq!(check_digit("490880").unwrap(), '2');
+        assert!(check_digit("0000003").is_err());
+    }
+
+    #[test]
+    fn test_pzn8_generation() {
+        let barcode = generate_pzn8("1234567").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::PZN8);
+        assert_eq!(barcode.data, "PZN - 12345678");
+
+        let code39 = generate_code39("-12345678").unwrap();
+        assert_eq!(barcode.modules.as_linear(), code39.modules.as_linear());
+
+        assert_eq!(
+            generate_pzn8("PZN - 12345678").unwrap().data,
+            "PZN - 12345678"
+        );
+        assert_eq!(generate_pzn8("-12345678").unwrap().data, "PZN - 12345678");
+    }
+
+    #[test]
+    fn test_pzn7_generation() {
+        let barcode = generate_pzn7("4908802").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::PZN7);
+        assert_eq!(barcode.data, "PZN - 4908802");
+    }
+
+    #[test]
+    fn test_pzn_invalid_data() {
+        assert!(generate_pzn8("").is_err());
+        assert!(generate_pzn8("123456").is_err());
+        assert!(generate_pzn8("12345679").is_err());
+        assert!(generate_pzn8("12A4567").is_err());
+        assert!(generate_pzn7("12345678").is_err());
+    }
+}
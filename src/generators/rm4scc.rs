@@ -0,0 +1,178 @@
+//! Royal Mail 4-State Customer Code (RM4SCC) and Dutch KIX generators
+//!
+//! RM4SCC encodes a UK postcode plus delivery point suffix in four-state
+//! bars, with start/stop bars and a modulo-6 row/column check character.
+//! KIX (Klant IndeX), used by PostNL, shares the same character set and bar
+//! patterns without start/stop bars or check character.
+
+use crate::types::{
+    BarGeometry, BarState, Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError,
+    Result,
+};
+
+/// Character set, ordered by (row, column) of the 6x6 encoding grid
+const CHARSET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// Bar patterns for each character: F = full, A = ascender, D = descender, T = tracker
+const PATTERNS: [&str; 36] = [
+    "TTFF", "TDAF", "TDFA", "DTAF", "DTFA", "DDAA", "TADF", "TFTF", "TFDA", "DATF", "DADA", "DFTA",
+    "TAFD", "TFAD", "TFFT", "DAAD", "DAFT", "DFAT", "ATDF", "ADTF", "ADDA", "FTTF", "FTDA", "FDTA",
+    "ATFD", "ADAD", "ADFT", "FTAD", "FTFT", "FDAT", "AADD", "AFTD", "AFDT", "FATD", "FADT", "FFTT",
+];
+
+/// Nominal dimensions: 0.5 mm bars at 1.2 mm pitch, 5 mm full bars and a
+/// centred 1.3 mm tracker
+const FOUR_STATE_GEOMETRY: BarGeometry = BarGeometry {
+    bar_width: 0.5,
+    pitch: 1.2,
+    height: 5.0,
+    tracker_top: 1.85,
+    tracker_bottom: 3.15,
+};
+
+/// Generate a Royal Mail 4-State Customer Code with its check character
+pub fn generate_rm4scc(data: &str) -> Result<Barcode> {
+    let data = normalize(data, "RM4SCC")?;
+    let check = check_character(&data);
+
+    let mut bars = vec![BarState::Ascender]; // start bar
+    bars.extend(encode(&data));
+    bars.extend(encode(&check.to_string()));
+    bars.push(BarState::Full); // stop bar
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::RoyalMail,
+        data: format!("{}{}", data, check),
+        modules: BarcodeModules::HeightModulated {
+            bars,
+            geometry: FOUR_STATE_GEOMETRY,
+        },
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Generate a Dutch KIX code
+pub fn generate_kix(data: &str) -> Result<Barcode> {
+    let data = normalize(data, "KIX")?;
+
+    Ok(Barcode {
+        barcode_type: BarcodeType::KIX,
+        modules: BarcodeModules::HeightModulated {
+            bars: encode(&data),
+            geometry: FOUR_STATE_GEOMETRY,
+        },
+        data,
+        config: BarcodeConfig::default(),
+    })
+}
+
+/// Uppercase the data, drop spaces and validate the character set
+fn normalize(data: &str, name: &str) -> Result<String> {
+    let data: String = data
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    if data.is_empty() {
+        return Err(QuickCodesError::InvalidData(format!(
+            "{} data cannot be empty",
+            name
+        )));
+    }
+
+    if let Some(c) = data.chars().find(|&c| !CHARSET.contains(c)) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Invalid character '{}' for {}. Only 0-9 and A-Z are allowed.",
+            c, name
+        )));
+    }
+
+    Ok(data)
+}
+
+/// Check character: sums of the row and column values modulo 6
+fn check_character(data: &str) -> char {
+    let (rows, columns) = data
+        .chars()
+        .filter_map(|c| CHARSET.find(c))
+        .fold((0, 0), |(rows, columns), index| {
+            (rows + index / 6 + 1, columns + index % 6 + 1)
+        });
+
+    // A remainder of 0 stands for row/column 6
+    let row = (rows + 5) % 6;
+    let column = (columns + 5) % 6;
+    CHARSET.as_bytes()[6 * row + column] as char
+}
+
+fn encode(data: &str) -> Vec<BarState> {
+    data.chars()
+        .filter_map(|c| CHARSET.find(c))
+        .flat_map(|index| PATTERNS[index].bytes())
+        .map(|bar| match bar {
+            b'F' => BarState::Full,
+            b'A' => BarState::Ascender,
+            b'D' => BarState::Descender,
+            _ => BarState::Tracker,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rm4scc_check_character() {
+        assert_eq!(check_character("SN34RD1A"), 'K');
+    }
+
+    #[test]
+    fn test_rm4scc_generation() {
+        let barcode = generate_rm4scc("sn34 rd1a").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::RoyalMail);
+        assert_eq!(barcode.data, "SN34RD1AK");
+
+        let bars = barcode.modules.as_bars().unwrap();
+        // Start + 9 characters of 4 bars + stop
+        assert_eq!(bars.len(), 2 + 9 * 4);
+        assert_eq!(bars[0], BarState::Ascender);
+        assert_eq!(bars[bars.len() - 1], BarState::Full);
+    }
+
+    #[test]
+    fn test_four_state_patterns() {
+        // Every character has two ascending and two descending bars
+        for pattern in PATTERNS {
+            let ascending = pattern.bytes().filter(|b| matches!(b, b'F' | b'A')).count();
+            let descending = pattern.bytes().filter(|b| matches!(b, b'F' | b'D')).count();
+            assert_eq!((ascending, descending), (2, 2), "{}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_kix_generation() {
+        let barcode = generate_kix("2500GG30250").unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::KIX);
+        assert_eq!(barcode.data, "2500GG30250");
+        // No start/stop bars or check character
+        assert_eq!(barcode.modules.as_bars().unwrap().len(), 11 * 4);
+        assert_eq!(
+            &barcode.modules.as_bars().unwrap()[..4],
+            &[
+                BarState::Tracker,
+                BarState::Descender,
+                BarState::Full,
+                BarState::Ascender
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rm4scc_invalid_data() {
+        assert!(generate_rm4scc("").is_err());
+        assert!(generate_rm4scc("SN34-RD1A").is_err());
+        assert!(generate_kix("2500 GG#").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! UPC-A barcode generator
 
+use crate::gs1;
 use crate::types::{Barcode, BarcodeConfig, BarcodeModules, BarcodeType, QuickCodesError, Result};
 
 // UPC-A uses the same patterns as EAN-13 for the digits
@@ -100,24 +101,8 @@ fn process_upc_a_data(data: &str) -> Result<Vec<u8>> {
 
 /// Calculate UPC-A check digit
 fn calculate_upc_a_check_digit(digits: &[u8]) -> u8 {
-    let sum: u32 = digits
-        .iter()
-        .enumerate()
-        .map(|(i, &digit)| {
-            if i % 2 == 0 {
-                (digit as u32) * 3
-            } else {
-                digit as u32
-            }
-        })
-        .sum();
-
-    let remainder = sum % 10;
-    if remainder == 0 {
-        0
-    } else {
-        10 - remainder as u8
-    }
+    let digits: String = digits.iter().map(|d| char::from(b'0' + d)).collect();
+    gs1::check_digit(&digits)
 }
 
 /// Generate the binary pattern for UPC-A
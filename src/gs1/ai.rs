@@ -0,0 +1,684 @@
+//! GS1 Application Identifier definitions
+//!
+//! Each AI has a data format made of components, e.g. `N14` for a GTIN or
+//! `N13 X..17` for a GDTI: fixed-length parts followed by at most one
+//! variable-length part, and a rule for check digits or dates. Families of
+//! four-digit AIs that differ only in the last digit, such as the trade
+//! measures 3100-3105, are defined once by their first three digits.
+
+use super::{check_digit, Element};
+use crate::types::{QuickCodesError, Result};
+
+/// Part of an AI's data format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+    /// Exactly `n` digits
+    Numeric(usize),
+    /// Up to `n` digits
+    NumericUpTo(usize),
+    /// Exactly `n` characters of GS1 character set 82
+    Alphanumeric(usize),
+    /// Up to `n` characters of GS1 character set 82
+    AlphanumericUpTo(usize),
+    /// Up to `n` characters of GS1 character set 39
+    Cset39UpTo(usize),
+}
+
+/// Extra check on the start of a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    None,
+    /// GS1 modulo-10 check digit as the last of the first `n` digits
+    CheckDigit(usize),
+    /// `YYMMDD`; with `day_zero`, day 00 stands for the end of the month
+    Date {
+        day_zero: bool,
+    },
+    /// `YYMMDDHH`, optionally followed by minutes and seconds
+    DateHour,
+    /// `YYMMDDHHMM`
+    DateTime,
+    /// `YYYYMMDD`
+    FullDate,
+}
+
+/// An Application Identifier and its data format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AiDefinition {
+    /// AI digits; three digits for a family of four-digit AIs
+    pub ai: &'static str,
+    /// Data title, as printed on logistics labels
+    pub title: &'static str,
+    pub format: &'static [Component],
+    pub rule: Rule,
+}
+
+use Component::*;
+use Rule::{CheckDigit, Date, DateHour, DateTime, FullDate};
+
+macro_rules! ai {
+    ($ai:literal, $title:literal, [$($component:expr),+]) => {
+        ai!($ai, $title, [$($component),+], Rule::None)
+    };
+    ($ai:literal, $title:literal, [$($component:expr),+], $rule:expr) => {
+        AiDefinition {
+            ai: $ai,
+            title: $title,
+            format: &[$($component),+],
+            rule: $rule,
+        }
+    };
+}
+
+const DATE: Rule = Date { day_zero: false };
+const DATE_OR_MONTH: Rule = Date { day_zero: true };
+
+/// AIs of the GS1 General Specifications
+const AI_TABLE: &[AiDefinition] = &[
+    ai!("00", "SSCC", [Numeric(18)], CheckDigit(18)),
+    ai!("01", "GTIN", [Numeric(14)], CheckDigit(14)),
+    ai!("02", "CONTENT", [Numeric(14)], CheckDigit(14)),
+    ai!("03", "MTO GTIN", [Numeric(14)], CheckDigit(14)),
+    ai!("10", "BATCH/LOT", [AlphanumericUpTo(20)]),
+    ai!("11", "PROD DATE", [Numeric(6)], DATE_OR_MONTH),
+    ai!("12", "DUE DATE", [Numeric(6)], DATE_OR_MONTH),
+    ai!("13", "PACK DATE", [Numeric(6)], DATE_OR_MONTH),
+    ai!("15", "BEST BEFORE or BEST BY", [Numeric(6)], DATE_OR_MONTH),
+    ai!("16", "SELL BY", [Numeric(6)], DATE_OR_MONTH),
+    ai!("17", "USE BY OR EXPIRY", [Numeric(6)], DATE_OR_MONTH),
+    ai!("20", "VARIANT", [Numeric(2)]),
+    ai!("21", "SERIAL", [AlphanumericUpTo(20)]),
+    ai!("22", "CPV", [AlphanumericUpTo(20)]),
+    ai!("235", "TPX", [AlphanumericUpTo(28)]),
+    ai!("240", "ADDITIONAL ID", [AlphanumericUpTo(30)]),
+    ai!("241", "CUST. PART No.", [AlphanumericUpTo(30)]),
+    ai!("242", "MTO VARIANT", [NumericUpTo(6)]),
+    ai!("243", "PCN", [AlphanumericUpTo(20)]),
+    ai!("250", "SECONDARY SERIAL", [AlphanumericUpTo(30)]),
+    ai!("251", "REF. TO SOURCE", [AlphanumericUpTo(30)]),
+    ai!(
+        "253",
+        "GDTI",
+        [Numeric(13), AlphanumericUpTo(17)],
+        CheckDigit(13)
+    ),
+    ai!("254", "GLN EXTENSION COMPONENT", [AlphanumericUpTo(20)]),
+    ai!("255", "GCN", [Numeric(13), NumericUpTo(12)], CheckDigit(13)),
+    ai!("30", "VAR. COUNT", [NumericUpTo(8)]),
+    ai!("310", "NET WEIGHT (kg)", [Numeric(6)]),
+    ai!("311", "LENGTH (m)", [Numeric(6)]),
+    ai!("312", "WIDTH (m)", [Numeric(6)]),
+    ai!("313", "HEIGHT (m)", [Numeric(6)]),
+    ai!("314", "AREA (m2)", [Numeric(6)]),
+    ai!("315", "NET VOLUME (l)", [Numeric(6)]),
+    ai!("316", "NET VOLUME (m3)", [Numeric(6)]),
+    ai!("320", "NET WEIGHT (lb)", [Numeric(6)]),
+    ai!("321", "LENGTH (in)", [Numeric(6)]),
+    ai!("322", "LENGTH (ft)", [Numeric(6)]),
+    ai!("323", "LENGTH (yd)", [Numeric(6)]),
+    ai!("324", "WIDTH (in)", [Numeric(6)]),
+    ai!("325", "WIDTH (ft)", [Numeric(6)]),
+    ai!("326", "WIDTH (yd)", [Numeric(6)]),
+    ai!("327", "HEIGHT (in)", [Numeric(6)]),
+    ai!("328", "HEIGHT (ft)", [Numeric(6)]),
+    ai!("329", "HEIGHT (yd)", [Numeric(6)]),
+    ai!("330", "GROSS WEIGHT (kg)", [Numeric(6)]),
+    ai!("331", "LENGTH (m), log", [Numeric(6)]),
+    ai!("332", "WIDTH (m), log", [Numeric(6)]),
+    ai!("333", "HEIGHT (m), log", [Numeric(6)]),
+    ai!("334", "AREA (m2), log", [Numeric(6)]),
+    ai!("335", "VOLUME (l), log", [Numeric(6)]),
+    ai!("336", "VOLUME (m3), log", [Numeric(6)]),
+    ai!("337", "KG PER m2", [Numeric(6)]),
+    ai!("340", "GROSS WEIGHT (lb)", [Numeric(6)]),
+    ai!("341", "LENGTH (in), log", [Numeric(6)]),
+    ai!("342", "LENGTH (ft), log", [Numeric(6)]),
+    ai!("343", "LENGTH (yd), log", [Numeric(6)]),
+    ai!("344", "WIDTH (in), log", [Numeric(6)]),
+    ai!("345", "WIDTH (ft), log", [Numeric(6)]),
+    ai!("346", "WIDTH (yd), log", [Numeric(6)]),
+    ai!("347", "HEIGHT (in), log", [Numeric(6)]),
+    ai!("348", "HEIGHT (ft), log", [Numeric(6)]),
+    ai!("349", "HEIGHT (yd), log", [Numeric(6)]),
+    ai!("350", "AREA (in2)", [Numeric(6)]),
+    ai!("351", "AREA (ft2)", [Numeric(6)]),
+    ai!("352", "AREA (yd2)", [Numeric(6)]),
+    ai!("353", "AREA (in2), log", [Numeric(6)]),
+    ai!("354", "AREA (ft2), log", [Numeric(6)]),
+    ai!("355", "AREA (yd2), log", [Numeric(6)]),
+    ai!("356", "NET WEIGHT (t oz)", [Numeric(6)]),
+    ai!("357", "NET VOLUME (oz)", [Numeric(6)]),
+    ai!("360", "NET VOLUME (qt)", [Numeric(6)]),
+    ai!("361", "NET VOLUME (gal.)", [Numeric(6)]),
+    ai!("362", "VOLUME (qt), log", [Numeric(6)]),
+    ai!("363", "VOLUME (gal.), log", [Numeric(6)]),
+    ai!("364", "VOLUME (in3)", [Numeric(6)]),
+    ai!("365", "VOLUME (ft3)", [Numeric(6)]),
+    ai!("366", "VOLUME (yd3)", [Numeric(6)]),
+    ai!("367", "VOLUME (in3), log", [Numeric(6)]),
+    ai!("368", "VOLUME (ft3), log", [Numeric(6)]),
+    ai!("369", "VOLUME (yd3), log", [Numeric(6)]),
+    ai!("37", "COUNT", [NumericUpTo(8)]),
+    ai!("390", "AMOUNT", [NumericUpTo(15)]),
+    ai!("391", "AMOUNT", [Numeric(3), NumericUpTo(15)]),
+    ai!("392", "PRICE", [NumericUpTo(15)]),
+    ai!("393", "PRICE", [Numeric(3), NumericUpTo(15)]),
+    ai!("394", "PRCNT OFF", [Numeric(4)]),
+    ai!("395", "PRICE/UoM", [Numeric(6)]),
+    ai!("400", "ORDER NUMBER", [AlphanumericUpTo(30)]),
+    ai!("401", "GINC", [AlphanumericUpTo(30)]),
+    ai!("402", "GSIN", [Numeric(17)], CheckDigit(17)),
+    ai!("403", "ROUTE", [AlphanumericUpTo(30)]),
+    ai!("410", "SHIP TO LOC", [Numeric(13)], CheckDigit(13)),
+    ai!("411", "BILL TO", [Numeric(13)], CheckDigit(13)),
+    ai!("412", "PURCHASE FROM", [Numeric(13)], CheckDigit(13)),
+    ai!("413", "SHIP FOR LOC", [Numeric(13)], CheckDigit(13)),
+    ai!("414", "LOC No.", [Numeric(13)], CheckDigit(13)),
+    ai!("415", "PAY TO", [Numeric(13)], CheckDigit(13)),
+    ai!("416", "PROD/SERV LOC", [Numeric(13)], CheckDigit(13)),
+    ai!("417", "PARTY", [Numeric(13)], CheckDigit(13)),
+    ai!("420", "SHIP TO POST", [AlphanumericUpTo(20)]),
+    ai!("421", "SHIP TO POST", [Numeric(3), AlphanumericUpTo(9)]),
+    ai!("422", "ORIGIN", [Numeric(3)]),
+    ai!(
+        "423",
+        "COUNTRY - INITIAL PROCESS.",
+        [Numeric(3), NumericUpTo(12)]
+    ),
+    ai!("424", "COUNTRY - PROCESS.", [Numeric(3)]),
+    ai!(
+        "425",
+        "COUNTRY - DISASSEMBLY",
+        [Numeric(3), NumericUpTo(12)]
+    ),
+    ai!("426", "COUNTRY - FULL PROCESS", [Numeric(3)]),
+    ai!("427", "ORIGIN SUBDIVISION", [AlphanumericUpTo(3)]),
+    ai!("4300", "SHIP TO COMP", [AlphanumericUpTo(35)]),
+    ai!("4301", "SHIP TO NAME", [AlphanumericUpTo(35)]),
+    ai!("4302", "SHIP TO ADD1", [AlphanumericUpTo(70)]),
+    ai!("4303", "SHIP TO ADD2", [AlphanumericUpTo(70)]),
+    ai!("4304", "SHIP TO SUB", [AlphanumericUpTo(70)]),
+    ai!("4305", "SHIP TO LOC", [AlphanumericUpTo(70)]),
+    ai!("4306", "SHIP TO REG", [AlphanumericUpTo(70)]),
+    ai!("4307", "SHIP TO COUNTRY", [Alphanumeric(2)]),
+    ai!("4308", "SHIP TO PHONE", [AlphanumericUpTo(30)]),
+    ai!("4309", "SHIP TO GEO", [Numeric(20)]),
+    ai!("4310", "RTN TO COMP", [AlphanumericUpTo(35)]),
+    ai!("4311", "RTN TO NAME", [AlphanumericUpTo(35)]),
+    ai!("4312", "RTN TO ADD1", [AlphanumericUpTo(70)]),
+    ai!("4313", "RTN TO ADD2", [AlphanumericUpTo(70)]),
+    ai!("4314", "RTN TO SUB", [AlphanumericUpTo(70)]),
+    ai!("4315", "RTN TO LOC", [AlphanumericUpTo(70)]),
+    ai!("4316", "RTN TO REG", [AlphanumericUpTo(70)]),
+    ai!("4317", "RTN TO COUNTRY", [Alphanumeric(2)]),
+    ai!("4318", "RTN TO POST", [AlphanumericUpTo(20)]),
+    ai!("4319", "RTN TO PHONE", [AlphanumericUpTo(30)]),
+    ai!("4320", "SRV DESCRIPTION", [AlphanumericUpTo(35)]),
+    ai!("4321", "DANGEROUS GOODS", [Numeric(1)]),
+    ai!("4322", "AUTH LEAVE", [Numeric(1)]),
+    ai!("4323", "SIG REQUIRED", [Numeric(1)]),
+    ai!("4324", "NBEF DEL DT", [Numeric(10)], DateTime),
+    ai!("4325", "NAFT DEL DT", [Numeric(10)], DateTime),
+    ai!("4326", "REL DATE", [Numeric(6)], DATE),
+    ai!("7001", "NSN", [Numeric(13)]),
+    ai!("7002", "MEAT CUT", [AlphanumericUpTo(30)]),
+    ai!("7003", "EXPIRY TIME", [Numeric(10)], DateTime),
+    ai!("7004", "ACTIVE POTENCY", [NumericUpTo(4)]),
+    ai!("7005", "CATCH AREA", [AlphanumericUpTo(12)]),
+    ai!("7006", "FIRST FREEZE DATE", [Numeric(6)], DATE),
+    ai!("7007", "HARVEST DATE", [NumericUpTo(12)], DATE),
+    ai!("7008", "AQUATIC SPECIES", [AlphanumericUpTo(3)]),
+    ai!("7009", "FISHING GEAR TYPE", [AlphanumericUpTo(10)]),
+    ai!("7010", "PROD METHOD", [AlphanumericUpTo(2)]),
+    ai!("7011", "TEST BY DATE", [NumericUpTo(10)], DATE),
+    ai!("7020", "REFURB LOT", [AlphanumericUpTo(20)]),
+    ai!("7021", "FUNC STAT", [AlphanumericUpTo(20)]),
+    ai!("7022", "REV STAT", [AlphanumericUpTo(20)]),
+    ai!("7023", "GIAI - ASSEMBLY", [AlphanumericUpTo(30)]),
+    ai!("703", "PROCESSOR # s", [Numeric(3), AlphanumericUpTo(27)]),
+    ai!("7040", "UIC+EXT", [Numeric(1), Alphanumeric(3)]),
+    ai!("7041", "UFRGT UNIT TYPE", [AlphanumericUpTo(4)]),
+    ai!("710", "NHRN PZN", [AlphanumericUpTo(20)]),
+    ai!("711", "NHRN CIP", [AlphanumericUpTo(20)]),
+    ai!("712", "NHRN CN", [AlphanumericUpTo(20)]),
+    ai!("713", "NHRN DRN", [AlphanumericUpTo(20)]),
+    ai!("714", "NHRN AIM", [AlphanumericUpTo(20)]),
+    ai!("715", "NHRN NDC", [AlphanumericUpTo(20)]),
+    ai!("716", "NHRN AIC", [AlphanumericUpTo(20)]),
+    ai!("723", "CERT # s", [Alphanumeric(2), AlphanumericUpTo(28)]),
+    ai!("7240", "PROTOCOL", [AlphanumericUpTo(20)]),
+    ai!("7241", "AIDC MEDIA TYPE", [Numeric(2)]),
+    ai!("7242", "VCN", [AlphanumericUpTo(25)]),
+    ai!("7250", "DOB", [Numeric(8)], FullDate),
+    ai!("7251", "DOB TIME", [Numeric(12)], FullDate),
+    ai!("7252", "BIO SEX", [Numeric(1)]),
+    ai!("7253", "FAMILY NAME", [AlphanumericUpTo(40)]),
+    ai!("7254", "GIVEN NAME", [AlphanumericUpTo(40)]),
+    ai!("7255", "SUFFIX", [AlphanumericUpTo(10)]),
+    ai!("7256", "FULL NAME", [AlphanumericUpTo(90)]),
+    ai!("7257", "PERSON ADDR", [AlphanumericUpTo(70)]),
+    ai!(
+        "7258",
+        "BIRTH SEQUENCE",
+        [Numeric(1), Alphanumeric(1), Numeric(1)]
+    ),
+    ai!("7259", "BABY", [AlphanumericUpTo(40)]),
+    ai!("8001", "DIMENSIONS", [Numeric(14)]),
+    ai!("8002", "CMT No.", [AlphanumericUpTo(20)]),
+    ai!(
+        "8003",
+        "GRAI",
+        [Numeric(14), AlphanumericUpTo(16)],
+        CheckDigit(14)
+    ),
+    ai!("8004", "GIAI", [AlphanumericUpTo(30)]),
+    ai!("8005", "PRICE PER UNIT", [Numeric(6)]),
+    ai!(
+        "8006",
+        "ITIP",
+        [Numeric(14), Numeric(2), Numeric(2)],
+        CheckDigit(14)
+    ),
+    ai!("8007", "IBAN", [AlphanumericUpTo(34)]),
+    ai!("8008", "PROD TIME", [NumericUpTo(12)], DateHour),
+    ai!("8009", "OPTSEN", [AlphanumericUpTo(50)]),
+    ai!("8010", "CPID", [Cset39UpTo(30)]),
+    ai!("8011", "CPID SERIAL", [NumericUpTo(12)]),
+    ai!("8012", "VERSION", [AlphanumericUpTo(20)]),
+    ai!("8013", "GMN", [AlphanumericUpTo(25)]),
+    ai!("8017", "GSRN - PROVIDER", [Numeric(18)], CheckDigit(18)),
+    ai!("8018", "GSRN - RECIPIENT", [Numeric(18)], CheckDigit(18)),
+    ai!("8019", "SRIN", [NumericUpTo(10)]),
+    ai!("8020", "REF No.", [AlphanumericUpTo(25)]),
+    ai!(
+        "8026",
+        "ITIP CONTENT",
+        [Numeric(14), Numeric(2), Numeric(2)],
+        CheckDigit(14)
+    ),
+    ai!("8110", "", [AlphanumericUpTo(70)]),
+    ai!("8111", "POINTS", [Numeric(4)]),
+    ai!("8112", "", [AlphanumericUpTo(70)]),
+    ai!("8200", "PRODUCT URL", [AlphanumericUpTo(70)]),
+    ai!("90", "INTERNAL", [AlphanumericUpTo(30)]),
+    ai!("91", "INTERNAL", [AlphanumericUpTo(90)]),
+    ai!("92", "INTERNAL", [AlphanumericUpTo(90)]),
+    ai!("93", "INTERNAL", [AlphanumericUpTo(90)]),
+    ai!("94", "INTERNAL", [AlphanumericUpTo(90)]),
+    ai!("95", "INTERNAL", [AlphanumericUpTo(90)]),
+    ai!("96", "INTERNAL", [AlphanumericUpTo(90)]),
+    ai!("97", "INTERNAL", [AlphanumericUpTo(90)]),
+    ai!("98", "INTERNAL", [AlphanumericUpTo(90)]),
+    ai!("99", "INTERNAL", [AlphanumericUpTo(90)]),
+];
+
+/// An AI (or AI prefix) that needs others in the same element string: one
+/// AI of each group must be present
+type Association = (&'static str, &'static [&'static [&'static str]]);
+
+const REQUIRES: &[Association] = &[
+    ("02", &[&["37"]]),
+    ("10", &[TRADE_ITEM]),
+    ("11", &[TRADE_ITEM]),
+    ("12", &[&["415"]]),
+    ("13", &[TRADE_ITEM]),
+    ("15", &[TRADE_ITEM]),
+    ("16", &[TRADE_ITEM]),
+    ("17", &[TRADE_ITEM]),
+    ("20", &[TRADE_ITEM]),
+    ("21", &[&["01", "03", "8006"]]),
+    ("22", &[&["01"]]),
+    ("235", &[&["01"]]),
+    ("250", &[&["01", "8006"], &["21"]]),
+    ("251", &[&["01", "8006"]]),
+    ("254", &[&["414"]]),
+    ("30", &[&["01", "8006"]]),
+    ("31", &[TRADE_ITEM]),
+    ("32", &[TRADE_ITEM]),
+    ("33", &[&["00", "01", "02", "8006", "8026"]]),
+    ("34", &[&["00", "01", "02", "8006", "8026"]]),
+    ("35", &[TRADE_ITEM]),
+    ("36", &[TRADE_ITEM]),
+    ("37", &[&["02", "8026"]]),
+    ("390", &[&["8020", "255"]]),
+    ("391", &[&["8020"]]),
+    ("392", &[&["01", "8006"]]),
+    ("393", &[&["01", "8006"]]),
+    ("394", &[&["255"]]),
+    ("395", &[&["01", "8006"]]),
+    ("415", &[&["8020"]]),
+    ("7040", &[&["417"]]),
+    ("8011", &[&["8010"]]),
+    ("8019", &[&["8017", "8018"]]),
+    ("8020", &[&["415"]]),
+    ("8111", &[&["255"]]),
+];
+
+/// Keys of trade items, for attributes such as lots and dates
+const TRADE_ITEM: &[&str] = &["01", "02", "03", "8006", "8026"];
+
+/// AIs (or AI prefixes) that must not appear together
+const EXCLUDES: &[(&str, &str)] = &[
+    ("01", "02"),
+    ("01", "03"),
+    ("01", "37"),
+    ("02", "03"),
+    ("02", "8006"),
+    ("21", "235"),
+    ("394", "8111"),
+];
+
+/// Definition of an AI, or `None` when it is not assigned
+pub fn lookup(ai: &str) -> Option<&'static AiDefinition> {
+    if !ai.bytes().all(|b| b.is_ascii_digit()) || ai_length(ai) != Some(ai.len()) {
+        return None;
+    }
+    AI_TABLE
+        .iter()
+        .find(|definition| definition.ai == ai)
+        .or_else(|| {
+            let family = ai.get(..3)?;
+            AI_TABLE
+                .iter()
+                .find(|definition| definition.ai == family && ai.len() == 4)
+        })
+}
+
+/// Number of digits of the AIs starting with `prefix` (two digits), as
+/// fixed by the GS1 General Specifications
+pub fn ai_length(prefix: &str) -> Option<usize> {
+    let prefix: u8 = prefix.get(..2)?.parse().ok()?;
+    match prefix {
+        0..=22 | 30 | 37 | 90..=99 => Some(2),
+        23..=25 | 40..=42 | 71 => Some(3),
+        31..=36 | 39 | 43 | 70 | 72 | 80..=82 => Some(4),
+        _ => None,
+    }
+}
+
+/// Value length of AIs with a predefined length: these are never followed
+/// by an FNC1 separator, whatever their position
+pub fn predefined_length(ai: &str) -> Option<usize> {
+    let prefix: u8 = ai.get(..2)?.parse().ok()?;
+    match prefix {
+        0 => Some(18),
+        1..=3 => Some(14),
+        4 => Some(16),
+        11..=19 => Some(6),
+        20 => Some(2),
+        31..=36 => Some(6),
+        41 => Some(13),
+        _ => None,
+    }
+}
+
+/// Decimal places of AIs whose last digit is a decimal point position,
+/// such as 3103 (three decimals)
+pub fn decimal_places(ai: &str) -> Option<u32> {
+    match ai.get(..2)? {
+        "31" | "32" | "33" | "34" | "35" | "36" | "39" if ai.len() == 4 => ai[3..].parse().ok(),
+        _ => None,
+    }
+}
+
+/// Check a value against the data format and rule of its AI
+pub fn validate(ai: &str, value: &str) -> Result<&'static AiDefinition> {
+    let definition = lookup(ai).ok_or_else(|| invalid(ai, "unknown application identifier"))?;
+    if let Some(places) = decimal_places(ai) {
+        let max = match &ai[..3] {
+            "394" => 3,
+            "390" | "391" | "392" | "393" => 9,
+            _ => 5,
+        };
+        if places > max {
+            return Err(invalid(ai, "unsupported decimal point position"));
+        }
+    }
+
+    let mut rest = value;
+    for component in definition.format {
+        let (length, fixed) = component.length();
+        let part = if fixed {
+            rest.get(..length)
+                .ok_or_else(|| invalid(ai, &format!("'{}' is too short", value)))?
+        } else {
+            if rest.is_empty() {
+                return Err(invalid(ai, "the value is empty"));
+            }
+            if rest.len() > length {
+                return Err(invalid(ai, &format!("'{}' is too long", value)));
+            }
+            rest
+        };
+        if let Some(c) = part.chars().find(|&c| !component.allows(c)) {
+            return Err(invalid(
+                ai,
+                &format!("'{}' is not allowed in '{}'", c, value),
+            ));
+        }
+        rest = &rest[part.len()..];
+    }
+    if !rest.is_empty() {
+        return Err(invalid(ai, &format!("'{}' is too long", value)));
+    }
+
+    match definition.rule {
+        Rule::None => {}
+        CheckDigit(length) => {
+            let digits = &value[..length];
+            let expected = check_digit(&digits[..length - 1]);
+            if digits.as_bytes()[length - 1] - b'0' != expected {
+                return Err(invalid(
+                    ai,
+                    &format!("wrong check digit in '{}', expected {}", digits, expected),
+                ));
+            }
+        }
+        Date { day_zero } => check_date(ai, value, day_zero)?,
+        DateHour | DateTime => {
+            check_date(ai, value, false)?;
+            let time = &value[6..];
+            let valid = time.len() >= 2
+                && time.len() % 2 == 0
+                && &time[..2] < "24"
+                && time
+                    .as_bytes()
+                    .chunks(2)
+                    .skip(1)
+                    .all(|pair| pair < b"60".as_slice());
+            if !valid || (definition.rule == DateTime && time.len() != 4) {
+                return Err(invalid(ai, &format!("'{}' has an invalid time", value)));
+            }
+        }
+        FullDate => check_date(ai, &value[2..], false)?,
+    }
+    Ok(definition)
+}
+
+/// Check an element list: each value, repeated AIs, AIs that need others
+/// and AIs that exclude each other
+pub fn validate_elements(elements: &[Element]) -> Result<()> {
+    let present = |ai: &str| elements.iter().any(|element| element.ai.starts_with(ai));
+    for (i, element) in elements.iter().enumerate() {
+        validate(&element.ai, &element.value)?;
+        if let Some(other) = elements[..i]
+            .iter()
+            .find(|other| other.ai == element.ai && other.value != element.value)
+        {
+            return Err(invalid(
+                &element.ai,
+                &format!(
+                    "appears with values '{}' and '{}'",
+                    other.value, element.value
+                ),
+            ));
+        }
+        for (ai, groups) in REQUIRES {
+            if !element.ai.starts_with(ai) {
+                continue;
+            }
+            if let Some(group) = groups
+                .iter()
+                .find(|group| !group.iter().any(|ai| present(ai)))
+            {
+                return Err(invalid(
+                    &element.ai,
+                    &format!("requires ({})", group.join(") or (")),
+                ));
+            }
+        }
+    }
+    for (first, second) in EXCLUDES {
+        if present(first) && present(second) {
+            return Err(invalid(first, &format!("cannot appear with ({})", second)));
+        }
+    }
+    Ok(())
+}
+
+impl Component {
+    /// Maximum or exact length, and whether it is exact
+    pub fn length(&self) -> (usize, bool) {
+        match *self {
+            Numeric(n) | Alphanumeric(n) => (n, true),
+            NumericUpTo(n) | AlphanumericUpTo(n) | Cset39UpTo(n) => (n, false),
+        }
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Numeric(_) | NumericUpTo(_))
+    }
+
+    /// Whether the component's character set contains `c`
+    pub fn allows(&self, c: char) -> bool {
+        match self {
+            Numeric(_) | NumericUpTo(_) => c.is_ascii_digit(),
+            Alphanumeric(_) | AlphanumericUpTo(_) => is_cset82(c),
+            Cset39UpTo(_) => c.is_ascii_digit() || c.is_ascii_uppercase() || "#-/".contains(c),
+        }
+    }
+}
+
+/// GS1 AI encodable character set 82
+pub fn is_cset82(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!\"%&'()*+,-./:;<=>?_".contains(c)
+}
+
+/// `YYMMDD` at the start of a value
+fn check_date(ai: &str, value: &str, day_zero: bool) -> Result<()> {
+    let field = |range: std::ops::Range<usize>| -> u32 {
+        value
+            .get(range)
+            .and_then(|digits| digits.parse().ok())
+            .unwrap_or(u32::MAX)
+    };
+    let (year, month, day) = (field(0..2), field(2..4), field(4..6));
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 => 29,
+        2 => 28,
+        _ => 0,
+    };
+    if days == 0 || day > days || (day == 0 && !day_zero) {
+        return Err(invalid(ai, &format!("'{}' is not a valid date", value)));
+    }
+    Ok(())
+}
+
+fn invalid(ai: &str, reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid GS1 AI ({}): {}", ai, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ai_lookup() {
+        assert_eq!(lookup("01").unwrap().title, "GTIN");
+        assert_eq!(lookup("3103").unwrap().ai, "310");
+        assert_eq!(lookup("7031").unwrap().ai, "703");
+        assert!(lookup("310").is_none());
+        assert!(lookup("4103").is_none());
+        assert!(lookup("14").is_none());
+        assert!(lookup("0A").is_none());
+
+        assert_eq!(ai_length("01"), Some(2));
+        assert_eq!(ai_length("41"), Some(3));
+        assert_eq!(ai_length("71"), Some(3));
+        assert_eq!(ai_length("80"), Some(4));
+        assert_eq!(ai_length("3"), None);
+        assert_eq!(ai_length("50"), None);
+
+        assert_eq!(predefined_length("01"), Some(14));
+        assert_eq!(predefined_length("3103"), Some(6));
+        assert_eq!(predefined_length("10"), None);
+        assert_eq!(predefined_length("422"), None);
+        assert_eq!(decimal_places("3103"), Some(3));
+        assert_eq!(decimal_places("3925"), Some(5));
+        assert_eq!(decimal_places("37"), None);
+    }
+
+    #[test]
+    fn test_ai_validate() {
+        assert!(validate("01", "09520123456788").is_ok());
+        assert!(validate("01", "09520123456787").is_err());
+        assert!(validate("01", "0952012345678").is_err());
+        assert!(validate("01", "0952012345678A").is_err());
+        assert!(validate("00", "395123451234567895").is_ok());
+        assert!(validate("414", "9520123456788").is_ok());
+        assert!(validate("414", "9520123456789").is_err());
+        assert!(validate("10", "ABC-123").is_ok());
+        assert!(validate("10", "").is_err());
+        assert!(validate("10", &"A".repeat(21)).is_err());
+        assert!(validate("10", "AB C").is_err());
+        assert!(validate("253", "9520123456788ABC").is_ok());
+        assert!(validate("253", "952012345678").is_err());
+        assert!(validate("7040", "1A2B").is_ok());
+        assert!(validate("8010", "ABC#1").is_ok());
+        assert!(validate("8010", "abc").is_err());
+        assert!(validate("3103", "000195").is_ok());
+        assert!(validate("3106", "000195").is_err());
+        assert!(validate("3929", "1995").is_ok());
+        assert!(validate("14", "x").is_err());
+    }
+
+    #[test]
+    fn test_ai_dates() {
+        assert!(validate("17", "251231").is_ok());
+        assert!(validate("17", "250200").is_ok());
+        assert!(validate("7006", "250200").is_err());
+        assert!(validate("17", "250229").is_err());
+        assert!(validate("17", "240229").is_ok());
+        assert!(validate("17", "251301").is_err());
+        assert!(validate("17", "250431").is_err());
+        assert!(validate("7003", "2512312359").is_ok());
+        assert!(validate("7003", "2512312460").is_err());
+        assert!(validate("8008", "25123123").is_ok());
+        assert!(validate("8008", "2512312359").is_ok());
+        assert!(validate("8008", "251231235").is_err());
+        assert!(validate("7250", "19991231").is_ok());
+        assert!(validate("7250", "19991232").is_err());
+    }
+
+    #[test]
+    fn test_ai_associations() {
+        let elements = |data: &[(&str, &str)]| -> Vec<Element> {
+            data.iter()
+                .map(|(ai, value)| Element::new(ai, value))
+                .collect()
+        };
+        let gtin = ("01", "09520123456788");
+        assert!(validate_elements(&elements(&[gtin, ("10", "A1"), ("17", "251231")])).is_ok());
+        assert!(validate_elements(&elements(&[("10", "A1")])).is_err());
+        assert!(validate_elements(&elements(&[gtin, ("250", "X")])).is_err());
+        assert!(validate_elements(&elements(&[gtin, ("21", "1"), ("250", "X")])).is_ok());
+        assert!(validate_elements(&elements(&[("02", "09520123456788"), ("37", "12")])).is_ok());
+        assert!(validate_elements(&elements(&[("02", "09520123456788")])).is_err());
+        assert!(validate_elements(&elements(&[gtin, ("02", "09520123456788")])).is_err());
+        assert!(validate_elements(&elements(&[gtin, ("3103", "000195")])).is_ok());
+        assert!(validate_elements(&elements(&[gtin, ("10", "A"), ("10", "B")])).is_err());
+        assert!(validate_elements(&elements(&[gtin, ("10", "A"), ("10", "A")])).is_ok());
+    }
+}
@@ -0,0 +1,782 @@
+//! GS1 Digital Link URIs (GS1 Digital Link Standard: URI Syntax)
+//!
+//! A Digital Link URI carries GS1 identifiers on the web: the primary key
+//! and its qualifiers as path segments (`/01/{gtin}/10/{lot}/21/{serial}`)
+//! and other AIs as query parameters (`?17=251231`), under any domain. The
+//! compressed form packs the same AIs into a binary string written as
+//! URI-safe base64, for smaller QR codes.
+
+use super::ai::{self, Component};
+use super::Element;
+use crate::generators;
+use crate::payloads::escape::{percent_decode, percent_encode};
+use crate::types::{Barcode, BarcodeType, QuickCodesError, Result};
+
+/// Domain of the GS1 resolver, used when none is configured
+pub const DEFAULT_DOMAIN: &str = "https://id.gs1.org";
+
+/// A primary key AI and the key qualifiers allowed after it, in path order
+type PrimaryKey = (&'static str, &'static [&'static str]);
+
+const PRIMARY_KEYS: &[PrimaryKey] = &[
+    ("01", &["22", "10", "21", "235"]),
+    ("8006", &["22", "10", "21"]),
+    ("8010", &["8011"]),
+    ("414", &["254"]),
+    ("417", &["7040"]),
+    ("8017", &["8019"]),
+    ("8018", &["8019"]),
+    ("00", &[]),
+    ("253", &[]),
+    ("255", &[]),
+    ("401", &[]),
+    ("402", &[]),
+    ("8003", &[]),
+    ("8004", &[]),
+    ("8013", &[]),
+];
+
+/// URI-safe base64 alphabet (RFC 4648 section 5)
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// An optimization code and the AIs it stands for
+type Optimization = (u8, &'static [&'static str]);
+
+/// Optimization codes of compressed URIs: an 8-bit code replaces the AIs
+/// of a common sequence, whose values follow in order
+const OPTIMIZATIONS: &[Optimization] = &[
+    (0x0A, &["01", "22"]),
+    (0x0B, &["01", "10"]),
+    (0x0C, &["01", "21"]),
+    (0x0D, &["01", "17"]),
+    (0x0E, &["01", "7003"]),
+    (0x0F, &["01", "30"]),
+];
+
+// Encodings of alphanumeric values in compressed URIs
+const ENCODING_NUMERIC: u8 = 0;
+const ENCODING_LOWER_HEX: u8 = 1;
+const ENCODING_UPPER_HEX: u8 = 2;
+const ENCODING_BASE64URL: u8 = 3;
+const ENCODING_ASCII: u8 = 4;
+
+/// A GS1 Digital Link: domain, AIs and other query parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigitalLink {
+    /// Scheme, host and optional path prefix, e.g. `https://id.gs1.org`
+    pub domain: String,
+    /// Primary key, then its qualifiers in path order, then data attributes
+    pub elements: Vec<Element>,
+    /// Query parameters that are not AIs
+    pub extra: Vec<(String, String)>,
+}
+
+/// Elements split by their place in the URI
+struct Parts<'a> {
+    path: Vec<&'a Element>,
+    query: Vec<&'a Element>,
+}
+
+impl DigitalLink {
+    /// Link under the GS1 resolver domain; the elements are validated and
+    /// put in URI order
+    pub fn new(elements: Vec<Element>) -> Result<Self> {
+        let mut link = Self {
+            domain: DEFAULT_DOMAIN.to_string(),
+            elements,
+            extra: Vec::new(),
+        };
+        let parts = link.parts()?;
+        link.elements = parts.path.into_iter().chain(parts.query).cloned().collect();
+        Ok(link)
+    }
+
+    /// Link from a bracketed element string such as
+    /// `(01)09520123456788(10)ABC123(17)251231`
+    pub fn from_element_string(data: &str) -> Result<Self> {
+        Self::new(super::parse_bracketed(data)?)
+    }
+
+    /// Use another domain, e.g. `https://brand.example.com/dl`
+    pub fn with_domain(mut self, domain: &str) -> Self {
+        self.domain = domain.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Bracketed element string of the AIs
+    pub fn element_string(&self) -> String {
+        super::format_hri(&self.elements)
+    }
+
+    /// Uncompressed URI: key and qualifiers in the path, attributes in the
+    /// query
+    pub fn uri(&self) -> Result<String> {
+        let parts = self.parts()?;
+        let mut uri = self.base()?.to_string();
+        for element in &parts.path {
+            uri.push_str(&format!(
+                "/{}/{}",
+                element.ai,
+                percent_encode(&element.value)
+            ));
+        }
+        let query: Vec<(&str, &str)> = parts
+            .query
+            .iter()
+            .map(|element| (element.ai.as_str(), element.value.as_str()))
+            .collect();
+        uri.push_str(&self.query(&query));
+        Ok(uri)
+    }
+
+    /// Compressed URI: all AIs in one path segment; other query parameters
+    /// stay uncompressed
+    pub fn compressed_uri(&self) -> Result<String> {
+        let parts = self.parts()?;
+        let mut elements: Vec<&Element> = parts.path.into_iter().chain(parts.query).collect();
+        let mut bits = Vec::new();
+        // Longest optimized sequence first, the first in the table of equal
+        // ones
+        while let Some((code, sequence)) = OPTIMIZATIONS
+            .iter()
+            .rev()
+            .filter(|(_, sequence)| {
+                sequence
+                    .iter()
+                    .all(|ai| elements.iter().any(|element| element.ai == *ai))
+            })
+            .max_by_key(|(_, sequence)| sequence.len())
+        {
+            push_bits(&mut bits, u32::from(*code), 8);
+            for ai in sequence.iter() {
+                let position = elements
+                    .iter()
+                    .position(|element| element.ai == *ai)
+                    .expect("sequence AIs are present");
+                compress_value(elements.remove(position), &mut bits)?;
+            }
+        }
+        for element in elements {
+            for digit in element.ai.bytes() {
+                push_bits(&mut bits, u32::from(digit - b'0'), 4);
+            }
+            compress_value(element, &mut bits)?;
+        }
+        Ok(format!(
+            "{}/{}{}",
+            self.base()?,
+            to_base64url(&bits),
+            self.query(&[])
+        ))
+    }
+
+    /// Parse an uncompressed or compressed Digital Link URI
+    pub fn parse(uri: &str) -> Result<Self> {
+        let uri = uri.trim();
+        let uri = uri.split_once('#').map_or(uri, |(uri, _)| uri);
+        let (location, query) = uri.split_once('?').unwrap_or((uri, ""));
+        let scheme_end = location
+            .find("://")
+            .filter(|_| location.starts_with("http://") || location.starts_with("https://"))
+            .ok_or_else(|| invalid("the URI must start with http:// or https://"))?;
+        let (host, path) = location[scheme_end + 3..]
+            .split_once('/')
+            .ok_or_else(|| invalid("the URI has no path"))?;
+        let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+
+        let (prefix, mut elements) = match find_primary_key(&segments) {
+            Some(start) => {
+                let mut elements = Vec::new();
+                for pair in segments[start..].chunks(2) {
+                    let value = percent_decode(pair[1])
+                        .ok_or_else(|| invalid("bad percent-encoding in the path"))?;
+                    elements.push(Element::new(pair[0], &value));
+                }
+                (&segments[..start], elements)
+            }
+            None => {
+                let (last, prefix) = segments
+                    .split_last()
+                    .filter(|(last, _)| !last.is_empty())
+                    .ok_or_else(|| invalid("no primary key in the path"))?;
+                (prefix, decompress(last)?)
+            }
+        };
+
+        let mut extra = Vec::new();
+        for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+            let (key, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+            let value = percent_decode(value)
+                .ok_or_else(|| invalid("bad percent-encoding in the query"))?;
+            if ai::lookup(key).is_some() && key.bytes().all(|b| b.is_ascii_digit()) {
+                elements.push(Element::new(key, &value));
+            } else {
+                let key = percent_decode(key)
+                    .ok_or_else(|| invalid("bad percent-encoding in the query"))?;
+                extra.push((key, value));
+            }
+        }
+
+        // GTIN-8, -12 and -13 are padded to 14 digits
+        for element in elements.iter_mut().filter(|element| element.ai == "01") {
+            if matches!(element.value.len(), 8 | 12 | 13) {
+                element.value = format!("{:0>14}", element.value);
+            }
+        }
+
+        let mut domain = format!("{}{}", &location[..scheme_end + 3], host);
+        for segment in prefix {
+            domain.push('/');
+            domain.push_str(segment);
+        }
+        let mut link = Self::new(elements)?.with_domain(&domain);
+        link.extra = extra;
+        Ok(link)
+    }
+
+    /// Domain without a trailing slash, checked for a scheme
+    fn base(&self) -> Result<&str> {
+        let domain = self.domain.trim_end_matches('/');
+        let host = domain
+            .strip_prefix("https://")
+            .or_else(|| domain.strip_prefix("http://"))
+            .ok_or_else(|| invalid("the domain must start with http:// or https://"))?;
+        if host.is_empty() || host.contains(['?', '#']) {
+            return Err(invalid(&format!("'{}' is not a domain", self.domain)));
+        }
+        Ok(domain)
+    }
+
+    /// Query string of AIs and the extra parameters, with its `?`
+    fn query(&self, elements: &[(&str, &str)]) -> String {
+        let parameters: Vec<String> = elements
+            .iter()
+            .copied()
+            .chain(
+                self.extra
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str())),
+            )
+            .map(|(key, value)| format!("{}={}", percent_encode(key), percent_encode(value)))
+            .collect();
+        if parameters.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", parameters.join("&"))
+        }
+    }
+
+    /// Validate the elements and split them into path and query
+    fn parts(&self) -> Result<Parts<'_>> {
+        ai::validate_elements(&self.elements)?;
+        for (i, element) in self.elements.iter().enumerate() {
+            if self.elements[..i]
+                .iter()
+                .any(|other| other.ai == element.ai)
+            {
+                return Err(invalid(&format!("AI ({}) appears twice", element.ai)));
+            }
+        }
+        let (key, qualifiers) = self
+            .elements
+            .iter()
+            .find_map(|element| {
+                PRIMARY_KEYS
+                    .iter()
+                    .find(|(key, _)| *key == element.ai)
+                    .map(|(_, qualifiers)| (element, *qualifiers))
+            })
+            .ok_or_else(|| invalid("no primary key such as a GTIN (01) or SSCC (00)"))?;
+
+        let mut path = vec![key];
+        path.extend(qualifiers.iter().filter_map(|qualifier| {
+            self.elements
+                .iter()
+                .find(|element| element.ai == *qualifier)
+        }));
+        let query = self
+            .elements
+            .iter()
+            .filter(|element| !path.iter().any(|other| other.ai == element.ai))
+            .collect();
+        Ok(Parts { path, query })
+    }
+}
+
+/// Generate a QR Code or DataMatrix from a Digital Link URI, after checking
+/// that it parses
+pub fn generate_digital_link(uri: &str, barcode_type: BarcodeType) -> Result<Barcode> {
+    DigitalLink::parse(uri)?;
+    match barcode_type {
+        BarcodeType::QRCode => generators::generate_qr(uri),
+        BarcodeType::DataMatrix => generators::generate_datamatrix(uri),
+        _ => Err(QuickCodesError::InvalidType(format!(
+            "GS1 Digital Link is not defined for {:?}; use QRCode or DataMatrix",
+            barcode_type
+        ))),
+    }
+}
+
+/// Index of the primary key in the path segments: a key followed only by
+/// its qualifiers, in order, each with a value
+fn find_primary_key(segments: &[&str]) -> Option<usize> {
+    (0..segments.len()).find(|&start| {
+        let Some((_, qualifiers)) = PRIMARY_KEYS.iter().find(|(key, _)| *key == segments[start])
+        else {
+            return false;
+        };
+        if (segments.len() - start) % 2 != 0 {
+            return false;
+        }
+        let mut allowed = qualifiers.iter();
+        segments[start + 2..]
+            .chunks(2)
+            .all(|pair| allowed.any(|qualifier| *qualifier == pair[0]))
+    })
+}
+
+/// Append the value of an element, component by component: fixed numeric
+/// parts in binary, variable numeric parts with their length first, and
+/// alphanumeric parts with an encoding, their length if variable and the
+/// encoded characters
+fn compress_value(element: &Element, bits: &mut Vec<bool>) -> Result<()> {
+    let definition = ai::validate(&element.ai, &element.value)?;
+    let mut rest = element.value.as_str();
+    for component in definition.format {
+        let (length, fixed) = component.length();
+        let part = if fixed { &rest[..length] } else { rest };
+        match component {
+            Component::Numeric(_) => {
+                bits.extend(decimal_to_bits(part, numeric_bits(length)));
+            }
+            Component::NumericUpTo(_) => {
+                push_bits(bits, part.len() as u32, length_bits(length));
+                bits.extend(decimal_to_bits(part, numeric_bits(part.len())));
+            }
+            _ => {
+                let encoding = choose_encoding(part);
+                push_bits(bits, u32::from(encoding), 3);
+                if !fixed {
+                    push_bits(bits, part.len() as u32, length_bits(length));
+                }
+                encode_characters(part, encoding, bits);
+            }
+        }
+        rest = &rest[part.len()..];
+    }
+    Ok(())
+}
+
+/// Elements of a compressed path segment
+fn decompress(segment: &str) -> Result<Vec<Element>> {
+    let mut bits = Vec::with_capacity(segment.len() * 6);
+    for c in segment.bytes() {
+        let value = BASE64URL
+            .iter()
+            .position(|&b| b == c)
+            .ok_or_else(|| invalid(&format!("'{}' is neither a path nor compressed", segment)))?;
+        push_bits(&mut bits, value as u32, 6);
+    }
+
+    let mut reader = BitReader {
+        bits: &bits,
+        position: 0,
+    };
+    let mut elements = Vec::new();
+    // Fewer than 8 bits left is padding
+    while bits.len() - reader.position >= 8 {
+        let prefix = reader.read(8)?;
+        if prefix >> 4 > 9 || prefix & 0xF > 9 {
+            let (_, sequence) = OPTIMIZATIONS
+                .iter()
+                .find(|(code, _)| u32::from(*code) == prefix)
+                .ok_or_else(|| invalid(&format!("unknown optimization code {:02X}", prefix)))?;
+            for ai in sequence.iter() {
+                elements.push(reader.element(ai)?);
+            }
+            continue;
+        }
+
+        let mut ai = format!("{:02X}", prefix);
+        let length =
+            ai::ai_length(&ai).ok_or_else(|| invalid(&format!("unknown AI prefix {}", ai)))?;
+        while ai.len() < length {
+            ai.push(reader.digit()?);
+        }
+        elements.push(reader.element(&ai)?);
+    }
+    if elements.is_empty() {
+        return Err(invalid("the compressed URI holds no AIs"));
+    }
+    Ok(elements)
+}
+
+/// Smallest encoding that holds every character of a value
+fn choose_encoding(value: &str) -> u8 {
+    let all = |allowed: fn(u8) -> bool| value.bytes().all(allowed);
+    if all(|b| b.is_ascii_digit()) {
+        ENCODING_NUMERIC
+    } else if all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)) {
+        ENCODING_LOWER_HEX
+    } else if all(|b| b.is_ascii_digit() || (b'A'..=b'F').contains(&b)) {
+        ENCODING_UPPER_HEX
+    } else if all(|b| BASE64URL.contains(&b)) {
+        ENCODING_BASE64URL
+    } else {
+        ENCODING_ASCII
+    }
+}
+
+fn encode_characters(value: &str, encoding: u8, bits: &mut Vec<bool>) {
+    match encoding {
+        ENCODING_NUMERIC => bits.extend(decimal_to_bits(value, numeric_bits(value.len()))),
+        ENCODING_LOWER_HEX | ENCODING_UPPER_HEX => {
+            for c in value.chars() {
+                push_bits(bits, c.to_digit(16).unwrap_or(0), 4);
+            }
+        }
+        ENCODING_BASE64URL => {
+            for b in value.bytes() {
+                let index = BASE64URL.iter().position(|&c| c == b).unwrap_or(0);
+                push_bits(bits, index as u32, 6);
+            }
+        }
+        _ => {
+            for b in value.bytes() {
+                push_bits(bits, u32::from(b), 7);
+            }
+        }
+    }
+}
+
+struct BitReader<'a> {
+    bits: &'a [bool],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn take(&mut self, count: usize) -> Result<&[bool]> {
+        let bits = self
+            .bits
+            .get(self.position..self.position + count)
+            .ok_or_else(|| invalid("the compressed URI is truncated"))?;
+        self.position += count;
+        Ok(bits)
+    }
+
+    fn read(&mut self, count: usize) -> Result<u32> {
+        Ok(self
+            .take(count)?
+            .iter()
+            .fold(0, |value, &bit| value << 1 | u32::from(bit)))
+    }
+
+    fn digit(&mut self) -> Result<char> {
+        let value = self.read(4)?;
+        char::from_digit(value, 10).ok_or_else(|| invalid("AI digit out of range"))
+    }
+
+    /// Value of AI `ai`, read component by component
+    fn element(&mut self, ai: &str) -> Result<Element> {
+        let definition = ai::lookup(ai)
+            .ok_or_else(|| invalid(&format!("unsupported AI ({}) in compressed URI", ai)))?;
+        let mut value = String::new();
+        for component in definition.format {
+            let (length, fixed) = component.length();
+            match component {
+                Component::Numeric(_) => {
+                    value.push_str(&self.decimal(length, numeric_bits(length))?);
+                }
+                Component::NumericUpTo(_) => {
+                    let count = self.read(length_bits(length))? as usize;
+                    value.push_str(&self.decimal(count, numeric_bits(count))?);
+                }
+                _ => {
+                    let encoding = self.read(3)? as u8;
+                    let count = if fixed {
+                        length
+                    } else {
+                        self.read(length_bits(length))? as usize
+                    };
+                    value.push_str(&self.characters(encoding, count)?);
+                }
+            }
+        }
+        ai::validate(ai, &value)?;
+        Ok(Element::new(ai, &value))
+    }
+
+    fn decimal(&mut self, length: usize, width: usize) -> Result<String> {
+        let bits = self.take(width)?;
+        bits_to_decimal(bits, length).ok_or_else(|| invalid("numeric value out of range"))
+    }
+
+    fn characters(&mut self, encoding: u8, count: usize) -> Result<String> {
+        match encoding {
+            ENCODING_NUMERIC => self.decimal(count, numeric_bits(count)),
+            ENCODING_LOWER_HEX | ENCODING_UPPER_HEX => (0..count)
+                .map(|_| {
+                    let c = char::from_digit(self.read(4)?, 16).unwrap_or('0');
+                    Ok(if encoding == ENCODING_UPPER_HEX {
+                        c.to_ascii_uppercase()
+                    } else {
+                        c
+                    })
+                })
+                .collect(),
+            ENCODING_BASE64URL => (0..count)
+                .map(|_| Ok(char::from(BASE64URL[self.read(6)? as usize])))
+                .collect(),
+            ENCODING_ASCII => (0..count)
+                .map(|_| Ok(char::from(self.read(7)? as u8)))
+                .collect(),
+            _ => Err(invalid(&format!("unknown encoding {}", encoding))),
+        }
+    }
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: u32, count: usize) {
+    bits.extend((0..count).rev().map(|i| value >> i & 1 == 1));
+}
+
+/// Bits for a number of `digits` decimal digits: ceil(digits * log2(10))
+fn numeric_bits(digits: usize) -> usize {
+    (digits as f64 * std::f64::consts::LOG2_10).ceil() as usize
+}
+
+/// Bits of the length indicator of a value of up to `max` characters
+fn length_bits(max: usize) -> usize {
+    (usize::BITS - max.leading_zeros()) as usize
+}
+
+/// Decimal digits as a `width`-bit binary number
+fn decimal_to_bits(digits: &str, width: usize) -> Vec<bool> {
+    // Little-endian 32-bit limbs
+    let mut limbs: Vec<u32> = vec![0];
+    for digit in digits.bytes() {
+        let mut carry = u64::from(digit - b'0');
+        for limb in limbs.iter_mut() {
+            let value = u64::from(*limb) * 10 + carry;
+            *limb = value as u32;
+            carry = value >> 32;
+        }
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+    }
+    (0..width)
+        .rev()
+        .map(|i| {
+            limbs
+                .get(i / 32)
+                .is_some_and(|limb| limb >> (i % 32) & 1 == 1)
+        })
+        .collect()
+}
+
+/// Binary number as `length` decimal digits; `None` if it needs more
+fn bits_to_decimal(bits: &[bool], length: usize) -> Option<String> {
+    let mut limbs = vec![0u32; bits.len() / 32 + 1];
+    for (i, &bit) in bits.iter().rev().enumerate() {
+        if bit {
+            limbs[i / 32] |= 1 << (i % 32);
+        }
+    }
+    let mut digits = vec![b'0'; length];
+    for digit in digits.iter_mut().rev() {
+        let mut remainder = 0u64;
+        for limb in limbs.iter_mut().rev() {
+            let value = remainder << 32 | u64::from(*limb);
+            *limb = (value / 10) as u32;
+            remainder = value % 10;
+        }
+        *digit = b'0' + remainder as u8;
+    }
+    if limbs.iter().any(|&limb| limb != 0) {
+        return None;
+    }
+    String::from_utf8(digits).ok()
+}
+
+/// Bits padded with zeros to whole base64 characters
+fn to_base64url(bits: &[bool]) -> String {
+    bits.chunks(6)
+        .map(|chunk| {
+            let value = (0..6).fold(0, |value, i| {
+                value << 1 | usize::from(chunk.get(i).copied().unwrap_or(false))
+            });
+            char::from(BASE64URL[value])
+        })
+        .collect()
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid GS1 Digital Link: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digital_link_uri() {
+        let link =
+            DigitalLink::from_element_string("(01)09520123456788(17)251231(10)ABC/123(21)12345")
+                .unwrap();
+        assert_eq!(
+            link.uri().unwrap(),
+            "https://id.gs1.org/01/09520123456788/10/ABC%2F123/21/12345?17=251231"
+        );
+        assert_eq!(
+            link.element_string(),
+            "(01)09520123456788(10)ABC/123(21)12345(17)251231"
+        );
+
+        let mut link = link.with_domain("https://brand.example.com/dl/");
+        link.extra
+            .push(("linkType".to_string(), "gs1:pip".to_string()));
+        let uri = link.uri().unwrap();
+        assert!(uri.starts_with("https://brand.example.com/dl/01/09520123456788/10/"));
+        assert!(uri.ends_with("?17=251231&linkType=gs1%3Apip"));
+        assert_eq!(DigitalLink::parse(&uri).unwrap(), link);
+    }
+
+    #[test]
+    fn test_digital_link_parse() {
+        let link = DigitalLink::parse("https://example.com/01/9520123456788/21/ABC?3103=000195#x")
+            .unwrap();
+        assert_eq!(link.domain, "https://example.com");
+        assert_eq!(
+            link.elements,
+            vec![
+                Element::new("01", "09520123456788"),
+                Element::new("21", "ABC"),
+                Element::new("3103", "000195"),
+            ]
+        );
+
+        // Path prefixes may contain AI-like segments
+        let link = DigitalLink::parse("https://example.com/00/products/414/9520123456788/254/32a")
+            .unwrap();
+        assert_eq!(link.domain, "https://example.com/00/products");
+        assert_eq!(link.elements[1], Element::new("254", "32a"));
+
+        assert!(DigitalLink::parse("ftp://example.com/01/09520123456788").is_err());
+        assert!(DigitalLink::parse("https://example.com/10/ABC").is_err());
+        assert!(DigitalLink::parse("https://example.com/01/0952012345678X").is_err());
+        // Qualifiers out of order
+        assert!(DigitalLink::parse("https://example.com/01/09520123456788/21/1/10/2").is_err());
+    }
+
+    #[test]
+    fn test_digital_link_compression() {
+        let link = DigitalLink::from_element_string(
+            "(01)09520123456788(10)abc123(21)A-1_b(17)251231(3103)000195(253)9520123456788XY!",
+        )
+        .unwrap()
+        .with_domain("https://example.com");
+        let compressed = link.compressed_uri().unwrap();
+        assert!(compressed.len() < link.uri().unwrap().len());
+        let segment = compressed.rsplit('/').next().unwrap();
+        assert!(segment.bytes().all(|b| BASE64URL.contains(&b)));
+        assert_eq!(DigitalLink::parse(&compressed).unwrap(), link);
+
+        // GTIN alone: AI (8 bits) and 14 digits (47 bits), padded to 60 bits
+        let link = DigitalLink::from_element_string("(01)09520123456788").unwrap();
+        let compressed = link.compressed_uri().unwrap();
+        assert_eq!(compressed.len(), DEFAULT_DOMAIN.len() + 1 + 10);
+        assert_eq!(DigitalLink::parse(&compressed).unwrap(), link);
+
+        let mut link = link;
+        link.extra.push(("lang".to_string(), "pt".to_string()));
+        assert!(link.compressed_uri().unwrap().ends_with("?lang=pt"));
+        assert_eq!(
+            DigitalLink::parse(&link.compressed_uri().unwrap()).unwrap(),
+            link
+        );
+
+        assert!(DigitalLink::parse("https://example.com/AB.C").is_err());
+    }
+
+    #[test]
+    fn test_digital_link_compression_bits() {
+        let compressed = |data: &str| {
+            DigitalLink::from_element_string(data)
+                .unwrap()
+                .compressed_uri()
+                .unwrap()
+        };
+
+        // Optimization code 0B for (01)(10), the GTIN in 47 bits, then
+        // upper-case hex encoding 010, length 6 in 5 bits and six nibbles
+        let uri = compressed("(01)09520123456788(10)ABC123");
+        assert_eq!(uri, "https://id.gs1.org/CxFRJydaKI1XgkY");
+        assert_eq!(
+            DigitalLink::parse(&uri).unwrap().element_string(),
+            "(01)09520123456788(10)ABC123"
+        );
+
+        // Code 0F for (01)(30); the count has a 4-bit length and 7 bits of
+        // value instead of an encoding indicator
+        let uri = compressed("(01)09520123456788(30)12");
+        assert_eq!(uri, "https://id.gs1.org/DxFRJydaKEM");
+        assert_eq!(
+            DigitalLink::parse(&uri).unwrap().element_string(),
+            "(01)09520123456788(30)12"
+        );
+
+        // Code 0D for (01)(17), then (3103) with its AI digits
+        let uri = compressed("(01)09520123456788(17)251231(3103)000195");
+        assert_eq!(uri, "https://id.gs1.org/DRFRJydaKHqr5iBgAYY");
+
+        // No optimization for an SSCC: AI digits and 60 bits
+        assert_eq!(
+            compressed("(00)306141411234567891"),
+            "https://id.gs1.org/AEP6H906xW0w"
+        );
+
+        // Of equal sequences the first in the table is used: 0A for
+        // (01)(22), then (10) with its AI digits
+        let uri = compressed("(01)09520123456788(22)2A(10)ABC");
+        assert_eq!(uri, "https://id.gs1.org/ChFRJydaKIRUIIdXg");
+        assert_eq!(
+            DigitalLink::parse(&uri).unwrap().element_string(),
+            "(01)09520123456788(22)2A(10)ABC"
+        );
+
+        // Unknown optimization code 1A
+        assert!(DigitalLink::parse("https://id.gs1.org/GhFRJydaKEM").is_err());
+    }
+
+    #[test]
+    fn test_decimal_bits() {
+        assert_eq!(numeric_bits(1), 4);
+        assert_eq!(numeric_bits(14), 47);
+        assert_eq!(numeric_bits(18), 60);
+        let bits = decimal_to_bits("09520123456788", 47);
+        assert_eq!(bits_to_decimal(&bits, 14).unwrap(), "09520123456788");
+        let digits = "9".repeat(30);
+        let bits = decimal_to_bits(&digits, numeric_bits(30));
+        assert_eq!(bits_to_decimal(&bits, 30).unwrap(), digits);
+        assert!(bits_to_decimal(&[true; 8], 2).is_none());
+    }
+
+    #[test]
+    fn test_generate_digital_link() {
+        let uri = "https://id.gs1.org/01/09520123456788";
+        assert_eq!(
+            generate_digital_link(uri, BarcodeType::QRCode)
+                .unwrap()
+                .barcode_type,
+            BarcodeType::QRCode
+        );
+        assert_eq!(
+            generate_digital_link(uri, BarcodeType::DataMatrix)
+                .unwrap()
+                .barcode_type,
+            BarcodeType::DataMatrix
+        );
+        assert!(generate_digital_link(uri, BarcodeType::EAN13).is_err());
+        assert!(generate_digital_link("https://example.com", BarcodeType::QRCode).is_err());
+    }
+}
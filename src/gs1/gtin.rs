@@ -0,0 +1,458 @@
+//! Global Trade Item Numbers: GTIN-8, GTIN-12 (UPC-A), GTIN-13 (EAN-13)
+//! and GTIN-14 (ITF-14 case codes)
+//!
+//! Every GTIN fits in 14 digits by padding with leading zeros, which is the
+//! form carried in AI (01) and used here to compare and convert them. The
+//! last digit is the GS1 modulo-10 check digit ([`check_digit`]).
+
+use super::check_digit;
+use crate::types::{QuickCodesError, Result};
+
+/// GTIN length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GtinFormat {
+    /// EAN-8
+    Gtin8,
+    /// UPC-A
+    Gtin12,
+    /// EAN-13
+    Gtin13,
+    /// ITF-14 and AI (01)
+    Gtin14,
+}
+
+impl GtinFormat {
+    /// Number of digits, check digit included
+    pub fn length(self) -> usize {
+        match self {
+            GtinFormat::Gtin8 => 8,
+            GtinFormat::Gtin12 => 12,
+            GtinFormat::Gtin13 => 13,
+            GtinFormat::Gtin14 => 14,
+        }
+    }
+
+    pub fn from_length(length: usize) -> Option<Self> {
+        match length {
+            8 => Some(GtinFormat::Gtin8),
+            12 => Some(GtinFormat::Gtin12),
+            13 => Some(GtinFormat::Gtin13),
+            14 => Some(GtinFormat::Gtin14),
+            _ => None,
+        }
+    }
+}
+
+/// What a GS1 prefix is allocated to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixUsage {
+    /// Company prefixes issued by the GS1 member organisation of a country
+    MemberOrganisation(&'static str),
+    /// Restricted circulation within a company or a geographic region,
+    /// including variable measure items (020-029, 040-049, 200-299)
+    RestrictedCirculation,
+    /// Coupons (050-059, 981-984, 990-999)
+    Coupon,
+    /// Bookland ISBN (978-979)
+    Isbn,
+    /// Serial publications ISSN (977)
+    Issn,
+    /// Refund receipts (980)
+    RefundReceipt,
+    /// GS1 Global Office, EPC and demonstration numbers
+    GlobalOffice,
+    /// Not allocated
+    Unassigned,
+}
+
+/// GS1 prefix of a GTIN and the range of the prefix table it falls in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gs1Prefix {
+    /// First three digits of the GTIN-13 form
+    pub prefix: u16,
+    pub start: u16,
+    pub end: u16,
+    pub usage: PrefixUsage,
+}
+
+use PrefixUsage::{
+    Coupon, GlobalOffice, Isbn, Issn, MemberOrganisation as Mo, RefundReceipt,
+    RestrictedCirculation,
+};
+
+/// First and last prefix of a range and its usage
+type PrefixRange = (u16, u16, PrefixUsage);
+
+/// GS1 prefix ranges; the gaps are unassigned
+const PREFIXES: &[PrefixRange] = &[
+    (0, 19, Mo("United States")),
+    (20, 29, RestrictedCirculation),
+    (30, 39, Mo("United States")),
+    (40, 49, RestrictedCirculation),
+    (50, 59, Coupon),
+    (60, 139, Mo("United States")),
+    (200, 299, RestrictedCirculation),
+    (300, 379, Mo("France")),
+    (380, 380, Mo("Bulgaria")),
+    (383, 383, Mo("Slovenia")),
+    (385, 385, Mo("Croatia")),
+    (387, 387, Mo("Bosnia and Herzegovina")),
+    (389, 389, Mo("Montenegro")),
+    (390, 390, Mo("Kosovo")),
+    (400, 440, Mo("Germany")),
+    (450, 459, Mo("Japan")),
+    (460, 469, Mo("Russia")),
+    (470, 470, Mo("Kyrgyzstan")),
+    (471, 471, Mo("Taiwan")),
+    (474, 474, Mo("Estonia")),
+    (475, 475, Mo("Latvia")),
+    (476, 476, Mo("Azerbaijan")),
+    (477, 477, Mo("Lithuania")),
+    (478, 478, Mo("Uzbekistan")),
+    (479, 479, Mo("Sri Lanka")),
+    (480, 480, Mo("Philippines")),
+    (481, 481, Mo("Belarus")),
+    (482, 482, Mo("Ukraine")),
+    (483, 483, Mo("Turkmenistan")),
+    (484, 484, Mo("Moldova")),
+    (485, 485, Mo("Armenia")),
+    (486, 486, Mo("Georgia")),
+    (487, 487, Mo("Kazakhstan")),
+    (488, 488, Mo("Tajikistan")),
+    (489, 489, Mo("Hong Kong")),
+    (490, 499, Mo("Japan")),
+    (500, 509, Mo("United Kingdom")),
+    (520, 521, Mo("Greece")),
+    (528, 528, Mo("Lebanon")),
+    (529, 529, Mo("Cyprus")),
+    (530, 530, Mo("Albania")),
+    (531, 531, Mo("North Macedonia")),
+    (535, 535, Mo("Malta")),
+    (539, 539, Mo("Ireland")),
+    (540, 549, Mo("Belgium and Luxembourg")),
+    (560, 560, Mo("Portugal")),
+    (569, 569, Mo("Iceland")),
+    (570, 579, Mo("Denmark")),
+    (590, 590, Mo("Poland")),
+    (594, 594, Mo("Romania")),
+    (599, 599, Mo("Hungary")),
+    (600, 601, Mo("South Africa")),
+    (603, 603, Mo("Ghana")),
+    (604, 604, Mo("Senegal")),
+    (608, 608, Mo("Bahrain")),
+    (609, 609, Mo("Mauritius")),
+    (611, 611, Mo("Morocco")),
+    (613, 613, Mo("Algeria")),
+    (615, 615, Mo("Nigeria")),
+    (616, 616, Mo("Kenya")),
+    (617, 617, Mo("Cameroon")),
+    (618, 618, Mo("Côte d'Ivoire")),
+    (619, 619, Mo("Tunisia")),
+    (620, 620, Mo("Tanzania")),
+    (621, 621, Mo("Syria")),
+    (622, 622, Mo("Egypt")),
+    (623, 623, Mo("Brunei")),
+    (624, 624, Mo("Libya")),
+    (625, 625, Mo("Jordan")),
+    (626, 626, Mo("Iran")),
+    (627, 627, Mo("Kuwait")),
+    (628, 628, Mo("Saudi Arabia")),
+    (629, 629, Mo("United Arab Emirates")),
+    (630, 630, Mo("Qatar")),
+    (631, 631, Mo("Namibia")),
+    (640, 649, Mo("Finland")),
+    (690, 699, Mo("China")),
+    (700, 709, Mo("Norway")),
+    (729, 729, Mo("Israel")),
+    (730, 739, Mo("Sweden")),
+    (740, 740, Mo("Guatemala")),
+    (741, 741, Mo("El Salvador")),
+    (742, 742, Mo("Honduras")),
+    (743, 743, Mo("Nicaragua")),
+    (744, 744, Mo("Costa Rica")),
+    (745, 745, Mo("Panama")),
+    (746, 746, Mo("Dominican Republic")),
+    (750, 750, Mo("Mexico")),
+    (754, 755, Mo("Canada")),
+    (759, 759, Mo("Venezuela")),
+    (760, 769, Mo("Switzerland")),
+    (770, 771, Mo("Colombia")),
+    (773, 773, Mo("Uruguay")),
+    (775, 775, Mo("Peru")),
+    (777, 777, Mo("Bolivia")),
+    (778, 779, Mo("Argentina")),
+    (780, 780, Mo("Chile")),
+    (784, 784, Mo("Paraguay")),
+    (786, 786, Mo("Ecuador")),
+    (789, 790, Mo("Brazil")),
+    (800, 839, Mo("Italy")),
+    (840, 849, Mo("Spain")),
+    (850, 850, Mo("Cuba")),
+    (858, 858, Mo("Slovakia")),
+    (859, 859, Mo("Czech Republic")),
+    (860, 860, Mo("Serbia")),
+    (865, 865, Mo("Mongolia")),
+    (867, 867, Mo("North Korea")),
+    (868, 869, Mo("Türkiye")),
+    (870, 879, Mo("Netherlands")),
+    (880, 881, Mo("South Korea")),
+    (883, 883, Mo("Myanmar")),
+    (884, 884, Mo("Cambodia")),
+    (885, 885, Mo("Thailand")),
+    (888, 888, Mo("Singapore")),
+    (890, 890, Mo("India")),
+    (893, 893, Mo("Vietnam")),
+    (896, 896, Mo("Pakistan")),
+    (899, 899, Mo("Indonesia")),
+    (900, 919, Mo("Austria")),
+    (930, 939, Mo("Australia")),
+    (940, 949, Mo("New Zealand")),
+    (950, 952, GlobalOffice),
+    (955, 955, Mo("Malaysia")),
+    (958, 958, Mo("Macau")),
+    (960, 969, GlobalOffice),
+    (977, 977, Issn),
+    (978, 979, Isbn),
+    (980, 980, RefundReceipt),
+    (981, 984, Coupon),
+    (990, 999, Coupon),
+];
+
+/// Validate a GTIN of 8, 12, 13 or 14 digits including its check digit.
+/// Spaces and hyphens are ignored.
+pub fn validate_gtin(gtin: &str) -> Result<GtinFormat> {
+    let digits = clean(gtin)?;
+    let format = GtinFormat::from_length(digits.len()).ok_or_else(|| {
+        invalid(format!(
+            "a GTIN has 8, 12, 13 or 14 digits, got {}",
+            digits.len()
+        ))
+    })?;
+    let (body, check) = digits.split_at(digits.len() - 1);
+    let expected = check_digit(body);
+    if check.as_bytes()[0] - b'0' != expected {
+        return Err(invalid(format!(
+            "check digit of {} should be {}, got {}",
+            digits, expected, check
+        )));
+    }
+    Ok(format)
+}
+
+/// Whether `gtin` is a GTIN of a supported length with a correct check digit
+pub fn is_valid_gtin(gtin: &str) -> bool {
+    validate_gtin(gtin).is_ok()
+}
+
+/// Append the check digit to 7, 11, 12 or 13 digits
+pub fn complete_gtin(body: &str) -> Result<String> {
+    let digits = clean(body)?;
+    if GtinFormat::from_length(digits.len() + 1).is_none() {
+        return Err(invalid(format!(
+            "expected 7, 11, 12 or 13 digits without the check digit, got {}",
+            digits.len()
+        )));
+    }
+    let check = check_digit(&digits);
+    Ok(format!("{}{}", digits, check))
+}
+
+/// Validate a GTIN and pad it with leading zeros to the 14 digits of AI (01)
+pub fn normalize_gtin(gtin: &str) -> Result<String> {
+    validate_gtin(gtin)?;
+    Ok(format!("{:0>14}", clean(gtin)?))
+}
+
+/// Convert a GTIN to another length. Converting to a shorter form only
+/// removes leading zeros, so it fails when the digits to drop are not zero,
+/// e.g. a GTIN-14 with an indicator digit has no GTIN-13 form.
+pub fn convert_gtin(gtin: &str, format: GtinFormat) -> Result<String> {
+    let normalized = normalize_gtin(gtin)?;
+    let (padding, converted) = normalized.split_at(14 - format.length());
+    if padding.bytes().any(|b| b != b'0') {
+        return Err(invalid(format!(
+            "{} has no {}-digit form",
+            gtin.trim(),
+            format.length()
+        )));
+    }
+    Ok(converted.to_string())
+}
+
+/// Indicator digit of a GTIN, the first of its 14-digit form: 0 for trade
+/// items, 1-8 for packaging levels of the same item and 9 for variable
+/// measure items
+pub fn indicator_digit(gtin: &str) -> Result<u8> {
+    Ok(normalize_gtin(gtin)?.as_bytes()[0] - b'0')
+}
+
+/// GTIN-14 of a case code for ITF-14: `indicator` replaces the first digit
+/// of the 14-digit form of `gtin` and the check digit is recalculated
+pub fn with_indicator(gtin: &str, indicator: u8) -> Result<String> {
+    if indicator > 9 {
+        return Err(invalid(format!(
+            "indicator digit must be 0-9, got {}",
+            indicator
+        )));
+    }
+    let normalized = normalize_gtin(gtin)?;
+    complete_gtin(&format!("{}{}", indicator, &normalized[1..13]))
+}
+
+/// GS1 prefix of a GTIN and what it is allocated to.
+///
+/// The prefix is read from the GTIN-13 form, skipping the indicator digit of
+/// a GTIN-14. It identifies the member organisation that issued the company
+/// prefix, not the country where the product is made. GTIN-8 starting with
+/// 0 or 2 are for restricted circulation.
+pub fn gs1_prefix(gtin: &str) -> Result<Gs1Prefix> {
+    let normalized = normalize_gtin(gtin)?;
+    let digits = match normalized.strip_prefix("000000") {
+        Some(gtin8) if gtin8.starts_with('0') || gtin8.starts_with('2') => {
+            let first = u16::from(gtin8.as_bytes()[0] - b'0') * 100;
+            return Ok(Gs1Prefix {
+                prefix: gtin8[..3].parse().unwrap_or(0),
+                start: first,
+                end: first + 99,
+                usage: RestrictedCirculation,
+            });
+        }
+        Some(gtin8) => &gtin8[..3],
+        None => &normalized[1..4],
+    };
+    let prefix: u16 = digits.parse().unwrap_or(0);
+    Ok(PREFIXES
+        .iter()
+        .find(|&&(start, end, _)| (start..=end).contains(&prefix))
+        .map_or(
+            Gs1Prefix {
+                prefix,
+                start: prefix,
+                end: prefix,
+                usage: PrefixUsage::Unassigned,
+            },
+            |&(start, end, usage)| Gs1Prefix {
+                prefix,
+                start,
+                end,
+                usage,
+            },
+        ))
+}
+
+/// Digits of a GTIN without spaces and hyphens
+fn clean(gtin: &str) -> Result<String> {
+    let digits = gtin.trim().replace([' ', '-'], "");
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid(format!("{} must contain only digits", gtin.trim())));
+    }
+    Ok(digits)
+}
+
+fn invalid(reason: String) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid GTIN: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_gtin() {
+        assert_eq!(validate_gtin("96385074").unwrap(), GtinFormat::Gtin8);
+        assert_eq!(validate_gtin("036000291452").unwrap(), GtinFormat::Gtin12);
+        assert_eq!(validate_gtin("7891000315507").unwrap(), GtinFormat::Gtin13);
+        assert_eq!(
+            validate_gtin("789-1000-31550-7").unwrap(),
+            GtinFormat::Gtin13
+        );
+        assert_eq!(validate_gtin("17891000315504").unwrap(), GtinFormat::Gtin14);
+
+        assert!(validate_gtin("7891000315508").is_err());
+        assert!(validate_gtin("789100031550").is_err());
+        assert!(validate_gtin("78910003155A7").is_err());
+        assert!(validate_gtin("").is_err());
+        assert!(!is_valid_gtin("12345"));
+    }
+
+    #[test]
+    fn test_complete_and_normalize() {
+        assert_eq!(complete_gtin("789100031550").unwrap(), "7891000315507");
+        assert_eq!(complete_gtin("03600029145").unwrap(), "036000291452");
+        assert!(complete_gtin("123").is_err());
+
+        assert_eq!(normalize_gtin("96385074").unwrap(), "00000096385074");
+        assert_eq!(normalize_gtin("036000291452").unwrap(), "00036000291452");
+        assert_eq!(normalize_gtin("7891000315507").unwrap(), "07891000315507");
+    }
+
+    #[test]
+    fn test_convert_gtin() {
+        assert_eq!(
+            convert_gtin("036000291452", GtinFormat::Gtin13).unwrap(),
+            "0036000291452"
+        );
+        assert_eq!(
+            convert_gtin("00036000291452", GtinFormat::Gtin12).unwrap(),
+            "036000291452"
+        );
+        assert_eq!(
+            convert_gtin("00000096385074", GtinFormat::Gtin8).unwrap(),
+            "96385074"
+        );
+        assert!(convert_gtin("7891000315507", GtinFormat::Gtin12).is_err());
+        assert!(convert_gtin("17891000315504", GtinFormat::Gtin13).is_err());
+    }
+
+    #[test]
+    fn test_indicator() {
+        assert_eq!(
+            with_indicator("7891000315507", 1).unwrap(),
+            "17891000315504"
+        );
+        assert_eq!(indicator_digit("17891000315504").unwrap(), 1);
+        assert_eq!(indicator_digit("7891000315507").unwrap(), 0);
+        assert_eq!(
+            with_indicator("17891000315504", 0).unwrap(),
+            "07891000315507"
+        );
+        assert!(with_indicator("7891000315507", 10).is_err());
+    }
+
+    #[test]
+    fn test_gs1_prefix() {
+        let prefix = gs1_prefix("7891000315507").unwrap();
+        assert_eq!(prefix.prefix, 789);
+        assert_eq!((prefix.start, prefix.end), (789, 790));
+        assert_eq!(prefix.usage, PrefixUsage::MemberOrganisation("Brazil"));
+
+        assert_eq!(
+            gs1_prefix("17891000315504").unwrap().usage,
+            PrefixUsage::MemberOrganisation("Brazil")
+        );
+        assert_eq!(
+            gs1_prefix("036000291452").unwrap().usage,
+            PrefixUsage::MemberOrganisation("United States")
+        );
+        assert_eq!(
+            gs1_prefix("9780306406157").unwrap().usage,
+            PrefixUsage::Isbn
+        );
+        assert_eq!(
+            gs1_prefix("2012345000001").unwrap().usage,
+            PrefixUsage::RestrictedCirculation
+        );
+        assert_eq!(
+            gs1_prefix("96385074").unwrap().usage,
+            PrefixUsage::GlobalOffice
+        );
+        assert_eq!(
+            gs1_prefix("02345673").unwrap().usage,
+            PrefixUsage::RestrictedCirculation
+        );
+        assert_eq!(
+            gs1_prefix("1400000000007").unwrap().usage,
+            PrefixUsage::Unassigned
+        );
+    }
+}
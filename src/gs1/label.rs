@@ -0,0 +1,266 @@
+//! GS1 logistics label for pallets and other logistic units
+//!
+//! The label follows the layout of the GS1 Logistic Label Guideline:
+//! free-form shipping addresses at the top, the data titles and values in
+//! the middle, and GS1-128 barcodes at the bottom with the SSCC lowest.
+//! Barcodes are at least the logistics minimums of the GS1 General
+//! Specifications (X of 0.495 mm, bars of 31.75 mm). Labels are 4 x 6 inches
+//! unless [`LabelSize::SixByEight`] is chosen; content data is split over as
+//! many GS1-128 symbols as the page width requires. At those minimums a
+//! 4 x 6 inch label holds three barcode rows, so content with an expiry date
+//! as well as a batch needs the 6 x 8 inch page.
+
+use chrono::NaiveDate;
+
+use super::{normalize_gtin, validate_elements, Element, Sscc};
+use crate::generators;
+use crate::types::{Barcode, Result};
+
+/// Label margin, in millimetres
+pub(crate) const LABEL_MARGIN: f64 = 5.0;
+
+/// X dimension range of GS1-128 on logistic labels, in millimetres
+pub(crate) const MODULE_WIDTH: (f64, f64) = (0.495, 1.016);
+
+/// Minimum bar height of GS1-128 on logistic labels, in millimetres
+#[cfg(feature = "pdf")]
+pub(crate) const MIN_BAR_HEIGHT: f64 = 31.75;
+
+/// Quiet zone on each side of a GS1-128 symbol, in modules
+pub(crate) const QUIET_ZONE: usize = 10;
+
+/// Data title and value of a line in the middle section
+pub type TextLine = (&'static str, String);
+
+/// Page size of a logistics label
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LabelSize {
+    /// 4 x 6 inches (101.6 x 152.4 mm)
+    #[default]
+    FourBySix,
+    /// 6 x 8 inches (152.4 x 203.2 mm), for content data that does not fit
+    /// on 4 x 6
+    SixByEight,
+}
+
+impl LabelSize {
+    /// Width and height in millimetres
+    pub fn dimensions(self) -> (f64, f64) {
+        match self {
+            LabelSize::FourBySix => (101.6, 152.4),
+            LabelSize::SixByEight => (152.4, 203.2),
+        }
+    }
+}
+
+/// Contents of a logistics label
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogisticsLabel {
+    pub sscc: Sscc,
+    /// Page size, 4 x 6 inches by default
+    pub size: LabelSize,
+    /// Sender name and address lines
+    pub ship_from: Vec<String>,
+    /// Receiver name and address lines
+    pub ship_to: Vec<String>,
+    /// GTIN of the trade items on the unit, AI (02); requires `count`
+    pub content: Option<String>,
+    /// Number of trade items, AI (37)
+    pub count: Option<u32>,
+    /// AI (10)
+    pub batch: Option<String>,
+    /// AI (17)
+    pub expiry: Option<NaiveDate>,
+}
+
+impl LogisticsLabel {
+    /// Label with only the SSCC
+    pub fn new(sscc: Sscc) -> Self {
+        Self {
+            sscc,
+            size: LabelSize::default(),
+            ship_from: Vec::new(),
+            ship_to: Vec::new(),
+            content: None,
+            count: None,
+            batch: None,
+            expiry: None,
+        }
+    }
+
+    /// Content and batch elements, fixed-length AIs first so the barcode
+    /// needs fewer FNC1 separators; empty when there is no content data
+    pub fn content_elements(&self) -> Result<Vec<Element>> {
+        let mut elements = Vec::new();
+        if let Some(content) = &self.content {
+            elements.push(Element::new("02", &normalize_gtin(content)?));
+        }
+        if let Some(expiry) = self.expiry {
+            elements.push(Element::new("17", &expiry.format("%y%m%d").to_string()));
+        }
+        if let Some(count) = self.count {
+            elements.push(Element::new("37", &count.to_string()));
+        }
+        if let Some(batch) = &self.batch {
+            elements.push(Element::new("10", batch));
+        }
+        validate_elements(&elements)?;
+        Ok(elements)
+    }
+
+    /// GS1-128 barcodes from top to bottom: content and batch data, when
+    /// present, then the SSCC. Content elements are packed in order into
+    /// symbols that fit the page width at the minimum X dimension.
+    pub fn barcodes(&self) -> Result<Vec<Barcode>> {
+        let content = self.content_elements()?;
+        let max_modules = max_symbol_modules(self.size.dimensions().0);
+        let mut barcodes = Vec::new();
+        let mut start = 0;
+        while start < content.len() {
+            let mut end = start + 1;
+            let mut barcode = generators::code128::gs1_128_symbol(&content[start..end])?;
+            while end < content.len() {
+                match generators::code128::gs1_128_symbol(&content[start..=end]) {
+                    Ok(wider) if symbol_modules(&wider) <= max_modules => {
+                        barcode = wider;
+                        end += 1;
+                    }
+                    _ => break,
+                }
+            }
+            barcodes.push(barcode);
+            start = end;
+        }
+        barcodes.push(super::generate_sscc(&self.sscc)?);
+        Ok(barcodes)
+    }
+
+    /// Data titles and values of the middle section, e.g. `("SSCC",
+    /// "306141411234567891")`; dates are shown as `DD.MM.YYYY`
+    pub fn text_block(&self) -> Result<Vec<TextLine>> {
+        let sscc = self.sscc.element()?;
+        let mut lines = vec![(sscc.title(), sscc.value.clone())];
+        for element in self.content_elements()? {
+            let value = match (element.ai.as_str(), self.expiry) {
+                ("17", Some(expiry)) => expiry.format("%d.%m.%Y").to_string(),
+                _ => element.value.clone(),
+            };
+            lines.push((element.title(), value));
+        }
+        Ok(lines)
+    }
+
+    /// The label as a single-page PDF
+    #[cfg(feature = "pdf")]
+    pub fn to_pdf(&self) -> Result<Vec<u8>> {
+        crate::exporters::pdf::export_logistics_labels(std::slice::from_ref(self))
+    }
+}
+
+/// Most modules of a GS1-128 symbol that fits across a label `width`
+/// millimetres wide at the minimum X dimension, quiet zones left out
+pub(crate) fn max_symbol_modules(width: f64) -> usize {
+    ((width - 2.0 * LABEL_MARGIN) / MODULE_WIDTH.0) as usize - 2 * QUIET_ZONE
+}
+
+fn symbol_modules(barcode: &Barcode) -> usize {
+    barcode
+        .modules
+        .as_linear()
+        .map_or(0, |modules| modules.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label() -> LogisticsLabel {
+        let mut label = LogisticsLabel::new(Sscc::new(3, "0614141", 123_456_789));
+        label.ship_from = vec!["Distribuidora Exemplo".to_string()];
+        label.ship_to = vec!["Loja 42".to_string(), "São Paulo SP".to_string()];
+        label.content = Some("7891000315507".to_string());
+        label.count = Some(48);
+        label.batch = Some("L2026A".to_string());
+        label.expiry = NaiveDate::from_ymd_opt(2027, 3, 31);
+        label
+    }
+
+    #[test]
+    fn test_label_elements() {
+        let label = label();
+        let content = label.content_elements().unwrap();
+        assert_eq!(
+            super::super::format_hri(&content),
+            "(02)07891000315507(17)270331(37)48(10)L2026A"
+        );
+        assert_eq!(label.size, LabelSize::FourBySix);
+
+        // The content is split so each symbol fits 4 x 6 at X = 0.495 mm
+        let barcodes = label.barcodes().unwrap();
+        assert_eq!(
+            barcodes.iter().map(|b| b.data.as_str()).collect::<Vec<_>>(),
+            [
+                "(02)07891000315507",
+                "(17)270331(37)48",
+                "(10)L2026A",
+                "(00)306141411234567891"
+            ]
+        );
+        let max_modules = max_symbol_modules(LabelSize::FourBySix.dimensions().0);
+        assert!(barcodes.iter().all(|b| symbol_modules(b) <= max_modules));
+
+        // The wider 6 x 8 page packs the content into fewer symbols
+        let mut wide = label.clone();
+        wide.size = LabelSize::SixByEight;
+        let barcodes = wide.barcodes().unwrap();
+        assert_eq!(
+            barcodes.iter().map(|b| b.data.as_str()).collect::<Vec<_>>(),
+            [
+                "(02)07891000315507(17)270331(37)48",
+                "(10)L2026A",
+                "(00)306141411234567891"
+            ]
+        );
+        let max_modules = max_symbol_modules(LabelSize::SixByEight.dimensions().0);
+        assert!(barcodes.iter().all(|b| symbol_modules(b) <= max_modules));
+
+        let text = label.text_block().unwrap();
+        assert_eq!(text[0], ("SSCC", "306141411234567891".to_string()));
+        assert_eq!(text[2], ("USE BY OR EXPIRY", "31.03.2027".to_string()));
+
+        // SSCC only
+        let sscc_only = LogisticsLabel::new(label.sscc.clone());
+        assert_eq!(sscc_only.barcodes().unwrap().len(), 1);
+
+        // A count without the content GTIN is rejected
+        let mut invalid = LogisticsLabel::new(label.sscc.clone());
+        invalid.count = Some(10);
+        assert!(invalid.barcodes().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "pdf")]
+    fn test_label_pdf() {
+        // SSCC, content and batch on the default 4 x 6 inch page
+        let mut small = label();
+        small.expiry = None;
+        small.ship_from.push("Rua Exemplo 100".to_string());
+        small.ship_from.push("Campinas SP".to_string());
+        assert_eq!(small.barcodes().unwrap().len(), 3);
+        let pdf = small.to_pdf().unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+
+        // An address too long for the page is an error, not truncated
+        let mut long_address = small.clone();
+        long_address
+            .ship_to
+            .extend(["Bloco B".to_string(), "Doca 7".to_string()]);
+        assert!(long_address.to_pdf().is_err());
+
+        // With an expiry date as well, the content needs the 6 x 8 page
+        let mut expiry = label();
+        assert!(expiry.to_pdf().is_err());
+        expiry.size = LabelSize::SixByEight;
+        assert!(expiry.to_pdf().unwrap().starts_with(b"%PDF"));
+    }
+}
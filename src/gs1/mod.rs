@@ -0,0 +1,275 @@
+//! GS1 system data: Application Identifiers, GTINs, SSCCs and Digital Link
+//! URIs
+//!
+//! Element strings come in three forms, all parsed into the same list of
+//! [`Element`]s: bracketed as in human-readable text
+//! (`(01)09520123456788(10)ABC1`), unbracketed with a GS (FNC1) after
+//! variable-length values as carried in barcodes, and Digital Link URIs.
+
+pub mod ai;
+pub mod digital_link;
+pub mod gtin;
+pub mod label;
+pub mod sscc;
+
+pub use ai::{
+    decimal_places, lookup, predefined_length, validate, validate_elements, AiDefinition,
+    Component, Rule,
+};
+pub use digital_link::{generate_digital_link, DigitalLink, DEFAULT_DOMAIN};
+pub use gtin::{
+    complete_gtin, convert_gtin, gs1_prefix, indicator_digit, is_valid_gtin, normalize_gtin,
+    validate_gtin, with_indicator, Gs1Prefix, GtinFormat, PrefixUsage,
+};
+pub use label::{LabelSize, LogisticsLabel};
+pub use sscc::{generate_sscc, validate_sscc, Sscc, SsccAllocator};
+
+use crate::types::{QuickCodesError, Result};
+
+/// Group separator, which stands for FNC1 between elements
+pub const GS: char = '\x1d';
+
+/// Symbology identifiers of GS1 data in barcode reader output
+const SYMBOLOGY_IDENTIFIERS: &[&str] = &["]C1", "]e0", "]d2", "]Q3", "]J1"];
+
+/// An AI and its value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub ai: String,
+    pub value: String,
+}
+
+impl Element {
+    pub fn new(ai: &str, value: &str) -> Self {
+        Self {
+            ai: ai.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    /// Definition of the AI, `None` when it is not assigned
+    pub fn definition(&self) -> Option<&'static AiDefinition> {
+        lookup(&self.ai)
+    }
+
+    /// Data title of the AI, e.g. `BATCH/LOT`
+    pub fn title(&self) -> &'static str {
+        self.definition().map_or("", |definition| definition.title)
+    }
+
+    /// Value of AIs with a decimal point position, e.g. 0.195 for
+    /// `(3103)000195`. Amounts with a currency, (391n) and (393n), give the
+    /// amount alone: 19.95 for `(3912)9781995`.
+    pub fn decimal(&self) -> Option<f64> {
+        let places = decimal_places(&self.ai)?;
+        let digits = match self.currency() {
+            Some(currency) => &self.value[currency.len()..],
+            None => self.value.as_str(),
+        };
+        let value: f64 = digits.parse().ok()?;
+        Some(value / 10f64.powi(places as i32))
+    }
+
+    /// ISO 4217 numeric currency code leading the amount of (391n) and
+    /// (393n), e.g. `978` (euro) for `(3912)9781995`
+    pub fn currency(&self) -> Option<&str> {
+        match self.ai.get(..3)? {
+            "391" | "393" if self.ai.len() == 4 => self.value.get(..3),
+            _ => None,
+        }
+    }
+}
+
+/// Parse an element string in any form: bracketed, unbracketed (with GS
+/// separators and an optional symbology identifier such as `]C1`) or a
+/// Digital Link URI
+pub fn parse(data: &str) -> Result<Vec<Element>> {
+    let data = data.trim_matches(|c: char| c.is_whitespace() && c != GS);
+    if data.starts_with('(') {
+        parse_bracketed(data)
+    } else if data.starts_with("http://") || data.starts_with("https://") {
+        Ok(DigitalLink::parse(data)?.elements)
+    } else {
+        parse_unbracketed(data)
+    }
+}
+
+/// Parse a bracketed element string such as `(01)09520123456788(10)ABC1`
+pub fn parse_bracketed(data: &str) -> Result<Vec<Element>> {
+    let data = data.trim();
+    let mut rest = data.strip_prefix('(').ok_or_else(|| {
+        QuickCodesError::InvalidData(
+            "GS1 data must be an element string like (01)09501101530003".to_string(),
+        )
+    })?;
+    let mut elements = Vec::new();
+    loop {
+        let close = rest.find(')').ok_or_else(|| {
+            QuickCodesError::InvalidData("Unterminated application identifier".to_string())
+        })?;
+        let ai = &rest[..close];
+        let value_end = rest[close + 1..]
+            .find('(')
+            .map_or(rest.len(), |i| close + 1 + i);
+        elements.push(Element::new(ai, &rest[close + 1..value_end]));
+        match rest[value_end..].strip_prefix('(') {
+            Some(next) => rest = next,
+            None => break,
+        }
+    }
+    validate_elements(&elements)?;
+    Ok(elements)
+}
+
+/// Parse an unbracketed element string: AIs of predefined length are
+/// followed directly by the next AI, the others by GS or the end
+pub fn parse_unbracketed(data: &str) -> Result<Vec<Element>> {
+    // Values are split by byte count; GS1 character sets are ASCII anyway
+    if !data.is_ascii() {
+        return Err(QuickCodesError::InvalidData(format!(
+            "GS1 element string '{}' has non-ASCII characters",
+            data
+        )));
+    }
+    let mut rest = SYMBOLOGY_IDENTIFIERS
+        .iter()
+        .find_map(|identifier| data.strip_prefix(identifier))
+        .unwrap_or(data);
+    let mut elements = Vec::new();
+    while !rest.is_empty() {
+        let length = ai::ai_length(rest)
+            .filter(|&length| {
+                rest.get(..length)
+                    .is_some_and(|ai| ai.bytes().all(|b| b.is_ascii_digit()))
+            })
+            .ok_or_else(|| {
+                QuickCodesError::InvalidData(format!("No GS1 application identifier at '{}'", rest))
+            })?;
+        let (ai, tail) = rest.split_at(length);
+        let value_end = match predefined_length(ai) {
+            Some(value_length) => value_length.min(tail.len()),
+            None => tail.find(GS).unwrap_or(tail.len()),
+        };
+        elements.push(Element::new(ai, &tail[..value_end]));
+        rest = &tail[value_end..];
+        rest = rest.strip_prefix(GS).unwrap_or(rest);
+    }
+    if elements.is_empty() {
+        return Err(QuickCodesError::InvalidData(
+            "GS1 element string is empty".to_string(),
+        ));
+    }
+    validate_elements(&elements)?;
+    Ok(elements)
+}
+
+/// Human-readable interpretation: each AI in parentheses before its value
+pub fn format_hri(elements: &[Element]) -> String {
+    elements
+        .iter()
+        .map(|element| format!("({}){}", element.ai, element.value))
+        .collect()
+}
+
+/// Unbracketed element string as encoded in barcodes, with GS after each
+/// value that is not last and whose AI has no predefined length
+pub fn to_unbracketed(elements: &[Element]) -> String {
+    let mut data = String::new();
+    for (i, element) in elements.iter().enumerate() {
+        data.push_str(&element.ai);
+        data.push_str(&element.value);
+        if i + 1 < elements.len() && predefined_length(&element.ai).is_none() {
+            data.push(GS);
+        }
+    }
+    data
+}
+
+/// GS1 modulo-10 check digit of a digit string: weights 3 and 1
+/// alternating from the rightmost digit
+pub fn check_digit(digits: &str) -> u8 {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| u32::from(b - b'0') * if i % 2 == 0 { 3 } else { 1 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected() -> Vec<Element> {
+        vec![
+            Element::new("01", "09520123456788"),
+            Element::new("3103", "000195"),
+            Element::new("10", "ABC1"),
+            Element::new("17", "251231"),
+        ]
+    }
+
+    #[test]
+    fn test_bracketed_element_string() {
+        let elements = parse_bracketed("(01)09520123456788(3103)000195(10)ABC1(17)251231").unwrap();
+        assert_eq!(elements, expected());
+        assert_eq!(
+            format_hri(&elements),
+            "(01)09520123456788(3103)000195(10)ABC1(17)251231"
+        );
+
+        assert!(parse_bracketed("0109520123456788").is_err());
+        assert!(parse_bracketed("(01)0952012345678").is_err());
+        assert!(parse_bracketed("(01").is_err());
+        assert!(parse_bracketed("(99)ABC(98)").is_err());
+    }
+
+    #[test]
+    fn test_unbracketed_element_string() {
+        let data = "0109520123456788310300019510ABC1\x1d17251231";
+        assert_eq!(to_unbracketed(&expected()), data);
+        assert_eq!(parse_unbracketed(data).unwrap(), expected());
+        assert_eq!(parse(&format!("]C1{}", data)).unwrap(), expected());
+        assert_eq!(parse(&format!("]d2{}\x1d", data)).unwrap(), expected());
+
+        assert!(parse_unbracketed("").is_err());
+        assert!(parse_unbracketed("0109520123456788A").is_err());
+        assert!(parse_unbracketed("5012").is_err());
+        assert!(parse("110！！").is_err());
+        assert!(parse("0109520123456788é").is_err());
+    }
+
+    #[test]
+    fn test_parse_any_form() {
+        let uri = "https://id.gs1.org/01/09520123456788/10/ABC1?3103=000195&17=251231";
+        let elements = parse(uri).unwrap();
+        assert_eq!(elements[0], Element::new("01", "09520123456788"));
+        assert_eq!(elements.len(), 4);
+        assert_eq!(parse(" (01)09520123456788 ").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_element_accessors() {
+        let elements = expected();
+        assert_eq!(elements[0].title(), "GTIN");
+        assert_eq!(elements[1].title(), "NET WEIGHT (kg)");
+        assert_eq!(elements[1].decimal(), Some(0.195));
+        assert_eq!(elements[2].decimal(), None);
+        assert_eq!(Element::new("3922", "1995").decimal(), Some(19.95));
+        assert_eq!(Element::new("3922", "1995").currency(), None);
+
+        // The currency of (391n) and (393n) is not part of the amount
+        let amount = Element::new("3912", "9781995");
+        assert_eq!(amount.currency(), Some("978"));
+        assert_eq!(amount.decimal(), Some(19.95));
+        assert_eq!(Element::new("3930", "986150").decimal(), Some(150.0));
+    }
+
+    #[test]
+    fn test_check_digit() {
+        assert_eq!(check_digit("0952012345678"), 8);
+        assert_eq!(check_digit("39512345123456789"), 5);
+        assert_eq!(check_digit("629104150021"), 3);
+    }
+}
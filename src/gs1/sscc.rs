@@ -0,0 +1,271 @@
+//! Serial Shipping Container Code (SSCC-18), AI (00)
+//!
+//! An SSCC identifies a logistic unit such as a pallet: an extension digit,
+//! the GS1 company prefix, a serial reference filling the remaining digits
+//! and the GS1 modulo-10 check digit, 18 digits in all. Each SSCC must be
+//! allocated only once, so [`SsccAllocator`] hands out serial references in
+//! order from a counter that the caller persists between runs.
+
+use super::{check_digit, Element};
+use crate::generators;
+use crate::types::{Barcode, QuickCodesError, Result};
+
+/// Digits of the company prefix and serial reference together
+const KEY_DIGITS: usize = 16;
+
+/// Shortest and longest GS1 company prefix
+const PREFIX_LENGTHS: std::ops::RangeInclusive<usize> = 7..=12;
+
+/// SSCC of a logistic unit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sscc {
+    /// Extension digit 0-9, free for the company to use (e.g. per site)
+    pub extension: u8,
+    /// GS1 company prefix, 7 to 12 digits
+    pub company_prefix: String,
+    /// Serial reference, with as many digits as the prefix leaves (16
+    /// minus the prefix length)
+    pub serial_reference: u64,
+}
+
+impl Sscc {
+    pub fn new(extension: u8, company_prefix: &str, serial_reference: u64) -> Self {
+        Self {
+            extension,
+            company_prefix: company_prefix.to_string(),
+            serial_reference,
+        }
+    }
+
+    /// The 18 digits, check digit included
+    pub fn encode(&self) -> Result<String> {
+        if self.extension > 9 {
+            return Err(invalid(format!(
+                "extension digit must be 0-9, got {}",
+                self.extension
+            )));
+        }
+        let capacity = serial_capacity(&self.company_prefix)?;
+        if self.serial_reference >= capacity {
+            return Err(invalid(format!(
+                "serial reference {} does not fit in {} digits",
+                self.serial_reference,
+                KEY_DIGITS - self.company_prefix.len()
+            )));
+        }
+        let body = format!(
+            "{}{}{:0width$}",
+            self.extension,
+            self.company_prefix,
+            self.serial_reference,
+            width = KEY_DIGITS - self.company_prefix.len()
+        );
+        let check = check_digit(&body);
+        Ok(format!("{}{}", body, check))
+    }
+
+    /// Split an 18-digit SSCC, whose company prefix has `prefix_length`
+    /// digits, into its parts
+    pub fn parse(sscc: &str, prefix_length: usize) -> Result<Self> {
+        validate_sscc(sscc)?;
+        if !PREFIX_LENGTHS.contains(&prefix_length) {
+            return Err(invalid(format!(
+                "company prefix has 7 to 12 digits, got {}",
+                prefix_length
+            )));
+        }
+        Ok(Self {
+            extension: sscc.as_bytes()[0] - b'0',
+            company_prefix: sscc[1..1 + prefix_length].to_string(),
+            serial_reference: sscc[1 + prefix_length..17].parse().unwrap_or(0),
+        })
+    }
+
+    /// The SSCC as the AI (00) element
+    pub fn element(&self) -> Result<Element> {
+        Ok(Element::new("00", &self.encode()?))
+    }
+}
+
+/// Check that `sscc` has 18 digits and a correct check digit
+pub fn validate_sscc(sscc: &str) -> Result<()> {
+    if sscc.len() != 18 || !sscc.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid(format!("'{}' is not 18 digits", sscc)));
+    }
+    let expected = check_digit(&sscc[..17]);
+    if sscc.as_bytes()[17] - b'0' != expected {
+        return Err(invalid(format!(
+            "check digit should be {}, got {}",
+            expected,
+            &sscc[17..]
+        )));
+    }
+    Ok(())
+}
+
+/// Generate the SSCC as a GS1-128 barcode with AI (00)
+pub fn generate_sscc(sscc: &Sscc) -> Result<Barcode> {
+    generators::generate_gs1_128(&format!("(00){}", sscc.encode()?))
+}
+
+/// Allocates SSCCs with consecutive serial references.
+///
+/// Store [`next_serial`](Self::next_serial) after each allocation (or
+/// batch) and pass it to [`resume`](Self::resume) on the next run so no
+/// SSCC is issued twice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsccAllocator {
+    extension: u8,
+    company_prefix: String,
+    next: u64,
+}
+
+impl SsccAllocator {
+    /// Allocator starting at serial reference 0
+    pub fn new(extension: u8, company_prefix: &str) -> Result<Self> {
+        Self::resume(extension, company_prefix, 0)
+    }
+
+    /// Allocator continuing from a persisted counter: `next_serial` is the
+    /// serial reference of the next SSCC to issue
+    pub fn resume(extension: u8, company_prefix: &str, next_serial: u64) -> Result<Self> {
+        let allocator = Self {
+            extension,
+            company_prefix: company_prefix.to_string(),
+            next: next_serial,
+        };
+        // Validates the extension digit and the prefix
+        Sscc::new(extension, company_prefix, 0).encode()?;
+        if next_serial > allocator.capacity() {
+            return Err(invalid(format!(
+                "serial reference {} is out of range",
+                next_serial
+            )));
+        }
+        Ok(allocator)
+    }
+
+    /// Serial reference of the next SSCC, the value to persist
+    pub fn next_serial(&self) -> u64 {
+        self.next
+    }
+
+    /// Number of SSCCs left before the serial references run out
+    pub fn remaining(&self) -> u64 {
+        self.capacity() - self.next
+    }
+
+    /// Issue the next SSCC
+    pub fn allocate(&mut self) -> Result<Sscc> {
+        Ok(self.allocate_many(1)?.remove(0))
+    }
+
+    /// Issue `count` consecutive SSCCs; nothing is issued when fewer than
+    /// `count` remain
+    pub fn allocate_many(&mut self, count: u64) -> Result<Vec<Sscc>> {
+        if count > self.remaining() {
+            return Err(invalid(format!(
+                "{} SSCCs requested but only {} serial references remain for prefix {}",
+                count,
+                self.remaining(),
+                self.company_prefix
+            )));
+        }
+        let start = self.next;
+        self.next += count;
+        Ok((start..self.next)
+            .map(|serial| Sscc::new(self.extension, &self.company_prefix, serial))
+            .collect())
+    }
+
+    fn capacity(&self) -> u64 {
+        serial_capacity(&self.company_prefix).unwrap_or(0)
+    }
+}
+
+/// Number of serial references available to a company prefix
+fn serial_capacity(company_prefix: &str) -> Result<u64> {
+    if !PREFIX_LENGTHS.contains(&company_prefix.len())
+        || !company_prefix.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid(format!(
+            "company prefix must be 7 to 12 digits, got '{}'",
+            company_prefix
+        )));
+    }
+    Ok(10u64.pow((KEY_DIGITS - company_prefix.len()) as u32))
+}
+
+fn invalid(reason: String) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid SSCC: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sscc_encode() {
+        let sscc = Sscc::new(3, "0614141", 123_456_789);
+        assert_eq!(sscc.encode().unwrap(), "306141411234567891");
+        assert_eq!(
+            sscc.element().unwrap(),
+            Element::new("00", "306141411234567891")
+        );
+        assert_eq!(Sscc::parse("306141411234567891", 7).unwrap(), sscc);
+
+        assert!(Sscc::new(10, "0614141", 1).encode().is_err());
+        assert!(Sscc::new(3, "061414", 1).encode().is_err());
+        assert!(Sscc::new(3, "0614141", 1_000_000_000).encode().is_err());
+        assert!(validate_sscc("306141411234567890").is_err());
+        assert!(validate_sscc("30614141123456789").is_err());
+        assert!(Sscc::parse("306141411234567891", 13).is_err());
+
+        // A 12-digit prefix leaves 4 digits of serial reference
+        let sscc = Sscc::new(0, "061414112345", 6789);
+        assert_eq!(sscc.encode().unwrap(), "006141411234567890");
+        assert_eq!(Sscc::parse("006141411234567890", 12).unwrap(), sscc);
+        assert!(Sscc::new(0, "061414112345", 10_000).encode().is_err());
+        assert!(Sscc::new(0, "0614141123456", 1).encode().is_err());
+    }
+
+    #[test]
+    fn test_sscc_allocator() {
+        let mut allocator = SsccAllocator::new(0, "0614141").unwrap();
+        assert_eq!(allocator.allocate().unwrap().serial_reference, 0);
+        let batch = allocator.allocate_many(3).unwrap();
+        assert_eq!(
+            batch.iter().map(|s| s.serial_reference).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+        assert_eq!(allocator.next_serial(), 4);
+
+        // Resuming from the persisted counter continues the sequence
+        let mut resumed = SsccAllocator::resume(0, "0614141", allocator.next_serial()).unwrap();
+        assert_eq!(resumed.allocate().unwrap().serial_reference, 4);
+
+        // A 10-digit prefix leaves 6 digits of serial reference
+        let mut last = SsccAllocator::resume(1, "0614141999", 999_998).unwrap();
+        assert_eq!(last.remaining(), 2);
+        assert!(last.allocate_many(3).is_err());
+        assert_eq!(last.next_serial(), 999_998);
+        assert_eq!(
+            last.allocate_many(2).unwrap()[1].encode().unwrap(),
+            "106141419999999991"
+        );
+        assert!(last.allocate().is_err());
+
+        assert!(SsccAllocator::resume(0, "0614141999", 1_000_001).is_err());
+        assert!(SsccAllocator::new(0, "ABC").is_err());
+        assert_eq!(
+            SsccAllocator::new(0, "06141411234").unwrap().remaining(),
+            100_000
+        );
+    }
+
+    #[test]
+    fn test_generate_sscc() {
+        let barcode = generate_sscc(&Sscc::new(3, "0614141", 123_456_789)).unwrap();
+        assert_eq!(barcode.data, "(00)306141411234567891");
+    }
+}
@@ -0,0 +1,218 @@
+//! ANVISA IUM (Identificador Único de Medicamento), RDC 157/2017
+//!
+//! Brazilian medicine serialization identifies each unit by the GTIN of the
+//! presentation, the ANVISA registration number, a serial number, the
+//! expiry date and the lot, carried in a GS1 DataMatrix as AIs (01), (713),
+//! (21), (17) and (10).
+
+use chrono::NaiveDate;
+
+use crate::generators;
+use crate::gs1::{self, Element};
+use crate::types::{Barcode, QuickCodesError, Result};
+
+/// Digits of the ANVISA registration number
+const REGISTRATION_LENGTH: usize = 13;
+
+/// Maximum digits of the serial number
+const MAX_SERIAL_LENGTH: usize = 13;
+
+/// Maximum characters of the lot
+const MAX_LOT_LENGTH: usize = 20;
+
+/// Unique medicine identifier of a unit of sale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ium {
+    /// GTIN of the presentation, 8 to 14 digits (padded to 14 in AI (01))
+    pub gtin: String,
+    /// ANVISA registration number, 13 digits
+    pub registration: String,
+    /// Serial number, 1-13 digits, unique for the GTIN
+    pub serial: String,
+    /// Expiry date; a month-only expiry is the last day of the month
+    pub expiry: NaiveDate,
+    /// Lot, 1-20 GS1 characters
+    pub lot: String,
+}
+
+impl Ium {
+    pub fn new(gtin: &str, registration: &str, serial: &str, expiry: NaiveDate, lot: &str) -> Self {
+        Self {
+            gtin: gtin.to_string(),
+            registration: registration.to_string(),
+            serial: serial.to_string(),
+            expiry,
+            lot: lot.to_string(),
+        }
+    }
+
+    /// The GS1 elements, fixed-length AIs first: (01), (17), (713), (21)
+    /// and (10)
+    pub fn elements(&self) -> Result<Vec<Element>> {
+        let gtin = gs1::normalize_gtin(&self.gtin)?;
+        let numeric = |value: &str| value.bytes().all(|b| b.is_ascii_digit());
+        if self.registration.len() != REGISTRATION_LENGTH || !numeric(&self.registration) {
+            return Err(invalid(format!(
+                "registration number must be {} digits, got '{}'",
+                REGISTRATION_LENGTH, self.registration
+            )));
+        }
+        if self.serial.is_empty() || self.serial.len() > MAX_SERIAL_LENGTH || !numeric(&self.serial)
+        {
+            return Err(invalid(format!(
+                "serial number must be 1-{} digits, got '{}'",
+                MAX_SERIAL_LENGTH, self.serial
+            )));
+        }
+        if self.lot.is_empty() || self.lot.len() > MAX_LOT_LENGTH {
+            return Err(invalid(format!(
+                "lot must be 1-{} characters, got '{}'",
+                MAX_LOT_LENGTH, self.lot
+            )));
+        }
+
+        let elements = vec![
+            Element::new("01", &gtin),
+            Element::new("17", &self.expiry.format("%y%m%d").to_string()),
+            Element::new("713", &self.registration),
+            Element::new("21", &self.serial),
+            Element::new("10", &self.lot),
+        ];
+        gs1::validate_elements(&elements)?;
+        Ok(elements)
+    }
+
+    /// Unbracketed element string as encoded in the DataMatrix
+    pub fn encode(&self) -> Result<String> {
+        Ok(gs1::to_unbracketed(&self.elements()?))
+    }
+
+    /// Human-readable interpretation, e.g.
+    /// `(01)07891000315507(17)270331(713)1234567890123(21)1000001(10)L2026A`
+    pub fn hri(&self) -> Result<String> {
+        Ok(gs1::format_hri(&self.elements()?))
+    }
+
+    /// Read an IUM from an element string in any form accepted by
+    /// [`gs1::parse`]; all five AIs must be present. An expiry day of `00`
+    /// stands for the last day of the month.
+    pub fn parse(data: &str) -> Result<Self> {
+        let elements = gs1::parse(data)?;
+        let value = |ai: &str| {
+            elements
+                .iter()
+                .find(|element| element.ai == ai)
+                .map(|element| element.value.clone())
+                .ok_or_else(|| invalid(format!("AI ({}) is missing", ai)))
+        };
+        let expiry = value("17")?;
+        let ium = Self {
+            gtin: value("01")?,
+            registration: value("713")?,
+            serial: value("21")?,
+            expiry: parse_expiry(&expiry)
+                .ok_or_else(|| invalid(format!("invalid expiry date '{}'", expiry)))?,
+            lot: value("10")?,
+        };
+        ium.elements()?;
+        Ok(ium)
+    }
+}
+
+/// Encode an IUM as a GS1 DataMatrix
+pub fn generate_ium(ium: &Ium) -> Result<Barcode> {
+    generators::generate_gs1_datamatrix(&ium.encode()?)
+}
+
+/// `YYMMDD` of AI (17), with day `00` as the last day of the month
+fn parse_expiry(value: &str) -> Option<NaiveDate> {
+    let year = 2000 + value.get(0..2)?.parse::<i32>().ok()?;
+    let month = value.get(2..4)?.parse().ok()?;
+    match value.get(4..6)?.parse().ok()? {
+        0 if month == 12 => NaiveDate::from_ymd_opt(year, 12, 31),
+        0 => NaiveDate::from_ymd_opt(year, month + 1, 1)?.pred_opt(),
+        day => NaiveDate::from_ymd_opt(year, month, day),
+    }
+}
+
+fn invalid(reason: String) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid ANVISA IUM: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BarcodeType;
+
+    fn ium() -> Ium {
+        Ium::new(
+            "7891000315507",
+            "1234567890123",
+            "1000001",
+            NaiveDate::from_ymd_opt(2027, 3, 31).unwrap(),
+            "L2026A",
+        )
+    }
+
+    #[test]
+    fn test_ium_encode() {
+        let ium = ium();
+        assert_eq!(
+            ium.hri().unwrap(),
+            "(01)07891000315507(17)270331(713)1234567890123(21)1000001(10)L2026A"
+        );
+        assert_eq!(
+            ium.encode().unwrap(),
+            "0107891000315507172703317131234567890123\x1d211000001\x1d10L2026A"
+        );
+
+        let barcode = generate_ium(&ium).unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::DataMatrix);
+        assert_eq!(barcode.data, ium.hri().unwrap());
+
+        // The leading FNC1, two FNC1 separators and 30 codewords of element
+        // data fit the 36 data codewords of a 24 x 24 symbol
+        assert_eq!(barcode.modules.as_matrix().unwrap().len(), 24);
+    }
+
+    #[test]
+    fn test_ium_validation() {
+        let mut bad = ium();
+        bad.gtin = "7891000315508".to_string();
+        assert!(bad.encode().is_err());
+
+        let mut bad = ium();
+        bad.registration = "123456789012".to_string();
+        assert!(bad.encode().is_err());
+
+        let mut bad = ium();
+        bad.serial = "ABC123".to_string();
+        assert!(bad.encode().is_err());
+        bad.serial = "12345678901234".to_string();
+        assert!(bad.encode().is_err());
+
+        let mut bad = ium();
+        bad.lot = "L~1".to_string();
+        assert!(bad.encode().is_err());
+        bad.lot = String::new();
+        assert!(bad.encode().is_err());
+    }
+
+    #[test]
+    fn test_ium_parse() {
+        // The GTIN is read in its 14-digit form
+        let ium = Ium {
+            gtin: "07891000315507".to_string(),
+            ..ium()
+        };
+        assert_eq!(Ium::parse(&ium.hri().unwrap()).unwrap(), ium);
+        assert_eq!(Ium::parse(&ium.encode().unwrap()).unwrap(), ium);
+
+        // Day 00 is the end of the month
+        let parsed =
+            Ium::parse("(01)07891000315507(17)270200(713)1234567890123(21)1(10)L1").unwrap();
+        assert_eq!(parsed.expiry, NaiveDate::from_ymd_opt(2027, 2, 28).unwrap());
+
+        assert!(Ium::parse("(01)07891000315507(17)270331(21)1(10)L1").is_err());
+    }
+}
@@ -0,0 +1,495 @@
+//! HIBC LIC data structures (ANSI/HIBC 2.6)
+//!
+//! The Health Industry Bar Code Labeler Identification Code identifies
+//! medical devices without GS1 numbers. The primary data structure holds
+//! the labeler code, product code and unit of measure; the secondary one
+//! holds quantity, expiry date and lot or serial number. Each structure
+//! starts with the `+` flag and ends with a modulo-43 check character. A
+//! standalone secondary structure carries the primary check character as a
+//! link, and both can be joined with `/` in a single symbol.
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::generators;
+use crate::types::{Barcode, BarcodeType, QuickCodesError, Result};
+
+/// Characters of the modulo-43 check, in value order
+const CHECK_CHARACTERS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ-. $/+%";
+
+const MAX_PRODUCT_LENGTH: usize = 18;
+const MAX_LOT_LENGTH: usize = 18;
+
+/// Primary data structure: labeler, product and unit of measure
+#[derive(Debug, Clone)]
+pub struct HibcPrimary {
+    /// Labeler Identification Code: 4 characters, the first alphabetic
+    pub labeler: String,
+    /// Labeler's product or catalog number: 1-18 alphanumeric characters
+    pub product: String,
+    /// Unit of measure: 0 for the unit of use, 1-8 for packaging levels and
+    /// 9 for variable quantity
+    pub unit_of_measure: u8,
+}
+
+/// Expiry date of the secondary data structure in one of the HIBC formats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HibcDate {
+    /// MMYY
+    MonthYear(String),
+    /// MMDDYY
+    MonthDayYear(String),
+    /// YYMMDD
+    YearMonthDay(String),
+    /// YYMMDDHH
+    YearMonthDayHour(String),
+    /// YYJJJ (Julian day)
+    JulianDay(String),
+    /// YYJJJHH
+    JulianDayHour(String),
+}
+
+/// Secondary data structure: quantity, expiry and lot or serial number
+#[derive(Debug, Clone, Default)]
+pub struct HibcSecondary {
+    /// Lot or batch number, up to 18 alphanumeric characters
+    pub lot: Option<String>,
+    /// Serial number, up to 18 alphanumeric characters (instead of a lot)
+    pub serial: Option<String>,
+    /// Expiry date
+    pub expiry: Option<HibcDate>,
+    /// Quantity, 1-99999 (lot structures only)
+    pub quantity: Option<u32>,
+}
+
+impl HibcPrimary {
+    /// Primary data structure with its check character, e.g.
+    /// `+A123BJC5D6E71G`
+    pub fn encode(&self) -> Result<String> {
+        let data = self.data()?;
+        let check = check_character(&data)?;
+        Ok(format!("{}{}", data, check))
+    }
+
+    /// `+` flag, labeler, product and unit of measure without the check
+    /// character
+    fn data(&self) -> Result<String> {
+        let labeler = self.labeler.to_ascii_uppercase();
+        let valid_labeler = labeler.len() == 4
+            && labeler.starts_with(|c: char| c.is_ascii_alphabetic())
+            && labeler.chars().all(|c| c.is_ascii_alphanumeric());
+        if !valid_labeler {
+            return Err(QuickCodesError::InvalidData(format!(
+                "HIBC labeler code must be 4 alphanumeric characters starting with a letter, got '{}'",
+                self.labeler
+            )));
+        }
+
+        let product = self.product.to_ascii_uppercase();
+        if product.is_empty()
+            || product.len() > MAX_PRODUCT_LENGTH
+            || !product.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(QuickCodesError::InvalidData(format!(
+                "HIBC product code must be 1-{} alphanumeric characters, got '{}'",
+                MAX_PRODUCT_LENGTH, self.product
+            )));
+        }
+
+        if self.unit_of_measure > 9 {
+            return Err(QuickCodesError::InvalidData(format!(
+                "HIBC unit of measure must be a digit 0-9, got {}",
+                self.unit_of_measure
+            )));
+        }
+
+        Ok(format!("+{}{}{}", labeler, product, self.unit_of_measure))
+    }
+}
+
+impl HibcSecondary {
+    /// Standalone secondary data structure linked to a primary one through
+    /// its check character
+    pub fn encode(&self, primary: &HibcPrimary) -> Result<String> {
+        let link = primary
+            .encode()?
+            .pop()
+            .expect("encoded primary is not empty");
+        let data = format!("+{}{}", self.data()?, link);
+        let check = check_character(&data)?;
+        Ok(format!("{}{}", data, check))
+    }
+
+    /// Secondary fields starting with the `$` flag, without `+`, link and
+    /// check characters
+    fn data(&self) -> Result<String> {
+        let expiry = self.expiry.as_ref().map(HibcDate::encode).transpose()?;
+        match (&self.lot, &self.serial) {
+            (Some(_), Some(_)) => Err(QuickCodesError::InvalidData(
+                "HIBC secondary data holds either a lot or a serial number, not both".to_string(),
+            )),
+            (None, Some(serial)) => {
+                if self.quantity.is_some() {
+                    return Err(QuickCodesError::InvalidData(
+                        "HIBC quantity is only allowed with a lot number".to_string(),
+                    ));
+                }
+                let serial = identifier(serial, "serial number")?;
+                Ok(match expiry {
+                    Some(date) => format!("$$+{}{}", date, serial),
+                    None => format!("$+{}", serial),
+                })
+            }
+            (lot, None) => {
+                let lot =
+                    match lot {
+                        Some(lot) => identifier(lot, "lot number")?,
+                        None if expiry.is_some() || self.quantity.is_some() => String::new(),
+                        None => return Err(QuickCodesError::InvalidData(
+                            "HIBC secondary data needs a lot, serial number, expiry or quantity"
+                                .to_string(),
+                        )),
+                    };
+                let quantity = match self.quantity {
+                    None => String::new(),
+                    Some(quantity @ 1..=99) => format!("8{:02}", quantity),
+                    Some(quantity @ 100..=99_999) => format!("9{:05}", quantity),
+                    Some(quantity) => {
+                        return Err(QuickCodesError::InvalidData(format!(
+                            "HIBC quantity must be 1-99999, got {}",
+                            quantity
+                        )))
+                    }
+                };
+                Ok(match (quantity.is_empty(), expiry) {
+                    (true, None) => format!("${}", lot),
+                    (_, Some(date)) => format!("$${}{}{}", quantity, date, lot),
+                    // "7" marks a quantity without expiry date
+                    (false, None) => format!("$${}7{}", quantity, lot),
+                })
+            }
+        }
+    }
+}
+
+impl HibcDate {
+    /// Format flag followed by the date digits
+    fn encode(&self) -> Result<String> {
+        let (flag, digits, length) = match self {
+            HibcDate::MonthYear(digits) => ("", digits, 4),
+            HibcDate::MonthDayYear(digits) => ("2", digits, 6),
+            HibcDate::YearMonthDay(digits) => ("3", digits, 6),
+            HibcDate::YearMonthDayHour(digits) => ("4", digits, 8),
+            HibcDate::JulianDay(digits) => ("5", digits, 5),
+            HibcDate::JulianDayHour(digits) => ("6", digits, 7),
+        };
+        if digits.len() != length || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(QuickCodesError::InvalidData(format!(
+                "HIBC expiry date {:?} must have {} digits",
+                self, length
+            )));
+        }
+        // Also keeps the MMYY month, which has no flag, from reading as one
+        if self.date_time(digits).is_none() {
+            return Err(QuickCodesError::InvalidData(format!(
+                "Invalid HIBC expiry date {}",
+                digits
+            )));
+        }
+        Ok(format!("{}{}", flag, digits))
+    }
+
+    /// Date and hour of validated `digits`, `None` when they name no real
+    /// date; years are 20YY
+    fn date_time(&self, digits: &str) -> Option<NaiveDateTime> {
+        let number =
+            |range: std::ops::Range<usize>| -> u32 { digits[range].parse().expect("digits") };
+        let year = |range| 2000 + number(range) as i32;
+        let (date, hour) = match self {
+            HibcDate::MonthYear(_) => (NaiveDate::from_ymd_opt(year(2..4), number(0..2), 1), 0),
+            HibcDate::MonthDayYear(_) => (
+                NaiveDate::from_ymd_opt(year(4..6), number(0..2), number(2..4)),
+                0,
+            ),
+            HibcDate::YearMonthDay(_) => (
+                NaiveDate::from_ymd_opt(year(0..2), number(2..4), number(4..6)),
+                0,
+            ),
+            HibcDate::YearMonthDayHour(_) => (
+                NaiveDate::from_ymd_opt(year(0..2), number(2..4), number(4..6)),
+                number(6..8),
+            ),
+            HibcDate::JulianDay(_) => (NaiveDate::from_yo_opt(year(0..2), number(2..5)), 0),
+            HibcDate::JulianDayHour(_) => (
+                NaiveDate::from_yo_opt(year(0..2), number(2..5)),
+                number(5..7),
+            ),
+        };
+        date?.and_hms_opt(hour, 0, 0)
+    }
+}
+
+/// Lot or serial number: up to 18 alphanumeric characters, upper case
+fn identifier(value: &str, name: &str) -> Result<String> {
+    if value.is_empty()
+        || value.len() > MAX_LOT_LENGTH
+        || !value.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return Err(QuickCodesError::InvalidData(format!(
+            "HIBC {} must be 1-{} alphanumeric characters, got '{}'",
+            name, MAX_LOT_LENGTH, value
+        )));
+    }
+    Ok(value.to_ascii_uppercase())
+}
+
+/// Primary and secondary data joined with `/` in a single structure
+pub fn encode_concatenated(primary: &HibcPrimary, secondary: &HibcSecondary) -> Result<String> {
+    let data = format!("{}/{}", primary.data()?, secondary.data()?);
+    let check = check_character(&data)?;
+    Ok(format!("{}{}", data, check))
+}
+
+/// Modulo-43 check character over all characters, including the `+` flag
+pub fn check_character(data: &str) -> Result<char> {
+    let mut sum = 0;
+    for c in data.chars() {
+        let value = CHECK_CHARACTERS.find(c).ok_or_else(|| {
+            QuickCodesError::InvalidData(format!("Invalid character '{}' for HIBC", c))
+        })?;
+        sum += value;
+    }
+    Ok(char::from(CHECK_CHARACTERS.as_bytes()[sum % 43]))
+}
+
+/// Encode HIBC data with one of the symbologies HIBC allows: Code 128,
+/// Code 39, DataMatrix or QR Code
+///
+/// The data must be a complete structure as returned by the `encode`
+/// functions; its check character is verified.
+pub fn generate_hibc(data: &str, barcode_type: BarcodeType) -> Result<Barcode> {
+    let (body, check) = match data.char_indices().last() {
+        Some((index, check)) if data.starts_with('+') && index > 1 => (&data[..index], check),
+        _ => {
+            return Err(QuickCodesError::InvalidData(
+                "HIBC data must start with '+' and end with a check character".to_string(),
+            ))
+        }
+    };
+    let expected = check_character(body)?;
+    if check != expected {
+        return Err(QuickCodesError::InvalidData(format!(
+            "Invalid HIBC check character: expected {}, got {}",
+            expected, check
+        )));
+    }
+
+    match barcode_type {
+        BarcodeType::Code128 => generators::generate_code128(data),
+        BarcodeType::Code39 => generators::generate_code39(data)
+            .map_err(|e| QuickCodesError::GenerationError(e.to_string())),
+        BarcodeType::DataMatrix => generators::generate_datamatrix(data),
+        BarcodeType::QRCode => generators::generate_qr(data),
+        _ => Err(QuickCodesError::InvalidType(format!(
+            "HIBC is not defined for {:?}; use Code128, Code39, DataMatrix or QRCode",
+            barcode_type
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::code128::{append_widths, PATTERNS};
+
+    fn primary() -> HibcPrimary {
+        HibcPrimary {
+            labeler: "A123".to_string(),
+            product: "BJC5D6E7".to_string(),
+            unit_of_measure: 1,
+        }
+    }
+
+    #[test]
+    fn test_hibc_check_character() {
+        assert_eq!(check_character("+A123BJC5D6E71").unwrap(), 'G');
+        assert_eq!(check_character("+").unwrap(), '+');
+        assert!(check_character("+a").is_err());
+    }
+
+    #[test]
+    fn test_hibc_primary() {
+        assert_eq!(primary().encode().unwrap(), "+A123BJC5D6E71G");
+
+        let invalid = [
+            ("1234", "ABC", 0),
+            ("A12", "ABC", 0),
+            ("A123", "", 0),
+            ("A123", "AB-C", 0),
+            ("A123", "ABC", 10),
+        ];
+        for (labeler, product, unit_of_measure) in invalid {
+            let primary = HibcPrimary {
+                labeler: labeler.to_string(),
+                product: product.to_string(),
+                unit_of_measure,
+            };
+            assert!(
+                primary.encode().is_err(),
+                "{} {} {}",
+                labeler,
+                product,
+                unit_of_measure
+            );
+        }
+    }
+
+    #[test]
+    fn test_hibc_secondary_formats() {
+        let lot = |expiry: Option<HibcDate>, quantity: Option<u32>| HibcSecondary {
+            lot: Some("3C001".to_string()),
+            expiry,
+            quantity,
+            ..Default::default()
+        };
+        let cases = [
+            (lot(None, None), "$3C001"),
+            (
+                lot(Some(HibcDate::MonthYear("0526".to_string())), None),
+                "$$05263C001",
+            ),
+            (
+                lot(Some(HibcDate::YearMonthDay("260531".to_string())), None),
+                "$$32605313C001",
+            ),
+            (
+                lot(Some(HibcDate::JulianDay("26150".to_string())), None),
+                "$$5261503C001",
+            ),
+            (lot(None, Some(24)), "$$82473C001"),
+            (
+                lot(Some(HibcDate::MonthYear("0526".to_string())), Some(1000)),
+                "$$90100005263C001",
+            ),
+        ];
+        for (secondary, expected) in cases {
+            assert_eq!(secondary.data().unwrap(), expected);
+        }
+
+        let serial = HibcSecondary {
+            serial: Some("SN123".to_string()),
+            expiry: Some(HibcDate::MonthDayYear("053126".to_string())),
+            ..Default::default()
+        };
+        assert_eq!(serial.data().unwrap(), "$$+2053126SN123");
+    }
+
+    #[test]
+    fn test_hibc_secondary_link() {
+        let secondary = HibcSecondary {
+            lot: Some("3C001".to_string()),
+            expiry: Some(HibcDate::JulianDay("26150".to_string())),
+            ..Default::default()
+        };
+        let encoded = secondary.encode(&primary()).unwrap();
+        assert!(encoded.starts_with("+$$5261503C001G"));
+        assert_eq!(encoded.len(), 16);
+        assert_eq!(
+            check_character(&encoded[..15]).unwrap(),
+            encoded.chars().last().unwrap()
+        );
+
+        let concatenated = encode_concatenated(&primary(), &secondary).unwrap();
+        assert!(concatenated.starts_with("+A123BJC5D6E71/$$5261503C001"));
+    }
+
+    #[test]
+    fn test_hibc_secondary_invalid() {
+        let invalid = [
+            HibcSecondary::default(),
+            HibcSecondary {
+                lot: Some("LOT".to_string()),
+                serial: Some("SN".to_string()),
+                ..Default::default()
+            },
+            HibcSecondary {
+                serial: Some("SN".to_string()),
+                quantity: Some(5),
+                ..Default::default()
+            },
+            HibcSecondary {
+                lot: Some("LOT".to_string()),
+                quantity: Some(100_000),
+                ..Default::default()
+            },
+            HibcSecondary {
+                expiry: Some(HibcDate::MonthYear("1326".to_string())),
+                ..Default::default()
+            },
+            HibcSecondary {
+                expiry: Some(HibcDate::YearMonthDay("2605".to_string())),
+                ..Default::default()
+            },
+        ];
+        for secondary in invalid {
+            assert!(secondary.data().is_err(), "{:?}", secondary);
+        }
+
+        // Impossible dates and hours in every format
+        let invalid_dates = [
+            HibcDate::MonthDayYear("022926".to_string()),
+            HibcDate::YearMonthDay("251399".to_string()),
+            HibcDate::YearMonthDay("260431".to_string()),
+            HibcDate::YearMonthDayHour("26053124".to_string()),
+            HibcDate::JulianDay("26366".to_string()),
+            HibcDate::JulianDay("26000".to_string()),
+            HibcDate::JulianDayHour("2615025".to_string()),
+        ];
+        for expiry in invalid_dates {
+            let secondary = HibcSecondary {
+                expiry: Some(expiry),
+                ..Default::default()
+            };
+            assert!(secondary.data().is_err(), "{:?}", secondary);
+        }
+        for expiry in [
+            HibcDate::MonthDayYear("022928".to_string()),
+            HibcDate::JulianDay("28366".to_string()),
+            HibcDate::YearMonthDayHour("26053123".to_string()),
+        ] {
+            let secondary = HibcSecondary {
+                expiry: Some(expiry),
+                ..Default::default()
+            };
+            assert!(secondary.data().is_ok(), "{:?}", secondary);
+        }
+    }
+
+    #[test]
+    fn test_generate_hibc() {
+        let data = primary().encode().unwrap();
+        for barcode_type in [
+            BarcodeType::Code128,
+            BarcodeType::Code39,
+            BarcodeType::DataMatrix,
+            BarcodeType::QRCode,
+        ] {
+            let barcode = generate_hibc(&data, barcode_type).unwrap();
+            assert_eq!(barcode.barcode_type, barcode_type);
+        }
+
+        // Code 128 symbol characters, all in code set B, with the modulo-103
+        // check character (104 + 1*11 + 2*33 + ... + 15*39) % 103 = 32
+        let barcode = generate_hibc(&data, BarcodeType::Code128).unwrap();
+        let mut expected = Vec::new();
+        for value in [
+            104, 11, 33, 17, 18, 19, 34, 42, 35, 21, 36, 22, 37, 23, 17, 39, 32, 106,
+        ] {
+            append_widths(&mut expected, PATTERNS[value], true);
+        }
+        assert_eq!(barcode.modules.as_linear().unwrap(), &expected[..]);
+
+        assert!(generate_hibc("+A123BJC5D6E71H", BarcodeType::Code39).is_err());
+        assert!(generate_hibc("A123BJC5D6E71G", BarcodeType::Code39).is_err());
+        assert!(generate_hibc(&data, BarcodeType::EAN13).is_err());
+    }
+}
@@ -0,0 +1,478 @@
+//! ISBT 128 data structures (ICCBBA ST-001)
+//!
+//! ISBT 128 is the labeling standard for blood, cells, tissues and organs.
+//! Each data structure starts with a two-character data identifier, is
+//! printed as its own Code 128 symbol and can be combined with others in a
+//! DataMatrix compound message. The donation identification number has an
+//! ISO 7064 mod 37-2 check character that is not encoded but printed in a
+//! box for keyboard entry.
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::generators;
+use crate::types::{Barcode, QuickCodesError, Result};
+
+/// Characters of the ISO 7064 mod 37-2 check, in value order
+const CHECK_CHARACTERS: &[u8; 37] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ*";
+
+/// Donation Identification Number (data structure 001)
+#[derive(Debug, Clone)]
+pub struct DonationNumber {
+    /// Facility identification number: a letter (not O) or digit 1-9
+    /// followed by 4 digits, e.g. `W1234`
+    pub facility: String,
+    /// Year of collection (last two digits)
+    pub year: u8,
+    /// Sequence number assigned by the facility, up to 6 digits
+    pub sequence: u32,
+    /// Flag characters (00 when not used)
+    pub flags: u8,
+}
+
+/// ABO and RhD blood group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BloodGroup {
+    OPositive,
+    ONegative,
+    APositive,
+    ANegative,
+    BPositive,
+    BNegative,
+    ABPositive,
+    ABNegative,
+}
+
+/// Blood Groups [ABO and RhD] (data structure 002)
+#[derive(Debug, Clone)]
+pub struct BloodGroups {
+    pub group: BloodGroup,
+    /// Rh, Kell and Mia/Mur phenotype code (`0` when not tested)
+    pub phenotype: char,
+}
+
+/// Product Code (data structure 003)
+#[derive(Debug, Clone)]
+pub struct ProductCode {
+    /// Product description code: a letter and 4 digits, e.g. `E0001`
+    pub description: String,
+    /// Type of donation or collection, e.g. `V` (volunteer allogeneic) or
+    /// `0` (not specified)
+    pub donation_type: char,
+    /// Division code (`00` when the product is not divided)
+    pub division: String,
+}
+
+/// Expiration Date (data structure 004), with the time of day as data
+/// structure 005
+#[derive(Debug, Clone)]
+pub struct Expiration {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    /// Hour and minute; the date alone expires at 23:59
+    pub time: Option<(u8, u8)>,
+}
+
+/// Special testing data structures
+#[derive(Debug, Clone)]
+pub enum SpecialTesting {
+    /// Special Testing: General (data structure 010), a 5-character code
+    General(String),
+    /// Special Testing: Red Blood Cell Antigens (data structure 012),
+    /// 18 digits
+    RedCellAntigens(String),
+}
+
+/// An ISBT 128 data structure
+#[derive(Debug, Clone)]
+pub enum IsbtData {
+    Donation(DonationNumber),
+    BloodGroups(BloodGroups),
+    Product(ProductCode),
+    Expiration(Expiration),
+    SpecialTesting(SpecialTesting),
+}
+
+impl DonationNumber {
+    /// The 13 characters of the identification number: facility, year and
+    /// sequence
+    fn number(&self) -> Result<String> {
+        let facility = self.facility.to_ascii_uppercase();
+        let valid_facility = facility.len() == 5
+            && facility.bytes().next().is_some_and(|c| {
+                (c.is_ascii_uppercase() && c != b'O') || (b'1'..=b'9').contains(&c)
+            })
+            && facility[1..].bytes().all(|c| c.is_ascii_digit());
+        if !valid_facility {
+            return Err(QuickCodesError::InvalidData(format!(
+                "ISBT 128 facility number must be a letter or digit 1-9 followed by 4 digits, got '{}'",
+                self.facility
+            )));
+        }
+        if self.year > 99 || self.sequence > 999_999 || self.flags > 99 {
+            return Err(QuickCodesError::InvalidData(format!(
+                "Invalid ISBT 128 donation number: year {}, sequence {}, flags {}",
+                self.year, self.sequence, self.flags
+            )));
+        }
+        Ok(format!("{}{:02}{:06}", facility, self.year, self.sequence))
+    }
+
+    /// Keyboard entry check character over the 13-character number
+    pub fn check_character(&self) -> Result<char> {
+        check_character(&self.number()?)
+    }
+
+    /// Eye-readable form with spaces between facility, year, sequence and
+    /// flags, e.g. `W1234 26 000123 00`
+    fn text(&self) -> Result<String> {
+        let number = self.number()?;
+        Ok(format!(
+            "{} {} {} {:02}",
+            &number[..5],
+            &number[5..7],
+            &number[7..],
+            self.flags
+        ))
+    }
+}
+
+impl BloodGroup {
+    /// Blood group code (ISBT 128 Table RT005)
+    fn code(self) -> &'static str {
+        match self {
+            BloodGroup::ONegative => "95",
+            BloodGroup::OPositive => "51",
+            BloodGroup::ANegative => "06",
+            BloodGroup::APositive => "62",
+            BloodGroup::BNegative => "17",
+            BloodGroup::BPositive => "73",
+            BloodGroup::ABNegative => "28",
+            BloodGroup::ABPositive => "84",
+        }
+    }
+}
+
+impl Expiration {
+    /// Century digit, year and day of the year: `cyyjjj`
+    fn julian_date(&self) -> Result<String> {
+        let date = NaiveDate::from_ymd_opt(
+            i32::from(self.year),
+            u32::from(self.month),
+            u32::from(self.day),
+        )
+        .filter(|_| (2000..3000).contains(&self.year))
+        .ok_or_else(|| {
+            QuickCodesError::InvalidData(format!(
+                "Invalid ISBT 128 expiration date {}-{:02}-{:02}",
+                self.year, self.month, self.day
+            ))
+        })?;
+        Ok(format!(
+            "{}{:02}{:03}",
+            self.year / 100 - 20,
+            self.year % 100,
+            date.ordinal()
+        ))
+    }
+}
+
+impl IsbtData {
+    /// Data identifier and data content
+    pub fn encode(&self) -> Result<String> {
+        match self {
+            IsbtData::Donation(donation) => {
+                Ok(format!("={}{:02}", donation.number()?, donation.flags))
+            }
+            IsbtData::BloodGroups(groups) => {
+                if !groups.phenotype.is_ascii_alphanumeric() {
+                    return Err(QuickCodesError::InvalidData(format!(
+                        "Invalid ISBT 128 phenotype code '{}'",
+                        groups.phenotype
+                    )));
+                }
+                // The last character is reserved and always 0
+                Ok(format!("=%{}{}0", groups.group.code(), groups.phenotype))
+            }
+            IsbtData::Product(product) => {
+                let description = product.description.to_ascii_uppercase();
+                let valid = description.len() == 5
+                    && description.starts_with(|c: char| c.is_ascii_uppercase())
+                    && description[1..].chars().all(|c| c.is_ascii_digit())
+                    && product.donation_type.is_ascii_alphanumeric()
+                    && product.division.len() == 2
+                    && product.division.chars().all(|c| c.is_ascii_alphanumeric());
+                if !valid {
+                    return Err(QuickCodesError::InvalidData(format!(
+                        "Invalid ISBT 128 product code {}{}{}",
+                        product.description, product.donation_type, product.division
+                    )));
+                }
+                Ok(format!(
+                    "=<{}{}{}",
+                    description,
+                    product.donation_type.to_ascii_uppercase(),
+                    product.division.to_ascii_uppercase()
+                ))
+            }
+            IsbtData::Expiration(expiration) => {
+                let date = expiration.julian_date()?;
+                match expiration.time {
+                    None => Ok(format!("=>{}", date)),
+                    Some((hour, minute)) if hour < 24 && minute < 60 => {
+                        Ok(format!("&>{}{:02}{:02}", date, hour, minute))
+                    }
+                    Some((hour, minute)) => Err(QuickCodesError::InvalidData(format!(
+                        "Invalid ISBT 128 expiration time {:02}:{:02}",
+                        hour, minute
+                    ))),
+                }
+            }
+            IsbtData::SpecialTesting(SpecialTesting::General(code)) => {
+                if code.len() != 5 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    return Err(QuickCodesError::InvalidData(format!(
+                        "ISBT 128 special testing code must be 5 alphanumeric characters, got '{}'",
+                        code
+                    )));
+                }
+                Ok(format!("&({}", code.to_ascii_uppercase()))
+            }
+            IsbtData::SpecialTesting(SpecialTesting::RedCellAntigens(antigens)) => {
+                if antigens.len() != 18 || !antigens.chars().all(|c| c.is_ascii_digit()) {
+                    return Err(QuickCodesError::InvalidData(format!(
+                        "ISBT 128 red cell antigen data must be 18 digits, got '{}'",
+                        antigens
+                    )));
+                }
+                Ok(format!("=\\{}", antigens))
+            }
+        }
+    }
+
+    /// Data structure number in ST-001
+    fn number(&self) -> u16 {
+        match self {
+            IsbtData::Donation(_) => 1,
+            IsbtData::BloodGroups(_) => 2,
+            IsbtData::Product(_) => 3,
+            IsbtData::Expiration(Expiration { time: None, .. }) => 4,
+            IsbtData::Expiration(_) => 5,
+            IsbtData::SpecialTesting(SpecialTesting::General(_)) => 10,
+            IsbtData::SpecialTesting(SpecialTesting::RedCellAntigens(_)) => 12,
+        }
+    }
+}
+
+/// ISO 7064 mod 37-2 check character
+pub fn check_character(data: &str) -> Result<char> {
+    let mut sum = 0;
+    for c in data.chars() {
+        let value = CHECK_CHARACTERS
+            .iter()
+            .position(|&check| char::from(check) == c)
+            .ok_or_else(|| {
+                QuickCodesError::InvalidData(format!("Invalid character '{}' for ISBT 128", c))
+            })?;
+        sum = (sum + value) * 2 % 37;
+    }
+    Ok(char::from(CHECK_CHARACTERS[(38 - sum) % 37]))
+}
+
+/// Data of two symbols read as one by a concatenating scanner
+///
+/// Only the pairs printed side by side on the label may be concatenated:
+/// donation number with blood groups, and product code with expiration
+/// date.
+pub fn concatenate(first: &IsbtData, second: &IsbtData) -> Result<String> {
+    match (first.number(), second.number()) {
+        (1, 2) | (3, 4) | (3, 5) => Ok(format!("{}{}", first.encode()?, second.encode()?)),
+        (a, b) => Err(QuickCodesError::InvalidData(format!(
+            "ISBT 128 data structures {:03} and {:03} cannot be concatenated",
+            a, b
+        ))),
+    }
+}
+
+/// Compound message (data structure 023) holding several data structures
+/// for a single 2D symbol
+pub fn compound_message(structures: &[IsbtData]) -> Result<String> {
+    if !(2..=99).contains(&structures.len()) {
+        return Err(QuickCodesError::InvalidData(format!(
+            "An ISBT 128 compound message holds 2-99 data structures, got {}",
+            structures.len()
+        )));
+    }
+    // Reference 000: no predefined sequence of data structures
+    let mut message = format!("=+{:02}000", structures.len());
+    for structure in structures {
+        message.push_str(&structure.encode()?);
+    }
+    Ok(message)
+}
+
+/// Generate the Code 128 symbol of a data structure
+///
+/// The human-readable text leaves out the data identifier. For a donation
+/// number it is split in groups and the keyboard entry check character is
+/// printed in a box after it.
+pub fn generate_isbt128(structure: &IsbtData) -> Result<Barcode> {
+    let data = structure.encode()?;
+    let mut barcode = generators::generate_code128(&data)?;
+    match structure {
+        IsbtData::Donation(donation) => {
+            barcode.data = donation.text()?;
+            barcode.config.boxed_character = Some(donation.check_character()?);
+        }
+        _ => barcode.data = data[2..].to_string(),
+    }
+    Ok(barcode)
+}
+
+/// Generate a DataMatrix symbol with a compound message
+pub fn generate_isbt128_compound(structures: &[IsbtData]) -> Result<Barcode> {
+    generators::generate_datamatrix(&compound_message(structures)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::code128::{append_widths, PATTERNS};
+    use crate::types::BarcodeType;
+
+    fn donation() -> DonationNumber {
+        DonationNumber {
+            facility: "G1234".to_string(),
+            year: 17,
+            sequence: 123456,
+            flags: 0,
+        }
+    }
+
+    fn product() -> IsbtData {
+        IsbtData::Product(ProductCode {
+            description: "E0001".to_string(),
+            donation_type: 'V',
+            division: "00".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_isbt128_check_character() {
+        // ISO 7064 mod 37-2: the check character makes the weighted sum 1
+        for data in ["G123417123456", "W000026000001", "A999999999999"] {
+            let check = check_character(data).unwrap();
+            let mut sum = 0;
+            for c in format!("{}{}", data, check).bytes() {
+                let value = CHECK_CHARACTERS.iter().position(|&x| x == c).unwrap();
+                sum = (sum * 2 + value) % 37;
+            }
+            assert_eq!(sum, 1, "{}", data);
+        }
+        assert_eq!(check_character("0000000000000").unwrap(), '1');
+        assert!(check_character("t").is_err());
+        assert!(check_character("G12341712345é").is_err());
+    }
+
+    #[test]
+    fn test_isbt128_donation_number() {
+        let structure = IsbtData::Donation(donation());
+        assert_eq!(structure.encode().unwrap(), "=G12341712345600");
+        assert_eq!(donation().text().unwrap(), "G1234 17 123456 00");
+
+        for facility in ["O1234", "01234", "G123", "GA234"] {
+            let invalid = DonationNumber {
+                facility: facility.to_string(),
+                ..donation()
+            };
+            assert!(invalid.number().is_err(), "{}", facility);
+        }
+    }
+
+    #[test]
+    fn test_isbt128_structures() {
+        let groups = IsbtData::BloodGroups(BloodGroups {
+            group: BloodGroup::ONegative,
+            phenotype: '0',
+        });
+        assert_eq!(groups.encode().unwrap(), "=%9500");
+        assert_eq!(product().encode().unwrap(), "=<E0001V00");
+
+        let mut expiration = Expiration {
+            year: 2026,
+            month: 3,
+            day: 1,
+            time: None,
+        };
+        assert_eq!(
+            IsbtData::Expiration(expiration.clone()).encode().unwrap(),
+            "=>026060"
+        );
+        expiration.time = Some((14, 30));
+        assert_eq!(
+            IsbtData::Expiration(expiration.clone()).encode().unwrap(),
+            "&>0260601430"
+        );
+        expiration.year = 2028;
+        expiration.time = None;
+        assert_eq!(
+            IsbtData::Expiration(expiration.clone()).encode().unwrap(),
+            "=>028061"
+        );
+        expiration.month = 2;
+        expiration.day = 30;
+        assert!(IsbtData::Expiration(expiration).encode().is_err());
+
+        let general = IsbtData::SpecialTesting(SpecialTesting::General("N0001".to_string()));
+        assert_eq!(general.encode().unwrap(), "&(N0001");
+        let antigens = IsbtData::SpecialTesting(SpecialTesting::RedCellAntigens("0".repeat(18)));
+        assert_eq!(antigens.encode().unwrap(), format!("=\\{}", "0".repeat(18)));
+        let invalid = IsbtData::SpecialTesting(SpecialTesting::RedCellAntigens("123".to_string()));
+        assert!(invalid.encode().is_err());
+    }
+
+    #[test]
+    fn test_isbt128_concatenation() {
+        let groups = IsbtData::BloodGroups(BloodGroups {
+            group: BloodGroup::APositive,
+            phenotype: '0',
+        });
+        let donation = IsbtData::Donation(donation());
+        assert_eq!(
+            concatenate(&donation, &groups).unwrap(),
+            "=G12341712345600=%6200"
+        );
+        assert!(concatenate(&groups, &donation).is_err());
+        assert!(concatenate(&donation, &product()).is_err());
+
+        let message = compound_message(&[donation, product()]).unwrap();
+        assert_eq!(message, "=+02000=G12341712345600=<E0001V00");
+        assert!(compound_message(&[product()]).is_err());
+    }
+
+    #[test]
+    fn test_generate_isbt128() {
+        let barcode = generate_isbt128(&IsbtData::Donation(donation())).unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::Code128);
+        assert_eq!(barcode.data, "G1234 17 123456 00");
+        assert_eq!(
+            barcode.config.boxed_character,
+            Some(check_character("G123417123456").unwrap())
+        );
+
+        // =G in code set B, then the 14 digits in code set C; check
+        // character (104 + 1*29 + 2*39 + 3*99 + ... + 10*0) % 103 = 40
+        let mut expected = Vec::new();
+        for value in [104, 29, 39, 99, 12, 34, 17, 12, 34, 56, 0, 40, 106] {
+            append_widths(&mut expected, PATTERNS[value], true);
+        }
+        assert_eq!(barcode.modules.as_linear().unwrap(), &expected[..]);
+
+        let barcode = generate_isbt128(&product()).unwrap();
+        assert_eq!(barcode.data, "E0001V00");
+        assert_eq!(barcode.config.boxed_character, None);
+
+        let barcode =
+            generate_isbt128_compound(&[IsbtData::Donation(donation()), product()]).unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::DataMatrix);
+    }
+}
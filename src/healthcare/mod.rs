@@ -0,0 +1,12 @@
+//! Healthcare data structures encoded with the existing generators
+
+pub mod anvisa;
+pub mod hibc;
+pub mod isbt128;
+
+pub use anvisa::{generate_ium, Ium};
+pub use hibc::{encode_concatenated, generate_hibc, HibcDate, HibcPrimary, HibcSecondary};
+pub use isbt128::{
+    compound_message, concatenate, generate_isbt128, generate_isbt128_compound, BloodGroup,
+    BloodGroups, DonationNumber, Expiration, IsbtData, ProductCode, SpecialTesting,
+};
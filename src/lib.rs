@@ -1,7 +1,11 @@
 use anyhow::Result as AnyhowResult;
 
 pub mod exporters;
+pub mod fiscal;
 pub mod generators;
+pub mod gs1;
+pub mod healthcare;
+pub mod payloads;
 #[cfg(feature = "readers")]
 pub mod readers;
 pub mod types;
@@ -61,16 +65,62 @@ pub fn generate(
         BarcodeType::EAN13 => generators::ean13::generate_ean13(data)?,
         BarcodeType::UPCA => generators::upc::generate_upc_a(data)?,
         BarcodeType::Code128 => generators::code128::generate_code128(data)?,
+        BarcodeType::GS1128 => generators::code128::generate_gs1_128(data)?,
 
         // Phase 2: Advanced 2D codes
         BarcodeType::DataMatrix => generators::datamatrix::generate_datamatrix(data)?,
         BarcodeType::PDF417 => generators::pdf417::generate_pdf417(data)?,
         BarcodeType::Aztec => generators::aztec::generate_aztec(data)?,
+        BarcodeType::MaxiCode => generators::maxicode::generate_maxicode(data)?,
+        BarcodeType::HanXin => generators::hanxin::generate_hanxin(data)?,
+        BarcodeType::DotCode => generators::dotcode::generate_dotcode(data)?,
 
         // Phase 3: Legacy formats
         BarcodeType::Code39 => generators::code39::generate_code39(data)?,
         BarcodeType::ITF14 => generators::itf14::generate_itf14(data)?,
+        BarcodeType::Interleaved2of5 => {
+            generators::interleaved2of5::generate_interleaved_2of5(data)?
+        }
         BarcodeType::Codabar => generators::codabar::generate_codabar(data)?,
+
+        // Specialty linear formats
+        BarcodeType::Code11 => generators::code11::generate_code11(data)?,
+        BarcodeType::Code32 => generators::code32::generate_code32(data)?,
+        BarcodeType::PZN7 => generators::pzn::generate_pzn7(data)?,
+        BarcodeType::PZN8 => generators::pzn::generate_pzn8(data)?,
+        BarcodeType::Pharmacode => generators::pharmacode::generate_pharmacode(data)?,
+        BarcodeType::PharmacodeTwoTrack => generators::pharmacode::generate_pharmacode_with_tracks(
+            data,
+            generators::pharmacode::PharmacodeTracks::Two,
+        )?,
+
+        // GS1 DataBar family
+        BarcodeType::DataBar => generators::databar::generate_databar(data)?,
+        BarcodeType::DataBarTruncated => generators::databar::generate_databar_truncated(data)?,
+        BarcodeType::DataBarStacked => generators::databar::generate_databar_stacked(data)?,
+        BarcodeType::DataBarStackedOmni => {
+            generators::databar::generate_databar_stacked_omni(data)?
+        }
+        BarcodeType::DataBarLimited => generators::databar::generate_databar_limited(data)?,
+        BarcodeType::DataBarExpanded => generators::databar::generate_databar_expanded(data)?,
+        BarcodeType::DataBarExpandedStacked => {
+            generators::databar::generate_databar_expanded_stacked(data)?
+        }
+
+        // Stacked linear formats
+        BarcodeType::Code16K => generators::code16k::generate_code16k(data)?,
+        BarcodeType::CodablockF => generators::codablock::generate_codablock_f(data)?,
+
+        // Postal formats
+        BarcodeType::IntelligentMail => {
+            generators::intelligent_mail::generate_intelligent_mail(data)?
+        }
+        BarcodeType::RoyalMail => generators::rm4scc::generate_rm4scc(data)?,
+        BarcodeType::KIX => generators::rm4scc::generate_kix(data)?,
+        BarcodeType::AustraliaPost => generators::auspost::generate_auspost(data)?,
+        BarcodeType::JapanPost => generators::japanpost::generate_japanpost(data)?,
+        BarcodeType::Postnet => generators::postnet::generate_postnet(data)?,
+        BarcodeType::Planet => generators::postnet::generate_planet(data)?,
     };
 
     match format {
@@ -257,12 +307,39 @@ mod tests {
             (BarcodeType::EAN13, "123456789012"),
             (BarcodeType::UPCA, "03600029145"),
             (BarcodeType::Code128, "HELLO123"),
+            (BarcodeType::GS1128, "(00)012345678901234560"),
             (BarcodeType::Code39, "SERIAL123ABC"),
             (BarcodeType::ITF14, "1234567890123"),
+            (BarcodeType::Interleaved2of5, "1234567890"),
             (BarcodeType::Codabar, "A1234567890B"),
+            (BarcodeType::Code11, "123-4567890"),
+            (BarcodeType::Code32, "01234567"),
+            (BarcodeType::PZN7, "490880"),
+            (BarcodeType::PZN8, "1234567"),
+            (BarcodeType::Pharmacode, "1234"),
+            (BarcodeType::PharmacodeTwoTrack, "1234"),
+            (BarcodeType::DataBar, "0950110153000"),
+            (BarcodeType::DataBarTruncated, "0950110153000"),
+            (BarcodeType::DataBarStacked, "0950110153000"),
+            (BarcodeType::DataBarStackedOmni, "0950110153000"),
+            (BarcodeType::DataBarLimited, "0950110153000"),
+            (BarcodeType::DataBarExpanded, "(01)09501101530003(3103)000123"),
+            (BarcodeType::DataBarExpandedStacked, "(01)09501101530003(17)140704(10)AB-123"),
+            (BarcodeType::Code16K, "CODE16K-2024"),
+            (BarcodeType::CodablockF, "Codablock F Test 12345"),
+            (BarcodeType::IntelligentMail, "0123456709498765432101234567891"),
+            (BarcodeType::RoyalMail, "SN34RD1A"),
+            (BarcodeType::KIX, "2500GG30250"),
+            (BarcodeType::AustraliaPost, "3999999912345678"),
+            (BarcodeType::JapanPost, "6540123789-A-K-35"),
+            (BarcodeType::Postnet, "80123-4567-89"),
+            (BarcodeType::Planet, "40123456789"),
             (BarcodeType::DataMatrix, "DataMatrix Test"),
             (BarcodeType::PDF417, "PDF417 Test"),
             (BarcodeType::Aztec, "Aztec Test"),
+            (BarcodeType::MaxiCode, "MaxiCode Test"),
+            (BarcodeType::HanXin, "Han Xin 汉信码 12345"),
+            (BarcodeType::DotCode, "DotCode Test 12345"),
         ];
 
         for (barcode_type, data) in test_cases {
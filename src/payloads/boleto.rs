@@ -0,0 +1,512 @@
+//! Boleto bancário and arrecadação barcodes (FEBRABAN)
+//!
+//! Brazilian bank slips carry a 44-digit barcode printed as Interleaved
+//! 2 of 5, and a "linha digitável" with extra check digits for typing it
+//! in. Bank slips (boleto de cobrança) have a 47-digit line; utility and
+//! tax bills (arrecadação, starting with 8) have a 48-digit one.
+
+use chrono::NaiveDate;
+
+use crate::generators::interleaved2of5::generate_interleaved_2of5;
+use crate::types::{Barcode, LinearDimensions, QuickCodesError, Result};
+
+/// FEBRABAN print size: narrow bar of 0.254 mm (103 mm for the 405 modules
+/// of a 44-digit barcode) and 13 mm bars
+const FEBRABAN_DIMENSIONS: LinearDimensions = LinearDimensions {
+    module_width: 0.254,
+    height: 13.0,
+};
+
+/// Real (BRL) currency code of bank slips
+const CURRENCY_REAL: u8 = 9;
+
+/// Due date factor 1000 restarts the count after 9999 (2025-02-21)
+const FACTOR_CYCLE: i64 = 9000;
+
+/// Bank slip (boleto de cobrança)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Boleto {
+    /// Bank code (3 digits)
+    pub bank: String,
+    /// Currency code: 9 for real
+    pub currency: u8,
+    /// Due date factor (1000-9999), or 0 for slips without due date
+    pub due_factor: u16,
+    /// Amount in centavos, up to 10 digits
+    pub amount: u64,
+    /// Free field defined by each bank (25 digits)
+    pub free_field: String,
+}
+
+/// Utility or tax bill (arrecadação)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arrecadacao {
+    /// Segment (1-9): 1 city halls, 2 sanitation, 3 power and gas,
+    /// 4 telecommunications, 5 government agencies, 6 payment books,
+    /// 7 traffic fines, 9 bank use
+    pub segment: u8,
+    /// Value type: 6 or 7 (modulo-10 checks) and 8 or 9 (modulo-11),
+    /// 6 and 8 meaning the amount is in reais
+    pub value_type: u8,
+    /// Amount in centavos, up to 11 digits
+    pub amount: u64,
+    /// Company or agency identification followed by the free field (29
+    /// digits)
+    pub identification: String,
+}
+
+impl Boleto {
+    /// Bank slip in reais
+    pub fn new(bank: &str, due_factor: u16, amount: u64, free_field: &str) -> Self {
+        Self {
+            bank: bank.to_string(),
+            currency: CURRENCY_REAL,
+            due_factor,
+            amount,
+            free_field: free_field.to_string(),
+        }
+    }
+
+    /// 44-digit barcode: bank, currency, check digit, due date factor,
+    /// amount and free field
+    pub fn barcode(&self) -> Result<String> {
+        if self.bank.len() != 3 || !is_digits(&self.bank) {
+            return Err(invalid(&format!(
+                "bank code must be 3 digits, got '{}'",
+                self.bank
+            )));
+        }
+        if self.currency > 9 {
+            return Err(invalid(&format!(
+                "currency code must be a digit, got {}",
+                self.currency
+            )));
+        }
+        if self.due_factor != 0 && !(1000..=9999).contains(&self.due_factor) {
+            return Err(invalid(&format!(
+                "due date factor must be 0 or 1000-9999, got {}",
+                self.due_factor
+            )));
+        }
+        if self.amount > 9_999_999_999 {
+            return Err(invalid("amount is longer than 10 digits"));
+        }
+        if self.free_field.len() != 25 || !is_digits(&self.free_field) {
+            return Err(invalid("free field must be 25 digits"));
+        }
+
+        let body = format!(
+            "{}{}{:04}{:010}{}",
+            self.bank, self.currency, self.due_factor, self.amount, self.free_field
+        );
+        let check = boleto_check_digit(&body);
+        Ok(format!("{}{}{}", &body[..4], check, &body[4..]))
+    }
+
+    /// 47-digit linha digitável, formatted as
+    /// `AAABC.CCCCX DDDDD.DDDDDY EEEEE.EEEEEZ K UUUUVVVVVVVVVV`
+    pub fn linha_digitavel(&self) -> Result<String> {
+        let barcode = self.barcode()?;
+        let field = |digits: String| {
+            let check = mod10(&digits);
+            format!("{}.{}{}", &digits[..5], &digits[5..], check)
+        };
+        Ok(format!(
+            "{} {} {} {} {}",
+            field(format!("{}{}", &barcode[..4], &barcode[19..24])),
+            field(barcode[24..34].to_string()),
+            field(barcode[34..44].to_string()),
+            &barcode[4..5],
+            &barcode[5..19]
+        ))
+    }
+
+    /// Parse and validate a 44-digit barcode
+    pub fn from_barcode(barcode: &str) -> Result<Self> {
+        if barcode.len() != 44 || !is_digits(barcode) {
+            return Err(invalid("the barcode must have 44 digits"));
+        }
+        if barcode.starts_with('8') {
+            return Err(invalid("barcodes starting with 8 are arrecadação bills"));
+        }
+        let boleto = Self {
+            bank: barcode[..3].to_string(),
+            currency: digit(barcode, 3),
+            due_factor: barcode[5..9].parse().expect("digits"),
+            amount: barcode[9..19].parse().expect("digits"),
+            free_field: barcode[19..].to_string(),
+        };
+        let expected = boleto.barcode()?;
+        if expected != barcode {
+            return Err(invalid(&format!(
+                "wrong check digit {}, expected {}",
+                &barcode[4..5],
+                &expected[4..5]
+            )));
+        }
+        Ok(boleto)
+    }
+
+    /// Parse and validate a 47-digit linha digitável; dots and spaces are
+    /// ignored
+    pub fn from_linha_digitavel(line: &str) -> Result<Self> {
+        let digits = typed_digits(line, 47)?;
+        for (start, end) in [(0, 9), (10, 20), (21, 31)] {
+            if mod10(&digits[start..end]) != digit(&digits, end) {
+                return Err(invalid(&format!(
+                    "wrong check digit in field {}",
+                    start / 10 + 1
+                )));
+            }
+        }
+        let barcode = format!(
+            "{}{}{}{}{}{}",
+            &digits[..4],
+            &digits[32..33],
+            &digits[33..47],
+            &digits[4..9],
+            &digits[10..20],
+            &digits[21..31]
+        );
+        Self::from_barcode(&barcode)
+    }
+}
+
+impl Arrecadacao {
+    /// 44-digit barcode: product (8), segment, value type, check digit,
+    /// amount and identification
+    pub fn barcode(&self) -> Result<String> {
+        if !(1..=9).contains(&self.segment) {
+            return Err(invalid(&format!(
+                "segment must be 1-9, got {}",
+                self.segment
+            )));
+        }
+        if !(6..=9).contains(&self.value_type) {
+            return Err(invalid(&format!(
+                "value type must be 6-9, got {}",
+                self.value_type
+            )));
+        }
+        if self.amount > 99_999_999_999 {
+            return Err(invalid("amount is longer than 11 digits"));
+        }
+        if self.identification.len() != 29 || !is_digits(&self.identification) {
+            return Err(invalid("identification must be 29 digits"));
+        }
+
+        let body = format!(
+            "8{}{}{:011}{}",
+            self.segment, self.value_type, self.amount, self.identification
+        );
+        let check = self.check_digit(&body);
+        Ok(format!("{}{}{}", &body[..3], check, &body[3..]))
+    }
+
+    /// 48-digit linha digitável: four blocks of 11 barcode digits, each
+    /// followed by its check digit
+    pub fn linha_digitavel(&self) -> Result<String> {
+        let barcode = self.barcode()?;
+        let blocks: Vec<String> = (0..4)
+            .map(|i| {
+                let block = &barcode[i * 11..(i + 1) * 11];
+                format!("{}-{}", block, self.check_digit(block))
+            })
+            .collect();
+        Ok(blocks.join(" "))
+    }
+
+    /// Parse and validate a 44-digit barcode
+    pub fn from_barcode(barcode: &str) -> Result<Self> {
+        if barcode.len() != 44 || !is_digits(barcode) || !barcode.starts_with('8') {
+            return Err(invalid(
+                "an arrecadação barcode has 44 digits starting with 8",
+            ));
+        }
+        let bill = Self {
+            segment: digit(barcode, 1),
+            value_type: digit(barcode, 2),
+            amount: barcode[4..15].parse().expect("digits"),
+            identification: barcode[15..].to_string(),
+        };
+        let expected = bill.barcode()?;
+        if expected != barcode {
+            return Err(invalid(&format!(
+                "wrong check digit {}, expected {}",
+                &barcode[3..4],
+                &expected[3..4]
+            )));
+        }
+        Ok(bill)
+    }
+
+    /// Parse and validate a 48-digit linha digitável; dashes and spaces are
+    /// ignored
+    pub fn from_linha_digitavel(line: &str) -> Result<Self> {
+        let digits = typed_digits(line, 48)?;
+        if !digits.starts_with('8') || !(6..=9).contains(&digit(&digits, 2)) {
+            return Err(invalid(
+                "an arrecadação line starts with 8 and a value type of 6-9",
+            ));
+        }
+        let mut barcode = String::with_capacity(44);
+        for block in 0..4 {
+            let start = block * 12;
+            let data = &digits[start..start + 11];
+            let check = digit(&digits, start + 11);
+            let expected = if digit(&digits, 2) <= 7 {
+                mod10(data)
+            } else {
+                arrecadacao_mod11(data)
+            };
+            if check != expected {
+                return Err(invalid(&format!(
+                    "wrong check digit in block {}",
+                    block + 1
+                )));
+            }
+            barcode.push_str(data);
+        }
+        Self::from_barcode(&barcode)
+    }
+
+    fn check_digit(&self, digits: &str) -> u8 {
+        if self.value_type <= 7 {
+            mod10(digits)
+        } else {
+            arrecadacao_mod11(digits)
+        }
+    }
+}
+
+/// Due date factor: days since 1997-10-07, restarting at 1000 after 9999
+pub fn due_date_factor(year: i32, month: u32, day: u32) -> Result<u16> {
+    let date = NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| {
+        invalid(&format!(
+            "invalid due date {}-{:02}-{:02}",
+            year, month, day
+        ))
+    })?;
+
+    let base = NaiveDate::from_ymd_opt(1997, 10, 7).expect("valid base date");
+    let days = date.signed_duration_since(base).num_days();
+    match days {
+        1000..=9999 => Ok(days as u16),
+        10000.. => Ok(((days - 10000) % FACTOR_CYCLE + 1000) as u16),
+        _ => Err(invalid("due dates before 2000-07-03 have no factor")),
+    }
+}
+
+/// Generate a bank slip barcode at FEBRABAN size
+pub fn generate_boleto(boleto: &Boleto) -> Result<Barcode> {
+    render(&boleto.barcode()?)
+}
+
+/// Generate a utility or tax bill barcode at FEBRABAN size
+pub fn generate_arrecadacao(bill: &Arrecadacao) -> Result<Barcode> {
+    render(&bill.barcode()?)
+}
+
+fn render(barcode: &str) -> Result<Barcode> {
+    let mut symbol = generate_interleaved_2of5(barcode)?;
+    symbol.config.dimensions = Some(FEBRABAN_DIMENSIONS);
+    // The linha digitável is printed above the barcode, not under it
+    symbol.config.include_text = false;
+    Ok(symbol)
+}
+
+/// Modulo 10 with weights 2 and 1 from the right, adding the digits of each
+/// product
+fn mod10(digits: &str) -> u8 {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .zip([2, 1].iter().cycle())
+        .map(|(b, weight)| {
+            let product = u32::from(b - b'0') * weight;
+            product / 10 + product % 10
+        })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Remainder of the modulo-11 sum with weights 2-9 from the right
+fn mod11_remainder(digits: &str) -> u32 {
+    let sum: u32 = digits
+        .bytes()
+        .rev()
+        .zip((2..=9).cycle())
+        .map(|(b, weight)| u32::from(b - b'0') * weight)
+        .sum();
+    sum % 11
+}
+
+/// General check digit of bank slips: 0, 10 and 11 become 1
+fn boleto_check_digit(digits: &str) -> u8 {
+    match 11 - mod11_remainder(digits) {
+        0 | 10 | 11 => 1,
+        check => check as u8,
+    }
+}
+
+/// Modulo-11 check digit of arrecadação bills: remainders 0 and 1 give 0
+fn arrecadacao_mod11(digits: &str) -> u8 {
+    match mod11_remainder(digits) {
+        0 | 1 => 0,
+        remainder => (11 - remainder) as u8,
+    }
+}
+
+/// Digits of a typed line, ignoring the usual separators
+fn typed_digits(line: &str, length: usize) -> Result<String> {
+    let digits: String = line
+        .chars()
+        .filter(|c| !matches!(c, '.' | ' ' | '-'))
+        .collect();
+    if digits.len() != length || !is_digits(&digits) {
+        return Err(invalid(&format!(
+            "the linha digitável must have {} digits",
+            length
+        )));
+    }
+    Ok(digits)
+}
+
+fn is_digits(value: &str) -> bool {
+    value.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn digit(digits: &str, index: usize) -> u8 {
+    digits.as_bytes()[index] - b'0'
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid boleto: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BarcodeType;
+
+    fn boleto() -> Boleto {
+        Boleto::new("001", 1000, 10000, "1234567890123456789012345")
+    }
+
+    fn bill(value_type: u8) -> Arrecadacao {
+        Arrecadacao {
+            segment: 3,
+            value_type,
+            amount: 12345,
+            identification: "01234567890123456789012345678".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_boleto_barcode() {
+        assert_eq!(
+            boleto().barcode().unwrap(),
+            "00191100000000100001234567890123456789012345"
+        );
+        assert_eq!(
+            boleto().linha_digitavel().unwrap(),
+            "00191.23454 67890.123457 67890.123457 1 10000000010000"
+        );
+    }
+
+    #[test]
+    fn test_boleto_parsing() {
+        let line = "00191.23454 67890.123457 67890.123457 1 10000000010000";
+        assert_eq!(Boleto::from_linha_digitavel(line).unwrap(), boleto());
+        assert_eq!(
+            Boleto::from_barcode("00191100000000100001234567890123456789012345").unwrap(),
+            boleto()
+        );
+
+        // Typing errors are caught by the field and general check digits
+        assert!(Boleto::from_linha_digitavel(
+            "00191.23455 67890.123457 67890.123457 1 10000000010000"
+        )
+        .is_err());
+        assert!(Boleto::from_linha_digitavel(
+            "00191.23454 67890.123457 67890.123457 2 10000000010000"
+        )
+        .is_err());
+        assert!(Boleto::from_barcode("00192100000000100001234567890123456789012345").is_err());
+        assert!(Boleto::from_linha_digitavel("00191.23454").is_err());
+    }
+
+    #[test]
+    fn test_boleto_validation() {
+        let mut invalid = boleto();
+        invalid.bank = "01".to_string();
+        assert!(invalid.barcode().is_err());
+        let mut invalid = boleto();
+        invalid.due_factor = 999;
+        assert!(invalid.barcode().is_err());
+        let mut invalid = boleto();
+        invalid.amount = 100_000_000_000;
+        assert!(invalid.barcode().is_err());
+        let mut invalid = boleto();
+        invalid.free_field = "123".to_string();
+        assert!(invalid.barcode().is_err());
+    }
+
+    #[test]
+    fn test_due_date_factor() {
+        assert_eq!(due_date_factor(2000, 7, 3).unwrap(), 1000);
+        assert_eq!(due_date_factor(2025, 2, 21).unwrap(), 9999);
+        assert_eq!(due_date_factor(2025, 2, 22).unwrap(), 1000);
+        assert_eq!(due_date_factor(2026, 10, 18).unwrap(), 1603);
+        assert!(due_date_factor(2000, 7, 2).is_err());
+        assert!(due_date_factor(2025, 2, 29).is_err());
+    }
+
+    #[test]
+    fn test_arrecadacao() {
+        let bill = bill(6);
+        assert_eq!(
+            bill.barcode().unwrap(),
+            "83640000001234501234567890123456789012345678"
+        );
+        assert_eq!(
+            bill.linha_digitavel().unwrap(),
+            "83640000001-1 23450123456-6 78901234567-2 89012345678-6"
+        );
+        assert_eq!(
+            Arrecadacao::from_linha_digitavel(&bill.linha_digitavel().unwrap()).unwrap(),
+            bill
+        );
+
+        let bill = self::bill(8);
+        assert_eq!(
+            bill.linha_digitavel().unwrap(),
+            "83840000001-4 23450123456-7 78901234567-5 89012345678-5"
+        );
+        assert_eq!(
+            Arrecadacao::from_barcode(&bill.barcode().unwrap()).unwrap(),
+            bill
+        );
+
+        assert!(Arrecadacao::from_linha_digitavel(
+            "83640000001-2 23450123456-6 78901234567-2 89012345678-6"
+        )
+        .is_err());
+        assert!(Boleto::from_barcode("83640000001234501234567890123456789012345678").is_err());
+        assert!(self::bill(5).barcode().is_err());
+    }
+
+    #[test]
+    fn test_generate_boleto() {
+        let barcode = generate_boleto(&boleto()).unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::Interleaved2of5);
+        assert_eq!(barcode.config.dimensions, Some(FEBRABAN_DIMENSIONS));
+        // 103 mm wide at FEBRABAN size
+        let modules = barcode.modules.as_linear().unwrap().len();
+        assert_eq!(modules, 405);
+        assert!((modules as f64 * FEBRABAN_DIMENSIONS.module_width - 103.0).abs() < 0.5);
+
+        let barcode = generate_arrecadacao(&bill(6)).unwrap();
+        assert_eq!(barcode.data, "83640000001234501234567890123456789012345678");
+    }
+}
@@ -0,0 +1,228 @@
+//! Calendar event payloads (iCalendar VEVENT, RFC 5545)
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+use super::escape::{escape_text, unescape};
+use crate::generators;
+use crate::types::{Barcode, QuickCodesError, Result};
+
+const DATE_FORMAT: &str = "%Y%m%d";
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// Start or end of an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventTime {
+    /// All-day event
+    Date(NaiveDate),
+    /// Local time of whoever reads the event ("floating" time)
+    Local(NaiveDateTime),
+    Utc(DateTime<Utc>),
+}
+
+/// Calendar event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub start: EventTime,
+    pub end: Option<EventTime>,
+    pub location: Option<String>,
+    pub description: Option<String>,
+}
+
+impl EventTime {
+    /// Property with its value, e.g. `DTSTART;VALUE=DATE:20250101`
+    fn property(&self, name: &str) -> String {
+        match self {
+            EventTime::Date(date) => format!("{};VALUE=DATE:{}", name, date.format(DATE_FORMAT)),
+            EventTime::Local(time) => format!("{}:{}", name, time.format(DATE_TIME_FORMAT)),
+            EventTime::Utc(time) => format!("{}:{}Z", name, time.format(DATE_TIME_FORMAT)),
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self> {
+        let invalid_time = || invalid(&format!("'{}' is not a date or date-time", value));
+        if let Some(utc) = value.strip_suffix('Z') {
+            let time =
+                NaiveDateTime::parse_from_str(utc, DATE_TIME_FORMAT).map_err(|_| invalid_time())?;
+            Ok(EventTime::Utc(Utc.from_utc_datetime(&time)))
+        } else if value.len() == 8 {
+            Ok(EventTime::Date(
+                NaiveDate::parse_from_str(value, DATE_FORMAT).map_err(|_| invalid_time())?,
+            ))
+        } else {
+            Ok(EventTime::Local(
+                NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT)
+                    .map_err(|_| invalid_time())?,
+            ))
+        }
+    }
+
+    /// Comparable instant; local times and dates are compared as UTC
+    fn instant(&self) -> NaiveDateTime {
+        match self {
+            EventTime::Date(date) => date.and_hms_opt(0, 0, 0).expect("midnight"),
+            EventTime::Local(time) => *time,
+            EventTime::Utc(time) => time.naive_utc(),
+        }
+    }
+}
+
+impl CalendarEvent {
+    pub fn new(summary: &str, start: EventTime) -> Self {
+        Self {
+            summary: summary.to_string(),
+            start,
+            end: None,
+            location: None,
+            description: None,
+        }
+    }
+
+    /// VEVENT with CRLF line endings
+    pub fn encode(&self) -> Result<String> {
+        if self.summary.trim().is_empty() {
+            return Err(invalid("the event needs a summary"));
+        }
+        if let Some(end) = &self.end {
+            if std::mem::discriminant(end) != std::mem::discriminant(&self.start) {
+                return Err(invalid("start and end must be the same kind of time"));
+            }
+            if end.instant() < self.start.instant() {
+                return Err(invalid("the event ends before it starts"));
+            }
+        }
+
+        let mut lines = vec![
+            "BEGIN:VEVENT".to_string(),
+            format!("SUMMARY:{}", escape_text(&self.summary)),
+            self.start.property("DTSTART"),
+        ];
+        if let Some(end) = &self.end {
+            lines.push(end.property("DTEND"));
+        }
+        if let Some(location) = &self.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(description) = &self.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push("END:VEVENT".to_string());
+        Ok(lines.join("\r\n"))
+    }
+
+    /// Parse a VEVENT, on its own or inside a VCALENDAR, such as decoded
+    /// `ReadResult::data`
+    pub fn parse(data: &str) -> Result<Self> {
+        let unfolded = data
+            .replace("\r\n", "\n")
+            .replace("\n ", "")
+            .replace("\n\t", "");
+        let mut lines = unfolded
+            .lines()
+            .map(str::trim_end)
+            .skip_while(|line| !line.eq_ignore_ascii_case("BEGIN:VEVENT"));
+        if lines.next().is_none() {
+            return Err(invalid("no BEGIN:VEVENT found"));
+        }
+
+        let (mut summary, mut start, mut end, mut location, mut description) =
+            (None, None, None, None, None);
+        for line in lines {
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            let name = name.split(';').next().unwrap_or(name).to_ascii_uppercase();
+            match name.as_str() {
+                "END" => break,
+                "SUMMARY" => summary = Some(unescape(value, true)),
+                "DTSTART" => start = Some(EventTime::parse(value)?),
+                "DTEND" => end = Some(EventTime::parse(value)?),
+                "LOCATION" => location = Some(unescape(value, true)),
+                "DESCRIPTION" => description = Some(unescape(value, true)),
+                _ => {}
+            }
+        }
+
+        let event = Self {
+            summary: summary.ok_or_else(|| invalid("the event has no SUMMARY"))?,
+            start: start.ok_or_else(|| invalid("the event has no DTSTART"))?,
+            end,
+            location,
+            description,
+        };
+        event.encode()?;
+        Ok(event)
+    }
+}
+
+/// Generate a QR code that adds an event to the calendar
+pub fn generate_event(event: &CalendarEvent) -> Result<Barcode> {
+    generators::generate_qr(&event.encode()?)
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid calendar event: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> EventTime {
+        EventTime::parse(value).unwrap()
+    }
+
+    #[test]
+    fn test_event_encode() {
+        let mut event = CalendarEvent::new("Lançamento, versão 2.0", utc("20251120T130000Z"));
+        event.end = Some(utc("20251120T150000Z"));
+        event.location = Some("Auditório; 3º andar".to_string());
+        event.description = Some("Agenda:\nabertura".to_string());
+        let encoded = event.encode().unwrap();
+        assert_eq!(
+            encoded,
+            "BEGIN:VEVENT\r\nSUMMARY:Lançamento\\, versão 2.0\r\nDTSTART:20251120T130000Z\r\n\
+             DTEND:20251120T150000Z\r\nLOCATION:Auditório\\; 3º andar\r\n\
+             DESCRIPTION:Agenda:\\nabertura\r\nEND:VEVENT"
+        );
+        assert_eq!(CalendarEvent::parse(&encoded).unwrap(), event);
+
+        let date = NaiveDate::from_ymd_opt(2025, 12, 25).unwrap();
+        let event = CalendarEvent::new("Natal", EventTime::Date(date));
+        assert!(event
+            .encode()
+            .unwrap()
+            .contains("\r\nDTSTART;VALUE=DATE:20251225\r\n"));
+    }
+
+    #[test]
+    fn test_event_parse() {
+        let data = "BEGIN:VCALENDAR\nVERSION:2.0\nBEGIN:VEVENT\nUID:1@example.com\n\
+                    SUMMARY:Reunião\nDTSTART;TZID=America/Sao_Paulo:20250310T090000\n\
+                    DTEND;TZID=America/Sao_Paulo:20250310T100000\nEND:VEVENT\nEND:VCALENDAR";
+        let event = CalendarEvent::parse(data).unwrap();
+        assert_eq!(event.summary, "Reunião");
+        assert_eq!(event.start, EventTime::parse("20250310T090000").unwrap());
+        assert!(matches!(event.end, Some(EventTime::Local(_))));
+
+        assert!(CalendarEvent::parse("BEGIN:VEVENT\nDTSTART:20250310\nEND:VEVENT").is_err());
+        assert!(
+            CalendarEvent::parse("BEGIN:VEVENT\nSUMMARY:x\nDTSTART:2025031\nEND:VEVENT").is_err()
+        );
+        assert!(CalendarEvent::parse("BEGIN:VCARD\nEND:VCARD").is_err());
+    }
+
+    #[test]
+    fn test_event_validation() {
+        let mut event = CalendarEvent::new("Reunião", utc("20250310T120000Z"));
+        event.end = Some(utc("20250310T110000Z"));
+        assert!(event.encode().is_err());
+        event.end = Some(EventTime::Date(
+            NaiveDate::from_ymd_opt(2025, 3, 11).unwrap(),
+        ));
+        assert!(event.encode().is_err());
+        assert!(CalendarEvent::new(" ", utc("20250310T120000Z"))
+            .encode()
+            .is_err());
+    }
+}
@@ -0,0 +1,388 @@
+//! Contact payloads: vCard 3.0 / 4.0 (RFC 2426, RFC 6350) and MeCard
+//!
+//! Both carry the same contact details. vCards are lines of
+//! `PROPERTY:value` between `BEGIN:VCARD` and `END:VCARD`; MeCards are a
+//! compact single line, `MECARD:N:Family,Given;TEL:...;;`, with `\`, `;`,
+//! `,` and `:` escaped with a backslash.
+
+use super::escape::{escape, escape_text, split_unescaped, unescape};
+use crate::generators;
+use crate::types::{Barcode, QuickCodesError, Result};
+
+const MECARD_PREFIX: &str = "MECARD:";
+
+/// Characters escaped in MeCard values
+const MECARD_SPECIAL: &str = ";,:";
+
+/// vCard version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VCardVersion {
+    V3,
+    V4,
+}
+
+/// Postal address
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PostalAddress {
+    pub street: String,
+    pub city: String,
+    pub region: String,
+    pub postal_code: String,
+    pub country: String,
+}
+
+/// Contact details shared by vCard and MeCard
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Contact {
+    pub given_name: String,
+    pub family_name: String,
+    /// Organization (not in MeCard)
+    pub organization: Option<String>,
+    /// Job title (not in MeCard)
+    pub title: Option<String>,
+    pub phones: Vec<String>,
+    pub emails: Vec<String>,
+    pub url: Option<String>,
+    pub address: Option<PostalAddress>,
+    pub note: Option<String>,
+}
+
+impl PostalAddress {
+    /// Street, city, region, postal code and country, after the PO box and
+    /// extended address that both formats also define
+    fn components(&self) -> [&str; 7] {
+        [
+            "",
+            "",
+            &self.street,
+            &self.city,
+            &self.region,
+            &self.postal_code,
+            &self.country,
+        ]
+    }
+
+    fn from_components(components: &[String]) -> Self {
+        let component = |i: usize| components.get(i).cloned().unwrap_or_default();
+        Self {
+            street: component(2),
+            city: component(3),
+            region: component(4),
+            postal_code: component(5),
+            country: component(6),
+        }
+    }
+}
+
+impl Contact {
+    pub fn new(given_name: &str, family_name: &str) -> Self {
+        Self {
+            given_name: given_name.to_string(),
+            family_name: family_name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Formatted name: given name followed by family name
+    pub fn full_name(&self) -> String {
+        format!("{} {}", self.given_name, self.family_name)
+            .trim()
+            .to_string()
+    }
+
+    /// vCard with CRLF line endings
+    pub fn to_vcard(&self, version: VCardVersion) -> Result<String> {
+        self.check_name()?;
+        let mut lines = vec![
+            "BEGIN:VCARD".to_string(),
+            match version {
+                VCardVersion::V3 => "VERSION:3.0",
+                VCardVersion::V4 => "VERSION:4.0",
+            }
+            .to_string(),
+            format!(
+                "N:{};{};;;",
+                escape_text(&self.family_name),
+                escape_text(&self.given_name)
+            ),
+            format!("FN:{}", escape_text(&self.full_name())),
+        ];
+        if let Some(organization) = &self.organization {
+            lines.push(format!("ORG:{}", escape_text(organization)));
+        }
+        if let Some(title) = &self.title {
+            lines.push(format!("TITLE:{}", escape_text(title)));
+        }
+        for phone in &self.phones {
+            lines.push(match version {
+                VCardVersion::V3 => format!("TEL:{}", escape_text(phone)),
+                VCardVersion::V4 => format!("TEL;VALUE=uri:tel:{}", phone.replace(' ', "")),
+            });
+        }
+        for email in &self.emails {
+            lines.push(format!("EMAIL:{}", escape_text(email)));
+        }
+        if let Some(address) = &self.address {
+            let components: Vec<String> = address
+                .components()
+                .iter()
+                .map(|c| escape_text(c))
+                .collect();
+            lines.push(format!("ADR:{}", components.join(";")));
+        }
+        if let Some(url) = &self.url {
+            lines.push(format!("URL:{}", url));
+        }
+        if let Some(note) = &self.note {
+            lines.push(format!("NOTE:{}", escape_text(note)));
+        }
+        lines.push("END:VCARD".to_string());
+        Ok(lines.join("\r\n"))
+    }
+
+    /// Parse a vCard 3.0 or 4.0, such as decoded `ReadResult::data`.
+    /// Folded lines are joined and unknown properties are ignored
+    pub fn from_vcard(data: &str) -> Result<Self> {
+        let unfolded = data
+            .replace("\r\n", "\n")
+            .replace("\n ", "")
+            .replace("\n\t", "");
+        let mut lines = unfolded.lines().map(str::trim_end);
+        if !lines
+            .next()
+            .is_some_and(|line| line.eq_ignore_ascii_case("BEGIN:VCARD"))
+        {
+            return Err(invalid("a vCard starts with BEGIN:VCARD"));
+        }
+
+        let mut contact = Self::default();
+        let mut formatted_name = None;
+        let mut version = None;
+        for line in lines {
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            // Property name without group prefix and parameters
+            let (name, parameters) = name.split_once(';').unwrap_or((name, ""));
+            let name = name.rsplit('.').next().unwrap_or(name).to_ascii_uppercase();
+            match name.as_str() {
+                "END" => break,
+                "VERSION" => version = Some(value.to_string()),
+                "N" => {
+                    let parts = split_unescaped(value, ';');
+                    contact.family_name = unescape(parts[0], true);
+                    contact.given_name =
+                        parts.get(1).map(|p| unescape(p, true)).unwrap_or_default();
+                }
+                "FN" => formatted_name = Some(unescape(value, true)),
+                "ORG" => {
+                    let units: Vec<String> = split_unescaped(value, ';')
+                        .into_iter()
+                        .map(|unit| unescape(unit, true))
+                        .collect();
+                    contact.organization = Some(units.join(", "));
+                }
+                "TITLE" => contact.title = Some(unescape(value, true)),
+                "TEL" => {
+                    let uri = parameters.to_ascii_lowercase().contains("value=uri");
+                    let phone = match value.strip_prefix("tel:") {
+                        Some(number) if uri => number.to_string(),
+                        _ => unescape(value, true),
+                    };
+                    contact.phones.push(phone);
+                }
+                "EMAIL" => contact.emails.push(unescape(value, true)),
+                "ADR" => {
+                    let components: Vec<String> = split_unescaped(value, ';')
+                        .into_iter()
+                        .map(|c| unescape(c, true))
+                        .collect();
+                    contact.address = Some(PostalAddress::from_components(&components));
+                }
+                "URL" => contact.url = Some(value.to_string()),
+                "NOTE" => contact.note = Some(unescape(value, true)),
+                _ => {}
+            }
+        }
+
+        match version.as_deref() {
+            Some("3.0" | "4.0") => {}
+            Some(other) => return Err(invalid(&format!("unsupported vCard version {}", other))),
+            None => return Err(invalid("the vCard has no VERSION")),
+        }
+        // Without N, split the formatted name at its last space
+        if contact.given_name.is_empty() && contact.family_name.is_empty() {
+            let name = formatted_name.unwrap_or_default();
+            match name.rsplit_once(' ') {
+                Some((given, family)) => {
+                    contact.given_name = given.to_string();
+                    contact.family_name = family.to_string();
+                }
+                None => contact.given_name = name,
+            }
+        }
+        contact.check_name()?;
+        Ok(contact)
+    }
+
+    /// MeCard; organization and title are left out
+    pub fn to_mecard(&self) -> Result<String> {
+        self.check_name()?;
+        let mut payload = format!(
+            "{}N:{},{};",
+            MECARD_PREFIX,
+            escape(&self.family_name, MECARD_SPECIAL),
+            escape(&self.given_name, MECARD_SPECIAL)
+        );
+        for phone in &self.phones {
+            payload.push_str(&format!("TEL:{};", escape(phone, MECARD_SPECIAL)));
+        }
+        for email in &self.emails {
+            payload.push_str(&format!("EMAIL:{};", escape(email, MECARD_SPECIAL)));
+        }
+        if let Some(address) = &self.address {
+            let components: Vec<String> = address
+                .components()
+                .iter()
+                .map(|c| escape(c, MECARD_SPECIAL))
+                .collect();
+            payload.push_str(&format!("ADR:{};", components.join(",")));
+        }
+        if let Some(url) = &self.url {
+            payload.push_str(&format!("URL:{};", escape(url, MECARD_SPECIAL)));
+        }
+        if let Some(note) = &self.note {
+            payload.push_str(&format!("NOTE:{};", escape(note, MECARD_SPECIAL)));
+        }
+        payload.push(';');
+        Ok(payload)
+    }
+
+    /// Parse a MeCard, such as decoded `ReadResult::data`
+    pub fn from_mecard(data: &str) -> Result<Self> {
+        let body = data
+            .strip_prefix(MECARD_PREFIX)
+            .ok_or_else(|| invalid("a MeCard starts with MECARD:"))?;
+
+        let mut contact = Self::default();
+        for field in split_unescaped(body, ';') {
+            let Some((key, value)) = field.split_once(':') else {
+                continue;
+            };
+            let parts: Vec<String> = split_unescaped(value, ',')
+                .into_iter()
+                .map(|part| unescape(part, false))
+                .collect();
+            let value = unescape(value, false);
+            match key {
+                "N" => {
+                    contact.family_name = parts[0].clone();
+                    contact.given_name = parts.get(1).cloned().unwrap_or_default();
+                }
+                "TEL" => contact.phones.push(value),
+                "EMAIL" => contact.emails.push(value),
+                "ADR" => contact.address = Some(PostalAddress::from_components(&parts)),
+                "URL" => contact.url = Some(value),
+                "NOTE" => contact.note = Some(value),
+                "ORG" => contact.organization = Some(value),
+                _ => {}
+            }
+        }
+        contact.check_name()?;
+        Ok(contact)
+    }
+
+    fn check_name(&self) -> Result<()> {
+        if self.full_name().is_empty() {
+            return Err(invalid("the contact needs a name"));
+        }
+        Ok(())
+    }
+}
+
+/// Generate a QR code with a vCard
+pub fn generate_vcard(contact: &Contact, version: VCardVersion) -> Result<Barcode> {
+    generators::generate_qr(&contact.to_vcard(version)?)
+}
+
+/// Generate a QR code with a MeCard, which is smaller than a vCard
+pub fn generate_mecard(contact: &Contact) -> Result<Barcode> {
+    generators::generate_qr(&contact.to_mecard()?)
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid contact payload: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contact() -> Contact {
+        let mut contact = Contact::new("João", "Silva");
+        contact.organization = Some("Acme, Ltda.".to_string());
+        contact.phones = vec!["+55 11 99999-9999".to_string()];
+        contact.emails = vec!["joao@example.com".to_string()];
+        contact.address = Some(PostalAddress {
+            street: "Av. Paulista, 1000".to_string(),
+            city: "São Paulo".to_string(),
+            region: "SP".to_string(),
+            postal_code: "01310-100".to_string(),
+            country: "Brasil".to_string(),
+        });
+        contact.url = Some("https://example.com".to_string());
+        contact.note = Some("Primeira linha\nsegunda; linha".to_string());
+        contact
+    }
+
+    #[test]
+    fn test_vcard() {
+        let vcard = contact().to_vcard(VCardVersion::V3).unwrap();
+        assert_eq!(
+            vcard,
+            "BEGIN:VCARD\r\nVERSION:3.0\r\nN:Silva;João;;;\r\nFN:João Silva\r\n\
+             ORG:Acme\\, Ltda.\r\nTEL:+55 11 99999-9999\r\nEMAIL:joao@example.com\r\n\
+             ADR:;;Av. Paulista\\, 1000;São Paulo;SP;01310-100;Brasil\r\n\
+             URL:https://example.com\r\nNOTE:Primeira linha\\nsegunda\\; linha\r\nEND:VCARD"
+        );
+        assert_eq!(Contact::from_vcard(&vcard).unwrap(), contact());
+
+        let vcard = contact().to_vcard(VCardVersion::V4).unwrap();
+        assert!(vcard.contains("VERSION:4.0\r\n"));
+        assert!(vcard.contains("TEL;VALUE=uri:tel:+551199999-9999\r\n"));
+        let parsed = Contact::from_vcard(&vcard).unwrap();
+        assert_eq!(parsed.phones, vec!["+551199999-9999"]);
+    }
+
+    #[test]
+    fn test_vcard_parse() {
+        // LF line endings, folded lines, groups, parameters and no N
+        let data = "BEGIN:VCARD\nVERSION:3.0\nFN:Maria da Silva\nitem1.TEL;TYPE=CELL:+5511\n 988887777\nX-CUSTOM:ignored\nEND:VCARD";
+        let contact = Contact::from_vcard(data).unwrap();
+        assert_eq!(contact.given_name, "Maria da");
+        assert_eq!(contact.family_name, "Silva");
+        assert_eq!(contact.phones, vec!["+5511988887777"]);
+
+        assert!(Contact::from_vcard("BEGIN:VCARD\nVERSION:2.1\nFN:A\nEND:VCARD").is_err());
+        assert!(Contact::from_vcard("BEGIN:VCARD\nFN:A\nEND:VCARD").is_err());
+        assert!(Contact::from_vcard("BEGIN:VCARD\nVERSION:3.0\nEND:VCARD").is_err());
+        assert!(Contact::from_vcard("MECARD:N:A;;").is_err());
+    }
+
+    #[test]
+    fn test_mecard() {
+        let mecard = contact().to_mecard().unwrap();
+        assert_eq!(
+            mecard,
+            "MECARD:N:Silva,João;TEL:+55 11 99999-9999;EMAIL:joao@example.com;\
+             ADR:,,Av. Paulista\\, 1000,São Paulo,SP,01310-100,Brasil;\
+             URL:https\\://example.com;NOTE:Primeira linha\nsegunda\\; linha;;"
+        );
+        let mut expected = contact();
+        expected.organization = None;
+        assert_eq!(Contact::from_mecard(&mecard).unwrap(), expected);
+
+        assert!(Contact::new("", "").to_mecard().is_err());
+        assert!(Contact::from_mecard("MECARD:TEL:123;;").is_err());
+    }
+}
@@ -0,0 +1,479 @@
+//! EMV QR Code merchant-presented mode (EMVCo QRCPS MPM)
+//!
+//! Merchant-presented payment QR codes are a sequence of data objects, each
+//! a two-digit ID, a two-digit length and the value. Merchant account
+//! information (IDs 26-51), additional data (62) and the other template IDs
+//! hold nested data objects of their own, starting with a globally unique
+//! identifier of the payment system. The payload ends with a CRC16 in data
+//! object 63. National schemes such as PIX, UPI, PromptPay, DuitNow and
+//! PayNow are profiles on top of this format.
+
+pub mod profiles;
+
+pub use profiles::{
+    generate_merchant_qr, EmvProfile, MerchantPayment, PayNowProxy, PromptPayTarget,
+};
+
+use crate::generators;
+use crate::types::{Barcode, QuickCodesError, Result};
+
+// Root data object IDs
+pub const PAYLOAD_FORMAT: u8 = 0;
+pub const INITIATION_METHOD: u8 = 1;
+pub const MERCHANT_CATEGORY: u8 = 52;
+pub const CURRENCY: u8 = 53;
+pub const AMOUNT: u8 = 54;
+pub const COUNTRY: u8 = 58;
+pub const MERCHANT_NAME: u8 = 59;
+pub const MERCHANT_CITY: u8 = 60;
+pub const POSTAL_CODE: u8 = 61;
+pub const ADDITIONAL_DATA: u8 = 62;
+pub const CRC: u8 = 63;
+
+/// Globally unique identifier, the first data object of a template
+pub const TEMPLATE_GUI: u8 = 0;
+
+/// Reference label inside the additional data template
+pub const REFERENCE_LABEL: u8 = 5;
+
+/// Initiation method values: static (reusable) and dynamic (one payment)
+pub const STATIC: &str = "11";
+pub const DYNAMIC: &str = "12";
+
+const MAX_VALUE_LENGTH: usize = 99;
+const MAX_AMOUNT_LENGTH: usize = 13;
+const MAX_NAME_LENGTH: usize = 25;
+const MAX_CITY_LENGTH: usize = 15;
+
+/// Value of a data object: text or a template of nested data objects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmvValue {
+    Text(String),
+    Template(EmvTemplate),
+}
+
+/// Data objects in ascending ID order
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmvTemplate {
+    objects: Vec<(u8, EmvValue)>,
+}
+
+/// Merchant-presented payload: the root data objects, without the CRC
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmvPayload {
+    pub objects: EmvTemplate,
+}
+
+impl From<&str> for EmvValue {
+    fn from(value: &str) -> Self {
+        EmvValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for EmvValue {
+    fn from(value: String) -> Self {
+        EmvValue::Text(value)
+    }
+}
+
+impl From<EmvTemplate> for EmvValue {
+    fn from(template: EmvTemplate) -> Self {
+        EmvValue::Template(template)
+    }
+}
+
+impl EmvTemplate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Template of a payment system: its GUI in data object 00
+    pub fn with_gui(gui: &str) -> Self {
+        Self::new().with(TEMPLATE_GUI, gui)
+    }
+
+    /// Add or replace a data object, for chaining
+    pub fn with(mut self, id: u8, value: impl Into<EmvValue>) -> Self {
+        self.set(id, value);
+        self
+    }
+
+    /// Add or replace a data object
+    pub fn set(&mut self, id: u8, value: impl Into<EmvValue>) {
+        let value = value.into();
+        match self
+            .objects
+            .binary_search_by_key(&id, |(object, _)| *object)
+        {
+            Ok(i) => self.objects[i].1 = value,
+            Err(i) => self.objects.insert(i, (id, value)),
+        }
+    }
+
+    pub fn remove(&mut self, id: u8) -> Option<EmvValue> {
+        let i = self.objects.iter().position(|(object, _)| *object == id)?;
+        Some(self.objects.remove(i).1)
+    }
+
+    pub fn get(&self, id: u8) -> Option<&EmvValue> {
+        self.objects
+            .iter()
+            .find(|(object, _)| *object == id)
+            .map(|(_, value)| value)
+    }
+
+    /// Text of a data object; `None` when absent or a template
+    pub fn text(&self, id: u8) -> Option<&str> {
+        match self.get(id)? {
+            EmvValue::Text(text) => Some(text),
+            EmvValue::Template(_) => None,
+        }
+    }
+
+    /// Nested data objects; `None` when absent or text
+    pub fn template(&self, id: u8) -> Option<&EmvTemplate> {
+        match self.get(id)? {
+            EmvValue::Template(template) => Some(template),
+            EmvValue::Text(_) => None,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &EmvValue)> {
+        self.objects.iter().map(|(id, value)| (*id, value))
+    }
+
+    /// ID-length-value string of the data objects
+    pub fn encode(&self) -> Result<String> {
+        let mut encoded = String::new();
+        for (id, value) in &self.objects {
+            if *id > 99 {
+                return Err(invalid(&format!("data object ID {} is not two digits", id)));
+            }
+            let value = match value {
+                EmvValue::Text(text) => text.clone(),
+                EmvValue::Template(template) => template.encode()?,
+            };
+            if value.is_empty() || value.len() > MAX_VALUE_LENGTH {
+                return Err(invalid(&format!(
+                    "data object {:02} must have 1-{} characters",
+                    id, MAX_VALUE_LENGTH
+                )));
+            }
+            encoded.push_str(&format!("{:02}{:02}{}", id, value.len(), value));
+        }
+        Ok(encoded)
+    }
+
+    /// Split ID-length-value data objects; with `root`, the template IDs
+    /// are parsed into nested templates
+    fn parse(data: &str, root: bool) -> Result<Self> {
+        let mut template = Self::new();
+        let mut rest = data;
+        while !rest.is_empty() {
+            let header = rest
+                .get(..4)
+                .filter(|header| header.bytes().all(|b| b.is_ascii_digit()))
+                .ok_or_else(|| invalid(&format!("malformed data object at '{}'", rest)))?;
+            let id: u8 = header[..2].parse().expect("digits");
+            let length: usize = header[2..].parse().expect("digits");
+            let value = rest
+                .get(4..4 + length)
+                .ok_or_else(|| invalid(&format!("data object {:02} is truncated", id)))?;
+            if template.get(id).is_some() {
+                return Err(invalid(&format!("data object {:02} appears twice", id)));
+            }
+            let value = if root && is_template(id) {
+                EmvValue::Template(Self::parse(value, false)?)
+            } else {
+                EmvValue::Text(value.to_string())
+            };
+            template.objects.push((id, value));
+            rest = &rest[4 + length..];
+        }
+        template.objects.sort_by_key(|(id, _)| *id);
+        Ok(template)
+    }
+}
+
+impl EmvPayload {
+    /// Payload with the format indicator and the mandatory merchant data
+    pub fn new(category: &str, currency: &str, country: &str, name: &str, city: &str) -> Self {
+        let objects = EmvTemplate::new()
+            .with(PAYLOAD_FORMAT, "01")
+            .with(MERCHANT_CATEGORY, category)
+            .with(CURRENCY, currency)
+            .with(COUNTRY, country)
+            .with(MERCHANT_NAME, name)
+            .with(MERCHANT_CITY, city);
+        Self { objects }
+    }
+
+    /// Payload string ending with data object 63, the CRC16 of everything
+    /// before it including `6304`
+    pub fn encode(&self) -> Result<String> {
+        self.validate()?;
+        let mut payload = self.objects.encode()?;
+        payload.push_str("6304");
+        let crc = crc16(payload.as_bytes());
+        Ok(format!("{}{:04X}", payload, crc))
+    }
+
+    /// Parse a payload, checking its CRC and mandatory data objects
+    pub fn parse(payload: &str) -> Result<Self> {
+        let payload = payload.trim();
+        if payload.len() < 8 || !payload.is_char_boundary(payload.len() - 4) {
+            return Err(invalid("the payload is too short"));
+        }
+        let (body, crc) = payload.split_at(payload.len() - 4);
+        if !body.ends_with("6304") {
+            return Err(invalid("the payload must end with the CRC data object 63"));
+        }
+        let expected = format!("{:04X}", crc16(body.as_bytes()));
+        if !crc.eq_ignore_ascii_case(&expected) {
+            return Err(invalid(&format!(
+                "wrong CRC {}, expected {}",
+                crc, expected
+            )));
+        }
+
+        if !body.starts_with("00") {
+            return Err(invalid("the payload must start with the format indicator"));
+        }
+        let parsed = Self {
+            objects: EmvTemplate::parse(&body[..body.len() - 4], true)?,
+        };
+        parsed.validate()?;
+        Ok(parsed)
+    }
+
+    /// Check the mandatory data objects and the formats of the common ones
+    fn validate(&self) -> Result<()> {
+        let objects = &self.objects;
+        let require = |id: u8, name: &str| {
+            objects
+                .text(id)
+                .ok_or_else(|| invalid(&format!("missing {} (data object {:02})", name, id)))
+        };
+
+        if require(PAYLOAD_FORMAT, "payload format indicator")? != "01" {
+            return Err(invalid("the payload format indicator must be 01"));
+        }
+        if let Some(method) = objects.get(INITIATION_METHOD) {
+            if *method != EmvValue::from(STATIC) && *method != EmvValue::from(DYNAMIC) {
+                return Err(invalid("the point of initiation method must be 11 or 12"));
+            }
+        }
+        if !objects.iter().any(|(id, _)| (2..=51).contains(&id)) {
+            return Err(invalid(
+                "missing merchant account information (data objects 02-51)",
+            ));
+        }
+        for (id, value) in objects.iter() {
+            let template = matches!(value, EmvValue::Template(_));
+            if is_template(id) != template {
+                return Err(invalid(&format!(
+                    "data object {:02} must be {}",
+                    id,
+                    if template { "text" } else { "a template" }
+                )));
+            }
+        }
+
+        let digits = |value: &str, length: usize| {
+            value.len() == length && value.bytes().all(|b| b.is_ascii_digit())
+        };
+        if !digits(require(MERCHANT_CATEGORY, "merchant category code")?, 4) {
+            return Err(invalid("the merchant category code must be 4 digits"));
+        }
+        if !digits(require(CURRENCY, "transaction currency")?, 3) {
+            return Err(invalid("the currency must be an ISO 4217 numeric code"));
+        }
+        let country = require(COUNTRY, "country code")?;
+        if country.len() != 2 || !country.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(invalid("the country code must be two ISO 3166 letters"));
+        }
+        if require(MERCHANT_NAME, "merchant name")?.chars().count() > MAX_NAME_LENGTH {
+            return Err(invalid(&format!(
+                "the merchant name exceeds {} characters",
+                MAX_NAME_LENGTH
+            )));
+        }
+        if require(MERCHANT_CITY, "merchant city")?.chars().count() > MAX_CITY_LENGTH {
+            return Err(invalid(&format!(
+                "the merchant city exceeds {} characters",
+                MAX_CITY_LENGTH
+            )));
+        }
+        if let Some(amount) = objects.text(AMOUNT) {
+            if amount.len() > MAX_AMOUNT_LENGTH {
+                return Err(invalid("the amount exceeds 13 characters"));
+            }
+            parse_amount(amount)?;
+        }
+        Ok(())
+    }
+}
+
+/// Generate the QR code of a merchant-presented payload
+pub fn generate_emv(payload: &EmvPayload) -> Result<Barcode> {
+    generators::generate_qr(&payload.encode()?)
+}
+
+/// Amount with two decimals from minor units, e.g. `10.50`
+pub(crate) fn format_amount(cents: u64) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+/// Minor units (two decimals) from an amount such as `10.5`
+pub(crate) fn parse_amount(amount: &str) -> Result<u64> {
+    let (units, cents) = amount.split_once('.').unwrap_or((amount, ""));
+    let valid = !units.is_empty()
+        && cents.len() <= 2
+        && units
+            .chars()
+            .chain(cents.chars())
+            .all(|c| c.is_ascii_digit());
+    if !valid {
+        return Err(invalid(&format!("invalid amount '{}'", amount)));
+    }
+    let units: u64 = units
+        .parse()
+        .map_err(|_| invalid(&format!("invalid amount '{}'", amount)))?;
+    let cents: u64 = format!("{:0<2}", cents).parse().unwrap_or(0);
+    Ok(units * 100 + cents)
+}
+
+/// Whether a root data object holds nested data objects: merchant account
+/// templates, additional data, language template and unreserved templates
+fn is_template(id: u8) -> bool {
+    matches!(id, 26..=51 | 62 | 64 | 80..=99)
+}
+
+/// CRC16-CCITT (polynomial 0x1021, initial value 0xFFFF)
+pub(crate) fn crc16(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for &byte in data {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid EMV QR payload: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload() -> EmvPayload {
+        let mut payload = EmvPayload::new("5812", "986", "BR", "Padaria", "SAO PAULO");
+        payload.objects.set(INITIATION_METHOD, DYNAMIC);
+        payload.objects.set(
+            26,
+            EmvTemplate::with_gui("br.gov.bcb.pix").with(1, "12345678901"),
+        );
+        payload.objects.set(AMOUNT, format_amount(1050));
+        payload.objects.set(
+            ADDITIONAL_DATA,
+            EmvTemplate::new().with(REFERENCE_LABEL, "***"),
+        );
+        payload
+    }
+
+    #[test]
+    fn test_emv_encode() {
+        let encoded = payload().encode().unwrap();
+        assert!(encoded.starts_with(
+            "00020101021226330014br.gov.bcb.pix011112345678901520458125303986540510.50"
+        ));
+        assert!(encoded.contains("5802BR5907Padaria6009SAO PAULO62070503***6304"));
+        let crc = crc16(encoded[..encoded.len() - 4].as_bytes());
+        assert_eq!(&encoded[encoded.len() - 4..], format!("{:04X}", crc));
+
+        let parsed = EmvPayload::parse(&encoded).unwrap();
+        assert_eq!(parsed, payload());
+        assert_eq!(
+            parsed.objects.template(26).unwrap().text(1),
+            Some("12345678901")
+        );
+        assert_eq!(
+            parsed
+                .objects
+                .template(ADDITIONAL_DATA)
+                .unwrap()
+                .text(REFERENCE_LABEL),
+            Some("***")
+        );
+    }
+
+    #[test]
+    fn test_emv_template_order() {
+        // Data objects are kept in ascending ID order and replaced in place
+        let template = EmvTemplate::new().with(5, "b").with(1, "a").with(5, "c");
+        assert_eq!(template.encode().unwrap(), "0101a0501c");
+        let mut template = template;
+        assert_eq!(template.remove(1), Some(EmvValue::from("a")));
+        assert_eq!(template.encode().unwrap(), "0501c");
+        assert!(EmvTemplate::new().with(1, "").encode().is_err());
+        assert!(EmvTemplate::new()
+            .with(1, "x".repeat(100))
+            .encode()
+            .is_err());
+    }
+
+    #[test]
+    fn test_emv_validation() {
+        let mut payload = payload();
+        payload.objects.remove(26);
+        assert!(payload.encode().is_err());
+
+        let mut payload = self::payload();
+        payload.objects.remove(MERCHANT_CITY);
+        assert!(payload.encode().is_err());
+
+        let mut payload = self::payload();
+        payload.objects.set(26, "not a template");
+        assert!(payload.encode().is_err());
+
+        let mut payload = self::payload();
+        payload.objects.set(CURRENCY, "BRL");
+        assert!(payload.encode().is_err());
+
+        let mut payload = self::payload();
+        payload.objects.set(AMOUNT, "10,50");
+        assert!(payload.encode().is_err());
+
+        let mut payload = self::payload();
+        payload.objects.set(INITIATION_METHOD, "13");
+        assert!(payload.encode().is_err());
+    }
+
+    #[test]
+    fn test_emv_parse_errors() {
+        let encoded = payload().encode().unwrap();
+        let (body, _) = encoded.split_at(encoded.len() - 4);
+        assert!(EmvPayload::parse(&format!("{}0000", body)).is_err());
+        assert!(EmvPayload::parse("").is_err());
+        assert!(EmvPayload::parse("0002016304FFFF").is_err());
+
+        // Truncated and duplicated data objects, with valid CRCs
+        for body in ["00020159", "0002010002016304"] {
+            let body = format!("{}6304", body.trim_end_matches("6304"));
+            let payload = format!("{}{:04X}", body, crc16(body.as_bytes()));
+            assert!(EmvPayload::parse(&payload).is_err());
+        }
+    }
+
+    #[test]
+    fn test_emv_crc16() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+    }
+}
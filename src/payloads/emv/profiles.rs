@@ -0,0 +1,356 @@
+//! National merchant-presented QR profiles
+//!
+//! Each scheme places its merchant account template under its own GUI and
+//! fixes the currency and country; the rest of the payload is the common
+//! EMV format. Scheme-specific data objects not covered here can be added
+//! to the payload returned by [`MerchantPayment::to_emv`].
+
+use super::{
+    format_amount, invalid, EmvPayload, EmvTemplate, ADDITIONAL_DATA, AMOUNT, DYNAMIC,
+    INITIATION_METHOD, REFERENCE_LABEL, STATIC,
+};
+use crate::generators;
+use crate::types::{Barcode, Result};
+
+/// UPI (NPCI, India): virtual payment address
+const UPI_GUI: &str = "A000000524";
+/// PromptPay (Thailand) credit transfer
+const PROMPTPAY_GUI: &str = "A000000677010111";
+/// DuitNow (PayNet, Malaysia)
+const DUITNOW_GUI: &str = "A0000006150001";
+/// PayNow (Singapore), as carried in SGQR codes
+const PAYNOW_GUI: &str = "SG.PAYNOW";
+
+const MAX_REFERENCE_LENGTH: usize = 25;
+
+/// PromptPay account identifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptPayTarget {
+    /// Thai mobile number, e.g. `0812345678`
+    Mobile(String),
+    /// National ID or tax ID, 13 digits
+    TaxId(String),
+    /// E-wallet ID, 15 digits
+    EWallet(String),
+}
+
+/// PayNow proxy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayNowProxy {
+    /// Mobile number with country code, e.g. `+6591234567`
+    Mobile(String),
+    /// Unique Entity Number of a business
+    Uen(String),
+}
+
+/// Payment scheme and the merchant account within it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmvProfile {
+    /// UPI (India): virtual payment address such as `merchant@bank`
+    Upi { vpa: String },
+    /// PromptPay (Thailand)
+    PromptPay(PromptPayTarget),
+    /// DuitNow (Malaysia): acquirer and merchant account IDs
+    DuitNow {
+        acquirer_id: String,
+        account_id: String,
+    },
+    /// PayNow in SGQR (Singapore); `editable` lets the payer change the
+    /// amount and `expiry` is a `YYYYMMDD` date
+    PayNow {
+        proxy: PayNowProxy,
+        editable: bool,
+        expiry: Option<String>,
+    },
+}
+
+/// Merchant payment request under a national profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerchantPayment {
+    pub profile: EmvProfile,
+    /// Merchant name, up to 25 characters
+    pub merchant_name: String,
+    /// Merchant city, up to 15 characters
+    pub merchant_city: String,
+    /// Merchant category code (ISO 18245), `0000` when not applicable
+    pub category_code: String,
+    /// Amount in minor units; payloads with an amount are dynamic
+    pub amount: Option<u64>,
+    /// Bill or order reference (additional data object 05)
+    pub reference: Option<String>,
+}
+
+impl EmvProfile {
+    /// ISO 4217 numeric currency of the scheme
+    pub fn currency(&self) -> &'static str {
+        match self {
+            EmvProfile::Upi { .. } => "356",
+            EmvProfile::PromptPay(_) => "764",
+            EmvProfile::DuitNow { .. } => "458",
+            EmvProfile::PayNow { .. } => "702",
+        }
+    }
+
+    /// ISO 3166 country code of the scheme
+    pub fn country(&self) -> &'static str {
+        match self {
+            EmvProfile::Upi { .. } => "IN",
+            EmvProfile::PromptPay(_) => "TH",
+            EmvProfile::DuitNow { .. } => "MY",
+            EmvProfile::PayNow { .. } => "SG",
+        }
+    }
+
+    /// Merchant account template ID and its data objects
+    fn account(&self) -> Result<(u8, EmvTemplate)> {
+        match self {
+            EmvProfile::Upi { vpa } => {
+                let valid = vpa.split_once('@').is_some_and(|(user, handle)| {
+                    !user.is_empty() && !handle.is_empty() && !vpa.contains(char::is_whitespace)
+                });
+                if !valid {
+                    return Err(invalid(&format!("'{}' is not a UPI address", vpa)));
+                }
+                Ok((26, EmvTemplate::with_gui(UPI_GUI).with(1, vpa.as_str())))
+            }
+            EmvProfile::PromptPay(target) => {
+                let (id, value) = match target {
+                    PromptPayTarget::Mobile(mobile) => {
+                        let local = mobile.trim_start_matches('0');
+                        if !digits(mobile) || local.len() != 9 {
+                            return Err(invalid(&format!(
+                                "'{}' is not a Thai mobile number",
+                                mobile
+                            )));
+                        }
+                        (1, format!("0066{}", local))
+                    }
+                    PromptPayTarget::TaxId(id) => (2, fixed_digits(id, 13, "tax ID")?),
+                    PromptPayTarget::EWallet(id) => (3, fixed_digits(id, 15, "e-wallet ID")?),
+                };
+                Ok((29, EmvTemplate::with_gui(PROMPTPAY_GUI).with(id, value)))
+            }
+            EmvProfile::DuitNow {
+                acquirer_id,
+                account_id,
+            } => {
+                if acquirer_id.is_empty() || account_id.is_empty() {
+                    return Err(invalid("DuitNow requires the acquirer and account IDs"));
+                }
+                let template = EmvTemplate::with_gui(DUITNOW_GUI)
+                    .with(1, acquirer_id.as_str())
+                    .with(2, account_id.as_str());
+                Ok((26, template))
+            }
+            EmvProfile::PayNow {
+                proxy,
+                editable,
+                expiry,
+            } => {
+                let (kind, value) = match proxy {
+                    PayNowProxy::Mobile(mobile) => {
+                        let valid = mobile
+                            .strip_prefix('+')
+                            .is_some_and(|number| digits(number) && number.len() >= 8);
+                        if !valid {
+                            return Err(invalid(&format!(
+                                "PayNow mobile '{}' needs the country code",
+                                mobile
+                            )));
+                        }
+                        ("0", mobile)
+                    }
+                    PayNowProxy::Uen(uen) => {
+                        if uen.is_empty() || !uen.bytes().all(|b| b.is_ascii_alphanumeric()) {
+                            return Err(invalid(&format!("'{}' is not a UEN", uen)));
+                        }
+                        ("2", uen)
+                    }
+                };
+                let mut template = EmvTemplate::with_gui(PAYNOW_GUI)
+                    .with(1, kind)
+                    .with(2, value.as_str())
+                    .with(3, if *editable { "1" } else { "0" });
+                if let Some(expiry) = expiry {
+                    template.set(4, fixed_digits(expiry, 8, "expiry date")?);
+                }
+                Ok((26, template))
+            }
+        }
+    }
+}
+
+impl MerchantPayment {
+    pub fn new(profile: EmvProfile, merchant_name: &str, merchant_city: &str) -> Self {
+        Self {
+            profile,
+            merchant_name: merchant_name.to_string(),
+            merchant_city: merchant_city.to_string(),
+            category_code: "0000".to_string(),
+            amount: None,
+            reference: None,
+        }
+    }
+
+    /// The generic payload, to add scheme-specific data objects such as the
+    /// SGQR ID template (51) before encoding
+    pub fn to_emv(&self) -> Result<EmvPayload> {
+        let (id, account) = self.profile.account()?;
+        let mut payload = EmvPayload::new(
+            &self.category_code,
+            self.profile.currency(),
+            self.profile.country(),
+            &self.merchant_name,
+            &self.merchant_city,
+        );
+        payload.objects.set(id, account);
+        match self.amount {
+            Some(amount) => {
+                payload.objects.set(INITIATION_METHOD, DYNAMIC);
+                payload.objects.set(AMOUNT, format_amount(amount));
+            }
+            None => payload.objects.set(INITIATION_METHOD, STATIC),
+        }
+        if let Some(reference) = &self.reference {
+            if reference.chars().count() > MAX_REFERENCE_LENGTH {
+                return Err(invalid(&format!(
+                    "the reference exceeds {} characters",
+                    MAX_REFERENCE_LENGTH
+                )));
+            }
+            payload.objects.set(
+                ADDITIONAL_DATA,
+                EmvTemplate::new().with(REFERENCE_LABEL, reference.as_str()),
+            );
+        }
+        Ok(payload)
+    }
+
+    /// Payload string, ending with its CRC
+    pub fn encode(&self) -> Result<String> {
+        self.to_emv()?.encode()
+    }
+}
+
+/// Generate the QR code of a merchant payment
+pub fn generate_merchant_qr(payment: &MerchantPayment) -> Result<Barcode> {
+    generators::generate_qr(&payment.encode()?)
+}
+
+fn digits(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn fixed_digits(value: &str, length: usize, name: &str) -> Result<String> {
+    if value.len() != length || !digits(value) {
+        return Err(invalid(&format!("the {} must be {} digits", name, length)));
+    }
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_promptpay() {
+        // Widely published PromptPay example for 0812345678
+        let payment = MerchantPayment::new(
+            EmvProfile::PromptPay(PromptPayTarget::Mobile("0812345678".to_string())),
+            "Shop",
+            "Bangkok",
+        );
+        let encoded = payment.encode().unwrap();
+        assert!(encoded.starts_with(
+            "00020101021129370016A000000677010111011300668123456785204000053037645802TH"
+        ));
+        let parsed = EmvPayload::parse(&encoded).unwrap();
+        assert_eq!(
+            parsed.objects.template(29).unwrap().text(1),
+            Some("0066812345678")
+        );
+
+        let mut payment = payment;
+        payment.profile =
+            EmvProfile::PromptPay(PromptPayTarget::TaxId("1234567890123".to_string()));
+        payment.amount = Some(5000);
+        let encoded = payment.encode().unwrap();
+        assert!(encoded.starts_with("000201010212"));
+        assert!(encoded.contains("02131234567890123"));
+        assert!(encoded.contains("540550.00"));
+
+        payment.profile = EmvProfile::PromptPay(PromptPayTarget::Mobile("12345".to_string()));
+        assert!(payment.encode().is_err());
+    }
+
+    #[test]
+    fn test_upi() {
+        let mut payment = MerchantPayment::new(
+            EmvProfile::Upi {
+                vpa: "merchant@okbank".to_string(),
+            },
+            "Chai Stall",
+            "Mumbai",
+        );
+        payment.category_code = "5812".to_string();
+        payment.reference = Some("INV42".to_string());
+        let encoded = payment.encode().unwrap();
+        assert!(encoded.contains("26330010A0000005240115merchant@okbank"));
+        assert!(encoded.contains("5303356"));
+        assert!(encoded.contains("5802IN"));
+        assert!(encoded.contains("62090505INV42"));
+
+        payment.profile = EmvProfile::Upi {
+            vpa: "merchant".to_string(),
+        };
+        assert!(payment.encode().is_err());
+    }
+
+    #[test]
+    fn test_duitnow_and_paynow() {
+        let payment = MerchantPayment::new(
+            EmvProfile::DuitNow {
+                acquirer_id: "890053".to_string(),
+                account_id: "0000123456".to_string(),
+            },
+            "Kedai Kopi",
+            "Kuala Lumpur",
+        );
+        let encoded = payment.encode().unwrap();
+        assert!(encoded.contains("26420014A0000006150001010689005302100000123456"));
+        assert!(encoded.contains("5303458"));
+        assert!(encoded.contains("5802MY"));
+
+        let payment = MerchantPayment::new(
+            EmvProfile::PayNow {
+                proxy: PayNowProxy::Uen("201403121W".to_string()),
+                editable: false,
+                expiry: Some("20301231".to_string()),
+            },
+            "Hawker Centre",
+            "Singapore",
+        );
+        // SGQR ID template, added through the generic payload
+        let mut emv = payment.to_emv().unwrap();
+        emv.objects.set(
+            51,
+            EmvTemplate::with_gui("SG.COM.NETS").with(1, "123456789012"),
+        );
+        let encoded = emv.encode().unwrap();
+        assert!(encoded.contains("26490009SG.PAYNOW010120210201403121W03010040820301231"));
+        assert!(encoded.contains("51310011SG.COM.NETS0112123456789012"));
+        assert_eq!(EmvPayload::parse(&encoded).unwrap(), emv);
+        assert!(generate_merchant_qr(&payment).is_ok());
+
+        let payment = MerchantPayment::new(
+            EmvProfile::PayNow {
+                proxy: PayNowProxy::Mobile("91234567".to_string()),
+                editable: true,
+                expiry: None,
+            },
+            "Hawker",
+            "Singapore",
+        );
+        assert!(payment.encode().is_err());
+    }
+}
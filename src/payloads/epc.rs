@@ -0,0 +1,346 @@
+//! EPC QR code payloads (EPC069-12, SEPA credit transfer)
+//!
+//! The European Payments Council QR code, also known as GiroCode, carries a
+//! SEPA credit transfer as up to twelve lines of text: service tag `BCD`,
+//! version, character set, identification `SCT`, the beneficiary's BIC,
+//! name and IBAN, and the optional amount, purpose and remittance
+//! information. The payload is limited to 331 bytes and must be encoded
+//! with error correction level M.
+
+use crate::generators;
+use crate::types::{Barcode, BarcodeConfig, QRErrorCorrection, QuickCodesError, Result};
+
+const SERVICE_TAG: &str = "BCD";
+const IDENTIFICATION: &str = "SCT";
+
+/// Character set 1 (UTF-8), which is how the QR generator encodes text
+const CHARSET_UTF8: &str = "1";
+
+const MAX_PAYLOAD_BYTES: usize = 331;
+const MAX_NAME_LENGTH: usize = 70;
+const MAX_REFERENCE_LENGTH: usize = 35;
+const MAX_TEXT_LENGTH: usize = 140;
+const MAX_INFORMATION_LENGTH: usize = 70;
+
+/// Largest amount, EUR 999999999.99, in cents
+const MAX_AMOUNT: u64 = 99_999_999_999;
+
+/// Payload version
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpcVersion {
+    /// Version 001: the BIC is mandatory
+    V001,
+    /// Version 002: the BIC is optional within the EEA
+    V002,
+}
+
+/// Remittance information, either structured or free text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Remittance {
+    /// Structured creditor reference (ISO 11649 `RF...`), up to 35
+    /// characters
+    Reference(String),
+    /// Unstructured text, up to 140 characters
+    Text(String),
+}
+
+/// SEPA credit transfer for an EPC QR code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpcPayload {
+    pub version: EpcVersion,
+    /// BIC of the beneficiary's bank (8 or 11 characters)
+    pub bic: Option<String>,
+    /// Beneficiary name, up to 70 characters
+    pub name: String,
+    /// Beneficiary IBAN; spaces are removed
+    pub iban: String,
+    /// Amount in euro cents
+    pub amount: Option<u64>,
+    /// Purpose code of four letters, e.g. `GDDS`
+    pub purpose: Option<String>,
+    pub remittance: Option<Remittance>,
+    /// Beneficiary to originator information, up to 70 characters
+    pub information: Option<String>,
+}
+
+impl EpcPayload {
+    /// Version 002 payload without BIC
+    pub fn new(name: &str, iban: &str) -> Self {
+        Self {
+            version: EpcVersion::V002,
+            bic: None,
+            name: name.to_string(),
+            iban: iban.to_string(),
+            amount: None,
+            purpose: None,
+            remittance: None,
+            information: None,
+        }
+    }
+
+    /// The payload text, with lines separated by LF and trailing empty
+    /// lines left out
+    pub fn encode(&self) -> Result<String> {
+        let version = match self.version {
+            EpcVersion::V001 => "001",
+            EpcVersion::V002 => "002",
+        };
+        let bic = match (&self.bic, self.version) {
+            (Some(bic), _) => validate_bic(bic)?,
+            (None, EpcVersion::V001) => return Err(invalid("version 001 requires the BIC")),
+            (None, EpcVersion::V002) => String::new(),
+        };
+        let name = self.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+            return Err(invalid(&format!(
+                "beneficiary name must have 1-{} characters",
+                MAX_NAME_LENGTH
+            )));
+        }
+        let iban = validate_iban(&self.iban)?;
+        let amount = match self.amount {
+            Some(amount) if (1..=MAX_AMOUNT).contains(&amount) => {
+                format!("EUR{}.{:02}", amount / 100, amount % 100)
+            }
+            Some(_) => return Err(invalid("amount must be EUR 0.01 to 999999999.99")),
+            None => String::new(),
+        };
+        let purpose = match &self.purpose {
+            Some(purpose)
+                if purpose.len() == 4 && purpose.bytes().all(|b| b.is_ascii_alphanumeric()) =>
+            {
+                purpose.to_ascii_uppercase()
+            }
+            Some(purpose) => {
+                return Err(invalid(&format!(
+                    "purpose must be a 4-character code, got '{}'",
+                    purpose
+                )))
+            }
+            None => String::new(),
+        };
+        let (reference, text) = match &self.remittance {
+            Some(Remittance::Reference(reference)) => {
+                check_length("remittance reference", reference, MAX_REFERENCE_LENGTH)?;
+                (reference.as_str(), "")
+            }
+            Some(Remittance::Text(text)) => {
+                check_length("remittance text", text, MAX_TEXT_LENGTH)?;
+                ("", text.as_str())
+            }
+            None => ("", ""),
+        };
+        let information = self.information.as_deref().unwrap_or("");
+        check_length(
+            "beneficiary information",
+            information,
+            MAX_INFORMATION_LENGTH,
+        )?;
+
+        let mut lines = vec![
+            SERVICE_TAG,
+            version,
+            CHARSET_UTF8,
+            IDENTIFICATION,
+            &bic,
+            name,
+            &iban,
+            &amount,
+            &purpose,
+            reference,
+            text,
+            information,
+        ];
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let payload = lines.join("\n");
+        if payload.len() > MAX_PAYLOAD_BYTES {
+            return Err(invalid(&format!(
+                "payload is {} bytes, the limit is {}",
+                payload.len(),
+                MAX_PAYLOAD_BYTES
+            )));
+        }
+        Ok(payload)
+    }
+}
+
+/// Generate an EPC QR code, always with error correction level M
+pub fn generate_epc(payload: &EpcPayload) -> Result<Barcode> {
+    let mut config = BarcodeConfig::default();
+    config.qr_config.error_correction = QRErrorCorrection::Medium;
+    generators::qr::generate_qr_with_config(&payload.encode()?, &config)
+}
+
+/// Uppercase BIC: bank (4 letters), country (2 letters), location (2) and
+/// optional branch (3)
+fn validate_bic(bic: &str) -> Result<String> {
+    let bic = bic.trim().to_ascii_uppercase();
+    let bytes = bic.as_bytes();
+    let valid = (bic.len() == 8 || bic.len() == 11)
+        && bytes[..6].iter().all(|b| b.is_ascii_uppercase())
+        && bytes[6..].iter().all(|b| b.is_ascii_alphanumeric());
+    if !valid {
+        return Err(invalid(&format!("'{}' is not a BIC", bic)));
+    }
+    Ok(bic)
+}
+
+/// IBAN without spaces, checked with ISO 7064 mod 97-10
+fn validate_iban(iban: &str) -> Result<String> {
+    let iban = normalize_iban(iban);
+    if !is_valid_iban(&iban) {
+        return Err(invalid(&format!("'{}' is not a valid IBAN", iban)));
+    }
+    Ok(iban)
+}
+
+/// IBAN in upper case without spaces
+pub(crate) fn normalize_iban(iban: &str) -> String {
+    iban.chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+/// Whether a normalized IBAN has a country code, check digits and 11-30
+/// letters or digits, with a correct mod 97-10 check
+pub(crate) fn is_valid_iban(iban: &str) -> bool {
+    let bytes = iban.as_bytes();
+    (15..=34).contains(&iban.len())
+        && bytes[..2].iter().all(|b| b.is_ascii_uppercase())
+        && bytes[2..4].iter().all(|b| b.is_ascii_digit())
+        && bytes[4..].iter().all(|b| b.is_ascii_alphanumeric())
+        && mod97(iban) == 1
+}
+
+/// ISO 7064 mod 97-10 remainder of an IBAN or ISO 11649 creditor
+/// reference: the first four characters move to the end and letters count
+/// as 10-35. Valid values give 1
+pub(crate) fn mod97(value: &str) -> u32 {
+    let bytes = value.as_bytes();
+    bytes[4..]
+        .iter()
+        .chain(&bytes[..4])
+        .fold(0, |remainder, &b| {
+            let value = u32::from(if b.is_ascii_digit() {
+                b - b'0'
+            } else {
+                b.to_ascii_uppercase() - b'A' + 10
+            });
+            if value < 10 {
+                (remainder * 10 + value) % 97
+            } else {
+                (remainder * 100 + value) % 97
+            }
+        })
+}
+
+fn check_length(name: &str, value: &str, max: usize) -> Result<()> {
+    if value.chars().count() > max {
+        return Err(invalid(&format!(
+            "{} is longer than {} characters",
+            name, max
+        )));
+    }
+    Ok(())
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid EPC payload: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload() -> EpcPayload {
+        let mut payload = EpcPayload::new(
+            "Wikimedia Foerdergesellschaft",
+            "DE33 1002 0500 0001 1947 00",
+        );
+        payload.bic = Some("BFSWDE33BER".to_string());
+        payload.amount = Some(12345);
+        payload.remittance = Some(Remittance::Text("Spende fuer Wikipedia".to_string()));
+        payload
+    }
+
+    #[test]
+    fn test_epc_payload() {
+        assert_eq!(
+            payload().encode().unwrap(),
+            "BCD\n002\n1\nSCT\nBFSWDE33BER\nWikimedia Foerdergesellschaft\n\
+             DE33100205000001194700\nEUR123.45\n\n\nSpende fuer Wikipedia"
+        );
+
+        // Version 002 without BIC, amount or remittance
+        let payload = EpcPayload::new("Red Cross", "DE89370400440532013000");
+        assert_eq!(
+            payload.encode().unwrap(),
+            "BCD\n002\n1\nSCT\n\nRed Cross\nDE89370400440532013000"
+        );
+
+        let mut payload = self::payload();
+        payload.purpose = Some("char".to_string());
+        payload.remittance = Some(Remittance::Reference("RF18539007547034".to_string()));
+        payload.information = Some("Thank you".to_string());
+        assert!(payload
+            .encode()
+            .unwrap()
+            .ends_with("EUR123.45\nCHAR\nRF18539007547034\n\nThank you"));
+    }
+
+    #[test]
+    fn test_epc_validation() {
+        assert!(validate_iban("DE89370400440532013001").is_err());
+        assert!(validate_iban("DE8937040044").is_err());
+        assert!(validate_iban("1289370400440532013000").is_err());
+        assert!(validate_bic("BFSWDE3").is_err());
+        assert!(validate_bic("BF5WDE33").is_err());
+
+        let mut payload = self::payload();
+        payload.version = EpcVersion::V001;
+        payload.bic = None;
+        assert!(payload.encode().is_err());
+
+        let mut payload = self::payload();
+        payload.amount = Some(0);
+        assert!(payload.encode().is_err());
+        payload.amount = Some(MAX_AMOUNT + 1);
+        assert!(payload.encode().is_err());
+
+        let mut payload = self::payload();
+        payload.name = "x".repeat(71);
+        assert!(payload.encode().is_err());
+        let mut payload = self::payload();
+        payload.purpose = Some("GOODS".to_string());
+        assert!(payload.encode().is_err());
+        let mut payload = self::payload();
+        payload.remittance = Some(Remittance::Text("x".repeat(141)));
+        assert!(payload.encode().is_err());
+    }
+
+    #[test]
+    fn test_epc_size_limit() {
+        // Multi-byte characters count as bytes towards the 331-byte limit
+        let mut payload = self::payload();
+        payload.name = "ä".repeat(70);
+        payload.remittance = Some(Remittance::Text("ü".repeat(140)));
+        assert!(payload.encode().is_err());
+
+        payload.name = "a".repeat(70);
+        payload.remittance = Some(Remittance::Text("u".repeat(140)));
+        assert!(payload.encode().unwrap().len() <= MAX_PAYLOAD_BYTES);
+    }
+
+    #[test]
+    fn test_generate_epc() {
+        let barcode = generate_epc(&payload()).unwrap();
+        assert_eq!(
+            barcode.config.qr_config.error_correction,
+            QRErrorCorrection::Medium
+        );
+        assert!(barcode.modules.as_matrix().is_some());
+    }
+}
@@ -0,0 +1,117 @@
+//! Escaping shared by the text payload formats
+
+/// Backslash-escape the special characters (and the backslash itself)
+pub(crate) fn escape(value: &str, special: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '\\' || special.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape a vCard or iCalendar text value: backslash, semicolon, comma and
+/// line breaks as `\n`
+pub(crate) fn escape_text(value: &str) -> String {
+    escape(&value.replace("\r\n", "\n"), ";,").replace('\n', "\\n")
+}
+
+/// Remove backslash escapes; with `newlines`, `\n` and `\N` are line breaks
+pub(crate) fn unescape(value: &str, newlines: bool) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') if newlines => unescaped.push('\n'),
+            Some(next) => unescaped.push(next),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Split at separators that are not backslash-escaped, keeping the escapes
+pub(crate) fn split_unescaped(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (i, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == separator {
+            parts.push(&value[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+/// Percent-encode everything but the unreserved characters of RFC 3986
+pub(crate) fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            encoded.push(char::from(b));
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+    encoded
+}
+
+/// Decode `%XX` sequences; `None` when they are malformed or not UTF-8
+pub(crate) fn percent_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backslash_escapes() {
+        let escaped = escape(r#"a;b,c:d"e\f"#, ";,:\"");
+        assert_eq!(escaped, r#"a\;b\,c\:d\"e\\f"#);
+        assert_eq!(unescape(&escaped, false), r#"a;b,c:d"e\f"#);
+        assert_eq!(
+            split_unescaped(r"S:a\;b;P:c;;", ';'),
+            vec![r"S:a\;b", "P:c", "", ""]
+        );
+
+        assert_eq!(escape_text("one, two;\nthree"), r"one\, two\;\nthree");
+        assert_eq!(unescape(r"one\, two\;\nthree", true), "one, two;\nthree");
+    }
+
+    #[test]
+    fn test_percent_encoding() {
+        assert_eq!(percent_encode("Olá mundo&x=1"), "Ol%C3%A1%20mundo%26x%3D1");
+        assert_eq!(
+            percent_decode("Ol%C3%A1%20mundo%26x%3D1").unwrap(),
+            "Olá mundo&x=1"
+        );
+        assert!(percent_decode("100%").is_none());
+        assert!(percent_decode("%zz").is_none());
+    }
+}
@@ -0,0 +1,147 @@
+//! Geographic location payloads (`geo:` URI, RFC 5870)
+
+use crate::generators;
+use crate::types::{Barcode, QuickCodesError, Result};
+
+/// Point in WGS 84 coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoLocation {
+    /// Latitude in decimal degrees (-90 to 90)
+    pub latitude: f64,
+    /// Longitude in decimal degrees (-180 to 180)
+    pub longitude: f64,
+    /// Altitude in metres
+    pub altitude: Option<f64>,
+    /// Uncertainty in metres (`u` parameter)
+    pub uncertainty: Option<f64>,
+}
+
+impl GeoLocation {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            altitude: None,
+            uncertainty: None,
+        }
+    }
+
+    pub fn encode(&self) -> Result<String> {
+        if !(-90.0..=90.0).contains(&self.latitude) {
+            return Err(invalid(&format!(
+                "latitude {} is out of range",
+                self.latitude
+            )));
+        }
+        if !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(invalid(&format!(
+                "longitude {} is out of range",
+                self.longitude
+            )));
+        }
+        let mut uri = format!("geo:{},{}", self.latitude, self.longitude);
+        if let Some(altitude) = self.altitude {
+            if !altitude.is_finite() {
+                return Err(invalid("altitude must be a finite number"));
+            }
+            uri.push_str(&format!(",{}", altitude));
+        }
+        if let Some(uncertainty) = self.uncertainty {
+            if !uncertainty.is_finite() || uncertainty < 0.0 {
+                return Err(invalid("uncertainty must be a positive number of metres"));
+            }
+            uri.push_str(&format!(";u={}", uncertainty));
+        }
+        Ok(uri)
+    }
+
+    /// Parse a `geo:` URI, such as decoded `ReadResult::data`. A query
+    /// (`?q=...`, as added by some map apps) is ignored
+    pub fn parse(data: &str) -> Result<Self> {
+        let scheme = data.get(..4).unwrap_or("");
+        if !scheme.eq_ignore_ascii_case("geo:") {
+            return Err(invalid("the URI must start with geo:"));
+        }
+        let path = data[4..].split('?').next().unwrap_or("");
+        let mut parts = path.split(';');
+        let coordinates: Vec<f64> = parts
+            .next()
+            .unwrap_or("")
+            .split(',')
+            .map(|value| {
+                value
+                    .trim()
+                    .parse()
+                    .map_err(|_| invalid(&format!("'{}' is not a number", value)))
+            })
+            .collect::<Result<_>>()?;
+        if !(2..=3).contains(&coordinates.len()) {
+            return Err(invalid(
+                "expected latitude, longitude and optional altitude",
+            ));
+        }
+
+        let mut location = Self::new(coordinates[0], coordinates[1]);
+        location.altitude = coordinates.get(2).copied();
+        for parameter in parts {
+            let (name, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+            match name.to_ascii_lowercase().as_str() {
+                "crs" if !value.eq_ignore_ascii_case("wgs84") => {
+                    return Err(invalid(&format!("unsupported coordinate system {}", value)))
+                }
+                "u" => {
+                    location.uncertainty = Some(
+                        value
+                            .parse()
+                            .map_err(|_| invalid(&format!("'{}' is not an uncertainty", value)))?,
+                    )
+                }
+                _ => {}
+            }
+        }
+        // Validate the ranges
+        location.encode()?;
+        Ok(location)
+    }
+}
+
+/// Generate a QR code with a location
+pub fn generate_geo(location: &GeoLocation) -> Result<Barcode> {
+    generators::generate_qr(&location.encode()?)
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid geo URI: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geo_encode() {
+        let mut location = GeoLocation::new(-23.5614, -46.6559);
+        assert_eq!(location.encode().unwrap(), "geo:-23.5614,-46.6559");
+        location.altitude = Some(760.0);
+        location.uncertainty = Some(25.0);
+        assert_eq!(location.encode().unwrap(), "geo:-23.5614,-46.6559,760;u=25");
+        assert_eq!(
+            GeoLocation::parse(&location.encode().unwrap()).unwrap(),
+            location
+        );
+
+        assert!(GeoLocation::new(91.0, 0.0).encode().is_err());
+        assert!(GeoLocation::new(0.0, -181.0).encode().is_err());
+    }
+
+    #[test]
+    fn test_geo_parse() {
+        let location = GeoLocation::parse("GEO:48.2010,16.3695;crs=wgs84?q=Wien").unwrap();
+        assert_eq!(location, GeoLocation::new(48.201, 16.3695));
+
+        assert!(GeoLocation::parse("geo:48.2010").is_err());
+        assert!(GeoLocation::parse("geo:a,b").is_err());
+        assert!(GeoLocation::parse("geo:1,2;crs=utm").is_err());
+        assert!(GeoLocation::parse("https://maps.example.com").is_err());
+    }
+}
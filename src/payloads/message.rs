@@ -0,0 +1,305 @@
+//! Messaging payloads: SMS (`SMSTO:` and `sms:`), e-mail (`MATMSG:` and
+//! `mailto:`) and phone calls (`tel:`)
+
+use super::escape::{escape, percent_decode, percent_encode, split_unescaped, unescape};
+use crate::generators;
+use crate::types::{Barcode, QuickCodesError, Result};
+
+/// Characters escaped in MATMSG values
+const MATMSG_SPECIAL: &str = ";:";
+
+/// Text message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sms {
+    pub number: String,
+    pub message: Option<String>,
+}
+
+/// E-mail to send
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Email {
+    pub to: String,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+}
+
+/// Phone number to call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Telephone {
+    /// Number in international format, e.g. `+55-11-99999-9999`
+    pub number: String,
+}
+
+impl Sms {
+    pub fn new(number: &str, message: &str) -> Self {
+        Self {
+            number: number.to_string(),
+            message: Some(message.to_string()).filter(|m| !m.is_empty()),
+        }
+    }
+
+    /// `SMSTO:number:message`, the form read by most phone cameras
+    pub fn encode(&self) -> Result<String> {
+        let number = phone_number(&self.number)?;
+        Ok(match &self.message {
+            Some(message) => format!("SMSTO:{}:{}", number, message),
+            None => format!("SMSTO:{}", number),
+        })
+    }
+
+    /// Parse `SMSTO:` or an `sms:` URI with an optional `body`, such as
+    /// decoded `ReadResult::data`
+    pub fn parse(data: &str) -> Result<Self> {
+        let (scheme, rest) = data
+            .split_once(':')
+            .ok_or_else(|| invalid("expected SMSTO: or sms:"))?;
+        let (number, message) = match scheme.to_ascii_uppercase().as_str() {
+            "SMSTO" => {
+                let (number, message) = rest.split_once(':').unwrap_or((rest, ""));
+                (number.to_string(), message.to_string())
+            }
+            "SMS" => {
+                let (number, query) = rest.split_once('?').unwrap_or((rest, ""));
+                let number =
+                    percent_decode(number).ok_or_else(|| invalid("bad percent-encoding"))?;
+                (number, query_parameter(query, "body")?.unwrap_or_default())
+            }
+            _ => return Err(invalid("expected SMSTO: or sms:")),
+        };
+        let sms = Self::new(&number, &message);
+        sms.encode()?;
+        Ok(sms)
+    }
+}
+
+impl Email {
+    pub fn new(to: &str) -> Self {
+        Self {
+            to: to.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// `MATMSG:TO:...;SUB:...;BODY:...;;`
+    pub fn to_matmsg(&self) -> Result<String> {
+        check_address(&self.to)?;
+        let mut payload = format!("MATMSG:TO:{};", escape(&self.to, MATMSG_SPECIAL));
+        if let Some(subject) = &self.subject {
+            payload.push_str(&format!("SUB:{};", escape(subject, MATMSG_SPECIAL)));
+        }
+        if let Some(body) = &self.body {
+            payload.push_str(&format!("BODY:{};", escape(body, MATMSG_SPECIAL)));
+        }
+        payload.push(';');
+        Ok(payload)
+    }
+
+    /// `mailto:` URI with percent-encoded subject and body
+    pub fn to_mailto(&self) -> Result<String> {
+        check_address(&self.to)?;
+        let mut parameters = Vec::new();
+        if let Some(subject) = &self.subject {
+            parameters.push(format!("subject={}", percent_encode(subject)));
+        }
+        if let Some(body) = &self.body {
+            parameters.push(format!("body={}", percent_encode(body)));
+        }
+        let mut uri = format!("mailto:{}", self.to);
+        if !parameters.is_empty() {
+            uri.push('?');
+            uri.push_str(&parameters.join("&"));
+        }
+        Ok(uri)
+    }
+
+    /// Parse `MATMSG:` or a `mailto:` URI, such as decoded
+    /// `ReadResult::data`
+    pub fn parse(data: &str) -> Result<Self> {
+        let email = if let Some(body) = data.strip_prefix("MATMSG:") {
+            let mut email = Self::default();
+            for field in split_unescaped(body, ';') {
+                let Some((key, value)) = field.split_once(':') else {
+                    continue;
+                };
+                let value = unescape(value, false);
+                match key {
+                    "TO" => email.to = value,
+                    "SUB" => email.subject = Some(value),
+                    "BODY" => email.body = Some(value),
+                    _ => {}
+                }
+            }
+            email
+        } else if data
+            .get(..7)
+            .is_some_and(|s| s.eq_ignore_ascii_case("mailto:"))
+        {
+            let (to, query) = data[7..].split_once('?').unwrap_or((&data[7..], ""));
+            Self {
+                to: percent_decode(to).ok_or_else(|| invalid("bad percent-encoding"))?,
+                subject: query_parameter(query, "subject")?,
+                body: query_parameter(query, "body")?,
+            }
+        } else {
+            return Err(invalid("expected MATMSG: or mailto:"));
+        };
+        check_address(&email.to)?;
+        Ok(email)
+    }
+}
+
+impl Telephone {
+    pub fn new(number: &str) -> Self {
+        Self {
+            number: number.to_string(),
+        }
+    }
+
+    /// `tel:` URI (RFC 3966), without spaces
+    pub fn encode(&self) -> Result<String> {
+        Ok(format!("tel:{}", phone_number(&self.number)?))
+    }
+
+    /// Parse a `tel:` URI, such as decoded `ReadResult::data`; parameters
+    /// such as `;ext=` are kept in the number
+    pub fn parse(data: &str) -> Result<Self> {
+        let number = data
+            .get(..4)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("tel:"))
+            .map(|_| &data[4..])
+            .ok_or_else(|| invalid("expected tel:"))?;
+        let telephone = Self::new(number);
+        telephone.encode()?;
+        Ok(telephone)
+    }
+}
+
+/// Generate a QR code that composes a text message
+pub fn generate_sms(sms: &Sms) -> Result<Barcode> {
+    generators::generate_qr(&sms.encode()?)
+}
+
+/// Generate a QR code that composes an e-mail (`mailto:`)
+pub fn generate_email(email: &Email) -> Result<Barcode> {
+    generators::generate_qr(&email.to_mailto()?)
+}
+
+/// Generate a QR code that calls a phone number
+pub fn generate_tel(telephone: &Telephone) -> Result<Barcode> {
+    generators::generate_qr(&telephone.encode()?)
+}
+
+/// Phone number without spaces: an optional `+`, digits, visual
+/// separators and parameters such as `;ext=123`
+fn phone_number(number: &str) -> Result<String> {
+    let number: String = number.chars().filter(|c| !c.is_whitespace()).collect();
+    let (main, parameters) = number.split_once(';').unwrap_or((&number, ""));
+    let digits = main.strip_prefix('+').unwrap_or(main);
+    let valid = digits.bytes().any(|b| b.is_ascii_digit())
+        && digits
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b"-.()*#".contains(&b))
+        && !parameters.contains(':');
+    if !valid {
+        return Err(invalid(&format!("'{}' is not a phone number", number)));
+    }
+    Ok(number)
+}
+
+fn check_address(address: &str) -> Result<()> {
+    let valid = address
+        .split_once('@')
+        .is_some_and(|(local, domain)| !local.is_empty() && !domain.is_empty())
+        && !address.contains(|c: char| c.is_whitespace() || c == '?' || c == ';');
+    if !valid {
+        return Err(invalid(&format!("'{}' is not an e-mail address", address)));
+    }
+    Ok(())
+}
+
+/// Percent-decoded value of a URI query parameter
+fn query_parameter(query: &str, name: &str) -> Result<Option<String>> {
+    for pair in query.split('&') {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        if key.eq_ignore_ascii_case(name) {
+            let value = percent_decode(&value.replace('+', "%20"))
+                .ok_or_else(|| invalid("bad percent-encoding"))?;
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid message payload: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sms() {
+        let sms = Sms::new("+55 11 99999-9999", "Olá: confirmado");
+        assert_eq!(
+            sms.encode().unwrap(),
+            "SMSTO:+551199999-9999:Olá: confirmado"
+        );
+        assert_eq!(
+            Sms::parse(&sms.encode().unwrap()).unwrap(),
+            Sms::new("+551199999-9999", "Olá: confirmado")
+        );
+
+        let sms = Sms::parse("sms:+15551234567?body=Hello%20there").unwrap();
+        assert_eq!(sms.number, "+15551234567");
+        assert_eq!(sms.message.as_deref(), Some("Hello there"));
+        assert_eq!(Sms::parse("SMSTO:12345").unwrap().message, None);
+
+        assert!(Sms::new("call me", "").encode().is_err());
+        assert!(Sms::parse("tel:12345").is_err());
+    }
+
+    #[test]
+    fn test_email() {
+        let mut email = Email::new("contato@example.com");
+        email.subject = Some("Pedido 123: dúvida".to_string());
+        email.body = Some("Olá; tudo bem?".to_string());
+
+        let matmsg = email.to_matmsg().unwrap();
+        assert_eq!(
+            matmsg,
+            "MATMSG:TO:contato@example.com;SUB:Pedido 123\\: dúvida;BODY:Olá\\; tudo bem?;;"
+        );
+        assert_eq!(Email::parse(&matmsg).unwrap(), email);
+
+        let mailto = email.to_mailto().unwrap();
+        assert_eq!(
+            mailto,
+            "mailto:contato@example.com?subject=Pedido%20123%3A%20d%C3%BAvida\
+             &body=Ol%C3%A1%3B%20tudo%20bem%3F"
+        );
+        assert_eq!(Email::parse(&mailto).unwrap(), email);
+        assert_eq!(
+            Email::parse("mailto:a@b.com").unwrap(),
+            Email::new("a@b.com")
+        );
+
+        assert!(Email::new("not an address").to_mailto().is_err());
+        assert!(Email::parse("MATMSG:SUB:x;;").is_err());
+    }
+
+    #[test]
+    fn test_tel() {
+        let telephone = Telephone::new("+55 (11) 99999-9999");
+        assert_eq!(telephone.encode().unwrap(), "tel:+55(11)99999-9999");
+        assert_eq!(
+            Telephone::parse("tel:+1-555-123-4567;ext=89")
+                .unwrap()
+                .number,
+            "+1-555-123-4567;ext=89"
+        );
+        assert!(Telephone::new("").encode().is_err());
+        assert!(Telephone::parse("tel:abc").is_err());
+        assert!(Telephone::parse("fax:123").is_err());
+    }
+}
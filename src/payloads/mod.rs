@@ -0,0 +1,133 @@
+//! Payload builders and parsers for data carried in barcodes
+
+pub mod boleto;
+pub mod calendar;
+pub mod contact;
+pub mod emv;
+pub mod epc;
+pub(crate) mod escape;
+pub mod geo;
+pub mod message;
+pub mod pix;
+pub mod swiss_qr;
+pub mod wifi;
+
+pub use boleto::{generate_arrecadacao, generate_boleto, Arrecadacao, Boleto};
+pub use calendar::{generate_event, CalendarEvent, EventTime};
+pub use contact::{generate_mecard, generate_vcard, Contact, PostalAddress, VCardVersion};
+pub use emv::{
+    generate_emv, generate_merchant_qr, EmvPayload, EmvProfile, EmvTemplate, EmvValue,
+    MerchantPayment, PayNowProxy, PromptPayTarget,
+};
+pub use epc::{generate_epc, EpcPayload, EpcVersion, Remittance};
+pub use geo::{generate_geo, GeoLocation};
+pub use message::{generate_email, generate_sms, generate_tel, Email, Sms, Telephone};
+pub use pix::{generate_pix, PixAccount, PixPayload};
+pub use swiss_qr::{
+    generate_swiss_qr, qrr_check_digit, SwissAddress, SwissCurrency, SwissQrBill, SwissReference,
+};
+pub use wifi::{generate_wifi, WifiNetwork, WifiSecurity};
+
+use crate::types::{QuickCodesError, Result};
+
+/// Structured content recognized in decoded barcode data
+#[derive(Debug, Clone, PartialEq)]
+pub enum Payload {
+    Wifi(WifiNetwork),
+    VCard(Contact),
+    MeCard(Contact),
+    Geo(GeoLocation),
+    Event(CalendarEvent),
+    Sms(Sms),
+    Email(Email),
+    Tel(Telephone),
+    Pix(PixPayload),
+}
+
+impl Payload {
+    /// Recognize the payload format from its prefix, such as in
+    /// `ReadResult::data`, and parse it
+    pub fn parse(data: &str) -> Result<Self> {
+        let upper = data
+            .get(..data.len().min(16))
+            .unwrap_or(data)
+            .to_ascii_uppercase();
+        let starts = |prefix: &str| upper.starts_with(prefix);
+        if starts("WIFI:") {
+            WifiNetwork::parse(data).map(Payload::Wifi)
+        } else if starts("BEGIN:VCARD") {
+            Contact::from_vcard(data).map(Payload::VCard)
+        } else if starts("MECARD:") {
+            Contact::from_mecard(data).map(Payload::MeCard)
+        } else if starts("GEO:") {
+            GeoLocation::parse(data).map(Payload::Geo)
+        } else if starts("BEGIN:VEVENT") || starts("BEGIN:VCALENDAR") {
+            CalendarEvent::parse(data).map(Payload::Event)
+        } else if starts("SMSTO:") || starts("SMS:") {
+            Sms::parse(data).map(Payload::Sms)
+        } else if starts("MATMSG:") || starts("MAILTO:") {
+            Email::parse(data).map(Payload::Email)
+        } else if starts("TEL:") {
+            Telephone::parse(data).map(Payload::Tel)
+        } else if starts("000201") {
+            PixPayload::parse(data).map(Payload::Pix)
+        } else {
+            Err(QuickCodesError::InvalidData(
+                "Unrecognized payload format".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_detection() {
+        assert!(matches!(
+            Payload::parse("WIFI:T:WPA;S:NetworkName;P:password123;;").unwrap(),
+            Payload::Wifi(_)
+        ));
+        assert!(matches!(
+            Payload::parse(
+                "BEGIN:VCARD\nVERSION:3.0\nFN:João Silva\nTEL:+5511999999999\nEND:VCARD"
+            )
+            .unwrap(),
+            Payload::VCard(_)
+        ));
+        assert!(matches!(
+            Payload::parse("MECARD:N:Silva,João;;").unwrap(),
+            Payload::MeCard(_)
+        ));
+        assert!(matches!(
+            Payload::parse("geo:1,2").unwrap(),
+            Payload::Geo(_)
+        ));
+        assert!(matches!(
+            Payload::parse("BEGIN:VEVENT\nSUMMARY:x\nDTSTART:20250101\nEND:VEVENT").unwrap(),
+            Payload::Event(_)
+        ));
+        assert!(matches!(
+            Payload::parse("SMSTO:123:hi").unwrap(),
+            Payload::Sms(_)
+        ));
+        assert_eq!(
+            Payload::parse("mailto:test@example.com").unwrap(),
+            Payload::Email(Email::new("test@example.com"))
+        );
+        assert!(matches!(
+            Payload::parse("tel:+123").unwrap(),
+            Payload::Tel(_)
+        ));
+
+        let pix = PixPayload::new_static("+5551999999995", "Padaria", "SAO PAULO");
+        assert!(matches!(
+            Payload::parse(&pix.encode().unwrap()).unwrap(),
+            Payload::Pix(_)
+        ));
+
+        assert!(Payload::parse("https://example.com").is_err());
+        assert!(Payload::parse("").is_err());
+    }
+}
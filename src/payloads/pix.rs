@@ -0,0 +1,403 @@
+//! PIX "BR Code" payloads (Manual de Padrões para Iniciação do Pix)
+//!
+//! A BR Code is an EMV merchant-presented QR payload: a sequence of
+//! ID-length-value fields with the PIX account information in template 26
+//! (GUI `br.gov.bcb.pix`) and a CRC16-CCITT in field 63. Static payloads
+//! carry the receiver's PIX key; dynamic ones carry the URL of a payload
+//! served by the receiver's payment provider.
+
+use super::emv::{
+    self, EmvPayload, EmvTemplate, ADDITIONAL_DATA, AMOUNT, COUNTRY, CURRENCY, DYNAMIC,
+    INITIATION_METHOD, MERCHANT_CITY, MERCHANT_NAME, POSTAL_CODE, REFERENCE_LABEL,
+};
+use crate::generators;
+use crate::types::{Barcode, QuickCodesError, Result};
+
+/// Globally unique identifier of the PIX arrangement
+const GUI: &str = "br.gov.bcb.pix";
+
+/// Merchant account information template of PIX
+const ACCOUNT_INFORMATION: u8 = 26;
+
+// Data objects of template 26
+const ACCOUNT_KEY: u8 = 1;
+const ACCOUNT_DESCRIPTION: u8 = 2;
+const ACCOUNT_URL: u8 = 25;
+
+/// Transaction ID of payloads without one
+const NO_TRANSACTION_ID: &str = "***";
+
+/// Brazilian real (ISO 4217)
+const BRL: &str = "986";
+
+const MAX_KEY_LENGTH: usize = 77;
+const MAX_NAME_LENGTH: usize = 25;
+const MAX_CITY_LENGTH: usize = 15;
+const MAX_TRANSACTION_ID_LENGTH: usize = 25;
+
+/// How the payer's app finds the receiving account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PixAccount {
+    /// Static payload: PIX key (CPF, CNPJ, phone, e-mail or random key) and
+    /// an optional message to the payer
+    Key {
+        key: String,
+        description: Option<String>,
+    },
+    /// Dynamic payload: location of the payload served by the payment
+    /// provider, without `https://`
+    Location(String),
+}
+
+/// A PIX BR Code payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PixPayload {
+    pub account: PixAccount,
+    /// Receiver name, up to 25 characters
+    pub merchant_name: String,
+    /// Receiver city, up to 15 characters
+    pub merchant_city: String,
+    /// Amount in centavos; when absent the payer types it in
+    pub amount: Option<u64>,
+    /// Transaction ID (txid) of static payloads, up to 25 alphanumeric
+    /// characters
+    pub transaction_id: Option<String>,
+    /// CEP of the receiver
+    pub postal_code: Option<String>,
+    /// Mark the payload as usable for a single payment (field 01 = 12)
+    pub single_use: bool,
+}
+
+impl PixPayload {
+    /// Static payload paying to a PIX key
+    pub fn new_static(key: &str, merchant_name: &str, merchant_city: &str) -> Self {
+        Self::new(
+            PixAccount::Key {
+                key: key.to_string(),
+                description: None,
+            },
+            merchant_name,
+            merchant_city,
+        )
+    }
+
+    /// Dynamic payload pointing to a payload location
+    pub fn new_dynamic(location: &str, merchant_name: &str, merchant_city: &str) -> Self {
+        Self::new(
+            PixAccount::Location(location.to_string()),
+            merchant_name,
+            merchant_city,
+        )
+    }
+
+    fn new(account: PixAccount, merchant_name: &str, merchant_city: &str) -> Self {
+        Self {
+            account,
+            merchant_name: merchant_name.to_string(),
+            merchant_city: merchant_city.to_string(),
+            amount: None,
+            transaction_id: None,
+            postal_code: None,
+            single_use: false,
+        }
+    }
+
+    /// BR Code string, ending with its CRC
+    pub fn encode(&self) -> Result<String> {
+        self.to_emv()?.encode()
+    }
+
+    /// The BR Code as a generic EMV payload
+    pub fn to_emv(&self) -> Result<EmvPayload> {
+        let mut account = EmvTemplate::with_gui(GUI);
+        match &self.account {
+            PixAccount::Key { key, description } => {
+                validate_key(key)?;
+                account.set(ACCOUNT_KEY, key.as_str());
+                if let Some(description) = description {
+                    account.set(ACCOUNT_DESCRIPTION, text(description, "description", 99)?);
+                }
+            }
+            PixAccount::Location(location) => {
+                if location.contains("://") {
+                    return Err(QuickCodesError::InvalidData(
+                        "PIX payload location must not include the URL scheme".to_string(),
+                    ));
+                }
+                account.set(
+                    ACCOUNT_URL,
+                    text(location, "payload location", MAX_KEY_LENGTH)?,
+                );
+            }
+        }
+
+        let mut payload = EmvPayload::new(
+            "0000",
+            BRL,
+            "BR",
+            text(&self.merchant_name, "merchant name", MAX_NAME_LENGTH)?,
+            text(&self.merchant_city, "merchant city", MAX_CITY_LENGTH)?,
+        );
+        let objects = &mut payload.objects;
+        if self.single_use {
+            objects.set(INITIATION_METHOD, DYNAMIC);
+        }
+        objects.set(ACCOUNT_INFORMATION, account);
+        if let Some(amount) = self.amount {
+            objects.set(AMOUNT, emv::format_amount(amount));
+        }
+        if let Some(postal_code) = &self.postal_code {
+            if postal_code.len() != 8 || !postal_code.chars().all(|c| c.is_ascii_digit()) {
+                return Err(QuickCodesError::InvalidData(format!(
+                    "PIX postal code must be 8 digits, got '{}'",
+                    postal_code
+                )));
+            }
+            objects.set(POSTAL_CODE, postal_code.as_str());
+        }
+
+        let transaction_id = match (&self.transaction_id, &self.account) {
+            (Some(_), PixAccount::Location(_)) => {
+                return Err(QuickCodesError::InvalidData(
+                    "Dynamic PIX payloads carry the transaction ID in the served payload"
+                        .to_string(),
+                ))
+            }
+            (Some(id), PixAccount::Key { .. }) => {
+                if id.is_empty()
+                    || id.len() > MAX_TRANSACTION_ID_LENGTH
+                    || !id.chars().all(|c| c.is_ascii_alphanumeric())
+                {
+                    return Err(QuickCodesError::InvalidData(format!(
+                        "PIX transaction ID must be 1-{} alphanumeric characters, got '{}'",
+                        MAX_TRANSACTION_ID_LENGTH, id
+                    )));
+                }
+                id.as_str()
+            }
+            (None, _) => NO_TRANSACTION_ID,
+        };
+        objects.set(
+            ADDITIONAL_DATA,
+            EmvTemplate::new().with(REFERENCE_LABEL, transaction_id),
+        );
+        Ok(payload)
+    }
+
+    /// Parse and validate a BR Code string, including its CRC
+    pub fn parse(payload: &str) -> Result<Self> {
+        let payload = payload.trim();
+        if !payload.is_ascii() {
+            return Err(invalid("the payload must be ASCII text"));
+        }
+        let objects = EmvPayload::parse(payload)?.objects;
+        let get = |id: u8| objects.text(id);
+
+        if get(CURRENCY) != Some(BRL) {
+            return Err(invalid("the currency must be 986 (BRL)"));
+        }
+        if get(COUNTRY) != Some("BR") {
+            return Err(invalid("the country code must be BR"));
+        }
+
+        let account = objects
+            .template(ACCOUNT_INFORMATION)
+            .filter(|account| {
+                account
+                    .text(emv::TEMPLATE_GUI)
+                    .is_some_and(|gui| gui.eq_ignore_ascii_case(GUI))
+            })
+            .ok_or_else(|| invalid("field 26 must start with the br.gov.bcb.pix GUI"))?;
+        let account_field = |id: u8| account.text(id).map(str::to_string);
+        let account = match (account_field(ACCOUNT_KEY), account_field(ACCOUNT_URL)) {
+            (Some(key), None) => PixAccount::Key {
+                key,
+                description: account_field(ACCOUNT_DESCRIPTION),
+            },
+            (None, Some(location)) => PixAccount::Location(location),
+            _ => {
+                return Err(invalid(
+                    "field 26 must hold either a PIX key or a payload location",
+                ))
+            }
+        };
+
+        let amount = get(AMOUNT).map(emv::parse_amount).transpose()?;
+        let transaction_id = objects
+            .template(ADDITIONAL_DATA)
+            .and_then(|data| data.text(REFERENCE_LABEL))
+            .filter(|id| *id != NO_TRANSACTION_ID)
+            .map(str::to_string);
+
+        Ok(Self {
+            account,
+            merchant_name: get(MERCHANT_NAME).unwrap_or_default().to_string(),
+            merchant_city: get(MERCHANT_CITY).unwrap_or_default().to_string(),
+            amount,
+            transaction_id,
+            postal_code: get(POSTAL_CODE).map(str::to_string),
+            single_use: get(INITIATION_METHOD) == Some(DYNAMIC),
+        })
+    }
+}
+
+/// Generate the QR Code of a PIX payload
+pub fn generate_pix(payload: &PixPayload) -> Result<Barcode> {
+    generators::generate_qr(&payload.encode()?)
+}
+
+/// Printable ASCII text of limited length
+fn text<'a>(value: &'a str, name: &str, max_length: usize) -> Result<&'a str> {
+    if value.is_empty()
+        || value.len() > max_length
+        || !value.chars().all(|c| (' '..='~').contains(&c))
+    {
+        return Err(QuickCodesError::InvalidData(format!(
+            "PIX {} must be 1-{} ASCII characters without accents, got '{}'",
+            name, max_length, value
+        )));
+    }
+    Ok(value)
+}
+
+/// PIX keys: CPF, CNPJ, phone (+55...), e-mail or random key (UUID)
+fn validate_key(key: &str) -> Result<()> {
+    let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let uuid = key.len() == 36
+        && key.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+    let valid = (digits(key) && matches!(key.len(), 11 | 14))
+        || key
+            .strip_prefix('+')
+            .is_some_and(|phone| digits(phone) && (12..=15).contains(&phone.len()))
+        || (key.contains('@') && key.len() <= MAX_KEY_LENGTH && !key.contains(' '))
+        || uuid;
+    if !valid {
+        return Err(QuickCodesError::InvalidData(format!(
+            "'{}' is not a PIX key: use a CPF, CNPJ, +55 phone number, e-mail or random key",
+            key
+        )));
+    }
+    Ok(())
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid PIX payload: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BarcodeType;
+
+    #[test]
+    fn test_pix_crc16() {
+        assert_eq!(emv::crc16(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn test_pix_static_payload() {
+        // Example from the BR Code manual
+        let mut payload = PixPayload::new_static(
+            "123e4567-e12b-12d1-a456-426655440000",
+            "Fulano de Tal",
+            "BRASILIA",
+        );
+        payload.transaction_id = None;
+        assert_eq!(
+            payload.encode().unwrap(),
+            "00020126580014br.gov.bcb.pix0136123e4567-e12b-12d1-a456-4266554400005204000053039865802BR5913Fulano de Tal6008BRASILIA62070503***63041D3D"
+        );
+
+        payload.amount = Some(1050);
+        payload.transaction_id = Some("PEDIDO123".to_string());
+        let encoded = payload.encode().unwrap();
+        assert!(encoded.contains("540510.50"));
+        assert!(encoded.contains("62130509PEDIDO123"));
+        assert_eq!(PixPayload::parse(&encoded).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_pix_dynamic_payload() {
+        let mut payload = PixPayload::new_dynamic(
+            "pix.example.com/qr/v2/9d36b84f",
+            "Loja Exemplo",
+            "SAO PAULO",
+        );
+        payload.single_use = true;
+        let encoded = payload.encode().unwrap();
+        assert!(encoded.starts_with("000201010212"));
+        assert!(encoded.contains("2530pix.example.com/qr/v2/9d36b84f"));
+        assert_eq!(PixPayload::parse(&encoded).unwrap(), payload);
+
+        payload.transaction_id = Some("ABC".to_string());
+        assert!(payload.encode().is_err());
+        let https = PixPayload::new_dynamic("https://pix.example.com/qr", "Loja", "SAO PAULO");
+        assert!(https.encode().is_err());
+    }
+
+    #[test]
+    fn test_pix_validation() {
+        for key in [
+            "12345678901",
+            "12345678000199",
+            "+5561999999999",
+            "fulano@example.com",
+            "123e4567-e12b-12d1-a456-426655440000",
+        ] {
+            assert!(validate_key(key).is_ok(), "{}", key);
+        }
+        for key in [
+            "1234",
+            "5561999999999",
+            "not a key",
+            "123e4567e12b12d1a456426655440000",
+        ] {
+            assert!(validate_key(key).is_err(), "{}", key);
+        }
+
+        assert!(
+            PixPayload::new_static("12345678901", "Padaria São João", "SAO PAULO")
+                .encode()
+                .is_err()
+        );
+        assert!(
+            PixPayload::new_static("12345678901", "Padaria", "SAO JOSE DOS CAMPOS")
+                .encode()
+                .is_err()
+        );
+        let mut payload = PixPayload::new_static("12345678901", "Padaria", "SAO PAULO");
+        payload.transaction_id = Some("PEDIDO-1".to_string());
+        assert!(payload.encode().is_err());
+    }
+
+    #[test]
+    fn test_pix_parse_errors() {
+        let encoded = PixPayload::new_static("12345678901", "Padaria", "SAO PAULO")
+            .encode()
+            .unwrap();
+        assert!(PixPayload::parse(&encoded).is_ok());
+        assert!(PixPayload::parse(&encoded.to_lowercase()).is_err());
+
+        // Wrong CRC, as in hand-built strings
+        let (body, _) = encoded.split_at(encoded.len() - 4);
+        assert!(PixPayload::parse(&format!("{}ABCD", body)).is_err());
+        assert!(PixPayload::parse("").is_err());
+        assert!(PixPayload::parse("0002016304FFFF").is_err());
+
+        assert_eq!(emv::parse_amount("10").unwrap(), 1000);
+        assert_eq!(emv::parse_amount("10.5").unwrap(), 1050);
+        assert!(emv::parse_amount("10.505").is_err());
+        assert!(emv::parse_amount("-1").is_err());
+    }
+
+    #[test]
+    fn test_generate_pix() {
+        let payload = PixPayload::new_static("fulano@example.com", "Fulano", "RECIFE");
+        let barcode = generate_pix(&payload).unwrap();
+        assert_eq!(barcode.barcode_type, BarcodeType::QRCode);
+        assert_eq!(barcode.data, payload.encode().unwrap());
+    }
+}
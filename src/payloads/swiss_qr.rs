@@ -0,0 +1,475 @@
+//! Swiss QR-bill payloads (Swiss Implementation Guidelines for the QR-bill,
+//! version 2.3)
+//!
+//! The Swiss QR code carries the payment part of a QR-bill as lines of
+//! text: header `SPC` 0200, the creditor's IBAN and address, amount and
+//! currency, the debtor's address, a reference and the trailer `EPD`. A
+//! QR-IBAN requires a QR reference (QRR, modulo-10 recursive check digit);
+//! other IBANs take a creditor reference (SCOR, ISO 11649) or none. The QR
+//! code is printed 46 x 46 mm at error correction level M with a 7 x 7 mm
+//! Swiss cross in its centre.
+
+use super::epc::{is_valid_iban, mod97, normalize_iban};
+use crate::generators;
+use crate::types::{Barcode, BarcodeConfig, QRErrorCorrection, QuickCodesError, Result};
+
+const QR_TYPE: &str = "SPC";
+const VERSION: &str = "0200";
+const CODING_LATIN: &str = "1";
+const TRAILER: &str = "EPD";
+
+/// Structured address type
+const ADDRESS_STRUCTURED: &str = "S";
+
+/// QR-IBANs have an institution identification (positions 5-9) in this range
+const QR_IID: std::ops::RangeInclusive<u32> = 30000..=31999;
+
+/// Modulo-10 recursive check digit table
+const MOD10_TABLE: [u32; 10] = [0, 9, 4, 6, 8, 2, 7, 1, 3, 5];
+
+const MAX_PAYLOAD_LENGTH: usize = 997;
+const MAX_NAME_LENGTH: usize = 70;
+const MAX_STREET_LENGTH: usize = 70;
+const MAX_BUILDING_NUMBER_LENGTH: usize = 16;
+const MAX_POSTAL_CODE_LENGTH: usize = 16;
+const MAX_TOWN_LENGTH: usize = 35;
+const MAX_MESSAGE_LENGTH: usize = 140;
+
+/// Largest amount, 999999999.99, in cents
+const MAX_AMOUNT: u64 = 99_999_999_999;
+
+/// Currency of a QR-bill
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwissCurrency {
+    CHF,
+    EUR,
+}
+
+/// Structured address (type S) of the creditor or debtor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwissAddress {
+    /// Name or company, up to 70 characters
+    pub name: String,
+    /// Street, up to 70 characters
+    pub street: Option<String>,
+    /// Building number, up to 16 characters
+    pub building_number: Option<String>,
+    /// Postal code without country prefix, up to 16 characters
+    pub postal_code: String,
+    /// Town, up to 35 characters
+    pub town: String,
+    /// Two-letter ISO 3166 country code
+    pub country: String,
+}
+
+/// Payment reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwissReference {
+    /// QR reference: 27 digits ending in a modulo-10 recursive check digit,
+    /// only with a QR-IBAN
+    Qrr(String),
+    /// Creditor reference (ISO 11649), e.g. `RF18539007547034`
+    Scor(String),
+    /// No reference
+    None,
+}
+
+/// Payment part of a Swiss QR-bill
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwissQrBill {
+    /// Creditor IBAN or QR-IBAN (CH or LI); spaces are removed
+    pub iban: String,
+    pub creditor: SwissAddress,
+    /// Amount in cents (Rappen); left blank for the payer to fill in when
+    /// absent
+    pub amount: Option<u64>,
+    pub currency: SwissCurrency,
+    pub debtor: Option<SwissAddress>,
+    pub reference: SwissReference,
+    /// Unstructured message to the payer
+    pub message: Option<String>,
+    /// Structured bill information (for example Swico `//S1/...`); shares
+    /// the 140 characters with the message
+    pub bill_information: Option<String>,
+}
+
+impl SwissAddress {
+    pub fn new(name: &str, postal_code: &str, town: &str, country: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            street: None,
+            building_number: None,
+            postal_code: postal_code.to_string(),
+            town: town.to_string(),
+            country: country.to_string(),
+        }
+    }
+
+    /// The seven address lines: type, name, street, building number, postal
+    /// code, town and country
+    fn lines(&self, role: &str) -> Result<Vec<String>> {
+        let street = self.street.as_deref().unwrap_or("");
+        let building_number = self.building_number.as_deref().unwrap_or("");
+        check_text(&format!("{} name", role), &self.name, 1, MAX_NAME_LENGTH)?;
+        check_text(&format!("{} street", role), street, 0, MAX_STREET_LENGTH)?;
+        check_text(
+            &format!("{} building number", role),
+            building_number,
+            0,
+            MAX_BUILDING_NUMBER_LENGTH,
+        )?;
+        check_text(
+            &format!("{} postal code", role),
+            &self.postal_code,
+            1,
+            MAX_POSTAL_CODE_LENGTH,
+        )?;
+        check_text(&format!("{} town", role), &self.town, 1, MAX_TOWN_LENGTH)?;
+        if self.country.len() != 2 || !self.country.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(invalid(&format!(
+                "{} country must be a two-letter ISO code, got '{}'",
+                role, self.country
+            )));
+        }
+        Ok(vec![
+            ADDRESS_STRUCTURED.to_string(),
+            self.name.clone(),
+            street.to_string(),
+            building_number.to_string(),
+            self.postal_code.clone(),
+            self.town.clone(),
+            self.country.clone(),
+        ])
+    }
+}
+
+impl SwissQrBill {
+    /// Bill in Swiss francs without amount, debtor or reference
+    pub fn new(iban: &str, creditor: SwissAddress) -> Self {
+        Self {
+            iban: iban.to_string(),
+            creditor,
+            amount: None,
+            currency: SwissCurrency::CHF,
+            debtor: None,
+            reference: SwissReference::None,
+            message: None,
+            bill_information: None,
+        }
+    }
+
+    /// The payload text, with lines separated by LF
+    pub fn encode(&self) -> Result<String> {
+        let iban = normalize_iban(&self.iban);
+        if !(iban.starts_with("CH") || iban.starts_with("LI"))
+            || iban.len() != 21
+            || !is_valid_iban(&iban)
+        {
+            return Err(invalid(&format!("'{}' is not a valid CH or LI IBAN", iban)));
+        }
+        let qr_iban = is_qr_iban(&iban);
+
+        let (reference_type, reference) = match &self.reference {
+            SwissReference::Qrr(reference) if qr_iban => ("QRR", validate_qrr(reference)?),
+            SwissReference::Scor(reference) if !qr_iban => ("SCOR", validate_scor(reference)?),
+            SwissReference::None if !qr_iban => ("NON", String::new()),
+            _ if qr_iban => return Err(invalid("a QR-IBAN requires a QR reference")),
+            _ => return Err(invalid("a QR reference requires a QR-IBAN")),
+        };
+
+        let amount = match self.amount {
+            Some(amount) if (1..=MAX_AMOUNT).contains(&amount) => {
+                format!("{}.{:02}", amount / 100, amount % 100)
+            }
+            Some(_) => return Err(invalid("amount must be 0.01 to 999999999.99")),
+            None => String::new(),
+        };
+        let currency = match self.currency {
+            SwissCurrency::CHF => "CHF",
+            SwissCurrency::EUR => "EUR",
+        };
+
+        let message = self.message.as_deref().unwrap_or("");
+        let bill_information = self.bill_information.as_deref().unwrap_or("");
+        check_text("message", message, 0, MAX_MESSAGE_LENGTH)?;
+        check_text("bill information", bill_information, 0, MAX_MESSAGE_LENGTH)?;
+        if message.chars().count() + bill_information.chars().count() > MAX_MESSAGE_LENGTH {
+            return Err(invalid(&format!(
+                "message and bill information together exceed {} characters",
+                MAX_MESSAGE_LENGTH
+            )));
+        }
+
+        let mut lines = vec![
+            QR_TYPE.to_string(),
+            VERSION.to_string(),
+            CODING_LATIN.to_string(),
+            iban,
+        ];
+        lines.extend(self.creditor.lines("creditor")?);
+        // Ultimate creditor, reserved for future use
+        lines.extend(std::iter::repeat(String::new()).take(7));
+        lines.push(amount);
+        lines.push(currency.to_string());
+        match &self.debtor {
+            Some(debtor) => lines.extend(debtor.lines("debtor")?),
+            None => lines.extend(std::iter::repeat(String::new()).take(7)),
+        }
+        lines.push(reference_type.to_string());
+        lines.push(reference);
+        lines.push(message.to_string());
+        lines.push(TRAILER.to_string());
+        if !bill_information.is_empty() {
+            lines.push(bill_information.to_string());
+        }
+
+        let payload = lines.join("\n");
+        if payload.chars().count() > MAX_PAYLOAD_LENGTH {
+            return Err(invalid(&format!(
+                "payload exceeds {} characters",
+                MAX_PAYLOAD_LENGTH
+            )));
+        }
+        Ok(payload)
+    }
+}
+
+/// Generate the Swiss QR code of a QR-bill, at error correction level M
+/// and with the Swiss cross in its centre
+pub fn generate_swiss_qr(bill: &SwissQrBill) -> Result<Barcode> {
+    let mut config = BarcodeConfig::default();
+    config.qr_config.error_correction = QRErrorCorrection::Medium;
+    config.swiss_cross = true;
+    config.include_text = false;
+    generators::qr::generate_qr_with_config(&bill.encode()?, &config)
+}
+
+/// Modulo-10 recursive check digit of a QR reference
+pub fn qrr_check_digit(digits: &str) -> Result<u8> {
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid("a QR reference has only digits"));
+    }
+    let carry = digits.bytes().fold(0, |carry, b| {
+        MOD10_TABLE[((carry + u32::from(b - b'0')) % 10) as usize]
+    });
+    Ok(((10 - carry) % 10) as u8)
+}
+
+fn is_qr_iban(iban: &str) -> bool {
+    iban[4..9]
+        .parse()
+        .is_ok_and(|iid: u32| QR_IID.contains(&iid))
+}
+
+/// QR reference without spaces: 27 digits with a valid check digit
+fn validate_qrr(reference: &str) -> Result<String> {
+    let reference: String = reference.chars().filter(|c| !c.is_whitespace()).collect();
+    if reference.len() != 27 || !reference.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid("a QR reference has 27 digits"));
+    }
+    let check = qrr_check_digit(&reference[..26])?;
+    if reference[26..] != check.to_string() {
+        return Err(invalid(&format!(
+            "wrong QR reference check digit {}, expected {}",
+            &reference[26..],
+            check
+        )));
+    }
+    Ok(reference)
+}
+
+/// Creditor reference without spaces: `RF`, two check digits and up to 21
+/// letters or digits, valid under ISO 7064 mod 97-10
+fn validate_scor(reference: &str) -> Result<String> {
+    let reference = normalize_iban(reference);
+    let bytes = reference.as_bytes();
+    let valid = (5..=25).contains(&reference.len())
+        && reference.starts_with("RF")
+        && bytes[2..4].iter().all(|b| b.is_ascii_digit())
+        && bytes[4..].iter().all(|b| b.is_ascii_alphanumeric())
+        && mod97(&reference) == 1;
+    if !valid {
+        return Err(invalid(&format!(
+            "'{}' is not a valid creditor reference",
+            reference
+        )));
+    }
+    Ok(reference)
+}
+
+/// Length limits and the character set of coding type 1
+fn check_text(name: &str, value: &str, min: usize, max: usize) -> Result<()> {
+    let length = value.chars().count();
+    if length < min || length > max {
+        return Err(invalid(&format!(
+            "{} must have {}-{} characters",
+            name, min, max
+        )));
+    }
+    if let Some(c) = value.chars().find(|&c| !is_latin(c)) {
+        return Err(invalid(&format!(
+            "{} contains {:?}, outside the Swiss Payment Standards Latin character set",
+            name, c
+        )));
+    }
+    Ok(())
+}
+
+/// Swiss Payment Standards Latin character set: Basic Latin, Latin-1
+/// Supplement and Latin Extended-A without control characters, plus Ș ș
+/// Ț ț and €. Line breaks would shift the fields.
+fn is_latin(c: char) -> bool {
+    matches!(
+        c,
+        '\u{20}'..='\u{7e}' | '\u{a0}'..='\u{17f}' | '\u{218}'..='\u{21b}' | '\u{20ac}'
+    )
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid Swiss QR-bill: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn creditor() -> SwissAddress {
+        let mut creditor = SwissAddress::new("Robert Schneider AG", "2501", "Biel", "CH");
+        creditor.street = Some("Rue du Lac".to_string());
+        creditor.building_number = Some("1268".to_string());
+        creditor
+    }
+
+    fn bill() -> SwissQrBill {
+        let mut bill = SwissQrBill::new("CH44 3199 9123 0008 8901 2", creditor());
+        bill.amount = Some(195000);
+        let mut debtor =
+            SwissAddress::new("Pia-Maria Rutschmann-Schnyder", "9400", "Rorschach", "CH");
+        debtor.street = Some("Grosse Marktgasse".to_string());
+        debtor.building_number = Some("28".to_string());
+        bill.debtor = Some(debtor);
+        bill.reference = SwissReference::Qrr("21 00000 00003 13947 14300 09017".to_string());
+        bill.message = Some("Auftrag vom 15.06.2020".to_string());
+        bill.bill_information = Some("//S1/10/10201409/11/200701/20/140.000-53/30/102673831/31/200615/32/7.7/33/7.7:10/40/0:30".to_string());
+        bill
+    }
+
+    #[test]
+    fn test_swiss_qr_payload() {
+        let expected = [
+            "SPC",
+            "0200",
+            "1",
+            "CH4431999123000889012",
+            "S",
+            "Robert Schneider AG",
+            "Rue du Lac",
+            "1268",
+            "2501",
+            "Biel",
+            "CH",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "1950.00",
+            "CHF",
+            "S",
+            "Pia-Maria Rutschmann-Schnyder",
+            "Grosse Marktgasse",
+            "28",
+            "9400",
+            "Rorschach",
+            "CH",
+            "QRR",
+            "210000000003139471430009017",
+            "Auftrag vom 15.06.2020",
+            "EPD",
+            "//S1/10/10201409/11/200701/20/140.000-53/30/102673831/31/200615/32/7.7/33/7.7:10/40/0:30",
+        ];
+        assert_eq!(bill().encode().unwrap(), expected.join("\n"));
+    }
+
+    #[test]
+    fn test_swiss_qr_references() {
+        assert_eq!(qrr_check_digit("21000000000313947143000901").unwrap(), 7);
+
+        // Regular IBAN with a creditor reference or no reference
+        let mut bill = SwissQrBill::new("CH5800791123000889012", creditor());
+        bill.reference = SwissReference::Scor("RF18 5390 0754 7034".to_string());
+        let payload = bill.encode().unwrap();
+        assert!(payload.contains("\nSCOR\nRF18539007547034\n\nEPD"));
+        assert!(payload.contains("\n\nCHF\n"));
+
+        bill.reference = SwissReference::None;
+        assert!(bill.encode().unwrap().ends_with("\nNON\n\n\nEPD"));
+
+        // References must match the kind of IBAN
+        bill.reference = SwissReference::Qrr("210000000003139471430009017".to_string());
+        assert!(bill.encode().is_err());
+        let mut bill = self::bill();
+        bill.reference = SwissReference::None;
+        assert!(bill.encode().is_err());
+
+        assert!(validate_qrr("210000000003139471430009018").is_err());
+        // 27 bytes, not 27 digits
+        assert!(validate_qrr("2100000000031394714300090é").is_err());
+        assert!(validate_qrr("21000000000313947143000901A").is_err());
+        assert!(validate_scor("RF19539007547034").is_err());
+        assert!(validate_scor("XX18539007547034").is_err());
+    }
+
+    #[test]
+    fn test_swiss_qr_validation() {
+        let mut bill = self::bill();
+        bill.iban = "DE89370400440532013000".to_string();
+        assert!(bill.encode().is_err());
+        let mut bill = self::bill();
+        bill.iban = "CH4431999123000889013".to_string();
+        assert!(bill.encode().is_err());
+
+        let mut bill = self::bill();
+        bill.amount = Some(0);
+        assert!(bill.encode().is_err());
+
+        let mut bill = self::bill();
+        bill.creditor.country = "Switzerland".to_string();
+        assert!(bill.encode().is_err());
+        let mut bill = self::bill();
+        bill.creditor.name = "Robert\nSchneider".to_string();
+        assert!(bill.encode().is_err());
+
+        // Only the Swiss Payment Standards Latin character set
+        let mut bill = self::bill();
+        bill.creditor.name = "Zoë Łukasiewicz Ștefan €".to_string();
+        assert!(bill.encode().is_ok());
+        bill.creditor.name = "北京".to_string();
+        assert!(bill.encode().is_err());
+        bill.creditor.name = "Robert Schneider AG ✓".to_string();
+        assert!(bill.encode().is_err());
+        let mut bill = self::bill();
+        bill.message = Some("Ordem nº 1 – março".to_string());
+        assert!(bill.encode().is_err());
+        let mut bill = self::bill();
+        bill.creditor.town = "x".repeat(36);
+        assert!(bill.encode().is_err());
+
+        let mut bill = self::bill();
+        bill.message = Some("x".repeat(100));
+        assert!(bill.encode().is_err());
+    }
+
+    #[test]
+    fn test_generate_swiss_qr() {
+        let barcode = generate_swiss_qr(&bill()).unwrap();
+        assert!(barcode.config.swiss_cross);
+        assert_eq!(
+            barcode.config.qr_config.error_correction,
+            QRErrorCorrection::Medium
+        );
+        assert!(barcode.modules.as_matrix().is_some());
+    }
+}
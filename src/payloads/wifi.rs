@@ -0,0 +1,192 @@
+//! Wi-Fi network configuration payloads (`WIFI:`)
+//!
+//! The de facto format read by phone cameras: `WIFI:T:WPA;S:ssid;P:pass;;`
+//! with the fields separated by semicolons and `\`, `;`, `,`, `:` and `"`
+//! escaped with a backslash.
+
+use super::escape::{escape, split_unescaped, unescape};
+use crate::generators;
+use crate::types::{Barcode, QuickCodesError, Result};
+
+const PREFIX: &str = "WIFI:";
+
+/// Characters escaped in field values
+const SPECIAL: &str = ";,:\"";
+
+/// Network authentication
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WifiSecurity {
+    /// WPA, WPA2 or WPA3 personal
+    Wpa,
+    Wep,
+    /// Open network
+    None,
+}
+
+/// Wi-Fi network to join
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WifiNetwork {
+    pub ssid: String,
+    pub security: WifiSecurity,
+    /// Password, required unless the network is open
+    pub password: Option<String>,
+    /// Whether the network does not broadcast its SSID
+    pub hidden: bool,
+}
+
+impl WifiNetwork {
+    /// WPA network
+    pub fn new(ssid: &str, password: &str) -> Self {
+        Self {
+            ssid: ssid.to_string(),
+            security: WifiSecurity::Wpa,
+            password: Some(password.to_string()),
+            hidden: false,
+        }
+    }
+
+    /// Open network without password
+    pub fn open(ssid: &str) -> Self {
+        Self {
+            ssid: ssid.to_string(),
+            security: WifiSecurity::None,
+            password: None,
+            hidden: false,
+        }
+    }
+
+    pub fn encode(&self) -> Result<String> {
+        if self.ssid.is_empty() {
+            return Err(invalid("the SSID cannot be empty"));
+        }
+        let security = match self.security {
+            WifiSecurity::Wpa => "WPA",
+            WifiSecurity::Wep => "WEP",
+            WifiSecurity::None => "nopass",
+        };
+        let mut payload = format!(
+            "{}T:{};S:{};",
+            PREFIX,
+            security,
+            escape(&self.ssid, SPECIAL)
+        );
+        match (&self.password, self.security) {
+            (Some(password), WifiSecurity::Wpa | WifiSecurity::Wep) if !password.is_empty() => {
+                payload.push_str(&format!("P:{};", escape(password, SPECIAL)));
+            }
+            (None, WifiSecurity::None) => {}
+            (_, WifiSecurity::None) => return Err(invalid("an open network has no password")),
+            _ => return Err(invalid("a WPA or WEP network needs a password")),
+        }
+        if self.hidden {
+            payload.push_str("H:true;");
+        }
+        payload.push(';');
+        Ok(payload)
+    }
+
+    /// Parse a `WIFI:` payload, such as decoded `ReadResult::data`
+    pub fn parse(data: &str) -> Result<Self> {
+        let body = data
+            .strip_prefix(PREFIX)
+            .ok_or_else(|| invalid("the payload must start with WIFI:"))?;
+
+        let mut network = Self::open("");
+        let mut security = None;
+        for field in split_unescaped(body, ';') {
+            let Some((key, value)) = field.split_once(':') else {
+                continue;
+            };
+            let value = unescape(value, false);
+            match key {
+                "S" => network.ssid = value,
+                "P" if !value.is_empty() => network.password = Some(value),
+                "H" => network.hidden = value.eq_ignore_ascii_case("true"),
+                "T" => {
+                    security = Some(match value.to_ascii_uppercase().as_str() {
+                        "WPA" | "WPA2" | "WPA3" | "SAE" => WifiSecurity::Wpa,
+                        "WEP" => WifiSecurity::Wep,
+                        "NOPASS" | "" => WifiSecurity::None,
+                        other => {
+                            return Err(invalid(&format!("unknown security type '{}'", other)))
+                        }
+                    })
+                }
+                _ => {}
+            }
+        }
+        // Readers treat a missing type as WPA when there is a password
+        network.security = security.unwrap_or(if network.password.is_some() {
+            WifiSecurity::Wpa
+        } else {
+            WifiSecurity::None
+        });
+        if network.ssid.is_empty() {
+            return Err(invalid("the SSID is missing"));
+        }
+        Ok(network)
+    }
+}
+
+/// Generate a QR code that joins a Wi-Fi network
+pub fn generate_wifi(network: &WifiNetwork) -> Result<Barcode> {
+    generators::generate_qr(&network.encode()?)
+}
+
+fn invalid(reason: &str) -> QuickCodesError {
+    QuickCodesError::InvalidData(format!("Invalid Wi-Fi payload: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wifi_encode() {
+        let network = WifiNetwork::new("MyNetwork", "password123");
+        assert_eq!(
+            network.encode().unwrap(),
+            "WIFI:T:WPA;S:MyNetwork;P:password123;;"
+        );
+
+        let mut network = WifiNetwork::new(r#"Café "Central"; 2,4GHz"#, r"p@ss:w\rd;");
+        network.hidden = true;
+        assert_eq!(
+            network.encode().unwrap(),
+            r#"WIFI:T:WPA;S:Café \"Central\"\; 2\,4GHz;P:p@ss\:w\\rd\;;H:true;;"#
+        );
+        assert_eq!(
+            WifiNetwork::parse(&network.encode().unwrap()).unwrap(),
+            network
+        );
+
+        assert_eq!(
+            WifiNetwork::open("Guest").encode().unwrap(),
+            "WIFI:T:nopass;S:Guest;;"
+        );
+    }
+
+    #[test]
+    fn test_wifi_parse() {
+        let network = WifiNetwork::parse("WIFI:S:Home;T:WEP;P:abcde;;").unwrap();
+        assert_eq!(network.security, WifiSecurity::Wep);
+        assert_eq!(network.ssid, "Home");
+        assert_eq!(network.password.as_deref(), Some("abcde"));
+
+        let network = WifiNetwork::parse("WIFI:S:Home;P:secret;;").unwrap();
+        assert_eq!(network.security, WifiSecurity::Wpa);
+
+        assert!(WifiNetwork::parse("WIFI:T:WPA;P:secret;;").is_err());
+        assert!(WifiNetwork::parse("WIFI:T:XYZ;S:Home;;").is_err());
+        assert!(WifiNetwork::parse("MECARD:N:Home;;").is_err());
+    }
+
+    #[test]
+    fn test_wifi_validation() {
+        assert!(WifiNetwork::new("", "secret").encode().is_err());
+        assert!(WifiNetwork::new("Home", "").encode().is_err());
+        let mut network = WifiNetwork::open("Guest");
+        network.password = Some("secret".to_string());
+        assert!(network.encode().is_err());
+    }
+}
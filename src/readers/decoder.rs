@@ -36,6 +36,35 @@ fn decode_region(image: &GrayImage, detection: &DetectionResult) -> Result<Optio
         BarcodeType::DataMatrix => decode_datamatrix(&region_image),
         BarcodeType::PDF417 => decode_pdf417(&region_image),
         BarcodeType::Aztec => decode_aztec(&region_image),
+
+        // Formatos ainda sem decodificador
+        BarcodeType::GS1128
+        | BarcodeType::Interleaved2of5
+        | BarcodeType::Code11
+        | BarcodeType::Code32
+        | BarcodeType::PZN7
+        | BarcodeType::PZN8
+        | BarcodeType::Pharmacode
+        | BarcodeType::PharmacodeTwoTrack
+        | BarcodeType::DataBar
+        | BarcodeType::DataBarTruncated
+        | BarcodeType::DataBarStacked
+        | BarcodeType::DataBarStackedOmni
+        | BarcodeType::DataBarLimited
+        | BarcodeType::DataBarExpanded
+        | BarcodeType::DataBarExpandedStacked
+        | BarcodeType::Code16K
+        | BarcodeType::CodablockF
+        | BarcodeType::IntelligentMail
+        | BarcodeType::RoyalMail
+        | BarcodeType::KIX
+        | BarcodeType::AustraliaPost
+        | BarcodeType::JapanPost
+        | BarcodeType::Postnet
+        | BarcodeType::Planet
+        | BarcodeType::MaxiCode
+        | BarcodeType::HanXin
+        | BarcodeType::DotCode => Ok(None),
     }
 }
 
@@ -10,15 +10,44 @@ pub enum BarcodeType {
     EAN13,
     UPCA,
     Code128,
+    GS1128,
     Code39,
     ITF14,
+    Interleaved2of5,
     Codabar,
+    Code11,
+    Code32,
+    PZN7,
+    PZN8,
+    Pharmacode,
+    PharmacodeTwoTrack,
+    DataBar,
+    DataBarTruncated,
+    DataBarStacked,
+    DataBarStackedOmni,
+    DataBarLimited,
+    DataBarExpanded,
+    DataBarExpandedStacked,
+    Code16K,
+    CodablockF,
+
+    // Postal Barcodes
+    IntelligentMail,
+    RoyalMail,
+    KIX,
+    AustraliaPost,
+    JapanPost,
+    Postnet,
+    Planet,
 
     // 2D Barcodes
     QRCode,
     DataMatrix,
     PDF417,
     Aztec,
+    MaxiCode,
+    HanXin,
+    DotCode,
 }
 
 #[derive(Debug, Error)]
@@ -36,12 +65,39 @@ impl FromStr for BarcodeType {
             "EAN13" => Ok(BarcodeType::EAN13),
             "UPCA" => Ok(BarcodeType::UPCA),
             "Code128" => Ok(BarcodeType::Code128),
+            "GS1128" => Ok(BarcodeType::GS1128),
             "Code39" => Ok(BarcodeType::Code39),
             "DataMatrix" => Ok(BarcodeType::DataMatrix),
             "PDF417" => Ok(BarcodeType::PDF417),
             "Aztec" => Ok(BarcodeType::Aztec),
+            "MaxiCode" => Ok(BarcodeType::MaxiCode),
+            "HanXin" => Ok(BarcodeType::HanXin),
+            "DotCode" => Ok(BarcodeType::DotCode),
             "ITF14" => Ok(BarcodeType::ITF14),
+            "Interleaved2of5" => Ok(BarcodeType::Interleaved2of5),
             "Codabar" => Ok(BarcodeType::Codabar),
+            "Code11" => Ok(BarcodeType::Code11),
+            "Code32" => Ok(BarcodeType::Code32),
+            "PZN7" => Ok(BarcodeType::PZN7),
+            "PZN8" => Ok(BarcodeType::PZN8),
+            "Pharmacode" => Ok(BarcodeType::Pharmacode),
+            "PharmacodeTwoTrack" => Ok(BarcodeType::PharmacodeTwoTrack),
+            "DataBar" => Ok(BarcodeType::DataBar),
+            "DataBarTruncated" => Ok(BarcodeType::DataBarTruncated),
+            "DataBarStacked" => Ok(BarcodeType::DataBarStacked),
+            "DataBarStackedOmni" => Ok(BarcodeType::DataBarStackedOmni),
+            "DataBarLimited" => Ok(BarcodeType::DataBarLimited),
+            "DataBarExpanded" => Ok(BarcodeType::DataBarExpanded),
+            "DataBarExpandedStacked" => Ok(BarcodeType::DataBarExpandedStacked),
+            "Code16K" => Ok(BarcodeType::Code16K),
+            "CodablockF" => Ok(BarcodeType::CodablockF),
+            "IntelligentMail" => Ok(BarcodeType::IntelligentMail),
+            "RoyalMail" => Ok(BarcodeType::RoyalMail),
+            "KIX" => Ok(BarcodeType::KIX),
+            "AustraliaPost" => Ok(BarcodeType::AustraliaPost),
+            "JapanPost" => Ok(BarcodeType::JapanPost),
+            "Postnet" => Ok(BarcodeType::Postnet),
+            "Planet" => Ok(BarcodeType::Planet),
             _ => Err(BarcodeTypeParseError::InvalidType(s.to_string())),
         }
     }
@@ -82,6 +138,16 @@ pub struct BarcodeConfig {
     pub background: [u8; 4],
     pub include_text: bool,
     pub qr_config: QRConfig,
+    pub module_shape: ModuleShape,
+    /// Character printed in a box after the human-readable text, such as the
+    /// ISBT 128 keyboard entry check character
+    pub boxed_character: Option<char>,
+    /// Physical size of linear barcodes; when absent they are drawn at a
+    /// fixed size in pixels
+    pub dimensions: Option<LinearDimensions>,
+    /// Draw the Swiss cross over the centre of a matrix barcode, as required
+    /// for the QR code of a Swiss QR-bill
+    pub swiss_cross: bool,
 }
 
 impl Default for BarcodeConfig {
@@ -94,6 +160,10 @@ impl Default for BarcodeConfig {
             background: [255, 255, 255, 255],
             include_text: true,
             qr_config: QRConfig::default(),
+            module_shape: ModuleShape::default(),
+            boxed_character: None,
+            dimensions: None,
+            swiss_cross: false,
         }
     }
 }
@@ -121,6 +191,57 @@ pub enum QRErrorCorrection {
     High,
 }
 
+/// Physical size of a linear barcode, in millimetres
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearDimensions {
+    /// Width of a narrow module
+    pub module_width: f64,
+    /// Bar height
+    pub height: f64,
+}
+
+/// Rectangle drawn over a symbol, in fractions of the symbol width from its
+/// top left corner
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlayRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub dark: bool,
+}
+
+/// Swiss cross of a Swiss QR code, 7 mm wide on the 46 mm symbol: a white
+/// border, a black square and the two white arms of the cross, in drawing
+/// order
+pub fn swiss_cross() -> [OverlayRect; 4] {
+    // The cross graphic is 19.8 units wide
+    let unit = 7.0 / 46.0 / 19.8;
+    let origin = (1.0 - 7.0 / 46.0) / 2.0;
+    let rect = |x: f64, y: f64, width: f64, height: f64, dark: bool| OverlayRect {
+        x: origin + x * unit,
+        y: origin + y * unit,
+        width: width * unit,
+        height: height * unit,
+        dark,
+    };
+    [
+        rect(0.0, 0.0, 19.8, 19.8, false),
+        rect(1.4, 1.4, 17.0, 17.0, true),
+        rect(8.25, 4.4, 3.3, 11.0, false),
+        rect(4.4, 8.25, 11.0, 3.3, false),
+    ]
+}
+
+/// Shape of the dark modules of a matrix barcode in vector output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ModuleShape {
+    #[default]
+    Square,
+    /// Round dots, as printed by continuous inkjet (DotCode)
+    Dot,
+}
+
 /// Internal representation of a barcode
 #[derive(Debug, Clone)]
 pub struct Barcode {
@@ -130,11 +251,102 @@ pub struct Barcode {
     pub config: BarcodeConfig,
 }
 
+/// Rows of modules of a 2D barcode, dark modules `true`
+pub type ModuleGrid = Vec<Vec<bool>>;
+
 /// Matrix of modules (pixels) that make up a barcode
 #[derive(Debug, Clone)]
 pub enum BarcodeModules {
     Linear(Vec<bool>),
     Matrix(Vec<Vec<bool>>),
+    /// Equally spaced bars whose information is carried by their height
+    HeightModulated {
+        bars: Vec<BarState>,
+        geometry: BarGeometry,
+    },
+    /// MaxiCode grid of hexagonal modules, odd rows offset half a module to
+    /// the right; the central bullseye is drawn by the exporters
+    Hexagonal(Vec<Vec<bool>>),
+}
+
+/// Vertical extent of a bar in a height-modulated barcode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarState {
+    /// Bar spanning the whole symbol height
+    Full,
+    /// Bar extending from the top of the symbol (top-only bar)
+    Ascender,
+    /// Bar extending down to the bottom of the symbol (bottom-only bar)
+    Descender,
+    /// Short bar covering only the tracker region
+    Tracker,
+}
+
+impl BarState {
+    /// Top and bottom of the bar, in millimetres from the top of the symbol
+    pub fn span(&self, geometry: &BarGeometry) -> (f64, f64) {
+        match self {
+            BarState::Full => (0.0, geometry.height),
+            BarState::Ascender => (0.0, geometry.tracker_bottom),
+            BarState::Descender => (geometry.tracker_top, geometry.height),
+            BarState::Tracker => (geometry.tracker_top, geometry.tracker_bottom),
+        }
+    }
+}
+
+/// Physical dimensions of a height-modulated barcode, in millimetres
+///
+/// Ascenders end at `tracker_bottom` and descenders start at `tracker_top`,
+/// both measured from the top of the symbol.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarGeometry {
+    pub bar_width: f64,
+    pub pitch: f64,
+    pub height: f64,
+    pub tracker_top: f64,
+    pub tracker_bottom: f64,
+}
+
+impl BarGeometry {
+    /// Total width of `bar_count` bars, from the left edge of the first bar
+    /// to the right edge of the last one
+    pub fn width(&self, bar_count: usize) -> f64 {
+        if bar_count == 0 {
+            0.0
+        } else {
+            (bar_count - 1) as f64 * self.pitch + self.bar_width
+        }
+    }
+}
+
+/// Circumradius of a hexagonal module one unit wide (1/√3)
+pub const HEX_RADIUS: f64 = 0.577_350_269_189_625_8;
+
+/// Radii of the bullseye circles, outermost first, in module widths; the
+/// rings between the first and second, third and fourth, and fifth and
+/// sixth circles are dark
+pub const BULLSEYE_RADII: [f64; 6] = [4.43, 3.64, 2.86, 2.07, 1.29, 0.5];
+
+/// Centre of the module at `row`, `col` of a hexagonal grid, in module
+/// widths from the top left corner
+pub fn hex_center(row: usize, col: usize) -> (f64, f64) {
+    let offset = if row % 2 == 1 { 1.0 } else { 0.5 };
+    (col as f64 + offset, HEX_RADIUS * (1.0 + 1.5 * row as f64))
+}
+
+/// Width and height of a hexagonal grid, in module widths
+pub fn hex_grid_size(rows: usize, cols: usize) -> (f64, f64) {
+    let height = if rows == 0 {
+        0.0
+    } else {
+        HEX_RADIUS * (2.0 + 1.5 * (rows - 1) as f64)
+    };
+    (cols as f64, height)
+}
+
+/// Centre of the bullseye of a hexagonal grid, in module widths
+pub fn bullseye_center(rows: usize, cols: usize) -> (f64, f64) {
+    hex_center(rows / 2, cols.saturating_sub(1) / 2)
 }
 
 impl BarcodeModules {
@@ -155,13 +367,14 @@ impl BarcodeModules {
                     false
                 }
             }
-            BarcodeModules::Matrix(data) => {
+            BarcodeModules::Matrix(data) | BarcodeModules::Hexagonal(data) => {
                 if y < data.len() && x < data[0].len() {
                     data[y][x]
                 } else {
                     false
                 }
             }
+            BarcodeModules::HeightModulated { bars, .. } => x < bars.len() && y == 0,
         }
     }
 
@@ -172,11 +385,13 @@ impl BarcodeModules {
                     data[x] = value;
                 }
             }
-            BarcodeModules::Matrix(data) => {
+            BarcodeModules::Matrix(data) | BarcodeModules::Hexagonal(data) => {
                 if y < data.len() && x < data[0].len() {
                     data[y][x] = value;
                 }
             }
+            // Every position holds a bar; heights are changed through the bar list
+            BarcodeModules::HeightModulated { .. } => {}
         }
     }
 
@@ -184,15 +399,31 @@ impl BarcodeModules {
     pub fn as_linear(&self) -> Option<&Vec<bool>> {
         match self {
             BarcodeModules::Linear(data) => Some(data),
-            BarcodeModules::Matrix(_) => None,
+            _ => None,
         }
     }
 
     /// Retorna os módulos como uma matriz (2D)
-    pub fn as_matrix(&self) -> Option<&Vec<Vec<bool>>> {
+    pub fn as_matrix(&self) -> Option<&ModuleGrid> {
         match self {
-            BarcodeModules::Linear(_) => None,
             BarcodeModules::Matrix(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Retorna a grade de módulos hexagonais (MaxiCode)
+    pub fn as_hexagonal(&self) -> Option<&ModuleGrid> {
+        match self {
+            BarcodeModules::Hexagonal(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Retorna as barras de um código modulado em altura
+    pub fn as_bars(&self) -> Option<&[BarState]> {
+        match self {
+            BarcodeModules::HeightModulated { bars, .. } => Some(bars),
+            _ => None,
         }
     }
 }
@@ -205,6 +436,14 @@ pub struct ReadResult {
     pub confidence: f32,
 }
 
+impl ReadResult {
+    /// GS1 elements of the data, read as an unbracketed element string
+    /// (with symbology identifier or GS separators) or a Digital Link URI
+    pub fn gs1_elements(&self) -> Result<Vec<crate::gs1::Element>> {
+        crate::gs1::parse(&self.data)
+    }
+}
+
 /// Error types for the library
 #[derive(Debug, Error)]
 pub enum QuickCodesError {